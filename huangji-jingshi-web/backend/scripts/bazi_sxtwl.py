@@ -30,11 +30,6 @@ def parse_datetime_utc(raw):
     return dt.astimezone(timezone.utc)
 
 
-def equation_of_time_spencer(day_of_year):
-    b = math.radians(360.0 * (day_of_year - 81.0) / 365.0)
-    return 9.87 * math.sin(2.0 * b) - 7.67 * math.sin(b + math.radians(78.7))
-
-
 def resolve_standard_local(dt_utc, timezone_name, tz_offset_minutes):
     if timezone_name:
         if ZoneInfo is None:
@@ -47,14 +42,6 @@ def resolve_standard_local(dt_utc, timezone_name, tz_offset_minutes):
     return dt_utc.astimezone(timezone(timedelta(minutes=tz_offset_minutes)))
 
 
-def true_solar_datetime(local_standard_dt, longitude, standard_meridian):
-    day_of_year = local_standard_dt.timetuple().tm_yday
-    eot_minutes = equation_of_time_spencer(day_of_year)
-    longitude_correction_minutes = (longitude - standard_meridian) * 4.0
-    total_minutes = longitude_correction_minutes + eot_minutes
-    return local_standard_dt + timedelta(minutes=total_minutes)
-
-
 def hour_to_zhi_index(hour_float):
     hour_float = hour_float % 24.0
     if hour_float >= 23.0 or hour_float < 1.0:
@@ -106,16 +93,14 @@ def run():
         dt_utc = parse_datetime_utc(payload["datetime_utc"])
         timezone_name = payload.get("timezone")
         tz_offset_minutes = int(payload.get("tz_offset_minutes", 480))
-        longitude = float(payload.get("longitude", 116.4))
-        time_basis = (payload.get("time_basis") or "standard").strip().lower()
+        solar_time = (payload.get("solar_time") or "meridian").strip().lower()
+        # The solar-time correction (longitude offset plus equation of time) is
+        # computed by the caller's SolarTimePolicy so both engines agree.
+        correction_seconds = float(payload.get("solar_time_correction_seconds", 0.0))
         day_rollover = (payload.get("day_rollover") or "zi_chu_23").strip().lower()
 
         local_standard = resolve_standard_local(dt_utc, timezone_name, tz_offset_minutes)
-        standard_meridian = (tz_offset_minutes / 60.0) * 15.0
-        if time_basis == "true_solar":
-            basis_dt = true_solar_datetime(local_standard, longitude, standard_meridian)
-        else:
-            basis_dt = local_standard
+        basis_dt = local_standard + timedelta(seconds=round(correction_seconds))
 
         hour_float = (
             basis_dt.hour
@@ -161,7 +146,7 @@ def run():
                 "hour": {"tg": int(hour_gz.tg), "dz": int(hour_gz.dz)},
                 "solar_term": solar_term_name,
                 "solar_longitude": None,
-                "true_solar_hour": hour_float if time_basis == "true_solar" else None,
+                "true_solar_hour": hour_float if solar_time != "meridian" else None,
                 "is_late_zi": bool(is_late_zi),
             }
         )
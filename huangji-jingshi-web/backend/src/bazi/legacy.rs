@@ -1,14 +1,10 @@
-use crate::bazi::models::{BaziRequestContext, DayRollover, PillarIndices, TimeBasis};
-use chrono::{Datelike, Timelike};
-use huangji_core::astro::solar::{
-    datetime_to_jd, hour_to_dizhi_index, solar_position, true_solar_hour, true_solar_time,
-    utc_to_jd,
-};
+use crate::bazi::models::{time_basis_label, BaziRequestContext, PillarIndices};
+use huangji_core::astro::solar::{true_solar_hour, utc_to_jd};
 use huangji_core::calendar::ganzhi::{
-    calc_day_pillar, calc_dayun_start_age, calc_hour_pillar, calc_month_pillar, calc_year_pillar,
-    DIZHI, GAN_WUXING, NAYIN, SHENGXIAO, TIANGAN, ZHI_WUXING,
+    calc_dayun_start_age, DIZHI, GAN_WUXING, NAYIN, SHENGXIAO, TIANGAN, ZHI_WUXING,
 };
 use huangji_core::calendar::jieqi::SolarTerm;
+use huangji_core::calendar::sexagenary::Sexagenary;
 use serde_json::{json, Value};
 use std::collections::HashMap;
 
@@ -196,49 +192,28 @@ fn calculate_liunian(birth_year: i32, current_year: i32, num_years: i32) -> Vec<
     liunian
 }
 
-fn basis_local_datetime(ctx: &BaziRequestContext) -> chrono::NaiveDateTime {
-    let standard_local = ctx.zone.local_datetime(ctx.datetime_utc);
-    if matches!(ctx.time_basis, TimeBasis::Standard) {
-        return standard_local;
-    }
-
-    let standard_meridian = (ctx.tz_offset_minutes as f64 / 60.0) * 15.0;
-    true_solar_time(&ctx.datetime_utc, ctx.longitude, standard_meridian)
-}
-
 pub fn compute_legacy_pillars(ctx: &BaziRequestContext) -> PillarIndices {
-    let jd_utc = utc_to_jd(&ctx.datetime_utc);
-    let solar_longitude = solar_position(jd_utc).ecliptic_longitude;
-    let basis_local = basis_local_datetime(ctx);
-
-    let basis_hour = basis_local.hour() as f64
-        + basis_local.minute() as f64 / 60.0
-        + basis_local.second() as f64 / 3600.0;
-    let (hour_zhi_idx, default_late_zi) = hour_to_dizhi_index(basis_hour.rem_euclid(24.0));
-    let is_late_zi = match ctx.day_rollover {
-        DayRollover::ZiChu23 => default_late_zi,
-        DayRollover::ZiZheng00 => false,
-    };
-
-    let basis_jd = datetime_to_jd(&basis_local);
-    let day_pillar = calc_day_pillar(basis_jd, is_late_zi);
-    let year_pillar = calc_year_pillar(basis_local.year(), solar_longitude);
-    let month_pillar = calc_month_pillar(year_pillar.0, solar_longitude);
-    let hour_pillar = calc_hour_pillar(day_pillar.0, hour_zhi_idx);
+    let basis_local = ctx.basis_local_datetime();
+    let delta_t = ctx.delta_t_model.provider();
+    let pillars = Sexagenary::new(ctx.day_rollover).pillars_with(
+        &ctx.datetime_utc,
+        &basis_local,
+        delta_t.as_ref(),
+    );
 
     PillarIndices {
-        year: year_pillar,
-        month: month_pillar,
-        day: day_pillar,
-        hour: hour_pillar,
-        solar_longitude: Some(solar_longitude),
+        year: pillars.year.pair(),
+        month: pillars.month.pair(),
+        day: pillars.day.pair(),
+        hour: pillars.hour.pair(),
+        solar_longitude: Some(pillars.solar_longitude),
         solar_term: Some(
-            SolarTerm::from_longitude(solar_longitude)
+            SolarTerm::from_longitude(pillars.solar_longitude)
                 .name()
                 .to_string(),
         ),
         true_solar_hour: Some(true_solar_hour(&ctx.datetime_utc, ctx.longitude)),
-        is_late_zi,
+        is_late_zi: pillars.is_late_zi,
     }
 }
 
@@ -314,9 +289,14 @@ pub fn build_payload_from_pillars(ctx: &BaziRequestContext, pillars: &PillarIndi
         .copied()
         .collect();
 
-    let jd_for_dayun = datetime_to_jd(&basis_local_datetime(ctx));
+    let jd_for_dayun = utc_to_jd(&ctx.datetime_utc);
     let is_male = ctx.gender == "male";
-    let start_age = calc_dayun_start_age(jd_for_dayun, pillars.year.0, is_male);
+    let start_age = calc_dayun_start_age(
+        jd_for_dayun,
+        pillars.year.0,
+        is_male,
+        ctx.delta_t_model.provider().as_ref(),
+    );
     let birth_year = ctx.birth_year();
     let current_year = ctx.current_year();
 
@@ -369,7 +349,8 @@ pub fn build_payload_from_pillars(ctx: &BaziRequestContext, pillars: &PillarIndi
         "is_late_zi": pillars.is_late_zi,
         "longitude": ctx.longitude,
         "tz_offset_minutes": ctx.tz_offset_minutes,
-        "time_basis": ctx.time_basis.as_str(),
+        "time_basis": time_basis_label(ctx.solar_time),
+        "solar_time": ctx.solar_time,
         "day_rollover": ctx.day_rollover.as_str()
     })
 }
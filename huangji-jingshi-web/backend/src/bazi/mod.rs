@@ -5,19 +5,22 @@ mod sxtwl_bridge;
 
 use axum::{extract::Query, http::StatusCode, Json};
 use models::{
-    parse_datetime_with_zone, ApiError, BaziQuery, BaziRequestContext, BaziSource, DayRollover,
-    TimeBasis,
+    legacy_time_basis, parse_datetime_with_zone, ApiError, BaziQuery, BaziRequestContext,
+    BaziSource, DayRollover,
 };
 use resolver::resolve_bazi;
 
+pub use models::{parse_delta_t_model, parse_local_time_resolution, parse_solar_time};
+
 pub async fn get_bazi(
     Query(params): Query<BaziQuery>,
 ) -> Result<Json<serde_json::Value>, (StatusCode, Json<serde_json::Value>)> {
     tracing::info!(
-        "🔮 八字排盘请求: datetime={}, source={:?}, timeBasis={:?}, dayRollover={:?}, lon={:?}",
+        "🔮 八字排盘请求: datetime={}, source={:?}, timeBasis={:?}, solarTime={:?}, dayRollover={:?}, lon={:?}",
         params.datetime,
         params.source,
         params.time_basis,
+        params.solar_time,
         params.day_rollover,
         params.lon
     );
@@ -33,12 +36,19 @@ pub fn log_sxtwl_health() {
 
 fn parse_request_context(params: BaziQuery) -> Result<BaziRequestContext, ApiError> {
     let requested_source = BaziSource::parse(params.source.as_deref());
-    let time_basis = TimeBasis::parse(params.time_basis.as_deref(), params.use_true_solar_time);
+    let legacy = legacy_time_basis(params.time_basis.as_deref(), params.use_true_solar_time)?;
+    let solar_time = parse_solar_time(params.solar_time.as_deref(), legacy)?;
     let day_rollover = DayRollover::parse(params.day_rollover.as_deref());
+    let delta_t_model = parse_delta_t_model(params.delta_t.as_deref())?;
 
     let timezone = params.timezone.as_deref();
-    let (datetime_utc, zone) =
-        parse_datetime_with_zone(&params.datetime, timezone, params.tz_offset_minutes)?;
+    let resolution = parse_local_time_resolution(params.local_time_resolution.as_deref())?;
+    let (datetime_utc, zone) = parse_datetime_with_zone(
+        &params.datetime,
+        timezone,
+        params.tz_offset_minutes,
+        resolution,
+    )?;
     let tz_offset_minutes = zone.offset_minutes_at_utc(datetime_utc);
 
     let gender = params
@@ -58,15 +68,17 @@ fn parse_request_context(params: BaziQuery) -> Result<BaziRequestContext, ApiErr
         longitude: params.lon.unwrap_or(116.4),
         gender,
         requested_source,
-        time_basis,
+        solar_time,
         day_rollover,
+        local_time_resolution: resolution,
+        delta_t_model,
     })
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use models::BaziQuery;
+    use models::{BaziQuery, LocalTimeResolution, SolarTimePolicy};
     use serde_json::json;
 
     #[tokio::test]
@@ -82,14 +94,90 @@ mod tests {
             time_basis: Some("standard".to_string()),
             day_rollover: Some("zi_chu_23".to_string()),
             use_true_solar_time: None,
+            solar_time: None,
+            local_time_resolution: None,
+            delta_t: None,
         };
 
         let result = parse_request_context(query);
         assert!(result.is_err());
-        let err = result.err().expect("should return bad request");
+        let err = result.expect_err("should return bad request");
         assert_eq!(err.0, StatusCode::BAD_REQUEST);
     }
 
+    #[test]
+    fn dst_local_times_follow_resolution_policy() {
+        let query = |datetime: &str, resolution: Option<&str>| BaziQuery {
+            datetime: datetime.to_string(),
+            timezone: Some("Asia/Shanghai".to_string()),
+            tz_offset_minutes: None,
+            lat: None,
+            lon: Some(121.5),
+            gender: None,
+            source: None,
+            time_basis: None,
+            day_rollover: None,
+            use_true_solar_time: None,
+            solar_time: None,
+            local_time_resolution: resolution.map(str::to_string),
+            delta_t: None,
+        };
+
+        // 1988-04-17 02:00 拨快至 03:00
+        let gap = parse_request_context(query("1988-04-17T02:30", None)).expect("context");
+        assert_eq!(gap.datetime_utc.to_rfc3339(), "1988-04-16T18:30:00+00:00");
+        assert_eq!(gap.tz_offset_minutes, 540);
+        assert_eq!(
+            gap.rule_profile().local_time_resolution,
+            LocalTimeResolution::Compatible
+        );
+        let err = parse_request_context(query("1988-04-17T02:30", Some("reject")))
+            .expect_err("gap rejected");
+        assert_eq!(err.1 .0["error"], "invalid_local_datetime");
+
+        // 1988-09-11 02:00 回拨至 01:00
+        let later =
+            parse_request_context(query("1988-09-11T01:30", Some("later"))).expect("context");
+        assert_eq!(later.datetime_utc.to_rfc3339(), "1988-09-10T17:30:00+00:00");
+        assert_eq!(later.tz_offset_minutes, 480);
+        let err = parse_request_context(query("1988-09-11T01:30", Some("nearest")))
+            .expect_err("invalid resolution");
+        assert_eq!(err.1 .0["error"], "invalid_local_time_resolution");
+    }
+
+    #[test]
+    fn legacy_time_basis_keeps_true_solar_and_rejects_invalid() {
+        let query = |time_basis: Option<&str>, use_true_solar_time: Option<bool>| BaziQuery {
+            datetime: "2024-02-10T12:00:00+08:00".to_string(),
+            timezone: None,
+            tz_offset_minutes: None,
+            lat: None,
+            lon: Some(116.4),
+            gender: None,
+            source: None,
+            time_basis: time_basis.map(str::to_string),
+            day_rollover: None,
+            use_true_solar_time,
+            solar_time: None,
+            local_time_resolution: None,
+            delta_t: None,
+        };
+
+        let solar_time = |time_basis, flag| {
+            parse_request_context(query(time_basis, flag))
+                .expect("context")
+                .solar_time
+        };
+        assert_eq!(solar_time(None, None), SolarTimePolicy::Meridian);
+        assert_eq!(
+            solar_time(Some("true_solar"), None),
+            SolarTimePolicy::Apparent
+        );
+        assert_eq!(solar_time(None, Some(true)), SolarTimePolicy::Apparent);
+        let err = parse_request_context(query(Some("sidereal"), None)).expect_err("invalid");
+        assert_eq!(err.1 .0["error"], "invalid_solar_time");
+    }
+
     #[tokio::test]
     async fn response_top_level_matches_resolved_variant() {
         let query = BaziQuery {
@@ -103,6 +191,9 @@ mod tests {
             time_basis: Some("standard".to_string()),
             day_rollover: Some("zi_chu_23".to_string()),
             use_true_solar_time: None,
+            solar_time: None,
+            local_time_resolution: None,
+            delta_t: None,
         };
 
         let context = parse_request_context(query).expect("context");
@@ -146,6 +237,9 @@ mod tests {
             time_basis: Some("standard".to_string()),
             day_rollover: Some("zi_chu_23".to_string()),
             use_true_solar_time: None,
+            solar_time: None,
+            local_time_resolution: None,
+            delta_t: None,
         };
         let context = parse_request_context(query).expect("context");
         let response = resolve_bazi(&context).await.expect("resolved response");
@@ -164,4 +258,47 @@ mod tests {
             assert_eq!(resolved_source, "sxtwl");
         }
     }
+
+    #[test]
+    fn legacy_pillars_match_core_lunar() {
+        use huangji_core::calendar::ganzhi::{DIZHI, TIANGAN};
+
+        let name = |(gan, zhi): (usize, usize)| format!("{}{}", TIANGAN[gan], DIZHI[zhi]);
+        for datetime in [
+            "2024-12-25T10:00:00",
+            "2025-02-03T23:30:00",
+            "2025-02-04T05:00:00",
+            "2025-01-14T00:40:00",
+        ] {
+            let query = BaziQuery {
+                datetime: datetime.to_string(),
+                timezone: Some("Asia/Shanghai".to_string()),
+                tz_offset_minutes: Some(480),
+                lat: None,
+                lon: Some(120.0),
+                gender: Some("male".to_string()),
+                source: Some("huangji_core".to_string()),
+                time_basis: Some("standard".to_string()),
+                day_rollover: Some("zi_zheng_00".to_string()),
+                use_true_solar_time: None,
+                solar_time: None,
+                local_time_resolution: None,
+                delta_t: None,
+            };
+            let context = parse_request_context(query).expect("context");
+            let pillars = legacy::compute_legacy_pillars(&context);
+            let lunar = huangji_core::lunar::compute_lunar(
+                &context.datetime_utc,
+                &huangji_core::calendar::zone::TimeZoneSpec::parse("Asia/Shanghai").unwrap(),
+                120.0,
+                models::SolarTimePolicy::Meridian,
+                &huangji_core::astro::delta_t::EspenakMeeus,
+            )
+            .expect("lunar");
+            assert_eq!(name(pillars.year), lunar.ganzhi_year, "{}", datetime);
+            assert_eq!(name(pillars.month), lunar.ganzhi_month, "{}", datetime);
+            assert_eq!(name(pillars.day), lunar.ganzhi_day, "{}", datetime);
+            assert_eq!(name(pillars.hour), lunar.ganzhi_hour, "{}", datetime);
+        }
+    }
 }
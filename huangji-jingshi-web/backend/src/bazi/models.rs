@@ -3,6 +3,12 @@ use chrono_tz::Tz;
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
 
+pub use huangji_core::astro::delta_t::DeltaTModel;
+pub use huangji_core::astro::solar_time::SolarTimePolicy;
+pub use huangji_core::calendar::sexagenary::DayRollover;
+pub use huangji_core::calendar::zone::LocalTimeResolution;
+use huangji_core::calendar::zone::TimeZoneSpec;
+
 pub type ApiError = (axum::http::StatusCode, axum::Json<Value>);
 
 #[derive(Debug, Deserialize)]
@@ -16,12 +22,27 @@ pub struct BaziQuery {
     pub lon: Option<f64>,
     pub gender: Option<String>,
     pub source: Option<String>,
+    /// 旧参数：standard、true_solar（亦接受 solarTime 的写法），无效取值返回 400
     #[serde(rename = "timeBasis")]
     pub time_basis: Option<String>,
     #[serde(rename = "dayRollover")]
     pub day_rollover: Option<String>,
     #[serde(rename = "useTrueSolarTime")]
     pub use_true_solar_time: Option<bool>,
+    /// 太阳时策略：meridian、mean、apparent、apparent_spencer
+    #[serde(rename = "solarTime")]
+    pub solar_time: Option<String>,
+    /// 夏令时重复或空缺的本地时间：compatible（默认）、earlier、later、reject
+    ///
+    /// 早先版本对空缺时间（如 1988-04-17 02:30 Asia/Shanghai）一律返回 `invalid_local_datetime`；
+    /// 现默认 compatible 按拨快前的偏移顺延（即 03:30），需旧行为时传 reject。
+    /// 实际采用的取舍见响应 `rule_profile.local_time_resolution`。
+    #[serde(rename = "localTimeResolution")]
+    pub local_time_resolution: Option<String>,
+    /// ΔT 模型：espenak_meeus（默认）、iers、fixed:<秒>；用于 huangji_core 四柱与起运岁数，
+    /// sxtwl 排四柱时用其自带的 ΔT
+    #[serde(rename = "deltaT")]
+    pub delta_t: Option<String>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -49,71 +70,61 @@ impl BaziSource {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub enum TimeBasis {
-    Standard,
-    TrueSolar,
+/// 太阳时策略：给出 `solarTime` 时按其解析，无效取值返回 400；否则取旧参数的含义 `legacy`
+///
+/// 各接口共用。旧参数含义因接口而异：`/api/bazi` 见 [`legacy_time_basis`]，
+/// 其余接口的 `useTrueSolarTime` 见 `SolarTimePolicy::from_true_solar_flag`。
+pub fn parse_solar_time(
+    raw: Option<&str>,
+    legacy: SolarTimePolicy,
+) -> Result<SolarTimePolicy, ApiError> {
+    raw.map(SolarTimePolicy::parse)
+        .transpose()
+        .map_err(|err| invalid_request("invalid_solar_time", err.to_string()))
+        .map(|policy| policy.unwrap_or(legacy))
 }
 
-impl TimeBasis {
-    pub fn parse(raw: Option<&str>, use_true_solar_time: Option<bool>) -> Self {
-        if matches!(use_true_solar_time, Some(true)) {
-            return Self::TrueSolar;
-        }
-
-        match raw
-            .unwrap_or("standard")
-            .trim()
-            .to_ascii_lowercase()
-            .as_str()
-        {
-            "true_solar" | "truesolar" => Self::TrueSolar,
-            _ => Self::Standard,
-        }
-    }
-
-    pub fn as_str(self) -> &'static str {
-        match self {
-            Self::Standard => "standard",
-            Self::TrueSolar => "true_solar",
-        }
-    }
+/// ΔT 模型：各接口共用，无效取值返回 400，缺省为 Espenak–Meeus
+pub fn parse_delta_t_model(raw: Option<&str>) -> Result<DeltaTModel, ApiError> {
+    raw.map(DeltaTModel::parse)
+        .transpose()
+        .map_err(|err| invalid_request("invalid_delta_t", err.to_string()))
+        .map(Option::unwrap_or_default)
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub enum DayRollover {
-    ZiChu23,
-    ZiZheng00,
+/// `/api/bazi` 旧参数的含义：`useTrueSolarTime=true` 或 `timeBasis=true_solar` 历来取精确真太阳时；
+/// `timeBasis` 与 `solarTime` 同样解析，无效取值返回 400
+pub fn legacy_time_basis(
+    time_basis: Option<&str>,
+    use_true_solar_time: Option<bool>,
+) -> Result<SolarTimePolicy, ApiError> {
+    if matches!(use_true_solar_time, Some(true)) {
+        return Ok(SolarTimePolicy::Apparent);
+    }
+    parse_solar_time(time_basis, SolarTimePolicy::Meridian)
 }
 
-impl DayRollover {
-    pub fn parse(raw: Option<&str>) -> Self {
-        match raw
-            .unwrap_or("zi_chu_23")
-            .trim()
-            .to_ascii_lowercase()
-            .as_str()
-        {
-            "zi_zheng_00" | "zizheng00" | "00:00" => Self::ZiZheng00,
-            _ => Self::ZiChu23,
-        }
-    }
-
-    pub fn as_str(self) -> &'static str {
-        match self {
-            Self::ZiChu23 => "zi_chu_23",
-            Self::ZiZheng00 => "zi_zheng_00",
-        }
+/// 旧字段 `time_basis` 的取值
+pub fn time_basis_label(policy: SolarTimePolicy) -> &'static str {
+    match policy {
+        SolarTimePolicy::Meridian => "standard",
+        SolarTimePolicy::Mean => "mean_solar",
+        SolarTimePolicy::Apparent | SolarTimePolicy::ApparentSpencer => "true_solar",
     }
 }
 
 #[derive(Debug, Clone, Serialize)]
 pub struct RuleProfile {
     pub time_basis: String,
+    pub solar_time: SolarTimePolicy,
     pub day_rollover: String,
     pub timezone: Option<String>,
     pub tz_offset_minutes: i32,
     pub longitude: f64,
+    /// 夏令时重复或空缺的本地时间取舍
+    pub local_time_resolution: LocalTimeResolution,
+    /// 实际使用的 ΔT 提供者
+    pub delta_t_provider: String,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -218,13 +229,6 @@ impl ZoneContext {
             Self::Fixed(offset) => Utc::now().with_timezone(offset).year(),
         }
     }
-
-    pub fn local_datetime(&self, dt: DateTime<Utc>) -> NaiveDateTime {
-        match self {
-            Self::Iana { tz, .. } => dt.with_timezone(tz).naive_local(),
-            Self::Fixed(offset) => dt.with_timezone(offset).naive_local(),
-        }
-    }
 }
 
 #[derive(Debug, Clone)]
@@ -235,18 +239,23 @@ pub struct BaziRequestContext {
     pub longitude: f64,
     pub gender: String,
     pub requested_source: BaziSource,
-    pub time_basis: TimeBasis,
+    pub solar_time: SolarTimePolicy,
     pub day_rollover: DayRollover,
+    pub local_time_resolution: LocalTimeResolution,
+    pub delta_t_model: DeltaTModel,
 }
 
 impl BaziRequestContext {
     pub fn rule_profile(&self) -> RuleProfile {
         RuleProfile {
-            time_basis: self.time_basis.as_str().to_string(),
+            time_basis: time_basis_label(self.solar_time).to_string(),
+            solar_time: self.solar_time,
             day_rollover: self.day_rollover.as_str().to_string(),
             timezone: self.zone.timezone_name(),
             tz_offset_minutes: self.tz_offset_minutes,
             longitude: self.longitude,
+            local_time_resolution: self.local_time_resolution,
+            delta_t_provider: self.delta_t_model.provider().name().to_string(),
         }
     }
 
@@ -257,6 +266,27 @@ impl BaziRequestContext {
     pub fn current_year(&self) -> i32 {
         self.zone.current_year()
     }
+
+    /// 出生时刻的区时偏移
+    pub fn standard_offset(&self) -> FixedOffset {
+        FixedOffset::east_opt(self.tz_offset_minutes * 60).expect("区时偏移取自有效时区")
+    }
+
+    /// 太阳时相对区时的校正（秒）
+    pub fn solar_time_correction_seconds(&self) -> f64 {
+        self.solar_time.correction_seconds(
+            &self.datetime_utc,
+            self.longitude,
+            self.standard_offset(),
+        )
+    }
+
+    /// 按太阳时策略取的本地时间，用于日柱与时辰
+    pub fn basis_local_datetime(&self) -> NaiveDateTime {
+        self.solar_time
+            .local_time(&self.datetime_utc, self.longitude, self.standard_offset())
+            .naive_local()
+    }
 }
 
 fn invalid_request(error: &str, message: impl Into<String>) -> ApiError {
@@ -281,10 +311,19 @@ fn parse_timezone(name: &str) -> Result<Tz, ApiError> {
     })
 }
 
+/// 夏令时取舍：各接口共用，无效取值返回 400，缺省为 compatible
+pub fn parse_local_time_resolution(raw: Option<&str>) -> Result<LocalTimeResolution, ApiError> {
+    raw.map(LocalTimeResolution::parse)
+        .transpose()
+        .map_err(|err| invalid_request("invalid_local_time_resolution", err.to_string()))
+        .map(Option::unwrap_or_default)
+}
+
 pub fn parse_datetime_with_zone(
     raw: &str,
     timezone: Option<&str>,
     tz_offset_minutes: Option<i32>,
+    resolution: LocalTimeResolution,
 ) -> Result<(DateTime<Utc>, ZoneContext), ApiError> {
     let timezone_name = timezone.map(str::trim).filter(|value| !value.is_empty());
 
@@ -329,22 +368,11 @@ pub fn parse_datetime_with_zone(
 
     if let Some(name) = timezone_name {
         let tz = parse_timezone(name)?;
-        let local = tz
-            .from_local_datetime(&naive)
-            .single()
-            .or_else(|| tz.from_local_datetime(&naive).earliest())
-            .or_else(|| tz.from_local_datetime(&naive).latest())
-            .ok_or_else(|| {
-                invalid_request(
-                    "invalid_local_datetime",
-                    format!(
-                        "cannot resolve local datetime '{}' in timezone '{}'",
-                        raw, name
-                    ),
-                )
-            })?;
+        let utc = TimeZoneSpec::Iana(tz)
+            .from_local(&naive, resolution)
+            .map_err(|err| invalid_request("invalid_local_datetime", err.to_string()))?;
         return Ok((
-            local.with_timezone(&Utc),
+            utc,
             ZoneContext::Iana {
                 name: name.to_string(),
                 tz,
@@ -70,7 +70,6 @@ fn bridge_failure_to_variant(failure: &BridgeFailure) -> BaziVariant {
 
 pub async fn resolve_bazi(ctx: &BaziRequestContext) -> Result<Value, ApiError> {
     let mut sxtwl_variant = BaziVariant::unavailable("not_requested");
-    let legacy_variant;
 
     let mut sxtwl_result: Option<EngineResult> = None;
     let mut sxtwl_failure: Option<BridgeFailure> = None;
@@ -90,7 +89,7 @@ pub async fn resolve_bazi(ctx: &BaziRequestContext) -> Result<Value, ApiError> {
     }
 
     let legacy_payload = legacy::build_legacy_payload(ctx);
-    legacy_variant = BaziVariant::available(legacy_payload.clone());
+    let legacy_variant = BaziVariant::available(legacy_payload.clone());
     let legacy_result = legacy_result(legacy_payload);
 
     let (resolved, fallback_reason) = match ctx.requested_source {
@@ -1,4 +1,4 @@
-use crate::bazi::models::{BaziRequestContext, PillarIndices};
+use crate::bazi::models::{time_basis_label, BaziRequestContext, PillarIndices};
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 use std::path::PathBuf;
@@ -26,6 +26,9 @@ struct ScriptInput {
     tz_offset_minutes: i32,
     longitude: f64,
     time_basis: String,
+    solar_time: String,
+    /// 太阳时相对区时的校正（秒），由 `SolarTimePolicy` 算出，脚本不再自行计算均时差
+    solar_time_correction_seconds: f64,
     day_rollover: String,
 }
 
@@ -138,7 +141,9 @@ pub async fn compute_with_sxtwl(ctx: &BaziRequestContext) -> Result<BridgeSucces
         timezone: ctx.zone.timezone_name(),
         tz_offset_minutes: ctx.tz_offset_minutes,
         longitude: ctx.longitude,
-        time_basis: ctx.time_basis.as_str().to_string(),
+        time_basis: time_basis_label(ctx.solar_time).to_string(),
+        solar_time: ctx.solar_time.name().to_string(),
+        solar_time_correction_seconds: ctx.solar_time_correction_seconds(),
         day_rollover: ctx.day_rollover.as_str().to_string(),
     };
 
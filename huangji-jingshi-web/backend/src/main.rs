@@ -1,11 +1,11 @@
 use axum::response::IntoResponse;
 use axum::{
     extract::{Path, Query},
-    http::StatusCode,
+    http::{header, StatusCode},
     routing::{get, post},
     Json, Router,
 };
-use chrono::{DateTime, Datelike, FixedOffset, NaiveDateTime, TimeZone, Utc};
+use chrono::{DateTime, Datelike, FixedOffset, NaiveDate, NaiveDateTime, TimeZone, Utc};
 use once_cell::sync::Lazy;
 use serde::{Deserialize, Serialize};
 use serde_json::json;
@@ -17,13 +17,35 @@ use tower_http::cors::CorsLayer;
 
 mod bazi;
 
+use bazi::{parse_delta_t_model, parse_local_time_resolution, parse_solar_time};
+
 // 使用 huangji_core 公共模块（天文/历法/八字计算）
+use huangji_core::calendar::ics::{generate_ics, IcsRequest};
+use huangji_core::calendar::jieqi::solar_terms_of_year;
+use huangji_core::calendar::month::compute_month_calendar;
 use huangji_core::calendar::time_rule::{utc_to_hj_year, YearStartMode};
+use huangji_core::calendar::zeri::{select_dates, ZeriRequest};
+use huangji_core::calendar::zone::{LocalTimeResolution, TimeZoneSpec};
 // use huangji_core::algorithm::year_to_acc;
 use huangji_core::algorithm;
+use huangji_core::astro::calendar_system::CalendarSystem;
+use huangji_core::astro::coords::{convert_coordinates, CoordinateFrame};
+use huangji_core::astro::delta_t::{install_iers_table, IersTable};
+use huangji_core::astro::eclipse::find_eclipses;
+use huangji_core::astro::mansions::DeterminativeSystem;
+use huangji_core::astro::observer::Observer;
+use huangji_core::astro::solar::utc_to_jd;
+use huangji_core::astro::solar_time::SolarTimePolicy;
+use huangji_core::astro::time::TimeScales;
+use huangji_core::fenye::{compute_fenye, FenyeRequest};
 use huangji_core::fortune::{compute_fortune, CalcMode, FortuneRequest, PrimaryMode};
 use huangji_core::huangji_table;
-use huangji_core::sky::{compute_sky, SkyRequest};
+use huangji_core::phenomena::{find_phenomena, GroupingOptions, PhenomenaRequest};
+use huangji_core::qizheng::{compute_qizheng, QizhengRequest};
+use huangji_core::sky::{
+    compute_rise_set, compute_sky, NodeConvention, NodeModel, RiseSetRequest, SiyuConvention,
+    SkyBody, SkyRequest, ZiqiEpoch,
+};
 use huangji_core::table_engine;
 
 // 静态数据缓存
@@ -85,6 +107,7 @@ async fn main() {
         panic!("加载数据文件失败: {}", err);
     }
     bazi::log_sxtwl_health();
+    load_iers_delta_t();
 
     // 创建路由
     let app = Router::new()
@@ -98,6 +121,17 @@ async fn main() {
         .route("/api/history", get(get_history))
         .route("/api/history/related", get(get_history_related))
         .route("/api/mapping/get", get(get_mapping))
+        // 月历 API
+        .route("/api/calendar/month", get(get_calendar_month))
+        .route("/api/calendar.ics", get(get_calendar_ics))
+        .route("/api/zeri", get(get_zeri))
+        .route("/api/eclipses", get(get_eclipses))
+        .route("/api/rise-set", get(get_rise_set))
+        .route("/api/fenye", get(get_fenye))
+        .route("/api/phenomena", get(get_phenomena))
+        .route("/api/coords", get(get_coords))
+        .route("/api/qizheng", get(get_qizheng))
+        .route("/api/jieqi", get(get_jieqi))
         .route("/api/celestial/hashes", get(get_celestial_hashes))
         .route("/api/sky/settings", get(get_sky_settings))
         .route("/api/sky/settings", post(update_sky_settings))
@@ -124,6 +158,22 @@ async fn main() {
     axum::serve(listener, app).await.unwrap();
 }
 
+// 加载 IERS ΔT 表（可选，路径由 IERS_DELTA_T_PATH 指定，JSON 或 CSV）
+fn load_iers_delta_t() {
+    let Ok(path) = env::var("IERS_DELTA_T_PATH") else {
+        tracing::info!("ℹ️ 未配置 IERS_DELTA_T_PATH，ΔT 模型 iers 将回退到 Espenak–Meeus");
+        return;
+    };
+    match IersTable::load(std::path::Path::new(&path)) {
+        Ok(table) => {
+            let (first, last) = table.coverage();
+            tracing::info!("✅ IERS ΔT 表加载成功: {} ({}–{})", path, first, last);
+            install_iers_table(table);
+        }
+        Err(err) => tracing::warn!("⚠️ IERS ΔT 表加载失败: {}", err),
+    }
+}
+
 // 智能路径检测函数
 fn find_data_path() -> Option<PathBuf> {
     let possible_paths = [
@@ -334,6 +384,16 @@ async fn root_handler() -> impl IntoResponse {
             "GET /api/history",
             "GET /api/history/related",
             "GET /api/mapping/get",
+            "GET /api/calendar/month",
+            "GET /api/calendar.ics",
+            "GET /api/zeri",
+            "GET /api/eclipses",
+            "GET /api/rise-set",
+            "GET /api/fenye",
+            "GET /api/phenomena",
+            "GET /api/coords",
+            "GET /api/qizheng",
+            "GET /api/jieqi",
             "GET /api/celestial/hashes",
             "GET /api/sky/settings",
             "POST /api/sky/settings",
@@ -366,11 +426,25 @@ struct TimelineQuery {
     /// 注意：与 JS Date.getTimezoneOffset() 符号相反！
     #[serde(rename = "tzOffsetMinutes")]
     tz_offset_minutes: Option<i32>,
+    /// 时区：IANA 时区名或 +08:00，优先于 tzOffsetMinutes
+    timezone: Option<String>,
+    /// 夏令时重复或空缺的本地时间：compatible（默认）、earlier、later、reject
+    #[serde(rename = "localTimeResolution")]
+    local_time_resolution: Option<String>,
     lon: Option<f64>, // 用于真太阳时校正
+    /// 太阳时策略：meridian（默认）、mean、apparent、apparent_spencer
+    #[serde(rename = "solarTime")]
+    solar_time: Option<String>,
     #[serde(rename = "yearStart")]
     year_start: Option<String>,
     mode: Option<String>,
     primary: Option<String>,
+    /// 输入日期的历法：gregorian（外推格里历）、julian、reform（1582 改历）、reform:YYYY-MM-DD；
+    /// 给出时按该历法解释 datetime，并在响应中附 calendar 日期
+    calendar: Option<String>,
+    /// ΔT 模型：espenak_meeus（默认）、iers、fixed:<秒>
+    #[serde(rename = "deltaT")]
+    delta_t: Option<String>,
 }
 
 #[derive(Deserialize)]
@@ -378,17 +452,55 @@ struct SkyFortuneQuery {
     datetime: String,
     lat: Option<f64>,
     lon: Option<f64>,
+    /// 海拔（米）
+    elevation: Option<f64>,
+    /// 气压 (hPa) 与气温 (°C)，用于大气折射
+    pressure: Option<f64>,
+    temperature: Option<f64>,
+    /// 星表极限星等；给出时返回 sky.stars
+    #[serde(rename = "starMagLimit")]
+    star_mag_limit: Option<f64>,
+    /// 星表最低视高度（度），如 0 只返回地平以上
+    #[serde(rename = "starMinAlt")]
+    star_min_alt: Option<f64>,
+    /// 二十八宿距星体系：modern（默认）、historical
+    #[serde(rename = "mansionSystem")]
+    mansion_system: Option<String>,
     /// 时区偏移（分钟），东为正 UTC+8=+480, 西为负 UTC-5=-300
     /// 注意：与 JS Date.getTimezoneOffset() 符号相反！
     #[serde(rename = "tzOffsetMinutes")]
     tz_offset_minutes: Option<i32>,
-    /// 是否使用真太阳时（可选）
+    /// 时区：IANA 时区名（如 Asia/Shanghai，含历史夏令时与地方平时）或 +08:00，优先于 tzOffsetMinutes
+    timezone: Option<String>,
+    /// 夏令时重复或空缺的本地时间：compatible（默认）、earlier、later、reject
+    #[serde(rename = "localTimeResolution")]
+    local_time_resolution: Option<String>,
+    /// 是否使用真太阳时（可选）；旧参数，true 等同 solarTime=mean（只按经度校正，不计均时差）
     #[serde(rename = "useTrueSolarTime")]
     use_true_solar_time: Option<bool>,
+    /// 太阳时策略：meridian（默认）、mean、apparent、apparent_spencer，优先于 useTrueSolarTime
+    #[serde(rename = "solarTime")]
+    solar_time: Option<String>,
     #[serde(rename = "yearStart")]
     year_start: Option<String>,
     mode: Option<String>,
     primary: Option<String>,
+    /// ΔT 模型：espenak_meeus（默认）、iers、fixed:<秒>；sky 与 fortune（立春岁首、黄历四柱）共用
+    #[serde(rename = "deltaT")]
+    delta_t: Option<String>,
+    /// 输入日期的历法：gregorian（外推格里历）、julian、reform（1582 改历）、reform:YYYY-MM-DD；
+    /// 给出时按该历法解释 datetime，并在响应中附 calendar 日期
+    calendar: Option<String>,
+    /// 罗睺所配交点：ascending（默认，罗睺为升交点）、descending
+    luohou: Option<String>,
+    /// 黄白交点：mean（默认）、true
+    #[serde(rename = "nodeModel")]
+    node_model: Option<String>,
+    /// 紫气起元：起元时刻（TT 儒略日）与其时紫气平黄经（度），须同时给出；未给出时不计紫气
+    #[serde(rename = "ziqiEpochJd")]
+    ziqi_epoch_jd: Option<f64>,
+    #[serde(rename = "ziqiEpochLon")]
+    ziqi_epoch_lon: Option<f64>,
 }
 
 // HistoryQuery 保留用于将来的历史数据过滤
@@ -416,6 +528,209 @@ struct MappingQuery {
     primary: Option<String>,
 }
 
+#[derive(Deserialize)]
+struct CalendarMonthQuery {
+    year: Option<i32>,
+    month: Option<u32>,
+    /// 观测地经纬度，同时提供时附逐日日出日落
+    lat: Option<f64>,
+    lon: Option<f64>,
+    /// 时区偏移（分钟），东为正 UTC+8=+480, 西为负 UTC-5=-300
+    #[serde(rename = "tzOffsetMinutes")]
+    tz_offset_minutes: Option<i32>,
+    /// ΔT 模型：espenak_meeus（默认）、iers、fixed:<秒>
+    #[serde(rename = "deltaT")]
+    delta_t: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct CalendarIcsQuery {
+    /// 起始年份（含），默认当年
+    #[serde(rename = "startYear")]
+    start_year: Option<i32>,
+    /// 结束年份（含），默认与起始年份相同
+    #[serde(rename = "endYear")]
+    end_year: Option<i32>,
+    lat: Option<f64>,
+    lon: Option<f64>,
+    /// 时区：IANA 时区名（如 Asia/Shanghai）、+08:00、UTC+8 或分钟数，优先于 tzOffsetMinutes
+    tz: Option<String>,
+    /// 时区偏移（分钟），东为正 UTC+8=+480, 西为负 UTC-5=-300；默认 +480
+    #[serde(rename = "tzOffsetMinutes")]
+    tz_offset_minutes: Option<i32>,
+    /// 是否包含皇极经世旬/世交界
+    #[serde(rename = "includeHuangji")]
+    include_huangji: Option<bool>,
+    /// ΔT 模型：espenak_meeus（默认）、iers、fixed:<秒>
+    #[serde(rename = "deltaT")]
+    delta_t: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct ZeriQuery {
+    /// 起始日期 YYYY-MM-DD（含）
+    #[serde(rename = "startDate")]
+    start_date: NaiveDate,
+    /// 结束日期 YYYY-MM-DD（含）
+    #[serde(rename = "endDate")]
+    end_date: NaiveDate,
+    /// 时区偏移（分钟），东为正 UTC+8=+480, 西为负 UTC-5=-300
+    #[serde(rename = "tzOffsetMinutes")]
+    tz_offset_minutes: Option<i32>,
+    /// 所办事项，如 嫁娶
+    activity: Option<String>,
+    /// 命主年支或生肖，如 午 / 马
+    #[serde(rename = "birthBranch")]
+    birth_branch: Option<String>,
+    /// 首选建除，逗号分隔，如 成,开
+    officers: Option<String>,
+    #[serde(rename = "avoidMonthBreak")]
+    avoid_month_break: Option<bool>,
+    #[serde(rename = "avoidYearBreak")]
+    avoid_year_break: Option<bool>,
+    limit: Option<usize>,
+    /// ΔT 模型：espenak_meeus（默认）、iers、fixed:<秒>
+    #[serde(rename = "deltaT")]
+    delta_t: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct EclipseQuery {
+    /// 起始年份（含），默认当年
+    #[serde(rename = "startYear")]
+    start_year: Option<i32>,
+    /// 结束年份（含），默认与起始年份相同
+    #[serde(rename = "endYear")]
+    end_year: Option<i32>,
+    /// 观测地纬度、经度；同时给出时附带当地情况
+    lat: Option<f64>,
+    lon: Option<f64>,
+    /// 海拔（米）
+    elevation: Option<f64>,
+    /// ΔT 模型：espenak_meeus（默认）、iers、fixed:<秒>
+    #[serde(rename = "deltaT")]
+    delta_t: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct RiseSetQuery {
+    /// 当地日期 YYYY-MM-DD，默认今天
+    date: Option<NaiveDate>,
+    lat: Option<f64>,
+    lon: Option<f64>,
+    /// 海拔（米）
+    elevation: Option<f64>,
+    /// 时区偏移（分钟），东为正 UTC+8=+480, 西为负 UTC-5=-300
+    #[serde(rename = "tzOffsetMinutes")]
+    tz_offset_minutes: Option<i32>,
+    /// ΔT 模型：espenak_meeus（默认）、iers、fixed:<秒>
+    #[serde(rename = "deltaT")]
+    delta_t: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct FenyeQuery {
+    datetime: String,
+    /// 时区偏移（分钟），东为正 UTC+8=+480, 西为负 UTC-5=-300
+    #[serde(rename = "tzOffsetMinutes")]
+    tz_offset_minutes: Option<i32>,
+    /// 二十八宿距星体系：modern（默认）、historical
+    #[serde(rename = "mansionSystem")]
+    mansion_system: Option<String>,
+    /// ΔT 模型：espenak_meeus（默认）、iers、fixed:<秒>
+    #[serde(rename = "deltaT")]
+    delta_t: Option<String>,
+    /// 输入日期的历法：gregorian（外推格里历）、julian、reform（1582 改历）、reform:YYYY-MM-DD；
+    /// 给出时按该历法解释 datetime，并在响应中附 calendar 日期
+    calendar: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct QizhengQuery {
+    /// 出生时刻
+    datetime: String,
+    /// 时区偏移（分钟），东为正 UTC+8=+480, 西为负 UTC-5=-300
+    #[serde(rename = "tzOffsetMinutes")]
+    tz_offset_minutes: Option<i32>,
+    /// 出生地纬度、经度，用于命宫
+    lat: Option<f64>,
+    lon: Option<f64>,
+    /// 二十八宿距星体系：modern（默认）、historical
+    #[serde(rename = "mansionSystem")]
+    mansion_system: Option<String>,
+    /// ΔT 模型：espenak_meeus（默认）、iers、fixed:<秒>
+    #[serde(rename = "deltaT")]
+    delta_t: Option<String>,
+    /// 输入日期的历法，同 /api/fenye
+    calendar: Option<String>,
+    /// 罗睺所配交点：ascending（默认，罗睺为升交点）、descending
+    luohou: Option<String>,
+    /// 黄白交点：mean（默认）、true
+    #[serde(rename = "nodeModel")]
+    node_model: Option<String>,
+    /// 紫气起元：起元时刻（TT 儒略日）与其时紫气平黄经（度），须同时给出；未给出时不计紫气
+    #[serde(rename = "ziqiEpochJd")]
+    ziqi_epoch_jd: Option<f64>,
+    #[serde(rename = "ziqiEpochLon")]
+    ziqi_epoch_lon: Option<f64>,
+}
+
+#[derive(Deserialize)]
+struct JieqiQuery {
+    /// 天文纪年（0 = 公元前 1 年），默认今年
+    year: Option<i32>,
+    /// 时区：IANA 时区名（如 Asia/Shanghai）、+08:00、UTC+8 或分钟数，默认 +08:00
+    tz: Option<String>,
+    /// 本地日期的历法，同 /api/fenye，默认 1582 改历
+    calendar: Option<String>,
+    /// ΔT 模型：espenak_meeus（默认）、iers、fixed:<秒>
+    #[serde(rename = "deltaT")]
+    delta_t: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct PhenomenaQuery {
+    /// 起止日期 YYYY-MM-DD（UTC，含两端），默认自今天起一年
+    #[serde(rename = "startDate")]
+    start_date: Option<NaiveDate>,
+    #[serde(rename = "endDate")]
+    end_date: Option<NaiveDate>,
+    /// 逗号分隔的天体，英文名或中文古名，默认七政
+    bodies: Option<String>,
+    /// 五星聚至少几颗星 (3–5)，默认 5
+    #[serde(rename = "minPlanets")]
+    min_planets: Option<usize>,
+    /// 五星聚最大黄经跨度（度），默认 30
+    #[serde(rename = "maxSpan")]
+    max_span: Option<f64>,
+    /// ΔT 模型：espenak_meeus（默认）、iers、fixed:<秒>
+    #[serde(rename = "deltaT")]
+    delta_t: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct CoordsQuery {
+    /// 输入坐标系：ecliptic_j2000、ecliptic_of_date、equatorial_j2000（默认）、
+    /// equatorial_mean_of_date、equatorial_of_date、galactic、horizontal
+    frame: Option<String>,
+    /// 经度类坐标（黄经、赤经、银经或方位角，度）
+    lon: f64,
+    /// 纬度类坐标（黄纬、赤纬、银纬或高度，度）
+    lat: f64,
+    /// 时刻，默认当前；无偏移时按 tzOffsetMinutes 解释
+    datetime: Option<String>,
+    #[serde(rename = "tzOffsetMinutes")]
+    tz_offset_minutes: Option<i32>,
+    /// 观测者纬度、经度，用于地平坐标，默认北京
+    #[serde(rename = "observerLat")]
+    observer_lat: Option<f64>,
+    #[serde(rename = "observerLon")]
+    observer_lon: Option<f64>,
+    /// ΔT 模型：espenak_meeus（默认）、iers、fixed:<秒>
+    #[serde(rename = "deltaT")]
+    delta_t: Option<String>,
+}
+
 #[derive(Deserialize)]
 struct GeocodeQuery {
     lat: f64,
@@ -451,9 +766,20 @@ fn parse_year_start_mode(input: Option<&str>) -> YearStartMode {
     }
 }
 
+fn invalid_local_datetime(err: impl std::fmt::Display) -> (StatusCode, Json<serde_json::Value>) {
+    (
+        StatusCode::BAD_REQUEST,
+        Json(json!({
+            "error": "invalid_local_datetime",
+            "message": err.to_string(),
+        })),
+    )
+}
+
 fn parse_datetime_or_bad_request(
     raw: &str,
-    tz_offset_minutes: i32,
+    zone: &TimeZoneSpec,
+    resolution: LocalTimeResolution,
 ) -> Result<DateTime<Utc>, (StatusCode, Json<serde_json::Value>)> {
     if let Ok(dt) = DateTime::parse_from_rfc3339(raw) {
         return Ok(dt.with_timezone(&Utc));
@@ -463,33 +789,9 @@ fn parse_datetime_or_bad_request(
         .or_else(|_| NaiveDateTime::parse_from_str(raw, "%Y-%m-%dT%H:%M"));
 
     if let Ok(naive) = naive {
-        let Some(offset) = FixedOffset::east_opt(tz_offset_minutes * 60) else {
-            return Err((
-                StatusCode::BAD_REQUEST,
-                Json(json!({
-                    "error": "invalid_tz_offset",
-                    "message": format!(
-                        "invalid tzOffsetMinutes '{}' for datetime '{}'",
-                        tz_offset_minutes, raw
-                    ),
-                })),
-            ));
-        };
-
-        let Some(local_dt) = offset.from_local_datetime(&naive).single() else {
-            return Err((
-                StatusCode::BAD_REQUEST,
-                Json(json!({
-                    "error": "invalid_local_datetime",
-                    "message": format!(
-                        "cannot resolve local datetime '{}' with tzOffsetMinutes '{}'",
-                        raw, tz_offset_minutes
-                    ),
-                })),
-            ));
-        };
-
-        return Ok(local_dt.with_timezone(&Utc));
+        return zone
+            .from_local(&naive, resolution)
+            .map_err(invalid_local_datetime);
     }
 
     Err((
@@ -504,16 +806,190 @@ fn parse_datetime_or_bad_request(
     ))
 }
 
+fn parse_mansion_system(
+    raw: Option<&str>,
+) -> Result<DeterminativeSystem, (StatusCode, Json<serde_json::Value>)> {
+    raw.map(DeterminativeSystem::parse)
+        .transpose()
+        .map_err(|err| {
+            (
+                StatusCode::BAD_REQUEST,
+                Json(json!({
+                    "error": "invalid_mansion_system",
+                    "message": err.to_string(),
+                })),
+            )
+        })
+        .map(Option::unwrap_or_default)
+}
+
+/// 时区：给出 timezone 时按其解析，否则取 tzOffsetMinutes
+fn parse_time_zone(
+    timezone: Option<&str>,
+    tz_offset_minutes: i32,
+) -> Result<TimeZoneSpec, (StatusCode, Json<serde_json::Value>)> {
+    match timezone.map(str::trim).filter(|raw| !raw.is_empty()) {
+        Some(raw) => TimeZoneSpec::parse(raw).map_err(|err| {
+            (
+                StatusCode::BAD_REQUEST,
+                Json(json!({
+                    "error": "invalid_timezone",
+                    "message": err.to_string(),
+                })),
+            )
+        }),
+        None => TimeZoneSpec::from_offset_minutes(tz_offset_minutes).map_err(|_| {
+            (
+                StatusCode::BAD_REQUEST,
+                Json(json!({
+                    "error": "invalid_tz_offset",
+                    "message": format!("invalid tzOffsetMinutes '{}'", tz_offset_minutes),
+                })),
+            )
+        }),
+    }
+}
+
+fn parse_siyu_convention(
+    luohou: Option<&str>,
+    node_model: Option<&str>,
+    ziqi_epoch_jd: Option<f64>,
+    ziqi_epoch_lon: Option<f64>,
+) -> Result<SiyuConvention, (StatusCode, Json<serde_json::Value>)> {
+    fn bad_request(err: impl std::fmt::Display) -> (StatusCode, Json<serde_json::Value>) {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(json!({
+                "error": "invalid_siyu_convention",
+                "message": err.to_string(),
+            })),
+        )
+    }
+    Ok(SiyuConvention {
+        node_convention: luohou
+            .map(NodeConvention::parse)
+            .transpose()
+            .map_err(bad_request)?
+            .unwrap_or_default(),
+        node_model: node_model
+            .map(NodeModel::parse)
+            .transpose()
+            .map_err(bad_request)?
+            .unwrap_or_default(),
+        ziqi_epoch: match (ziqi_epoch_jd, ziqi_epoch_lon) {
+            (Some(jd_tt), Some(longitude_deg))
+                if jd_tt.is_finite() && longitude_deg.is_finite() =>
+            {
+                Some(ZiqiEpoch {
+                    jd_tt,
+                    longitude_deg,
+                })
+            }
+            (None, None) => None,
+            _ => {
+                return Err(bad_request(
+                    "紫气起元须同时给出有效的 ziqiEpochJd 与 ziqiEpochLon",
+                ))
+            }
+        },
+    })
+}
+
+fn parse_calendar_system(
+    raw: Option<&str>,
+) -> Result<Option<CalendarSystem>, (StatusCode, Json<serde_json::Value>)> {
+    raw.map(CalendarSystem::parse).transpose().map_err(|err| {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(json!({
+                "error": "invalid_calendar",
+                "message": err.to_string(),
+            })),
+        )
+    })
+}
+
+/// 按指定历法解释日期（可含公元前年份）；无偏移时按所给时区解释
+fn parse_calendar_datetime_or_bad_request(
+    raw: &str,
+    zone: &TimeZoneSpec,
+    resolution: LocalTimeResolution,
+    calendar: &CalendarSystem,
+) -> Result<DateTime<Utc>, (StatusCode, Json<serde_json::Value>)> {
+    let (naive, offset) = calendar.parse_datetime(raw).map_err(|err| {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(json!({
+                "error": "invalid_datetime",
+                "message": err.to_string(),
+            })),
+        )
+    })?;
+    let Some(offset_minutes) = offset else {
+        return zone
+            .from_local(&naive, resolution)
+            .map_err(invalid_local_datetime);
+    };
+    FixedOffset::east_opt(offset_minutes * 60)
+        .and_then(|offset| offset.from_local_datetime(&naive).single())
+        .map(|local_dt| local_dt.with_timezone(&Utc))
+        .ok_or_else(|| {
+            (
+                StatusCode::BAD_REQUEST,
+                Json(json!({
+                    "error": "invalid_tz_offset",
+                    "message": format!(
+                        "invalid tzOffsetMinutes '{}' for datetime '{}'",
+                        offset_minutes, raw
+                    ),
+                })),
+            )
+        })
+}
+
+/// 响应中的历法日期：本地时刻在所请求历法下的写法
+fn calendar_date_json(
+    datetime_utc: DateTime<Utc>,
+    utc_offset_seconds: i32,
+    calendar: &CalendarSystem,
+) -> serde_json::Value {
+    let local = datetime_utc.naive_utc() + chrono::Duration::seconds(utc_offset_seconds as i64);
+    let date = calendar.from_naive(local.date());
+    json!({
+        "system": calendar.name(),
+        "calendar": date.calendar,
+        "date": date.to_string(),
+        "time": local.time().format("%H:%M:%S").to_string(),
+        "label": date.label_zh(),
+        "jdn": date.jdn(),
+    })
+}
+
 fn parse_query_datetime(
     raw: &str,
     tz_offset_minutes: i32,
+    calendar: Option<&CalendarSystem>,
+) -> Result<DateTime<Utc>, (StatusCode, Json<serde_json::Value>)> {
+    let zone = parse_time_zone(None, tz_offset_minutes)?;
+    parse_zoned_query_datetime(raw, &zone, LocalTimeResolution::default(), calendar)
+}
+
+/// 同 [`parse_query_datetime`]，本地时间按时区（可为 IANA 时区）的历史偏移解释
+fn parse_zoned_query_datetime(
+    raw: &str,
+    zone: &TimeZoneSpec,
+    resolution: LocalTimeResolution,
+    calendar: Option<&CalendarSystem>,
 ) -> Result<DateTime<Utc>, (StatusCode, Json<serde_json::Value>)> {
-    let parsed = parse_datetime_or_bad_request(raw, tz_offset_minutes);
+    let parsed = match calendar {
+        Some(calendar) => parse_calendar_datetime_or_bad_request(raw, zone, resolution, calendar),
+        None => parse_datetime_or_bad_request(raw, zone, resolution),
+    };
     if let Err((status, body)) = &parsed {
         tracing::warn!(
-            "❌ 日期时间解析失败: datetime={}, tzOffsetMinutes={}, status={}, error={:?}",
+            "❌ 日期时间解析失败: datetime={}, timezone={}, status={}, error={:?}",
             raw,
-            tz_offset_minutes,
+            zone,
             status,
             body.0
         );
@@ -534,18 +1010,32 @@ async fn get_sky_and_fortune(
     Query(params): Query<SkyFortuneQuery>,
 ) -> Result<Json<serde_json::Value>, (StatusCode, Json<serde_json::Value>)> {
     let tz_offset_minutes = params.tz_offset_minutes.unwrap_or(480);
+    let zone = parse_time_zone(params.timezone.as_deref(), tz_offset_minutes)?;
+    let resolution = parse_local_time_resolution(params.local_time_resolution.as_deref())?;
     let lat = params.lat.unwrap_or(39.9);
     let lon = params.lon.unwrap_or(116.4);
-    let use_true_solar_time = params.use_true_solar_time.unwrap_or(false);
+    let solar_time = parse_solar_time(
+        params.solar_time.as_deref(),
+        SolarTimePolicy::from_true_solar_flag(params.use_true_solar_time.unwrap_or(false)),
+    )?;
     let mode = parse_calc_mode(params.mode.as_deref());
     let primary = parse_primary_mode(params.primary.as_deref());
     let year_start = parse_year_start_mode(params.year_start.as_deref());
+    let delta_t_model = parse_delta_t_model(params.delta_t.as_deref())?;
+    let mansion_system = parse_mansion_system(params.mansion_system.as_deref())?;
+    let calendar = parse_calendar_system(params.calendar.as_deref())?;
+    let siyu = parse_siyu_convention(
+        params.luohou.as_deref(),
+        params.node_model.as_deref(),
+        params.ziqi_epoch_jd,
+        params.ziqi_epoch_lon,
+    )?;
 
     tracing::info!(
-        "🌟 获取天象运势: datetime={}, tzOffsetMinutes={}, useTrueSolarTime={}, lat={}, lon={}, mode={:?}, primary={:?}, yearStart={:?}",
+        "🌟 获取天象运势: datetime={}, timezone={}, solarTime={}, lat={}, lon={}, mode={:?}, primary={:?}, yearStart={:?}",
         params.datetime,
-        tz_offset_minutes,
-        use_true_solar_time,
+        zone,
+        solar_time.name(),
         lat,
         lon,
         mode,
@@ -553,31 +1043,57 @@ async fn get_sky_and_fortune(
         year_start
     );
 
-    // 解析输入时间：优先 RFC3339（带 Z 或 offset），否则按“本地时间 + tzOffsetMinutes”解释
-    let datetime_utc = parse_query_datetime(&params.datetime, tz_offset_minutes)?;
+    // 解析输入时间：优先 RFC3339（带 Z 或 offset），否则按“本地时间 + 时区”解释
+    let datetime_utc =
+        parse_zoned_query_datetime(&params.datetime, &zone, resolution, calendar.as_ref())?;
 
     let sky_resp = compute_sky(&SkyRequest {
         datetime: datetime_utc,
         lat_deg: lat,
         lon_deg: lon,
-        delta_t_provider: None,
+        elevation_m: params.elevation.unwrap_or(0.0),
+        pressure_hpa: params.pressure,
+        temperature_c: params.temperature,
+        delta_t_model,
         accuracy: None,
-    });
+        star_mag_limit: params.star_mag_limit,
+        star_min_alt_deg: params.star_min_alt,
+        mansion_system,
+        siyu,
+    })
+    .map_err(|err| {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(json!({
+                "error": "invalid_atmosphere",
+                "message": err.to_string(),
+            })),
+        )
+    })?;
 
     let fortune_resp = compute_fortune(&FortuneRequest {
         datetime: datetime_utc,
         tz_offset_minutes: Some(tz_offset_minutes),
+        timezone: Some(zone),
         lon: Some(lon),
-        use_true_solar_time: Some(use_true_solar_time),
+        use_true_solar_time: params.use_true_solar_time,
+        solar_time: Some(solar_time),
         mode: Some(mode),
         year_start: Some(year_start),
+        calendar,
         primary: Some(primary),
+        delta_t_model,
     });
 
-    Ok(Json(json!({
+    let mut body = json!({
         "sky": sky_resp,
         "fortune": fortune_resp
-    })))
+    });
+    if let Some(calendar) = &calendar {
+        let utc_offset_seconds = zone.offset_at(&datetime_utc).local_minus_utc();
+        body["calendar"] = calendar_date_json(datetime_utc, utc_offset_seconds, calendar);
+    }
+    Ok(Json(body))
 }
 
 // 获取历史相关事件 - 返回纯数组，不是对象
@@ -643,19 +1159,477 @@ async fn get_mapping(Query(params): Query<MappingQuery>) -> impl IntoResponse {
     }))
 }
 
+// 获取公历月历（逐日农历、干支、节气、建除、节日）
+async fn get_calendar_month(
+    Query(params): Query<CalendarMonthQuery>,
+) -> Result<Json<serde_json::Value>, (StatusCode, Json<serde_json::Value>)> {
+    let tz_offset_minutes = params.tz_offset_minutes.unwrap_or(480);
+    let today = FixedOffset::east_opt(tz_offset_minutes * 60)
+        .map(|offset| Utc::now().with_timezone(&offset).date_naive())
+        .unwrap_or_else(|| Utc::now().date_naive());
+    let year = params.year.unwrap_or_else(|| today.year());
+    let month = params.month.unwrap_or_else(|| today.month());
+    let observer = match (params.lat, params.lon) {
+        (Some(lat), Some(lon)) => Some(Observer::new(lat, lon)),
+        _ => None,
+    };
+    let delta_t_model = parse_delta_t_model(params.delta_t.as_deref())?;
+
+    tracing::debug!(
+        "📆 获取月历: year={}, month={}, tzOffsetMinutes={}, observer={:?}",
+        year,
+        month,
+        tz_offset_minutes,
+        observer
+    );
+
+    let calendar = compute_month_calendar(
+        year,
+        month,
+        tz_offset_minutes,
+        observer,
+        delta_t_model.provider().as_ref(),
+    )
+    .map_err(|err| {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(json!({
+                "error": "invalid_calendar_request",
+                "message": err.to_string(),
+            })),
+        )
+    })?;
+
+    Ok(Json(json!(calendar)))
+}
+
+// iCalendar 订阅：节气、节日、朔望及旬/世交界
+async fn get_calendar_ics(
+    Query(params): Query<CalendarIcsQuery>,
+) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    fn bad_request(err: impl std::fmt::Display) -> (StatusCode, Json<serde_json::Value>) {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(json!({
+                "error": "invalid_calendar_request",
+                "message": err.to_string(),
+            })),
+        )
+    }
+    let zone = match params.tz.as_deref() {
+        Some(raw) => TimeZoneSpec::parse(raw),
+        None => TimeZoneSpec::from_offset_minutes(params.tz_offset_minutes.unwrap_or(480)),
+    }
+    .map_err(bad_request)?;
+    let start_year = params
+        .start_year
+        .unwrap_or_else(|| zone.to_local(&Utc::now()).year());
+    let end_year = params.end_year.unwrap_or(start_year);
+    let delta_t_model = parse_delta_t_model(params.delta_t.as_deref())?;
+
+    tracing::debug!(
+        "📅 导出 ICS: startYear={}, endYear={}, tz={}, includeHuangji={:?}",
+        start_year,
+        end_year,
+        zone,
+        params.include_huangji
+    );
+
+    let request = IcsRequest {
+        start_year,
+        end_year,
+        zone,
+        lat_deg: params.lat,
+        lon_deg: params.lon,
+        include_huangji: params.include_huangji.unwrap_or(false),
+        delta_t_model,
+    };
+    let ics = generate_ics(&request).map_err(bad_request)?;
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, "text/calendar; charset=utf-8"),
+            (
+                header::CONTENT_DISPOSITION,
+                "inline; filename=\"huangji-calendar.ics\"",
+            ),
+        ],
+        ics,
+    ))
+}
+
+// 择日：按宜忌、冲煞、建除筛选并排序候选日
+async fn get_zeri(
+    Query(params): Query<ZeriQuery>,
+) -> Result<Json<serde_json::Value>, (StatusCode, Json<serde_json::Value>)> {
+    tracing::debug!(
+        "🗓️ 择日: {}~{}, activity={:?}, birthBranch={:?}, officers={:?}",
+        params.start_date,
+        params.end_date,
+        params.activity,
+        params.birth_branch,
+        params.officers
+    );
+    let delta_t_model = parse_delta_t_model(params.delta_t.as_deref())?;
+
+    let request = ZeriRequest {
+        start_date: params.start_date,
+        end_date: params.end_date,
+        tz_offset_minutes: params.tz_offset_minutes.unwrap_or(480),
+        activity: params.activity.filter(|a| !a.trim().is_empty()),
+        birth_branch: params.birth_branch.filter(|b| !b.trim().is_empty()),
+        preferred_officers: params
+            .officers
+            .as_deref()
+            .unwrap_or("")
+            .split(',')
+            .map(str::trim)
+            .filter(|o| !o.is_empty())
+            .map(str::to_string)
+            .collect(),
+        avoid_month_break: params.avoid_month_break.unwrap_or(false),
+        avoid_year_break: params.avoid_year_break.unwrap_or(false),
+        limit: params.limit,
+        delta_t_model,
+    };
+    let result = select_dates(&request).map_err(|err| {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(json!({
+                "error": "invalid_zeri_request",
+                "message": err.to_string(),
+            })),
+        )
+    })?;
+
+    Ok(Json(json!(result)))
+}
+
+// 日月食：全局食甚、食分与类型，给出观测地时附当地情况
+async fn get_eclipses(
+    Query(params): Query<EclipseQuery>,
+) -> Result<Json<serde_json::Value>, (StatusCode, Json<serde_json::Value>)> {
+    let start_year = params.start_year.unwrap_or_else(|| Utc::now().year());
+    let end_year = params.end_year.unwrap_or(start_year);
+    let observer = match (params.lat, params.lon) {
+        (Some(lat), Some(lon)) => Some(Observer {
+            lat_deg: lat,
+            lon_deg: lon,
+            elevation_m: params.elevation.unwrap_or(0.0),
+        }),
+        _ => None,
+    };
+    let delta_t_model = parse_delta_t_model(params.delta_t.as_deref())?;
+    let delta_t = delta_t_model.provider();
+
+    tracing::debug!(
+        "🌘 日月食: startYear={}, endYear={}, observer={:?}",
+        start_year,
+        end_year,
+        observer
+    );
+
+    let eclipses = find_eclipses(start_year, end_year, observer.as_ref(), delta_t.as_ref())
+        .map_err(|err| {
+            (
+                StatusCode::BAD_REQUEST,
+                Json(json!({
+                    "error": "invalid_eclipse_request",
+                    "message": err.to_string(),
+                })),
+            )
+        })?;
+    let events: Vec<serde_json::Value> = eclipses.iter().map(|e| e.to_event()).collect();
+
+    Ok(Json(json!({
+        "start_year": start_year,
+        "end_year": end_year,
+        "eclipses": eclipses,
+        "events": events,
+        "delta_t_provider": delta_t.name(),
+    })))
+}
+
+// 出没：日、月、行星的升、中天、落及晨昏蒙影
+async fn get_rise_set(
+    Query(params): Query<RiseSetQuery>,
+) -> Result<Json<serde_json::Value>, (StatusCode, Json<serde_json::Value>)> {
+    let tz_offset_minutes = params.tz_offset_minutes.unwrap_or(480);
+    let date = params.date.unwrap_or_else(|| {
+        FixedOffset::east_opt(tz_offset_minutes * 60)
+            .map(|offset| Utc::now().with_timezone(&offset).date_naive())
+            .unwrap_or_else(|| Utc::now().date_naive())
+    });
+    let observer = Observer {
+        lat_deg: params.lat.unwrap_or(39.9),
+        lon_deg: params.lon.unwrap_or(116.4),
+        elevation_m: params.elevation.unwrap_or(0.0),
+    };
+    let delta_t_model = parse_delta_t_model(params.delta_t.as_deref())?;
+
+    tracing::debug!(
+        "🌅 出没: date={}, tzOffsetMinutes={}, observer={:?}",
+        date,
+        tz_offset_minutes,
+        observer
+    );
+
+    let response = compute_rise_set(&RiseSetRequest {
+        date,
+        tz_offset_minutes,
+        observer,
+        delta_t_model,
+    })
+    .map_err(|err| {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(json!({
+                "error": "invalid_rise_set_request",
+                "message": err.to_string(),
+            })),
+        )
+    })?;
+
+    Ok(Json(json!(response)))
+}
+
+// 分野：七政所在之宿、次及受应之地
+async fn get_fenye(
+    Query(params): Query<FenyeQuery>,
+) -> Result<Json<serde_json::Value>, (StatusCode, Json<serde_json::Value>)> {
+    let tz_offset_minutes = params.tz_offset_minutes.unwrap_or(480);
+    let mansion_system = parse_mansion_system(params.mansion_system.as_deref())?;
+    let delta_t_model = parse_delta_t_model(params.delta_t.as_deref())?;
+    let calendar = parse_calendar_system(params.calendar.as_deref())?;
+    let datetime = parse_query_datetime(&params.datetime, tz_offset_minutes, calendar.as_ref())?;
+
+    tracing::debug!(
+        "🗺️ 分野: datetime={}, mansionSystem={:?}",
+        datetime,
+        mansion_system
+    );
+
+    let report = compute_fenye(&FenyeRequest {
+        datetime,
+        mansion_system,
+        delta_t_model,
+    });
+
+    let mut body = json!(report);
+    if let Some(calendar) = &calendar {
+        body["calendar"] = calendar_date_json(datetime, tz_offset_minutes * 60, calendar);
+    }
+    Ok(Json(body))
+}
+
+// 七政四余：十一曜所入之宿与所落之宫
+async fn get_qizheng(
+    Query(params): Query<QizhengQuery>,
+) -> Result<Json<serde_json::Value>, (StatusCode, Json<serde_json::Value>)> {
+    let tz_offset_minutes = params.tz_offset_minutes.unwrap_or(480);
+    let mansion_system = parse_mansion_system(params.mansion_system.as_deref())?;
+    let delta_t_model = parse_delta_t_model(params.delta_t.as_deref())?;
+    let calendar = parse_calendar_system(params.calendar.as_deref())?;
+    let siyu = parse_siyu_convention(
+        params.luohou.as_deref(),
+        params.node_model.as_deref(),
+        params.ziqi_epoch_jd,
+        params.ziqi_epoch_lon,
+    )?;
+    let datetime = parse_query_datetime(&params.datetime, tz_offset_minutes, calendar.as_ref())?;
+    let lat = params.lat.unwrap_or(39.9);
+    let lon = params.lon.unwrap_or(116.4);
+
+    tracing::debug!(
+        "🪐 七政四余: datetime={}, lat={}, lon={}, siyu={:?}",
+        datetime,
+        lat,
+        lon,
+        siyu
+    );
+
+    let chart = compute_qizheng(&QizhengRequest {
+        datetime,
+        lat_deg: lat,
+        lon_deg: lon,
+        mansion_system,
+        delta_t_model,
+        siyu,
+    });
+
+    let mut body = json!(chart);
+    if let Some(calendar) = &calendar {
+        body["calendar"] = calendar_date_json(datetime, tz_offset_minutes * 60, calendar);
+    }
+    Ok(Json(body))
+}
+
+// 二十四节气：某年各节气的本地交节时刻
+async fn get_jieqi(
+    Query(params): Query<JieqiQuery>,
+) -> Result<Json<serde_json::Value>, (StatusCode, Json<serde_json::Value>)> {
+    fn bad_request(err: impl std::fmt::Display) -> (StatusCode, Json<serde_json::Value>) {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(json!({
+                "error": "invalid_jieqi_request",
+                "message": err.to_string(),
+            })),
+        )
+    }
+    let zone =
+        TimeZoneSpec::parse(params.tz.as_deref().unwrap_or_default()).map_err(bad_request)?;
+    let calendar = parse_calendar_system(params.calendar.as_deref())?.unwrap_or_default();
+    let delta_t_model = parse_delta_t_model(params.delta_t.as_deref())?;
+    let year = params
+        .year
+        .unwrap_or_else(|| zone.to_local(&Utc::now()).year());
+
+    tracing::debug!(
+        "🌱 二十四节气: year={}, tz={}, calendar={}",
+        year,
+        zone,
+        calendar.name()
+    );
+
+    let terms = solar_terms_of_year(year, &zone, &calendar, delta_t_model.provider().as_ref())
+        .map_err(bad_request)?;
+    Ok(Json(json!(terms)))
+}
+
+// 行星天象：合、冲、大距、留与五星聚
+async fn get_phenomena(
+    Query(params): Query<PhenomenaQuery>,
+) -> Result<Json<serde_json::Value>, (StatusCode, Json<serde_json::Value>)> {
+    fn bad_request(err: impl std::fmt::Display) -> (StatusCode, Json<serde_json::Value>) {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(json!({
+                "error": "invalid_phenomena_request",
+                "message": err.to_string(),
+            })),
+        )
+    }
+    let start = params.start_date.unwrap_or_else(|| Utc::now().date_naive());
+    let end = params
+        .end_date
+        .unwrap_or_else(|| start + chrono::Duration::days(365));
+    let bodies = params
+        .bodies
+        .as_deref()
+        .map(|raw| {
+            raw.split(',')
+                .filter(|s| !s.trim().is_empty())
+                .map(SkyBody::parse)
+                .collect::<Result<Vec<_>, _>>()
+        })
+        .transpose()
+        .map_err(bad_request)?;
+    let defaults = GroupingOptions::default();
+    let grouping = GroupingOptions {
+        min_planets: params.min_planets.unwrap_or(defaults.min_planets),
+        max_span_deg: params.max_span.unwrap_or(defaults.max_span_deg),
+    };
+    let delta_t_model = parse_delta_t_model(params.delta_t.as_deref())?;
+
+    tracing::debug!(
+        "🪐 行星天象: startDate={}, endDate={}, bodies={:?}, grouping={:?}",
+        start,
+        end,
+        bodies,
+        grouping
+    );
+
+    let phenomena = find_phenomena(&PhenomenaRequest {
+        start,
+        end,
+        bodies,
+        grouping,
+        delta_t_model,
+    })
+    .map_err(bad_request)?;
+    let events: Vec<serde_json::Value> = phenomena.iter().map(|p| p.to_event()).collect();
+
+    Ok(Json(json!({
+        "start_date": start,
+        "end_date": end,
+        "phenomena": phenomena,
+        "events": events,
+    })))
+}
+
+// 坐标变换：黄道、赤道、银道、地平
+async fn get_coords(
+    Query(params): Query<CoordsQuery>,
+) -> Result<Json<serde_json::Value>, (StatusCode, Json<serde_json::Value>)> {
+    fn bad_request(err: impl std::fmt::Display) -> (StatusCode, Json<serde_json::Value>) {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(json!({
+                "error": "invalid_coords_request",
+                "message": err.to_string(),
+            })),
+        )
+    }
+    let tz_offset_minutes = params.tz_offset_minutes.unwrap_or(480);
+    let frame =
+        CoordinateFrame::parse(params.frame.as_deref().unwrap_or_default()).map_err(bad_request)?;
+    let datetime = match params.datetime.as_deref() {
+        Some(raw) => parse_query_datetime(raw, tz_offset_minutes, None)?,
+        None => Utc::now(),
+    };
+    let observer = Observer::new(
+        params.observer_lat.unwrap_or(39.9),
+        params.observer_lon.unwrap_or(116.4),
+    );
+    let delta_t_model = parse_delta_t_model(params.delta_t.as_deref())?;
+    let scales =
+        TimeScales::from_jd_utc_with(utc_to_jd(&datetime), delta_t_model.provider().as_ref());
+
+    tracing::debug!(
+        "🧭 坐标变换: frame={:?}, lon={}, lat={}, datetime={}",
+        frame,
+        params.lon,
+        params.lat,
+        datetime
+    );
+
+    let set = convert_coordinates(frame, params.lon, params.lat, &scales, &observer)
+        .map_err(bad_request)?;
+    Ok(Json(json!({
+        "datetime": datetime,
+        "observer": observer,
+        "coordinates": set,
+    })))
+}
+
 // 获取时间线
 async fn get_timeline(
     Query(params): Query<TimelineQuery>,
 ) -> Result<Json<serde_json::Value>, (StatusCode, Json<serde_json::Value>)> {
     let tz_offset_minutes = params.tz_offset_minutes.unwrap_or(480);
+    let zone = parse_time_zone(params.timezone.as_deref(), tz_offset_minutes)?;
+    let resolution = parse_local_time_resolution(params.local_time_resolution.as_deref())?;
     let lon = params.lon.unwrap_or(116.4);
     let mode = parse_calc_mode(params.mode.as_deref());
     let primary = parse_primary_mode(params.primary.as_deref());
     let year_start = parse_year_start_mode(params.year_start.as_deref());
+    let solar_time = parse_solar_time(params.solar_time.as_deref(), SolarTimePolicy::Meridian)?;
 
-    let datetime_utc = parse_query_datetime(&params.datetime, tz_offset_minutes)?;
+    let calendar = parse_calendar_system(params.calendar.as_deref())?;
+    let delta_t_model = parse_delta_t_model(params.delta_t.as_deref())?;
+    let datetime_utc =
+        parse_zoned_query_datetime(&params.datetime, &zone, resolution, calendar.as_ref())?;
 
-    let fallback_hj_year = utc_to_hj_year(datetime_utc, tz_offset_minutes, lon, false, year_start);
+    let fallback_hj_year = utc_to_hj_year(
+        datetime_utc,
+        &zone,
+        lon,
+        solar_time,
+        year_start,
+        &calendar.unwrap_or_default(),
+        delta_t_model.provider().as_ref(),
+    );
 
     tracing::debug!(
         "📅 查询时间线: mode={:?}, primary={:?}, year_start={:?}",
@@ -667,11 +1641,15 @@ async fn get_timeline(
     let fortune = compute_fortune(&FortuneRequest {
         datetime: datetime_utc,
         tz_offset_minutes: Some(tz_offset_minutes),
+        timezone: Some(zone),
         lon: Some(lon),
         use_true_solar_time: Some(false),
+        solar_time: Some(solar_time),
         mode: Some(mode),
         year_start: Some(year_start),
+        calendar,
         primary: Some(primary),
+        delta_t_model,
     });
 
     let hj_year = fortune
@@ -706,7 +1684,7 @@ async fn get_timeline(
         None
     };
 
-    Ok(Json(json!({
+    let mut body = json!({
         "year": hj_year,
         "current": primary_timeline.current,
         "yuan_list": primary_timeline.yuan_list,
@@ -727,7 +1705,12 @@ async fn get_timeline(
             "algorithm": algorithm_timeline,
             "table": table_timeline
         }
-    })))
+    });
+    if let Some(calendar) = &calendar {
+        let utc_offset_seconds = zone.offset_at(&datetime_utc).local_minus_utc();
+        body["calendar"] = calendar_date_json(datetime_utc, utc_offset_seconds, calendar);
+    }
+    Ok(Json(body))
 }
 
 // 获取历史数据 - 返回数组格式
@@ -981,7 +1964,14 @@ async fn get_geoip() -> impl IntoResponse {
 
 #[cfg(test)]
 mod tests {
-    use super::{extract_events_array, index_events_by_year, parse_query_datetime};
+    use super::{
+        calendar_date_json, extract_events_array, index_events_by_year, parse_query_datetime,
+        parse_siyu_convention, parse_solar_time, parse_time_zone, parse_zoned_query_datetime,
+        CalendarSystem, LocalTimeResolution, NodeConvention, NodeModel, SolarTimePolicy,
+        TimeZoneSpec,
+    };
+    use super::{get_sky_and_fortune, get_timeline, StatusCode};
+    use axum::extract::Query;
     use serde_json::json;
 
     #[test]
@@ -1058,13 +2048,146 @@ mod tests {
 
     #[test]
     fn parse_query_datetime_accepts_local_iso_with_offset() {
-        let parsed = parse_query_datetime("2026-06-01T08:00:00", 480).expect("valid datetime");
+        let parsed =
+            parse_query_datetime("2026-06-01T08:00:00", 480, None).expect("valid datetime");
         assert_eq!(parsed.to_rfc3339(), "2026-06-01T00:00:00+00:00");
     }
 
+    #[test]
+    fn parse_zoned_query_datetime_uses_historical_offsets() {
+        let shanghai = TimeZoneSpec::parse("Asia/Shanghai").unwrap();
+        let parse = |raw: &str, resolution: LocalTimeResolution| {
+            parse_zoned_query_datetime(raw, &shanghai, resolution, None)
+        };
+        // 1988 年夏令时 +09:00
+        let summer = parse("1988-07-01T08:00", LocalTimeResolution::Reject).expect("summer");
+        assert_eq!(summer.to_rfc3339(), "1988-06-30T23:00:00+00:00");
+        // 1988-09-11 01:30 出现两次
+        let fold = parse("1988-09-11T01:30", LocalTimeResolution::Later).expect("fold");
+        assert_eq!(fold.to_rfc3339(), "1988-09-10T17:30:00+00:00");
+        let (_, body) = parse("1988-09-11T01:30", LocalTimeResolution::Reject).unwrap_err();
+        assert_eq!(body.0["error"], "invalid_local_datetime");
+
+        let (_, body) = parse_time_zone(Some("Mars/Olympus"), 480).unwrap_err();
+        assert_eq!(body.0["error"], "invalid_timezone");
+        assert_eq!(
+            parse_time_zone(None, -300).unwrap(),
+            TimeZoneSpec::parse("-05:00").unwrap()
+        );
+    }
+
     #[test]
     fn parse_query_datetime_rejects_invalid_input() {
-        let parsed = parse_query_datetime("not-a-date", 480);
+        let parsed = parse_query_datetime("not-a-date", 480, None);
         assert!(parsed.is_err());
     }
+
+    #[test]
+    fn parse_query_datetime_accepts_julian_dates() {
+        let calendar = CalendarSystem::parse("reform").unwrap();
+        // 儒略历 1582-10-04 12:00 (UTC+8) = 外推格里历 1582-10-14 04:00Z
+        let parsed = parse_query_datetime("1582-10-04T12:00", 480, Some(&calendar))
+            .expect("valid julian datetime");
+        assert_eq!(parsed.to_rfc3339(), "1582-10-14T04:00:00+00:00");
+        let echoed = calendar_date_json(parsed, 480 * 60, &calendar);
+        assert_eq!(echoed["date"], "1582-10-04");
+        assert_eq!(echoed["calendar"], "julian");
+
+        let bce = parse_query_datetime("-0719-02-22T12:00:00Z", 480, Some(&calendar))
+            .expect("valid bce datetime");
+        assert_eq!(calendar_date_json(bce, 0, &calendar)["date"], "-0719-02-22");
+        assert!(parse_query_datetime("1582-10-10T12:00", 480, Some(&calendar)).is_err());
+    }
+
+    #[test]
+    fn parse_solar_time_prefers_policy_over_legacy_flag() {
+        let legacy = SolarTimePolicy::from_true_solar_flag;
+        assert_eq!(
+            parse_solar_time(None, legacy(false)).unwrap(),
+            SolarTimePolicy::Meridian
+        );
+        // 旧参数 useTrueSolarTime 历来只按经度校正
+        assert_eq!(
+            parse_solar_time(None, legacy(true)).unwrap(),
+            SolarTimePolicy::Mean
+        );
+        assert_eq!(
+            parse_solar_time(Some("apparent"), legacy(true)).unwrap(),
+            SolarTimePolicy::Apparent
+        );
+        let (_, body) = parse_solar_time(Some("sidereal"), legacy(false)).unwrap_err();
+        assert_eq!(body.0["error"], "invalid_solar_time");
+    }
+
+    #[test]
+    fn parse_siyu_convention_defaults_and_rejects() {
+        let default = parse_siyu_convention(None, None, None, None).expect("defaults");
+        assert_eq!(default.node_convention, NodeConvention::LuohouAscending);
+        assert_eq!(default.node_model, NodeModel::Mean);
+        assert_eq!(default.ziqi_epoch, None);
+        let swapped = parse_siyu_convention(
+            Some("descending"),
+            Some("true"),
+            Some(2451545.0),
+            Some(12.5),
+        )
+        .expect("valid convention");
+        assert_eq!(swapped.node_convention, NodeConvention::LuohouDescending);
+        assert_eq!(swapped.node_model, NodeModel::True);
+        assert_eq!(swapped.ziqi_epoch.map(|e| e.longitude_deg), Some(12.5));
+        let (_, body) = parse_siyu_convention(Some("north"), None, None, None).unwrap_err();
+        assert_eq!(body.0["error"], "invalid_siyu_convention");
+        let (_, body) = parse_siyu_convention(None, None, None, Some(12.5)).unwrap_err();
+        assert_eq!(body.0["error"], "invalid_siyu_convention");
+    }
+
+    #[tokio::test]
+    async fn calendar_param_sets_gregorian_new_year_before_reform() {
+        fn uri(path: &str, query: &str) -> axum::http::Uri {
+            format!("{}?{}", path, query).parse().expect("uri")
+        }
+        // 格里历 1500-01-05 = 儒略历 1499-12-27：默认改历（儒略历岁首）仍属 1499 年
+        for (calendar, expected) in [("", 1499), ("&calendar=gregorian", 1500)] {
+            let query = format!(
+                "datetime=1500-01-05T12:00&yearStart=gregorian&mode=algorithm{}",
+                calendar
+            );
+            let timeline =
+                get_timeline(Query::try_from_uri(&uri("/api/timeline", &query)).unwrap())
+                    .await
+                    .expect("timeline");
+            assert_eq!(timeline.0["calc_meta"]["hj_year"], expected, "{}", query);
+            let combined = get_sky_and_fortune(
+                Query::try_from_uri(&uri("/api/sky-and-fortune", &query)).unwrap(),
+            )
+            .await
+            .expect("sky-and-fortune");
+            assert_eq!(
+                combined.0["fortune"]["calc_meta"]["hj_year"], expected,
+                "{}",
+                query
+            );
+        }
+        // 改历日提前至 1400 年：1500 年已用格里历岁首
+        let query = "datetime=1500-01-05T12:00&yearStart=gregorian&mode=algorithm&calendar=reform:1400-01-01";
+        let timeline = get_timeline(Query::try_from_uri(&uri("/api/timeline", query)).unwrap())
+            .await
+            .expect("timeline");
+        assert_eq!(timeline.0["calc_meta"]["hj_year"], 1500);
+    }
+
+    #[tokio::test]
+    async fn sky_and_fortune_rejects_unphysical_atmosphere() {
+        for query in ["temperature=-300", "temperature=-273.15", "pressure=-1"] {
+            let uri: axum::http::Uri =
+                format!("/api/sky-and-fortune?datetime=2024-06-01T12:00&{}", query)
+                    .parse()
+                    .expect("uri");
+            let (status, body) = get_sky_and_fortune(Query::try_from_uri(&uri).unwrap())
+                .await
+                .expect_err("bad atmosphere");
+            assert_eq!(status, StatusCode::BAD_REQUEST, "{}", query);
+            assert_eq!(body.0["error"], "invalid_atmosphere", "{}", query);
+        }
+    }
 }
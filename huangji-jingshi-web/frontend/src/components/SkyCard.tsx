@@ -36,6 +36,8 @@ const SkyCard: React.FC<SkyCardProps> = ({ data, date, lat, lon, timezone, conta
     Mars: '火星',
     Jupiter: '木星',
     Saturn: '土星',
+    Uranus: '天王星',
+    Neptune: '海王星',
   };
   const rename = (n: string) => (renameZh ? nameMap[n] || n : n);
   const sun = data.bodies.find((b) => b.name === 'Sun');
@@ -42,6 +42,8 @@ export interface TimelineData {
 
 export type TimelineLevel = 'yuan' | 'hui' | 'yun' | 'shi' | 'xun';
 
+export type SolarTimePolicy = 'meridian' | 'mean' | 'apparent' | 'apparent_spencer' | string;
+
 export interface AuthorityEvidenceRef {
   label: string;
   url: string;
@@ -95,6 +97,9 @@ export interface FortuneResponse {
     primary: 'algorithm' | 'table';
     year_start: 'lichun' | 'gregorian';
     hj_year: number;
+    solar_time?: SolarTimePolicy;
+    timezone?: string;
+    utc_offset_seconds?: number;
   };
   variants?: {
     algorithm: FortuneVariant;
@@ -147,9 +152,54 @@ export interface SkyResponse {
     name: string;
     alt_deg: number;
     az_deg: number;
+    apparent_alt_deg?: number;
+    apparent_az_deg?: number;
     distance_au?: number;
     ra_deg?: number;
     dec_deg?: number;
+    mansion?: {
+      index: number;
+      name: string;
+      group: string;
+      determinative: string;
+      entry_deg: number;
+      entry_gudu: number;
+      width_deg: number;
+    } | null;
+    constellation?: {
+      iau?: { id: string; name: string; name_zh: string } | null;
+      chinese?: { id: string; name: string; name_zh: string } | null;
+    } | null;
+  }>;
+  stars?: Array<{
+    hip: number;
+    name?: string | null;
+    name_zh?: string | null;
+    mag: number;
+    bv?: number | null;
+    ra_deg: number;
+    dec_deg: number;
+    alt_deg: number;
+    az_deg: number;
+    apparent_alt_deg: number;
+  }>;
+  siyu?: Array<{
+    name: string;
+    name_zh: string;
+    ecliptic_lon_deg: number;
+    ra_deg: number;
+    dec_deg: number;
+    alt_deg: number;
+    az_deg: number;
+    mansion: {
+      index: number;
+      name: string;
+      group: string;
+      determinative: string;
+      entry_deg: number;
+      entry_gudu: number;
+      width_deg: number;
+    };
   }>;
   note: string;
   jd?: number;
@@ -181,7 +231,7 @@ export interface TimelineResponse extends TimelineData {
 }
 
 export type BaziSource = 'auto' | 'sxtwl' | 'huangji_core';
-export type BaziTimeBasis = 'standard' | 'true_solar';
+export type BaziTimeBasis = 'standard' | 'mean_solar' | 'true_solar';
 export type BaziDayRollover = 'zi_chu_23' | 'zi_zheng_00';
 
 export interface BaziAuthorityEvidenceRef {
@@ -192,10 +242,12 @@ export interface BaziAuthorityEvidenceRef {
 
 export interface BaziRuleProfile {
   time_basis: BaziTimeBasis | string;
+  solar_time?: SolarTimePolicy;
   day_rollover: BaziDayRollover | string;
   timezone?: string | null;
   tz_offset_minutes: number;
   longitude: number;
+  local_time_resolution?: 'compatible' | 'earlier' | 'later' | 'reject' | string;
 }
 
 export interface BaziAuthorityMeta {
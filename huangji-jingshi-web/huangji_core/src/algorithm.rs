@@ -53,7 +53,7 @@ pub fn year_to_acc(year: i32) -> Result<i32, &'static str> {
 
 /// 累积年 → 公元年（无0年）
 pub fn acc_to_year(acc: i32) -> i32 {
-    if acc >= ACC_BC1 + 1 {
+    if acc > ACC_BC1 {
         acc - ACC_BC1       // 67018 -> 1 AD
     } else {
         acc - ACC_BC1 - 1   // 67017 -> -1 (1 BC), 67016 -> -2 (2 BC)
@@ -0,0 +1,474 @@
+//! 儒略历与格里历
+//!
+//! 参考资料：
+//! - Jean Meeus, "Astronomical Algorithms" (2nd ed.), Chapter 7 "Julian Day"
+//!
+//! chrono 的 `NaiveDate` 一律按外推格里历计日，`utc_to_jd`、`datetime_to_jd` 亦然。
+//! 中国史料的公元日期在 1582 年改历以前习惯折算为儒略历，故须区分"某年某月某日"属于哪种历法：
+//! - `Gregorian`：外推格里历（与 chrono 一致）
+//! - `Julian`：外推儒略历
+//! - `Reform`：改历日（默认 1582-10-15）以前为儒略历，以后为格里历；
+//!   改历间隙（如 1582-10-05 至 10-14）的日期不存在
+//!
+//! 年份一律为天文纪年：公元前 1 年 = 0 年，公元前 n 年 = 1 - n。
+//! 各历法的日期经由儒略日数 (JDN) 互换；与 chrono 的桥接取同一 JDN 的外推格里历日期。
+
+use chrono::{Datelike, NaiveDate, NaiveDateTime, NaiveTime, Timelike};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// 公元 1 年 1 月 1 日（外推格里历）的儒略日数减一，用于与 chrono 的 `num_days_from_ce` 互换
+const JDN_OF_CE_EPOCH: i64 = 1_721_425;
+
+/// 一个日期所用的历法
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Calendar {
+    Julian,
+    Gregorian,
+}
+
+impl Calendar {
+    pub fn is_leap_year(self, year: i32) -> bool {
+        match self {
+            Calendar::Julian => year.rem_euclid(4) == 0,
+            Calendar::Gregorian => {
+                year.rem_euclid(4) == 0 && (year.rem_euclid(100) != 0 || year.rem_euclid(400) == 0)
+            }
+        }
+    }
+
+    pub fn days_in_month(self, year: i32, month: u32) -> u32 {
+        match month {
+            1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+            4 | 6 | 9 | 11 => 30,
+            2 if self.is_leap_year(year) => 29,
+            2 => 28,
+            _ => 0,
+        }
+    }
+
+    /// 儒略日数：该日正午的儒略日
+    pub fn jdn(self, year: i32, month: u32, day: u32) -> i64 {
+        let a = (14 - month as i64) / 12;
+        let y = year as i64 + 4800 - a;
+        let m = month as i64 + 12 * a - 3;
+        let days = day as i64 + (153 * m + 2) / 5 + 365 * y + y.div_euclid(4);
+        match self {
+            Calendar::Julian => days - 32083,
+            Calendar::Gregorian => days - y.div_euclid(100) + y.div_euclid(400) - 32045,
+        }
+    }
+
+    /// 由儒略日数求该历法的 (年, 月, 日)
+    pub fn date_of_jdn(self, jdn: i64) -> (i32, u32, u32) {
+        let (b, c) = match self {
+            Calendar::Julian => (0, jdn + 32082),
+            Calendar::Gregorian => {
+                let a = jdn + 32044;
+                let b = (4 * a + 3).div_euclid(146097);
+                (b, a - (146097 * b).div_euclid(4))
+            }
+        };
+        let d = (4 * c + 3).div_euclid(1461);
+        let e = c - (1461 * d).div_euclid(4);
+        let m = (5 * e + 2) / 153;
+        let day = e - (153 * m + 2) / 5 + 1;
+        let month = m + 3 - 12 * (m / 10);
+        let year = 100 * b + d - 4800 + m / 10;
+        (year as i32, month as u32, day as u32)
+    }
+
+    pub fn name_zh(self) -> &'static str {
+        match self {
+            Calendar::Julian => "儒略历",
+            Calendar::Gregorian => "格里历",
+        }
+    }
+}
+
+/// 某一历法下的日期
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CalendarDate {
+    /// 天文纪年：0 = 公元前 1 年
+    pub year: i32,
+    pub month: u32,
+    pub day: u32,
+    pub calendar: Calendar,
+}
+
+impl CalendarDate {
+    /// 历史纪年：无公元 0 年，公元前 1 年记作 -1
+    pub fn historical_year(&self) -> i32 {
+        if self.year <= 0 {
+            self.year - 1
+        } else {
+            self.year
+        }
+    }
+
+    /// 儒略日数
+    pub fn jdn(&self) -> i64 {
+        self.calendar.jdn(self.year, self.month, self.day)
+    }
+
+    /// 中文表述，如 公元前721年2月22日（儒略历）
+    pub fn label_zh(&self) -> String {
+        let year = self.historical_year();
+        let era = if year < 0 {
+            format!("公元前{}年", -year)
+        } else {
+            format!("公元{}年", year)
+        };
+        format!(
+            "{}{}月{}日（{}）",
+            era,
+            self.month,
+            self.day,
+            self.calendar.name_zh()
+        )
+    }
+}
+
+impl fmt::Display for CalendarDate {
+    /// ISO 8601 扩展年份写法，如 -0720-02-22
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.year < 0 {
+            write!(f, "-{:04}-{:02}-{:02}", -self.year, self.month, self.day)
+        } else {
+            write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+        }
+    }
+}
+
+/// 日期的解释方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CalendarSystem {
+    /// 外推格里历
+    Gregorian,
+    /// 外推儒略历
+    Julian,
+    /// 改历：`first_gregorian`（外推格里历日期）起用格里历，此前用儒略历
+    Reform { first_gregorian: NaiveDate },
+}
+
+impl Default for CalendarSystem {
+    /// 1582-10-15 改历（儒略历 1582-10-04 的次日）
+    fn default() -> Self {
+        Self::Reform {
+            first_gregorian: NaiveDate::from_ymd_opt(1582, 10, 15).expect("改历日有效"),
+        }
+    }
+}
+
+impl CalendarSystem {
+    /// 解析请求参数：`gregorian`、`julian`、`reform`（1582-10-15 改历）或 `reform:YYYY-MM-DD`
+    pub fn parse(raw: &str) -> anyhow::Result<Self> {
+        let raw = raw.trim().to_ascii_lowercase();
+        match raw.as_str() {
+            "gregorian" | "proleptic_gregorian" => Ok(Self::Gregorian),
+            "julian" | "proleptic_julian" => Ok(Self::Julian),
+            "" | "reform" | "auto" => Ok(Self::default()),
+            other => match other.strip_prefix("reform:") {
+                Some(date) => {
+                    let first_gregorian = NaiveDate::parse_from_str(date, "%Y-%m-%d")
+                        .map_err(|_| anyhow::anyhow!("无效的改历日期：{}", date))?;
+                    Ok(Self::Reform { first_gregorian })
+                }
+                None => anyhow::bail!(
+                    "无效的历法：{}（可选 gregorian、julian、reform、reform:YYYY-MM-DD）",
+                    other
+                ),
+            },
+        }
+    }
+
+    /// 请求参数写法，与 `parse` 互逆
+    pub fn name(&self) -> String {
+        match self {
+            Self::Gregorian => "gregorian".to_string(),
+            Self::Julian => "julian".to_string(),
+            Self::Reform { first_gregorian } => format!("reform:{}", first_gregorian),
+        }
+    }
+
+    fn reform_jdn(first_gregorian: &NaiveDate) -> i64 {
+        first_gregorian.num_days_from_ce() as i64 + JDN_OF_CE_EPOCH
+    }
+
+    /// 按本历法解释 (年, 月, 日)，返回带历法标记的日期
+    pub fn date(&self, year: i32, month: u32, day: u32) -> anyhow::Result<CalendarDate> {
+        let valid = |calendar: Calendar| {
+            (1..=12).contains(&month) && (1..=calendar.days_in_month(year, month)).contains(&day)
+        };
+        let calendar = match self {
+            Self::Gregorian => Calendar::Gregorian,
+            Self::Julian => Calendar::Julian,
+            Self::Reform { first_gregorian } => {
+                let switch = Self::reform_jdn(first_gregorian);
+                if valid(Calendar::Gregorian) && Calendar::Gregorian.jdn(year, month, day) >= switch
+                {
+                    Calendar::Gregorian
+                } else if valid(Calendar::Julian) && Calendar::Julian.jdn(year, month, day) < switch
+                {
+                    Calendar::Julian
+                } else if valid(Calendar::Gregorian) {
+                    anyhow::bail!(
+                        "{}-{:02}-{:02} 落在改历间隙（{} 起用格里历）中，该日不存在",
+                        year,
+                        month,
+                        day,
+                        first_gregorian
+                    )
+                } else {
+                    anyhow::bail!("无效的日期：{}-{:02}-{:02}", year, month, day)
+                }
+            }
+        };
+        if !valid(calendar) {
+            anyhow::bail!(
+                "无效的日期：{}-{:02}-{:02}（{}）",
+                year,
+                month,
+                day,
+                calendar.name_zh()
+            );
+        }
+        Ok(CalendarDate {
+            year,
+            month,
+            day,
+            calendar,
+        })
+    }
+
+    /// 儒略日数对应的本历法日期
+    pub fn date_of_jdn(&self, jdn: i64) -> CalendarDate {
+        let calendar = match self {
+            Self::Gregorian => Calendar::Gregorian,
+            Self::Julian => Calendar::Julian,
+            Self::Reform { first_gregorian } if jdn >= Self::reform_jdn(first_gregorian) => {
+                Calendar::Gregorian
+            }
+            Self::Reform { .. } => Calendar::Julian,
+        };
+        let (year, month, day) = calendar.date_of_jdn(jdn);
+        CalendarDate {
+            year,
+            month,
+            day,
+            calendar,
+        }
+    }
+
+    /// chrono 日期（外推格里历）在本历法下的写法
+    pub fn from_naive(&self, date: NaiveDate) -> CalendarDate {
+        self.date_of_jdn(naive_to_jdn(date))
+    }
+
+    /// 本历法的 (年, 月, 日) 转为 chrono 日期（外推格里历，同一天）
+    pub fn to_naive(&self, year: i32, month: u32, day: u32) -> anyhow::Result<NaiveDate> {
+        jdn_to_naive(self.date(year, month, day)?.jdn())
+    }
+
+    /// 本历法的日期与时刻转为儒略日
+    pub fn julian_day(
+        &self,
+        year: i32,
+        month: u32,
+        day: u32,
+        time: NaiveTime,
+    ) -> anyhow::Result<f64> {
+        let seconds = time.num_seconds_from_midnight() as f64 + time.nanosecond() as f64 * 1e-9;
+        Ok(self.date(year, month, day)?.jdn() as f64 - 0.5 + seconds / 86400.0)
+    }
+
+    /// 儒略日对应的本历法日期与时刻（精确到秒）
+    pub fn date_time_of_jd(&self, jd: f64) -> (CalendarDate, NaiveTime) {
+        let total_seconds = ((jd + 0.5) * 86400.0).round() as i64;
+        let jdn = total_seconds.div_euclid(86400);
+        let seconds = total_seconds.rem_euclid(86400) as u32;
+        let time = NaiveTime::from_num_seconds_from_midnight_opt(seconds, 0).expect("一日之内");
+        (self.date_of_jdn(jdn), time)
+    }
+
+    /// 解析本历法下的本地日期时间：`YYYY-MM-DD`、`YYYY-MM-DDTHH:MM[:SS]`，
+    /// 年份可带负号（天文纪年），可带 `Z` 或 `±HH:MM` 偏移
+    ///
+    /// 返回同一时刻的外推格里历 `NaiveDateTime`（仍为本地时间）及可选的 UTC 偏移（分钟）。
+    pub fn parse_datetime(&self, raw: &str) -> anyhow::Result<(NaiveDateTime, Option<i32>)> {
+        let invalid = || anyhow::anyhow!("无效的日期时间：{}", raw);
+        let raw = raw.trim();
+        let (negative, body) = match raw.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, raw.strip_prefix('+').unwrap_or(raw)),
+        };
+        let (date_part, rest) = match body.find(['T', ' ']) {
+            Some(i) => (&body[..i], &body[i + 1..]),
+            None => (body, ""),
+        };
+
+        let mut fields = date_part.split('-');
+        let mut next_number = || -> anyhow::Result<i64> {
+            fields
+                .next()
+                .and_then(|s| s.parse().ok())
+                .ok_or_else(invalid)
+        };
+        let year = next_number()?;
+        let month = next_number()?;
+        let day = next_number()?;
+        if fields.next().is_some() {
+            return Err(invalid());
+        }
+        let year = i32::try_from(if negative { -year } else { year }).map_err(|_| invalid())?;
+
+        // 时刻与偏移
+        let (time_part, offset) = if let Some(time) = rest.strip_suffix(['Z', 'z']) {
+            (time, Some(0))
+        } else if let Some(i) = rest.rfind(['+', '-']) {
+            let sign = if rest[i..].starts_with('-') { -1 } else { 1 };
+            let (h, m) = rest[i + 1..].split_once(':').ok_or_else(invalid)?;
+            let minutes = h.parse::<i32>().map_err(|_| invalid())? * 60
+                + m.parse::<i32>().map_err(|_| invalid())?;
+            (&rest[..i], Some(sign * minutes))
+        } else {
+            (rest, None)
+        };
+        let time = if time_part.is_empty() {
+            NaiveTime::MIN
+        } else {
+            NaiveTime::parse_from_str(time_part, "%H:%M:%S")
+                .or_else(|_| NaiveTime::parse_from_str(time_part, "%H:%M"))
+                .map_err(|_| invalid())?
+        };
+
+        let month = u32::try_from(month).map_err(|_| invalid())?;
+        let day = u32::try_from(day).map_err(|_| invalid())?;
+        let date = self.to_naive(year, month, day)?;
+        Ok((date.and_time(time), offset))
+    }
+}
+
+/// chrono 日期的儒略日数
+pub fn naive_to_jdn(date: NaiveDate) -> i64 {
+    date.num_days_from_ce() as i64 + JDN_OF_CE_EPOCH
+}
+
+/// 儒略日数转 chrono 日期（外推格里历）
+pub fn jdn_to_naive(jdn: i64) -> anyhow::Result<NaiveDate> {
+    i32::try_from(jdn - JDN_OF_CE_EPOCH)
+        .ok()
+        .and_then(NaiveDate::from_num_days_from_ce_opt)
+        .ok_or_else(|| anyhow::anyhow!("儒略日数 {} 超出可表示范围", jdn))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_meeus_chapter_7_examples() {
+        let noon = NaiveTime::from_hms_opt(12, 0, 0).unwrap();
+        let midnight = NaiveTime::MIN;
+        // 例 7.a：1957-10-04.81 = 2436116.31；例 7.b：333-01-27 12h = 1842713.0
+        let jd = CalendarSystem::default()
+            .julian_day(1957, 10, 4, NaiveTime::from_hms_opt(19, 26, 24).unwrap())
+            .unwrap();
+        assert!((jd - 2436116.31).abs() < 1e-6);
+        let reform = CalendarSystem::default();
+        assert_eq!(reform.julian_day(333, 1, 27, noon).unwrap(), 1842713.0);
+        // Meeus 表：-1000-07-12.5 = 1356001.0，-4712-01-01.5 = 0.0（儒略历）
+        assert_eq!(reform.julian_day(-1000, 7, 12, noon).unwrap(), 1356001.0);
+        assert_eq!(reform.julian_day(-4712, 1, 1, noon).unwrap(), 0.0);
+        assert_eq!(reform.julian_day(1600, 1, 1, midnight).unwrap(), 2305447.5);
+
+        let (date, time) = reform.date_time_of_jd(1842713.0);
+        assert_eq!(date.to_string(), "0333-01-27");
+        assert_eq!(date.calendar, Calendar::Julian);
+        assert_eq!(time, noon);
+        let (date, _) = reform.date_time_of_jd(0.0);
+        assert_eq!((date.year, date.month, date.day), (-4712, 1, 1));
+    }
+
+    #[test]
+    fn test_reform_gap() {
+        let reform = CalendarSystem::default();
+        let last_julian = reform.date(1582, 10, 4).unwrap();
+        let first_gregorian = reform.date(1582, 10, 15).unwrap();
+        assert_eq!(last_julian.calendar, Calendar::Julian);
+        assert_eq!(first_gregorian.jdn() - last_julian.jdn(), 1);
+        assert!(reform.date(1582, 10, 10).is_err());
+        // 儒略历 1500 年为闰年，格里历不是
+        assert!(reform.date(1500, 2, 29).is_ok());
+        assert!(CalendarSystem::Gregorian.date(1500, 2, 29).is_err());
+        assert!(reform.date(2023, 2, 29).is_err());
+        let err = reform.date(1700, 2, 29).unwrap_err().to_string();
+        assert!(err.contains("无效的日期"), "{}", err);
+
+        // 英国 1752 年改历：9 月 2 日的次日为 9 月 14 日
+        let british = CalendarSystem::parse("reform:1752-09-14").unwrap();
+        assert_eq!(
+            british.date(1700, 2, 29).unwrap().calendar,
+            Calendar::Julian
+        );
+        assert_eq!(
+            british.date(1752, 9, 14).unwrap().jdn() - british.date(1752, 9, 2).unwrap().jdn(),
+            1
+        );
+        assert_eq!(british.name(), "reform:1752-09-14");
+    }
+
+    #[test]
+    fn test_round_trip_and_chrono_bridge() {
+        for system in [
+            CalendarSystem::Gregorian,
+            CalendarSystem::Julian,
+            CalendarSystem::default(),
+        ] {
+            for jdn in (0..2_500_000).step_by(997) {
+                let date = system.date_of_jdn(jdn);
+                assert_eq!(date.jdn(), jdn, "{:?} {}", system, jdn);
+                assert_eq!(system.date(date.year, date.month, date.day).unwrap(), date);
+            }
+        }
+        // 儒略历 1582-10-04 即外推格里历 1582-10-14
+        let naive = CalendarSystem::Julian.to_naive(1582, 10, 4).unwrap();
+        assert_eq!(naive, NaiveDate::from_ymd_opt(1582, 10, 14).unwrap());
+        assert_eq!(
+            CalendarSystem::default().from_naive(naive).to_string(),
+            "1582-10-04"
+        );
+        assert_eq!(
+            naive_to_jdn(NaiveDate::from_ymd_opt(2000, 1, 1).unwrap()),
+            2451545
+        );
+    }
+
+    #[test]
+    fn test_parse_datetime_and_labels() {
+        let reform = CalendarSystem::default();
+        // 公元前 721 年（天文纪年 -720）儒略历 2 月 22 日，即外推格里历 2 月 14 日
+        let (naive, offset) = reform.parse_datetime("-0720-02-22T08:30").unwrap();
+        assert_eq!(
+            naive,
+            NaiveDate::from_ymd_opt(-720, 2, 14)
+                .unwrap()
+                .and_hms_opt(8, 30, 0)
+                .unwrap()
+        );
+        assert_eq!(offset, None);
+        let date = reform.from_naive(naive.date());
+        assert_eq!(date.label_zh(), "公元前721年2月22日（儒略历）");
+        assert_eq!(date.historical_year(), -721);
+
+        let (_, offset) = reform.parse_datetime("1066-10-14T09:00:00+01:00").unwrap();
+        assert_eq!(offset, Some(60));
+        let (_, offset) = reform.parse_datetime("2024-01-01T00:00:00Z").unwrap();
+        assert_eq!(offset, Some(0));
+        assert!(reform.parse_datetime("1582-10-10").is_err());
+        assert!(reform.parse_datetime("1582-13-01").is_err());
+        assert!(CalendarSystem::parse("mayan").is_err());
+    }
+}
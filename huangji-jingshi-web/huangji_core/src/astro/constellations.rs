@@ -0,0 +1,436 @@
+//! 星座归属（点在星座界内）
+//!
+//! 参考资料：
+//! - Delporte (1930), "Délimitation scientifique des constellations"：
+//!   IAU 星座界线沿 B1875.0 的赤经圈与赤纬圈划定
+//! - Roman (1987), "Identification of a constellation from a position", PASP 99, 695
+//!
+//! 界线取自星图数据 `constellations.bounds.json`（IAU 88 星座，顶点已由 B1875.0 岁差至 J2000.0）
+//! 与 `constellations.bounds.cn.json`（二十八宿赤经带，J2000.0，仅赤纬 ±50° 以内），
+//! 名称取自 `constellations.json`、`constellations.cn.json`。三垣及其余星官在数据中没有界线，不作归属。
+//! IAU 两份取自 `frontend/public/data/`，二十八宿两份取自 `backend/backend/data/celestial/`。
+//!
+//! 载入时把顶点一次性换回界线历元（IAU 为 B1875.0），查找时再把待查方向岁差至同一历元，
+//! 界线便重新成为赤经圈、赤纬圈。历元间岁差用 IAU 2006，未计 FK4 与 FK5 的差异（小于 1"）。
+
+use crate::astro::coords::{apply, EquatorialCoord, RotationMatrix};
+use crate::astro::mansions::{DeterminativeSystem, MansionTable};
+use crate::astro::numeric::wrap180;
+use crate::astro::precession::{precession_matrix, PrecessionNutation};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// B1875.0 的儒略日（IAU 星座界线历元）
+pub const B1875_JD: f64 = 2405889.258550475;
+
+/// J2000.0 的儒略日
+const J2000_JD: f64 = 2451545.0;
+
+/// 一个星座或星官
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Constellation {
+    /// IAU 缩写（如 "Ori"）；中国星官为星图数据中的编号
+    pub id: String,
+    /// 西文名：IAU 星座取拉丁名，中国星官取英译
+    pub name: String,
+    /// 中文名
+    pub name_zh: String,
+}
+
+/// 某一方向所在的星座
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ConstellationPlacement {
+    /// IAU 星座（全天无缝覆盖）
+    pub iau: Option<Constellation>,
+    /// 中国星官：仅在有界线处给出，目前为二十八宿（赤纬 ±50° 以内）
+    pub chinese: Option<Constellation>,
+}
+
+#[derive(Deserialize)]
+struct BoundsCollection {
+    features: Vec<BoundsFeature>,
+}
+
+#[derive(Deserialize)]
+struct BoundsFeature {
+    id: String,
+    geometry: BoundsGeometry,
+}
+
+#[derive(Deserialize)]
+struct BoundsGeometry {
+    /// 多边形外环 [赤经, 赤纬] (度)，赤经按 -180..180 存放
+    coordinates: Vec<Vec<[f64; 2]>>,
+}
+
+#[derive(Deserialize)]
+struct NamesCollection {
+    features: Vec<NamesFeature>,
+}
+
+#[derive(Deserialize)]
+struct NamesFeature {
+    id: String,
+    properties: NamesProperties,
+}
+
+#[derive(Deserialize)]
+struct NamesProperties {
+    name: String,
+    #[serde(default)]
+    en: String,
+    #[serde(default)]
+    zh: String,
+}
+
+/// 一块界内区域（同一星座可有多块，如巨蛇座首、尾）
+struct Region {
+    constellation: Constellation,
+    /// 界线历元的 [赤经, 赤纬] (度)
+    vertices: Vec<[f64; 2]>,
+    /// 环绕北天极（射线法需翻转）
+    contains_north_pole: bool,
+}
+
+/// 一套界线及其历元
+struct BoundarySet {
+    /// J2000.0 平赤道 → 界线历元平赤道
+    from_j2000: RotationMatrix,
+    regions: Vec<Region>,
+}
+
+/// IAU 星座中文名在星图数据中为繁体，按本库用字转为简体
+const TRADITIONAL_TO_SIMPLIFIED: [(char, char); 38] = [
+    ('寶', '宝'),
+    ('鷹', '鹰'),
+    ('壇', '坛'),
+    ('獵', '猎'),
+    ('馬', '马'),
+    ('鯨', '鲸'),
+    ('圓', '圆'),
+    ('規', '规'),
+    ('鴿', '鸽'),
+    ('髮', '发'),
+    ('烏', '乌'),
+    ('鵝', '鹅'),
+    ('劍', '剑'),
+    ('魚', '鱼'),
+    ('龍', '龙'),
+    ('爐', '炉'),
+    ('雙', '双'),
+    ('鶴', '鹤'),
+    ('時', '时'),
+    ('鐘', '钟'),
+    ('長', '长'),
+    ('蠍', '蝎'),
+    ('獅', '狮'),
+    ('貓', '猫'),
+    ('顯', '显'),
+    ('鏡', '镜'),
+    ('蒼', '苍'),
+    ('蠅', '蝇'),
+    ('極', '极'),
+    ('飛', '飞'),
+    ('鳳', '凤'),
+    ('繪', '绘'),
+    ('羅', '罗'),
+    ('網', '网'),
+    ('遠', '远'),
+    ('鵑', '鹃'),
+    ('處', '处'),
+    ('戶', '户'),
+];
+
+fn simplified(name: &str) -> String {
+    name.chars()
+        .map(|c| {
+            TRADITIONAL_TO_SIMPLIFIED
+                .iter()
+                .find(|(traditional, _)| *traditional == c)
+                .map_or(c, |(_, simplified)| *simplified)
+        })
+        .collect()
+}
+
+impl Region {
+    /// 射线法：自该点沿赤经圈向北，数与界线的交点
+    fn contains(&self, ra_deg: f64, dec_deg: f64) -> bool {
+        let mut inside = self.contains_north_pole;
+        let n = self.vertices.len();
+        for i in 0..n {
+            let [ra1, dec1] = self.vertices[i];
+            let [ra2, dec2] = self.vertices[(i + 1) % n];
+            let span = wrap180(ra2 - ra1);
+            let offset = wrap180(ra_deg - ra1);
+            if (offset < 0.0) == (offset < span) {
+                continue;
+            }
+            let crossing = dec1 + (dec2 - dec1) * offset / span;
+            if crossing > dec_deg {
+                inside = !inside;
+            }
+        }
+        inside
+    }
+}
+
+/// 星名表：编号 → 星座
+///
+/// IAU 星座 `name` 为拉丁名、`zh` 为中文名；中国星官 `name` 为中文名、`en` 为英译
+fn load_names(names: &str, chinese: bool) -> HashMap<String, Constellation> {
+    serde_json::from_str::<NamesCollection>(names)
+        .expect("星座名称数据解析失败")
+        .features
+        .into_iter()
+        .map(|feature| {
+            let p = feature.properties;
+            let (name, name_zh) = if chinese {
+                (p.en, p.name)
+            } else {
+                (p.name, simplified(&p.zh))
+            };
+            let constellation = Constellation {
+                id: feature.id.clone(),
+                name,
+                name_zh,
+            };
+            (feature.id, constellation)
+        })
+        .collect()
+}
+
+impl BoundarySet {
+    /// # 参数
+    /// - `epoch_jd`: 界线历元；数据顶点为 J2000.0，载入时换回该历元
+    /// - `name_of`: 由编号与界线历元顶点定名，返回 `None` 的区域舍去
+    fn load(
+        bounds: &str,
+        epoch_jd: f64,
+        name_of: impl Fn(&str, &[[f64; 2]]) -> Option<Constellation>,
+    ) -> Self {
+        let from_j2000 = precession_matrix(epoch_jd);
+        let regions = serde_json::from_str::<BoundsCollection>(bounds)
+            .expect("星座界线数据解析失败")
+            .features
+            .into_iter()
+            .filter_map(|feature| {
+                let mut vertices: Vec<[f64; 2]> = feature
+                    .geometry
+                    .coordinates
+                    .first()?
+                    .iter()
+                    .map(|&[ra_deg, dec_deg]| {
+                        let v = EquatorialCoord { ra_deg, dec_deg }.to_vector();
+                        let at_epoch = EquatorialCoord::from_vector(apply(&from_j2000, &v));
+                        [at_epoch.ra_deg, at_epoch.dec_deg]
+                    })
+                    .collect();
+                // GeoJSON 外环首尾重复
+                if vertices.len() > 1 && vertices.first() == vertices.last() {
+                    vertices.pop();
+                }
+                let constellation = name_of(&feature.id, &vertices)?;
+                let n = vertices.len();
+                let winding: f64 = (0..n)
+                    .map(|i| wrap180(vertices[(i + 1) % n][0] - vertices[i][0]))
+                    .sum();
+                let mean_dec = vertices.iter().map(|v| v[1]).sum::<f64>() / n.max(1) as f64;
+                Some(Region {
+                    constellation,
+                    vertices,
+                    contains_north_pole: winding.abs() > 180.0 && mean_dec > 0.0,
+                })
+            })
+            .collect();
+        Self {
+            from_j2000,
+            regions,
+        }
+    }
+
+    /// # 参数
+    /// - `j2000`: J2000.0 平赤道单位向量
+    fn locate(&self, j2000: &[f64; 3]) -> Option<&Constellation> {
+        let at_epoch = EquatorialCoord::from_vector(apply(&self.from_j2000, j2000));
+        self.regions
+            .iter()
+            .find(|region| region.contains(at_epoch.ra_deg, at_epoch.dec_deg))
+            .map(|region| &region.constellation)
+    }
+}
+
+static IAU_BOUNDARIES: Lazy<BoundarySet> = Lazy::new(|| {
+    let names = load_names(
+        include_str!("../../../frontend/public/data/constellations.json"),
+        false,
+    );
+    BoundarySet::load(
+        include_str!("../../../frontend/public/data/constellations.bounds.json"),
+        B1875_JD,
+        |id, _| names.get(id).cloned(),
+    )
+});
+
+/// 二十八宿赤经带
+///
+/// 星图数据中各带的编号与宿名错开一宿（如觜宿一至参宿三之间一带标为参宿），
+/// 故不按编号取名，而按带中央赤经所入之宿（今距星，J2000.0）定名。
+static CHINESE_BOUNDARIES: Lazy<BoundarySet> = Lazy::new(|| {
+    let names: HashMap<String, Constellation> = load_names(
+        include_str!("../../../backend/backend/data/celestial/constellations.cn.json"),
+        true,
+    )
+    .into_values()
+    .map(|constellation| (constellation.name_zh.clone(), constellation))
+    .collect();
+    let mansions = MansionTable::new(J2000_JD, DeterminativeSystem::Modern);
+    BoundarySet::load(
+        include_str!("../../../backend/backend/data/celestial/constellations.bounds.cn.json"),
+        J2000_JD,
+        |_, vertices| {
+            let first = vertices.first()?[0];
+            let offsets = vertices.iter().map(|v| wrap180(v[0] - first));
+            let (min, max) = offsets.fold((0.0f64, 0.0f64), |(lo, hi), x| (lo.min(x), hi.max(x)));
+            let mansion = mansions.placement(first + (min + max) / 2.0);
+            names.get(&format!("{}宿", mansion.name)).cloned()
+        },
+    )
+});
+
+fn placement_of_vector(j2000: &[f64; 3]) -> ConstellationPlacement {
+    ConstellationPlacement {
+        iau: IAU_BOUNDARIES.locate(j2000).cloned(),
+        chinese: CHINESE_BOUNDARIES.locate(j2000).cloned(),
+    }
+}
+
+/// J2000.0 平赤道坐标所在星座
+pub fn constellation_at(ra_deg: f64, dec_deg: f64) -> ConstellationPlacement {
+    placement_of_vector(&EquatorialCoord { ra_deg, dec_deg }.to_vector())
+}
+
+/// 某一时刻瞬时真赤道坐标的星座查找，可对多个天体复用
+#[derive(Debug, Clone, Copy)]
+pub struct ConstellationLocator {
+    /// 瞬时真赤道 → J2000.0 平赤道
+    to_j2000: RotationMatrix,
+}
+
+impl ConstellationLocator {
+    /// # 参数
+    /// - `jd_tt`: 力学时 (TT) 儒略日
+    pub fn new(jd_tt: f64) -> Self {
+        Self {
+            to_j2000: PrecessionNutation::new(jd_tt).inverse_matrix(),
+        }
+    }
+
+    /// 视赤经、视赤纬（瞬时真赤道，度）所在星座；未计周年光行差（至多约 20"）
+    pub fn locate(&self, ra_deg: f64, dec_deg: f64) -> ConstellationPlacement {
+        let v = EquatorialCoord { ra_deg, dec_deg }.to_vector();
+        placement_of_vector(&apply(&self.to_j2000, &v))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn iau(ra_deg: f64, dec_deg: f64) -> String {
+        constellation_at(ra_deg, dec_deg).iau.unwrap().id
+    }
+
+    fn xiu(ra_deg: f64, dec_deg: f64) -> String {
+        constellation_at(ra_deg, dec_deg).chinese.unwrap().name_zh
+    }
+
+    #[test]
+    fn test_bright_stars() {
+        // J2000.0 位置
+        assert_eq!(iau(88.7929, 7.4071), "Ori"); // 参宿四
+        assert_eq!(iau(101.2872, -16.7161), "CMa"); // 天狼
+        assert_eq!(iau(279.2347, 38.7837), "Lyr"); // 织女一
+        assert_eq!(iau(37.9546, 89.2641), "UMi"); // 北极星
+        assert_eq!(iau(201.2983, -11.1613), "Vir"); // 角宿一
+        assert_eq!(iau(0.0, -89.9), "Oct");
+        assert_eq!(iau(180.0, 89.9), "UMi");
+        let betelgeuse = constellation_at(88.7929, 7.4071);
+        assert_eq!(betelgeuse.iau.unwrap().name_zh, "猎户座");
+        assert_eq!(betelgeuse.chinese.unwrap().name_zh, "参宿");
+        assert_eq!(xiu(247.3519, -26.4320), "心宿"); // 心宿二
+
+        // 角宿一恰在宿界（距星）上，取角宿二
+        assert_eq!(xiu(203.6733, -0.5958), "角宿");
+        // 二十八宿界线只到赤纬 ±50°
+        assert!(constellation_at(37.9546, 89.2641).chinese.is_none());
+    }
+
+    #[test]
+    fn test_b1875_boundary_epoch() {
+        // 仙女座与仙后座之界：B1875.0 赤纬 +46°（赤经 0h10m 至 0h52m）
+        // 界线两侧各取 0.05°，换到 J2000.0 后赤纬已差约 0.5°，仍应分属两座
+        let to_j2000 = crate::astro::coords::transpose(&precession_matrix(B1875_JD));
+        let at = |ra_deg: f64, dec_deg: f64| {
+            let v = apply(&to_j2000, &EquatorialCoord { ra_deg, dec_deg }.to_vector());
+            let j2000 = EquatorialCoord::from_vector(v);
+            iau(j2000.ra_deg, j2000.dec_deg)
+        };
+        assert_eq!(at(7.5, 46.05), "Cas");
+        assert_eq!(at(7.5, 45.95), "And");
+    }
+
+    #[test]
+    fn test_iau_covers_whole_sky() {
+        // 全天网格上每一点恰属一个 IAU 星座
+        let mut dec = -89.37;
+        while dec < 90.0 {
+            let mut ra = 0.41;
+            while ra < 360.0 {
+                let v = EquatorialCoord {
+                    ra_deg: ra,
+                    dec_deg: dec,
+                }
+                .to_vector();
+                let at_epoch = EquatorialCoord::from_vector(apply(&IAU_BOUNDARIES.from_j2000, &v));
+                let hits = IAU_BOUNDARIES
+                    .regions
+                    .iter()
+                    .filter(|region| region.contains(at_epoch.ra_deg, at_epoch.dec_deg))
+                    .count();
+                assert_eq!(hits, 1, "ra={} dec={}", ra, dec);
+                ra += 2.9;
+            }
+            dec += 1.93;
+        }
+    }
+
+    #[test]
+    fn test_mansion_strips() {
+        // 二十八宿各一带，宿名不重
+        let mut names: Vec<&str> = CHINESE_BOUNDARIES
+            .regions
+            .iter()
+            .map(|region| region.constellation.name_zh.as_str())
+            .collect();
+        names.sort();
+        names.dedup();
+        assert_eq!(names.len(), 28);
+        // 觜宿一 (λ Ori) 与参宿三 (ζ Ori) 之间为觜宿
+        assert_eq!(constellation_at(84.5, 9.0).chinese.unwrap().name_zh, "觜宿");
+    }
+
+    #[test]
+    fn test_locator_of_date() {
+        // 瞬时真赤道：2025-08-01 前后，J2000.0 与当日坐标相差约 0.35°
+        let jd_tt = 2460888.5;
+        let pn = PrecessionNutation::new(jd_tt);
+        let j2000 = EquatorialCoord {
+            ra_deg: 88.7929,
+            dec_deg: 7.4071,
+        };
+        let of_date = EquatorialCoord::from_vector(apply(&pn.matrix(), &j2000.to_vector()));
+        let placement = ConstellationLocator::new(jd_tt).locate(of_date.ra_deg, of_date.dec_deg);
+        assert_eq!(placement, constellation_at(j2000.ra_deg, j2000.dec_deg));
+    }
+}
@@ -0,0 +1,504 @@
+//! 坐标系与坐标变换
+//!
+//! 参考资料：
+//! - Jean Meeus, "Astronomical Algorithms" (2nd ed.), Chapter 13 "Transformation of Coordinates"
+//! - ESA (1997), "The Hipparcos and Tycho Catalogues", Vol. 1, §1.5.3（银道坐标）
+//! - IERS Conventions (2010), Chapter 5
+//!
+//! 支持的坐标系：
+//! - 黄道：J2000.0 平黄道、瞬时真黄道
+//! - 赤道：J2000.0 平赤道、瞬时平赤道、瞬时真赤道
+//! - 银道：IAU 1958 银道系（由 J2000.0 平赤道变换）
+//! - 地平：方位角自北向东量度，高度为几何高度（未计大气折射与周日视差）
+//!
+//! 各坐标系之间经 J2000.0 平赤道单位向量换算，岁差章动取 IAU 2006/2000B（见 `precession` 模块）。
+
+use crate::astro::observer::Observer;
+use crate::astro::precession::{PrecessionNutation, J2000_OBLIQUITY_ARCSEC};
+use crate::astro::sidereal::local_apparent_sidereal_time;
+use crate::astro::time::TimeScales;
+use serde::{Deserialize, Serialize};
+
+/// 3×3 旋转矩阵（作用于列向量）
+pub type RotationMatrix = [[f64; 3]; 3];
+
+/// 绕 x 轴旋转坐标系 R1(φ)
+pub fn rotate_x(angle: f64) -> RotationMatrix {
+    let (s, c) = angle.sin_cos();
+    [[1.0, 0.0, 0.0], [0.0, c, s], [0.0, -s, c]]
+}
+
+/// 绕 y 轴旋转坐标系 R2(φ)
+pub fn rotate_y(angle: f64) -> RotationMatrix {
+    let (s, c) = angle.sin_cos();
+    [[c, 0.0, -s], [0.0, 1.0, 0.0], [s, 0.0, c]]
+}
+
+/// 绕 z 轴旋转坐标系 R3(φ)
+pub fn rotate_z(angle: f64) -> RotationMatrix {
+    let (s, c) = angle.sin_cos();
+    [[c, s, 0.0], [-s, c, 0.0], [0.0, 0.0, 1.0]]
+}
+
+pub fn multiply(a: &RotationMatrix, b: &RotationMatrix) -> RotationMatrix {
+    let mut m = [[0.0; 3]; 3];
+    for (i, row) in m.iter_mut().enumerate() {
+        for (j, value) in row.iter_mut().enumerate() {
+            *value = (0..3).map(|k| a[i][k] * b[k][j]).sum();
+        }
+    }
+    m
+}
+
+pub fn transpose(m: &RotationMatrix) -> RotationMatrix {
+    let mut t = [[0.0; 3]; 3];
+    for (i, row) in m.iter().enumerate() {
+        for (j, value) in row.iter().enumerate() {
+            t[j][i] = *value;
+        }
+    }
+    t
+}
+
+pub fn apply(m: &RotationMatrix, v: &[f64; 3]) -> [f64; 3] {
+    [0, 1, 2].map(|i| m[i][0] * v[0] + m[i][1] * v[1] + m[i][2] * v[2])
+}
+
+/// 球面坐标 (经度, 纬度) (度) → 单位向量
+fn unit_vector(lon_deg: f64, lat_deg: f64) -> [f64; 3] {
+    let (lon, lat) = (lon_deg.to_radians(), lat_deg.to_radians());
+    [lat.cos() * lon.cos(), lat.cos() * lon.sin(), lat.sin()]
+}
+
+/// 向量 → 球面坐标 (经度 0-360, 纬度) (度)
+fn spherical(v: &[f64; 3]) -> (f64, f64) {
+    let lon = v[1].atan2(v[0]).to_degrees().rem_euclid(360.0);
+    let lat = v[2].atan2((v[0] * v[0] + v[1] * v[1]).sqrt()).to_degrees();
+    (lon, lat)
+}
+
+/// 时角坐标系（x 指向子午圈与赤道交点、y 指向西点、z 指向北天极）
+/// → 地平坐标系（x 指向南点、y 指向西点、z 指向天顶）
+fn meridian_to_horizon(lat_deg: f64) -> RotationMatrix {
+    rotate_y((90.0 - lat_deg).to_radians())
+}
+
+/// 天文单位 (km)，IAU 2012 定义值
+pub const AU_KM: f64 = 149_597_870.7;
+
+/// 两点间的角距 (度)，黄道、赤道等球面坐标通用（Meeus 式 17.1）
+pub fn angular_separation(lon1_deg: f64, lat1_deg: f64, lon2_deg: f64, lat2_deg: f64) -> f64 {
+    let (l1, b1) = (lon1_deg.to_radians(), lat1_deg.to_radians());
+    let (l2, b2) = (lon2_deg.to_radians(), lat2_deg.to_radians());
+    let cos_d = b1.sin() * b2.sin() + b1.cos() * b2.cos() * (l1 - l2).cos();
+    cos_d.clamp(-1.0, 1.0).acos().to_degrees()
+}
+
+/// J2000.0 平赤道 → 银道（Hipparcos 星表 §1.5.3 式 1.5.11）
+const EQUATORIAL_TO_GALACTIC: RotationMatrix = [
+    [-0.0548755604162154, -0.873437090234885, -0.4838350155487132],
+    [0.4941094278755837, -0.4448296299600112, 0.746982244497219],
+    [-0.8676661490190047, -0.1980763734312015, 0.4559837761750669],
+];
+
+/// 黄道坐标 (度)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct EclipticCoord {
+    pub lon_deg: f64,
+    pub lat_deg: f64,
+}
+
+impl EclipticCoord {
+    pub fn to_vector(&self) -> [f64; 3] {
+        unit_vector(self.lon_deg, self.lat_deg)
+    }
+
+    pub fn from_vector(v: [f64; 3]) -> Self {
+        let (lon_deg, lat_deg) = spherical(&v);
+        Self { lon_deg, lat_deg }
+    }
+
+    /// 黄道 → 赤道（Meeus 式 13.3、13.4）
+    ///
+    /// # 参数
+    /// - `obliquity_deg`: 黄赤交角；瞬时真黄道取真黄赤交角，J2000.0 取 ε0
+    pub fn to_equatorial(&self, obliquity_deg: f64) -> EquatorialCoord {
+        EquatorialCoord::from_vector(apply(
+            &rotate_x(-obliquity_deg.to_radians()),
+            &self.to_vector(),
+        ))
+    }
+}
+
+/// 赤道坐标 (度)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct EquatorialCoord {
+    pub ra_deg: f64,
+    pub dec_deg: f64,
+}
+
+impl EquatorialCoord {
+    pub fn to_vector(&self) -> [f64; 3] {
+        unit_vector(self.ra_deg, self.dec_deg)
+    }
+
+    pub fn from_vector(v: [f64; 3]) -> Self {
+        let (ra_deg, dec_deg) = spherical(&v);
+        Self { ra_deg, dec_deg }
+    }
+
+    /// 赤道 → 黄道（Meeus 式 13.1、13.2）
+    pub fn to_ecliptic(&self, obliquity_deg: f64) -> EclipticCoord {
+        EclipticCoord::from_vector(apply(
+            &rotate_x(obliquity_deg.to_radians()),
+            &self.to_vector(),
+        ))
+    }
+
+    /// J2000.0 平赤道 → 银道
+    pub fn to_galactic(&self) -> GalacticCoord {
+        GalacticCoord::from_vector(apply(&EQUATORIAL_TO_GALACTIC, &self.to_vector()))
+    }
+
+    /// 瞬时真赤道 → 地平（Meeus 式 13.5、13.6）
+    ///
+    /// # 参数
+    /// - `lat_deg`: 地理纬度
+    /// - `local_sidereal_deg`: 地方视恒星时 (度)
+    pub fn to_horizontal(&self, lat_deg: f64, local_sidereal_deg: f64) -> HorizontalCoord {
+        self.hour_angle_to_horizontal(lat_deg, local_sidereal_deg - self.ra_deg)
+    }
+
+    /// 由时角换算地平坐标，供已知时角（如站心时角）的场合使用
+    pub fn hour_angle_to_horizontal(&self, lat_deg: f64, hour_angle_deg: f64) -> HorizontalCoord {
+        let h = apply(
+            &meridian_to_horizon(lat_deg),
+            &unit_vector(hour_angle_deg, self.dec_deg),
+        );
+        let (south_az, alt_deg) = spherical(&h);
+        HorizontalCoord {
+            alt_deg,
+            az_deg: (south_az + 180.0).rem_euclid(360.0),
+        }
+    }
+}
+
+/// 银道坐标 (度)
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct GalacticCoord {
+    pub l_deg: f64,
+    pub b_deg: f64,
+}
+
+impl GalacticCoord {
+    pub fn to_vector(&self) -> [f64; 3] {
+        unit_vector(self.l_deg, self.b_deg)
+    }
+
+    pub fn from_vector(v: [f64; 3]) -> Self {
+        let (l_deg, b_deg) = spherical(&v);
+        Self { l_deg, b_deg }
+    }
+
+    /// 银道 → J2000.0 平赤道
+    pub fn to_equatorial(&self) -> EquatorialCoord {
+        EquatorialCoord::from_vector(apply(
+            &transpose(&EQUATORIAL_TO_GALACTIC),
+            &self.to_vector(),
+        ))
+    }
+}
+
+/// 地平坐标 (度)：方位角自北向东
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct HorizontalCoord {
+    pub alt_deg: f64,
+    pub az_deg: f64,
+}
+
+impl HorizontalCoord {
+    /// 地平 → 瞬时真赤道
+    pub fn to_equatorial(&self, lat_deg: f64, local_sidereal_deg: f64) -> EquatorialCoord {
+        let h = unit_vector(self.az_deg - 180.0, self.alt_deg);
+        let v = apply(&transpose(&meridian_to_horizon(lat_deg)), &h);
+        let (hour_angle, dec_deg) = spherical(&v);
+        EquatorialCoord {
+            ra_deg: (local_sidereal_deg - hour_angle).rem_euclid(360.0),
+            dec_deg,
+        }
+    }
+}
+
+/// 坐标系
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CoordinateFrame {
+    /// J2000.0 平黄道与平春分点
+    EclipticJ2000,
+    /// 瞬时真黄道与真春分点
+    EclipticOfDate,
+    /// J2000.0 平赤道与平春分点
+    #[default]
+    EquatorialJ2000,
+    /// 瞬时平赤道与平春分点
+    EquatorialMeanOfDate,
+    /// 瞬时真赤道与真春分点
+    EquatorialOfDate,
+    Galactic,
+    Horizontal,
+}
+
+impl CoordinateFrame {
+    /// 解析请求参数，缺省为 J2000.0 赤道
+    pub fn parse(raw: &str) -> anyhow::Result<Self> {
+        match raw.trim().to_ascii_lowercase().as_str() {
+            "ecliptic_j2000" => Ok(Self::EclipticJ2000),
+            "ecliptic" | "ecliptic_of_date" => Ok(Self::EclipticOfDate),
+            "" | "equatorial_j2000" | "j2000" | "icrs" => Ok(Self::EquatorialJ2000),
+            "equatorial_mean_of_date" | "mean_of_date" => Ok(Self::EquatorialMeanOfDate),
+            "equatorial" | "equatorial_of_date" | "of_date" => Ok(Self::EquatorialOfDate),
+            "galactic" => Ok(Self::Galactic),
+            "horizontal" | "altaz" => Ok(Self::Horizontal),
+            other => anyhow::bail!(
+                "无效的坐标系：{}（可选 ecliptic_j2000、ecliptic_of_date、equatorial_j2000、\
+                 equatorial_mean_of_date、equatorial_of_date、galactic、horizontal）",
+                other
+            ),
+        }
+    }
+}
+
+/// 同一方向在各坐标系下的坐标
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CoordinateSet {
+    /// 输入所用坐标系
+    pub frame: CoordinateFrame,
+    pub jd_tt: f64,
+    pub ecliptic_j2000: EclipticCoord,
+    pub ecliptic_of_date: EclipticCoord,
+    pub equatorial_j2000: EquatorialCoord,
+    pub equatorial_mean_of_date: EquatorialCoord,
+    pub equatorial_of_date: EquatorialCoord,
+    pub galactic: GalacticCoord,
+    pub horizontal: HorizontalCoord,
+    /// 地方视恒星时 (度)
+    pub local_sidereal_deg: f64,
+    /// 平、真黄赤交角 (度)
+    pub mean_obliquity_deg: f64,
+    pub true_obliquity_deg: f64,
+}
+
+/// 将某一坐标系下的方向换算到全部坐标系
+///
+/// # 参数
+/// - `frame`: 输入坐标系
+/// - `lon_deg`, `lat_deg`: 输入的经度类坐标（黄经、赤经、银经、方位角）与纬度类坐标（黄纬、赤纬、银纬、高度）
+/// - `scales`: 时刻（岁差章动取 TT，恒星时取 UT1）
+/// - `observer`: 观测者，地平坐标使用
+pub fn convert_coordinates(
+    frame: CoordinateFrame,
+    lon_deg: f64,
+    lat_deg: f64,
+    scales: &TimeScales,
+    observer: &Observer,
+) -> anyhow::Result<CoordinateSet> {
+    if !lon_deg.is_finite() || !(-90.0..=90.0).contains(&lat_deg) {
+        anyhow::bail!("坐标超出范围：经度须为有限值，纬度须在 -90° 至 90° 之间");
+    }
+    let pn = PrecessionNutation::new(scales.jd_tt);
+    let eps0 = J2000_OBLIQUITY_ARCSEC / 3600.0;
+    let eps = pn.true_obliquity_deg();
+    let lst = local_apparent_sidereal_time(scales, observer.lon_deg);
+
+    // 统一换算到 J2000.0 平赤道
+    let j2000 = match frame {
+        CoordinateFrame::EclipticJ2000 => EclipticCoord { lon_deg, lat_deg }.to_equatorial(eps0),
+        CoordinateFrame::EquatorialJ2000 => EquatorialCoord {
+            ra_deg: lon_deg,
+            dec_deg: lat_deg,
+        },
+        CoordinateFrame::Galactic => GalacticCoord {
+            l_deg: lon_deg,
+            b_deg: lat_deg,
+        }
+        .to_equatorial(),
+        CoordinateFrame::EquatorialMeanOfDate => EquatorialCoord::from_vector(apply(
+            &transpose(&pn.precession),
+            &EquatorialCoord {
+                ra_deg: lon_deg,
+                dec_deg: lat_deg,
+            }
+            .to_vector(),
+        )),
+        of_date => {
+            let true_equatorial = match of_date {
+                CoordinateFrame::EclipticOfDate => {
+                    EclipticCoord { lon_deg, lat_deg }.to_equatorial(eps)
+                }
+                CoordinateFrame::Horizontal => HorizontalCoord {
+                    alt_deg: lat_deg,
+                    az_deg: lon_deg,
+                }
+                .to_equatorial(observer.lat_deg, lst),
+                _ => EquatorialCoord {
+                    ra_deg: lon_deg,
+                    dec_deg: lat_deg,
+                },
+            };
+            EquatorialCoord::from_vector(apply(&pn.inverse_matrix(), &true_equatorial.to_vector()))
+        }
+    };
+
+    let mean_of_date = EquatorialCoord::from_vector(apply(&pn.precession, &j2000.to_vector()));
+    let of_date = EquatorialCoord::from_vector(apply(&pn.matrix(), &j2000.to_vector()));
+    Ok(CoordinateSet {
+        frame,
+        jd_tt: scales.jd_tt,
+        ecliptic_j2000: j2000.to_ecliptic(eps0),
+        ecliptic_of_date: of_date.to_ecliptic(eps),
+        equatorial_j2000: j2000,
+        equatorial_mean_of_date: mean_of_date,
+        equatorial_of_date: of_date,
+        galactic: j2000.to_galactic(),
+        horizontal: of_date.to_horizontal(observer.lat_deg, lst),
+        local_sidereal_deg: lst,
+        mean_obliquity_deg: pn.mean_obliquity_deg,
+        true_obliquity_deg: eps,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_meeus_example_13a_ecliptic() {
+        // 北河三 (β Gem)：α = 116.328942°，δ = +28.026183°，ε = 23.4392911° → λ = 113.215630°，β = 6.684170°
+        let pollux = EquatorialCoord {
+            ra_deg: 116.328942,
+            dec_deg: 28.026183,
+        };
+        let ecliptic = pollux.to_ecliptic(23.4392911);
+        assert!((ecliptic.lon_deg - 113.215630).abs() < 1e-5);
+        assert!((ecliptic.lat_deg - 6.684170).abs() < 1e-5);
+        let back = ecliptic.to_equatorial(23.4392911);
+        assert!((back.ra_deg - pollux.ra_deg).abs() < 1e-9);
+        assert!((back.dec_deg - pollux.dec_deg).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_meeus_example_13b_horizontal() {
+        // 1987-04-10 19:21:00 UT 华盛顿所见金星：H = 64.352133°，A = 68.0337°（自南起量）→ 自北 248.0337°，h = 15.1249°
+        let venus = EquatorialCoord {
+            ra_deg: 347.3193375,
+            dec_deg: -6.719891667,
+        };
+        let lat = 38.0 + 55.0 / 60.0 + 17.0 / 3600.0;
+        let lst = 128.7368875 - 77.0655556;
+        let horizontal = venus.to_horizontal(lat, lst);
+        assert!(
+            (horizontal.az_deg - 248.0337).abs() < 1e-3,
+            "{:?}",
+            horizontal
+        );
+        assert!(
+            (horizontal.alt_deg - 15.1249).abs() < 1e-3,
+            "{:?}",
+            horizontal
+        );
+
+        let back = horizontal.to_equatorial(lat, lst);
+        assert!((back.ra_deg - venus.ra_deg).abs() < 1e-9);
+        assert!((back.dec_deg - venus.dec_deg).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_galactic_reference_points() {
+        // 银心 (l, b) = (0, 0)：α = 266.40499°，δ = -28.93617°；北银极 δ = 27.12825°
+        let center = GalacticCoord {
+            l_deg: 0.0,
+            b_deg: 0.0,
+        }
+        .to_equatorial();
+        assert!((center.ra_deg - 266.40499).abs() < 1e-4, "{:?}", center);
+        assert!((center.dec_deg - (-28.93617)).abs() < 1e-4, "{:?}", center);
+        let pole = EquatorialCoord {
+            ra_deg: 192.85948,
+            dec_deg: 27.12825,
+        }
+        .to_galactic();
+        assert!((pole.b_deg - 90.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_convert_round_trips_all_frames() {
+        let scales = TimeScales::from_jd_utc(2460000.25);
+        let observer = Observer::new(39.9, 116.4);
+        let source = convert_coordinates(
+            CoordinateFrame::EquatorialJ2000,
+            83.63,
+            22.01,
+            &scales,
+            &observer,
+        )
+        .unwrap();
+        // 岁差使赤经增大约 50"/年 × 23 年
+        let shift = source.equatorial_mean_of_date.ra_deg - 83.63;
+        assert!(shift > 0.2 && shift < 0.4, "{}", shift);
+
+        let inputs = [
+            (
+                CoordinateFrame::EclipticJ2000,
+                source.ecliptic_j2000.lon_deg,
+                source.ecliptic_j2000.lat_deg,
+            ),
+            (
+                CoordinateFrame::EclipticOfDate,
+                source.ecliptic_of_date.lon_deg,
+                source.ecliptic_of_date.lat_deg,
+            ),
+            (
+                CoordinateFrame::EquatorialMeanOfDate,
+                source.equatorial_mean_of_date.ra_deg,
+                source.equatorial_mean_of_date.dec_deg,
+            ),
+            (
+                CoordinateFrame::EquatorialOfDate,
+                source.equatorial_of_date.ra_deg,
+                source.equatorial_of_date.dec_deg,
+            ),
+            (
+                CoordinateFrame::Galactic,
+                source.galactic.l_deg,
+                source.galactic.b_deg,
+            ),
+            (
+                CoordinateFrame::Horizontal,
+                source.horizontal.az_deg,
+                source.horizontal.alt_deg,
+            ),
+        ];
+        for (frame, lon, lat) in inputs {
+            let set = convert_coordinates(frame, lon, lat, &scales, &observer).unwrap();
+            assert!(
+                (set.equatorial_j2000.ra_deg - 83.63).abs() < 1e-8,
+                "{:?}",
+                frame
+            );
+            assert!(
+                (set.equatorial_j2000.dec_deg - 22.01).abs() < 1e-8,
+                "{:?}",
+                frame
+            );
+        }
+
+        assert_eq!(
+            CoordinateFrame::parse("altaz").unwrap(),
+            CoordinateFrame::Horizontal
+        );
+        assert!(CoordinateFrame::parse("supergalactic").is_err());
+        assert!(
+            convert_coordinates(CoordinateFrame::Galactic, 0.0, 91.0, &scales, &observer).is_err()
+        );
+    }
+}
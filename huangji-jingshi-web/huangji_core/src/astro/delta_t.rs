@@ -0,0 +1,385 @@
+//! ΔT = TT - UT1 提供者
+//!
+//! 三种实现，可按请求选择（`DeltaTModel`），结果中报告实际使用的提供者：
+//! - `EspenakMeeus`：NASA 五千年日食典的分段多项式，-1999..+3000 年
+//! - `IersTable`：用户提供的 IERS 观测/预报表（JSON 或 CSV），线性插值；
+//!   表外年份回退到 Espenak–Meeus
+//! - `FixedDeltaT`：固定值，便于复现他人结果或测试
+//!
+//! IERS 表由调用方加载后经 `install_iers_table` 注册（后端在启动时读取配置），
+//! 核心库不读取环境变量或固定路径。已注册的表为进程级全局状态，只在选用 `DeltaTModel::Iers`
+//! 时读取，应于启动时注册一次，不宜按请求替换。
+//!
+//! 适用范围：星空、出没、星历表、行星天象、分野、七政四余、坐标变换，以及节气、八字、
+//! 运势岁首、月历、择日、ICS 导出与日月食，均接受所选提供者（后端各接口的 `deltaT` 参数），
+//! JSON 结果中以 `delta_t_provider` 报告。`time` 模块不带 `_with` 的默认函数仍固定取
+//! Espenak–Meeus；农历日期查表得出，旧版八字（sxtwl）用其库内 ΔT，均不受此参数影响。
+//!
+//! 参考资料：
+//! - F. Espenak, J. Meeus, "Five Millennium Canon of Solar Eclipses: -1999 to +3000",
+//!   NASA/TP-2006-214141, Section 2.6 "Polynomial Expressions for Delta T"
+//! - https://eclipse.gsfc.nasa.gov/SEhelp/deltatpoly2004.html
+//! - IERS Rapid Service/Prediction Centre: https://maia.usno.navy.mil/products/deltaT
+
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::sync::{Arc, RwLock};
+
+/// ΔT 提供者
+pub trait DeltaTProvider: Send + Sync {
+    /// 提供者名称，用于结果报告
+    fn name(&self) -> &'static str;
+
+    /// 按小数年份计算 ΔT（秒）
+    fn delta_t_for_year(&self, year: f64) -> f64;
+
+    /// 按儒略日计算 ΔT（秒）；UT 与 TT 之差对 ΔT 的影响可忽略
+    fn delta_t(&self, jd: f64) -> f64 {
+        self.delta_t_for_year(jd_to_decimal_year(jd))
+    }
+}
+
+/// 儒略日转小数年份
+pub fn jd_to_decimal_year(jd: f64) -> f64 {
+    2000.0 + (jd - 2451545.0) / 365.25
+}
+
+/// Espenak–Meeus 分段多项式
+#[derive(Debug, Clone, Copy, Default)]
+pub struct EspenakMeeus;
+
+impl DeltaTProvider for EspenakMeeus {
+    fn name(&self) -> &'static str {
+        "espenak_meeus"
+    }
+
+    fn delta_t_for_year(&self, y: f64) -> f64 {
+        let long_term = |y: f64| {
+            let u = (y - 1820.0) / 100.0;
+            -20.0 + 32.0 * u * u
+        };
+
+        if y < -500.0 {
+            long_term(y)
+        } else if y < 500.0 {
+            let u = y / 100.0;
+            10583.6 - 1014.41 * u + 33.78311 * u.powi(2)
+                - 5.952053 * u.powi(3)
+                - 0.1798452 * u.powi(4)
+                + 0.022174192 * u.powi(5)
+                + 0.0090316521 * u.powi(6)
+        } else if y < 1600.0 {
+            let u = (y - 1000.0) / 100.0;
+            1574.2 - 556.01 * u + 71.23472 * u.powi(2) + 0.319781 * u.powi(3)
+                - 0.8503463 * u.powi(4)
+                - 0.005050998 * u.powi(5)
+                + 0.0083572073 * u.powi(6)
+        } else if y < 1700.0 {
+            let t = y - 1600.0;
+            120.0 - 0.9808 * t - 0.01532 * t.powi(2) + t.powi(3) / 7129.0
+        } else if y < 1800.0 {
+            let t = y - 1700.0;
+            8.83 + 0.1603 * t - 0.0059285 * t.powi(2) + 0.00013336 * t.powi(3)
+                - t.powi(4) / 1174000.0
+        } else if y < 1860.0 {
+            let t = y - 1800.0;
+            13.72 - 0.332447 * t + 0.0068612 * t.powi(2) + 0.0041116 * t.powi(3)
+                - 0.00037436 * t.powi(4)
+                + 0.0000121272 * t.powi(5)
+                - 0.0000001699 * t.powi(6)
+                + 0.000000000875 * t.powi(7)
+        } else if y < 1900.0 {
+            let t = y - 1860.0;
+            7.62 + 0.5737 * t - 0.251754 * t.powi(2) + 0.01680668 * t.powi(3)
+                - 0.0004473624 * t.powi(4)
+                + t.powi(5) / 233174.0
+        } else if y < 1920.0 {
+            let t = y - 1900.0;
+            -2.79 + 1.494119 * t - 0.0598939 * t.powi(2) + 0.0061966 * t.powi(3)
+                - 0.000197 * t.powi(4)
+        } else if y < 1941.0 {
+            let t = y - 1920.0;
+            21.20 + 0.84493 * t - 0.076100 * t.powi(2) + 0.0020936 * t.powi(3)
+        } else if y < 1961.0 {
+            let t = y - 1950.0;
+            29.07 + 0.407 * t - t.powi(2) / 233.0 + t.powi(3) / 2547.0
+        } else if y < 1986.0 {
+            let t = y - 1975.0;
+            45.45 + 1.067 * t - t.powi(2) / 260.0 - t.powi(3) / 718.0
+        } else if y < 2005.0 {
+            let t = y - 2000.0;
+            63.86 + 0.3345 * t - 0.060374 * t.powi(2)
+                + 0.0017275 * t.powi(3)
+                + 0.000651814 * t.powi(4)
+                + 0.00002373599 * t.powi(5)
+        } else if y < 2050.0 {
+            let t = y - 2000.0;
+            62.92 + 0.32217 * t + 0.005589 * t.powi(2)
+        } else if y < 2150.0 {
+            long_term(y) - 0.5628 * (2150.0 - y)
+        } else {
+            long_term(y)
+        }
+    }
+}
+
+/// 固定 ΔT
+#[derive(Debug, Clone, Copy)]
+pub struct FixedDeltaT {
+    /// ΔT（秒）
+    pub seconds: f64,
+}
+
+impl DeltaTProvider for FixedDeltaT {
+    fn name(&self) -> &'static str {
+        "fixed"
+    }
+
+    fn delta_t_for_year(&self, _year: f64) -> f64 {
+        self.seconds
+    }
+}
+
+/// IERS ΔT 表（按年份升序的 (小数年份, ΔT 秒) 序列）
+#[derive(Debug, Clone)]
+pub struct IersTable {
+    points: Vec<(f64, f64)>,
+}
+
+/// JSON 表的单行
+#[derive(Deserialize)]
+struct IersRecord {
+    year: f64,
+    delta_t_sec: f64,
+}
+
+impl IersTable {
+    /// 由 (年份, ΔT) 序列构造；至少需要两个点
+    pub fn new(mut points: Vec<(f64, f64)>) -> anyhow::Result<Self> {
+        if points
+            .iter()
+            .any(|(year, value)| !year.is_finite() || !value.is_finite())
+        {
+            anyhow::bail!("IERS ΔT 表含非数值");
+        }
+        points.sort_by(|a, b| a.0.total_cmp(&b.0));
+        points.dedup_by(|a, b| a.0 == b.0);
+        if points.len() < 2 {
+            anyhow::bail!("IERS ΔT 表至少需要两行");
+        }
+        Ok(Self { points })
+    }
+
+    /// 解析 JSON：`[{"year": 2020.0, "delta_t_sec": 69.36}, ...]`
+    pub fn from_json_str(content: &str) -> anyhow::Result<Self> {
+        let records: Vec<IersRecord> = serde_json::from_str(content)
+            .map_err(|e| anyhow::anyhow!("IERS ΔT 表 JSON 解析失败: {}", e))?;
+        Self::new(
+            records
+                .into_iter()
+                .map(|r| (r.year, r.delta_t_sec))
+                .collect(),
+        )
+    }
+
+    /// 解析 CSV：每行 `年份,ΔT`（亦接受分号、制表符或空格分隔），忽略空行、`#` 注释与表头
+    pub fn from_csv_str(content: &str) -> anyhow::Result<Self> {
+        let mut points = Vec::new();
+        for line in content.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let parts: Vec<&str> = line
+                .split([',', ';', '\t', ' '])
+                .filter(|s| !s.is_empty())
+                .collect();
+            if let [year, value, ..] = parts[..] {
+                if let (Ok(year), Ok(value)) = (year.parse::<f64>(), value.parse::<f64>()) {
+                    points.push((year, value));
+                }
+            }
+        }
+        Self::new(points)
+    }
+
+    /// 从文件加载；扩展名为 `.json` 时按 JSON 解析，否则按 CSV
+    pub fn load(path: &Path) -> anyhow::Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| anyhow::anyhow!("读取 IERS ΔT 表 {} 失败: {}", path.display(), e))?;
+        if path.extension().is_some_and(|ext| ext == "json") {
+            Self::from_json_str(&content)
+        } else {
+            Self::from_csv_str(&content)
+        }
+    }
+
+    /// 表覆盖的年份范围
+    pub fn coverage(&self) -> (f64, f64) {
+        (self.points[0].0, self.points[self.points.len() - 1].0)
+    }
+
+    /// 表内插值；表外返回 None
+    pub fn interpolate(&self, year: f64) -> Option<f64> {
+        let (first, last) = self.coverage();
+        if !(first..=last).contains(&year) {
+            return None;
+        }
+        let upper = self.points.partition_point(|(y, _)| *y < year).max(1);
+        let (y0, v0) = self.points[upper - 1];
+        let (y1, v1) = self.points[upper];
+        Some(v0 + (year - y0) / (y1 - y0) * (v1 - v0))
+    }
+}
+
+impl DeltaTProvider for IersTable {
+    fn name(&self) -> &'static str {
+        "iers"
+    }
+
+    fn delta_t_for_year(&self, year: f64) -> f64 {
+        self.interpolate(year)
+            .unwrap_or_else(|| EspenakMeeus.delta_t_for_year(year))
+    }
+}
+
+/// 已注册的 IERS 表
+static IERS_TABLE: Lazy<RwLock<Option<Arc<IersTable>>>> = Lazy::new(|| RwLock::new(None));
+
+/// 注册全局 IERS 表，供 `DeltaTModel::Iers` 使用；默认换算（见 `time` 模块）不读取此表
+pub fn install_iers_table(table: IersTable) {
+    *IERS_TABLE.write().unwrap() = Some(Arc::new(table));
+}
+
+/// 当前注册的 IERS 表
+pub fn installed_iers_table() -> Option<Arc<IersTable>> {
+    IERS_TABLE.read().unwrap().clone()
+}
+
+/// 按请求选择的 ΔT 模型
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum DeltaTModel {
+    /// Espenak–Meeus 多项式（默认）
+    #[default]
+    EspenakMeeus,
+    /// 已注册的 IERS 表；未注册时回退到 Espenak–Meeus
+    Iers,
+    /// 固定值（秒）
+    Fixed { seconds: f64 },
+}
+
+impl DeltaTModel {
+    /// 解析请求参数：`espenak_meeus`（兼容 `nasa`、`segmented`）、`iers`、`fixed:<秒>` 或直接给出秒数
+    pub fn parse(raw: &str) -> anyhow::Result<Self> {
+        let raw = raw.trim();
+        match raw.to_ascii_lowercase().as_str() {
+            "" | "espenak_meeus" | "espenak-meeus" | "nasa" | "segmented" => {
+                return Ok(Self::EspenakMeeus)
+            }
+            "iers" => return Ok(Self::Iers),
+            _ => {}
+        }
+        let seconds = raw
+            .strip_prefix("fixed:")
+            .unwrap_or(raw)
+            .trim()
+            .parse::<f64>()
+            .ok()
+            .filter(|s| s.is_finite())
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "无效的 ΔT 模型：{}（可选 espenak_meeus、iers、fixed:<秒>）",
+                    raw
+                )
+            })?;
+        Ok(Self::Fixed { seconds })
+    }
+
+    /// 解析为具体提供者
+    pub fn provider(&self) -> Arc<dyn DeltaTProvider> {
+        match self {
+            Self::EspenakMeeus => Arc::new(EspenakMeeus),
+            Self::Iers => match installed_iers_table() {
+                Some(table) => table,
+                None => Arc::new(EspenakMeeus),
+            },
+            Self::Fixed { seconds } => Arc::new(FixedDeltaT { seconds: *seconds }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_espenak_meeus_known_values() {
+        // 多项式在各段的取值：1900 ≈ -2.8 s，1950 ≈ 29.1 s，2000 ≈ 63.9 s，
+        // 1000 年 ≈ 1574 s，-500 年 ≈ 17204 s（两段在此衔接）
+        for (year, expected, tolerance) in [
+            (1900.0, -2.79, 0.1),
+            (1950.0, 29.07, 0.1),
+            (2000.0, 63.86, 0.1),
+            (1000.0, 1574.2, 1.0),
+            (-500.0, 17203.7, 1.0),
+            (-500.001, 17203.7, 1.0),
+        ] {
+            let value = EspenakMeeus.delta_t_for_year(year);
+            assert!(
+                (value - expected).abs() < tolerance,
+                "{} 年 ΔT={}",
+                year,
+                value
+            );
+        }
+    }
+
+    #[test]
+    fn test_iers_table_interpolation_and_fallback() {
+        let table = IersTable::from_csv_str(
+            "# year, delta_t\nyear,dt\n2020.0, 69.36\n2021.0,69.36\n2022.0;69.20\n",
+        )
+        .unwrap();
+        assert_eq!(table.coverage(), (2020.0, 2022.0));
+        assert!((table.delta_t_for_year(2021.5) - 69.28).abs() < 1e-9);
+        assert!((table.delta_t_for_year(2020.0) - 69.36).abs() < 1e-9);
+        // 表外回退到 Espenak–Meeus
+        assert_eq!(
+            table.delta_t_for_year(1900.0),
+            EspenakMeeus.delta_t_for_year(1900.0)
+        );
+
+        let json = IersTable::from_json_str(
+            r#"[{"year": 2001, "delta_t_sec": 64.09}, {"year": 2000, "delta_t_sec": 63.83}]"#,
+        )
+        .unwrap();
+        assert!((json.delta_t_for_year(2000.5) - 63.96).abs() < 1e-9);
+
+        assert!(IersTable::from_csv_str("2020,69.36\n").is_err());
+        assert!(IersTable::from_json_str("{}").is_err());
+    }
+
+    #[test]
+    fn test_model_parse_and_provider() {
+        assert_eq!(
+            DeltaTModel::parse("nasa").unwrap(),
+            DeltaTModel::EspenakMeeus
+        );
+        assert_eq!(DeltaTModel::parse("IERS").unwrap(), DeltaTModel::Iers);
+        assert_eq!(
+            DeltaTModel::parse("fixed:69.2").unwrap(),
+            DeltaTModel::Fixed { seconds: 69.2 }
+        );
+        assert_eq!(
+            DeltaTModel::parse("32.184").unwrap(),
+            DeltaTModel::Fixed { seconds: 32.184 }
+        );
+        assert!(DeltaTModel::parse("bogus").is_err());
+        assert!(DeltaTModel::parse("fixed:nan").is_err());
+
+        let fixed = DeltaTModel::Fixed { seconds: 12.0 }.provider();
+        assert_eq!(fixed.name(), "fixed");
+        assert_eq!(fixed.delta_t(2451545.0), 12.0);
+    }
+}
@@ -0,0 +1,713 @@
+//! 日食与月食
+//!
+//! 参考资料：
+//! - Jean Meeus, "Astronomical Algorithms" (2nd ed.), Chapter 54 "Eclipses"
+//! - Explanatory Supplement to the Astronomical Almanac (1992), §8.3 "Eclipses of the Sun",
+//!   §8.4 "Eclipses of the Moon"
+//! - F. Espenak, J. Meeus, "Five Millennium Canon of Solar/Lunar Eclipses" (NASA TP-2006-214141, 2009-214172)
+//!
+//! 不用预制的贝塞尔根数，而是在每次朔望附近直接由太阳 (VSOP87D) 与月球 (ELP2000-82)
+//! 的地心位置构造影锥：
+//! - 日食：月影轴与地心的最近距离 γ（地球赤道半径）决定食甚，按本影锥在地面的半径分全食、环食，
+//!   γ 超出本影而未出半影者为偏食
+//! - 月食：月心与地影中心的角距决定食甚，地影按 Danjon 法放大 1%，
+//!   依本影食分与半影食分分全食、偏食、半影食
+//!
+//! 时刻误差约一分钟以内，食分误差约 0.01。地平高度未计大气折射。
+
+use crate::astro::coords::AU_KM;
+use crate::astro::delta_t::DeltaTProvider;
+use crate::astro::lunar::moon_position;
+use crate::astro::moon_phase::{find_moon_phases_with, MoonPhase};
+use crate::astro::numeric::{bisect, dot, minimize, norm, scale, sub};
+use crate::astro::nutation::true_obliquity;
+use crate::astro::observer::{Observer, EARTH_EQUATORIAL_RADIUS_KM};
+use crate::astro::solar::{apparent_sun, datetime_to_jd};
+use crate::astro::time::{jd_utc_to_tt_with, tt_to_utc_with, TimeScales};
+use chrono::{DateTime, Datelike, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+
+/// 太阳半径 (km)，对应 1 AU 处视半径 959.63"
+const SUN_RADIUS_KM: f64 = 696_000.0;
+
+/// 月球半径 (km)，k = 0.272481 个地球赤道半径
+const MOON_RADIUS_KM: f64 = 0.272481 * EARTH_EQUATORIAL_RADIUS_KM;
+
+/// 单次查询的最大年数
+pub const MAX_ECLIPSE_YEARS: i32 = 50;
+
+/// 朔望时月球黄纬超过此值 (度) 则不可能发生交食
+const ECLIPSE_LATITUDE_LIMIT: f64 = 1.8;
+
+/// 搜索食甚与初亏、复圆的时间窗半宽 (日)
+const SEARCH_WINDOW: f64 = 0.3;
+
+/// 日食或月食
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EclipseKind {
+    Solar,
+    Lunar,
+}
+
+/// 食的类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EclipseType {
+    /// 全食
+    Total,
+    /// 环食（仅日食）
+    Annular,
+    /// 偏食
+    Partial,
+    /// 半影食（仅月食）
+    Penumbral,
+}
+
+/// 接触时刻（初亏、食既、生光、复圆等）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EclipseContact {
+    /// 接触标记：月食 P1/U1/U2/U3/U4/P4，日食 C1/C2/C3/C4
+    pub label: String,
+    pub time: DateTime<Utc>,
+    /// 该时刻食体（日食为太阳，月食为月亮）的地平高度 (度)，仅在指定观测者时给出
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub altitude_deg: Option<f64>,
+}
+
+/// 观测地的局部情况
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LocalCircumstances {
+    pub observer: Observer,
+    /// 当地能否看到（食甚或任一接触时刻食体在地平线以上）
+    pub visible: bool,
+    /// 当地食甚
+    pub maximum: DateTime<Utc>,
+    /// 当地食分：日食为月面遮住太阳直径的比例，月食同全局食分
+    pub magnitude: f64,
+    /// 当地食甚时食体地平高度 (度)
+    pub altitude_deg: f64,
+    pub contacts: Vec<EclipseContact>,
+}
+
+/// 一次交食
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Eclipse {
+    pub kind: EclipseKind,
+    pub eclipse_type: EclipseType,
+    /// 食甚 (UTC)
+    pub greatest: DateTime<Utc>,
+    /// 食甚力学时儒略日
+    pub jd_tt: f64,
+    /// 食分：日食为食甚点（地面）的食分，月食为本影食分
+    pub magnitude: f64,
+    /// 半影食分（仅月食）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub penumbral_magnitude: Option<f64>,
+    /// 影轴与地心（月食为月心与地影轴）的最近距离，地球赤道半径为单位，北正南负
+    pub gamma: f64,
+    /// 全局接触时刻：月食为半影/本影接触；日食不给出
+    pub contacts: Vec<EclipseContact>,
+    /// 指定观测者时的当地情况；日食在当地不可见食象时为 None
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub local: Option<LocalCircumstances>,
+}
+
+impl Eclipse {
+    /// 中文名称，如 日全食、半影月食
+    pub fn name(&self) -> &'static str {
+        match (self.kind, self.eclipse_type) {
+            (EclipseKind::Solar, EclipseType::Total) => "日全食",
+            (EclipseKind::Solar, EclipseType::Annular) => "日环食",
+            (EclipseKind::Solar, _) => "日偏食",
+            (EclipseKind::Lunar, EclipseType::Total) => "月全食",
+            (EclipseKind::Lunar, EclipseType::Penumbral) => "半影月食",
+            (EclipseKind::Lunar, _) => "月偏食",
+        }
+    }
+
+    /// 英文标题，与 major_events.json 体例一致，如 "Total lunar eclipse (Mar 14, 2025)"
+    pub fn title(&self) -> String {
+        let kind = match self.kind {
+            EclipseKind::Solar => "solar",
+            EclipseKind::Lunar => "lunar",
+        };
+        let eclipse_type = match self.eclipse_type {
+            EclipseType::Total => "Total",
+            EclipseType::Annular => "Annular",
+            EclipseType::Partial => "Partial",
+            EclipseType::Penumbral => "Penumbral",
+        };
+        format!(
+            "{} {} eclipse ({})",
+            eclipse_type,
+            kind,
+            self.greatest.format("%b %-d, %Y")
+        )
+    }
+
+    /// 转为 major_events.json 的事件条目
+    pub fn to_event(&self) -> serde_json::Value {
+        let (kind_tag, magnitude_label) = match self.kind {
+            EclipseKind::Solar => ("Solar", "magnitude"),
+            EclipseKind::Lunar => ("Lunar", "umbral magnitude"),
+        };
+        serde_json::json!({
+            "year": self.greatest.year(),
+            "title": self.title(),
+            "description": format!(
+                "Greatest eclipse at {} UTC; {} {:.3}, gamma {:.4}.",
+                self.greatest.format("%Y-%m-%d %H:%M"),
+                magnitude_label,
+                self.magnitude,
+                self.gamma
+            ),
+            "category": "Astronomy",
+            "tags": ["Eclipse", kind_tag, "Computed"],
+        })
+    }
+}
+
+/// 查找 [start_year, end_year] 各年（UTC 日历年，含两端）的全部日月食，按时间排序
+///
+/// 指定 `observer` 时附带当地情况。食甚等时刻在 TT 下求得，按 `delta_t` 换回 UTC；
+/// 当地情况的地平高度也依此取地球自转角。
+pub fn find_eclipses(
+    start_year: i32,
+    end_year: i32,
+    observer: Option<&Observer>,
+    delta_t: &dyn DeltaTProvider,
+) -> anyhow::Result<Vec<Eclipse>> {
+    if end_year < start_year {
+        anyhow::bail!("结束年份 {} 早于起始年份 {}", end_year, start_year);
+    }
+    if end_year - start_year + 1 > MAX_ECLIPSE_YEARS {
+        anyhow::bail!("单次最多查询 {} 年", MAX_ECLIPSE_YEARS);
+    }
+    let year_start_jd = |year: i32| {
+        NaiveDate::from_ymd_opt(year, 1, 1)
+            .and_then(|date| date.and_hms_opt(0, 0, 0))
+            .map(|dt| datetime_to_jd(&dt))
+            .ok_or_else(|| anyhow::anyhow!("无效年份：{}", year))
+    };
+    let start_jd = year_start_jd(start_year)?;
+    let end_jd = year_start_jd(end_year + 1)?;
+
+    let mut eclipses = Vec::new();
+    for event in find_moon_phases_with(start_jd, end_jd, delta_t) {
+        let jde = jd_utc_to_tt_with(event.jd, delta_t);
+        if moon_position(jde).latitude.abs() > ECLIPSE_LATITUDE_LIMIT {
+            continue;
+        }
+        let eclipse = match event.phase {
+            MoonPhase::New => solar_eclipse(jde, observer, delta_t),
+            MoonPhase::Full => lunar_eclipse(jde, observer, delta_t),
+            _ => None,
+        };
+        eclipses.extend(eclipse);
+    }
+    Ok(eclipses)
+}
+
+/// 某时刻日、月的地心赤道直角坐标 (km)，瞬时真赤道与春分点
+struct SunMoon {
+    sun: [f64; 3],
+    moon: [f64; 3],
+}
+
+fn sun_moon(jde: f64) -> SunMoon {
+    let eps = true_obliquity(jde).to_radians();
+    let sun = apparent_sun(jde);
+    let moon = moon_position(jde);
+    SunMoon {
+        sun: ecliptic_to_equatorial(sun.longitude, sun.latitude, sun.radius * AU_KM, eps),
+        moon: ecliptic_to_equatorial(moon.longitude, moon.latitude, moon.distance_km, eps),
+    }
+}
+
+fn ecliptic_to_equatorial(lon_deg: f64, lat_deg: f64, distance: f64, eps: f64) -> [f64; 3] {
+    let (lon, lat) = (lon_deg.to_radians(), lat_deg.to_radians());
+    [
+        distance * lat.cos() * lon.cos(),
+        distance * (lat.cos() * lon.sin() * eps.cos() - lat.sin() * eps.sin()),
+        distance * (lat.cos() * lon.sin() * eps.sin() + lat.sin() * eps.cos()),
+    ]
+}
+
+/// 两向量夹角 (弧度)
+fn angle_between(a: [f64; 3], b: [f64; 3]) -> f64 {
+    (dot(a, b) / (norm(a) * norm(b))).clamp(-1.0, 1.0).acos()
+}
+
+/// 地平高度 (度)：目标方向与天顶的余角
+fn altitude(direction: [f64; 3], zenith: [f64; 3]) -> f64 {
+    (dot(direction, zenith) / norm(direction))
+        .clamp(-1.0, 1.0)
+        .asin()
+        .to_degrees()
+}
+
+/// 求 value(t) 等于 limit 的前后两次接触（以食甚为界），食甚时未达 limit 则无接触
+fn contact_pair(
+    value: &impl Fn(f64) -> f64,
+    limit: &impl Fn(f64) -> f64,
+    greatest: f64,
+) -> Option<(f64, f64)> {
+    let g = |t: f64| value(t) - limit(t);
+    if g(greatest) >= 0.0 || g(greatest - SEARCH_WINDOW) < 0.0 || g(greatest + SEARCH_WINDOW) < 0.0
+    {
+        return None;
+    }
+    Some((
+        bisect(g, greatest - SEARCH_WINDOW, greatest),
+        bisect(g, greatest, greatest + SEARCH_WINDOW),
+    ))
+}
+
+fn contact(
+    label: &str,
+    jde: f64,
+    altitude_deg: Option<f64>,
+    delta_t: &dyn DeltaTProvider,
+) -> Option<EclipseContact> {
+    Some(EclipseContact {
+        label: label.to_string(),
+        time: tt_to_utc_with(jde, delta_t)?,
+        altitude_deg,
+    })
+}
+
+/// 日食影锥在基本面（过地心且垂直于影轴的平面）上的几何
+struct SolarShadow {
+    /// 影轴到地心的距离，地球赤道半径为单位，北正南负
+    gamma: f64,
+    /// 半影半径 (km)
+    penumbra: f64,
+    /// 本影半径 (km)，正为本影（全食）、负为伪本影（环食）
+    umbra: f64,
+    /// 月球到基本面的距离 (km)
+    moon_height: f64,
+    /// 本影锥半顶角的正切
+    umbra_slope: f64,
+}
+
+fn solar_shadow(jde: f64) -> SolarShadow {
+    let SunMoon { sun, moon } = sun_moon(jde);
+    let sun_to_moon = sub(moon, sun);
+    let separation = norm(sun_to_moon);
+    let axis = scale(sun_to_moon, 1.0 / separation);
+
+    let along = dot(moon, axis);
+    let offset = sub(moon, scale(axis, along));
+    // 基本面内朝北的方向：天极在基本面上的投影
+    let north = sub([0.0, 0.0, 1.0], scale(axis, axis[2]));
+    let sign = if dot(offset, north) < 0.0 { -1.0 } else { 1.0 };
+
+    let moon_height = -along;
+    let penumbra_angle = ((SUN_RADIUS_KM + MOON_RADIUS_KM) / separation).asin();
+    let umbra_angle = ((SUN_RADIUS_KM - MOON_RADIUS_KM) / separation).asin();
+    SolarShadow {
+        gamma: sign * norm(offset) / EARTH_EQUATORIAL_RADIUS_KM,
+        penumbra: moon_height * penumbra_angle.tan() + MOON_RADIUS_KM / penumbra_angle.cos(),
+        umbra: MOON_RADIUS_KM / umbra_angle.cos() - moon_height * umbra_angle.tan(),
+        moon_height,
+        umbra_slope: umbra_angle.tan(),
+    }
+}
+
+fn solar_eclipse(
+    new_moon_jde: f64,
+    observer: Option<&Observer>,
+    delta_t: &dyn DeltaTProvider,
+) -> Option<Eclipse> {
+    let greatest = minimize(
+        |t| solar_shadow(t).gamma.abs(),
+        new_moon_jde - SEARCH_WINDOW,
+        new_moon_jde + SEARCH_WINDOW,
+    );
+    let shadow = solar_shadow(greatest);
+    let gamma = shadow.gamma.abs();
+    let penumbra = shadow.penumbra / EARTH_EQUATORIAL_RADIUS_KM;
+    let umbra = shadow.umbra / EARTH_EQUATORIAL_RADIUS_KM;
+    if gamma >= 1.0 + penumbra {
+        return None;
+    }
+
+    let (eclipse_type, magnitude) = if gamma < 1.0 {
+        // 中心食：影轴与地面交点离月球更近，本影半径相应放大
+        let depth = (1.0 - gamma * gamma).sqrt() * EARTH_EQUATORIAL_RADIUS_KM;
+        let umbra_at_surface = shadow.umbra + depth * shadow.umbra_slope;
+        let SunMoon { sun, .. } = sun_moon(greatest);
+        let moon_distance = shadow.moon_height - depth;
+        let sun_distance = norm(sun) - depth;
+        let magnitude = (MOON_RADIUS_KM / moon_distance) / (SUN_RADIUS_KM / sun_distance);
+        let eclipse_type = if umbra_at_surface > 0.0 {
+            EclipseType::Total
+        } else {
+            EclipseType::Annular
+        };
+        (eclipse_type, magnitude)
+    } else {
+        // 非中心食：按 Meeus 第 54 章以半影、本影半径内插地面最大食分
+        let magnitude = (1.0 + penumbra - gamma) / (penumbra - umbra);
+        let eclipse_type = if gamma < 1.0 + umbra.abs() {
+            if umbra > 0.0 {
+                EclipseType::Total
+            } else {
+                EclipseType::Annular
+            }
+        } else {
+            EclipseType::Partial
+        };
+        (eclipse_type, magnitude)
+    };
+
+    Some(Eclipse {
+        kind: EclipseKind::Solar,
+        eclipse_type,
+        greatest: tt_to_utc_with(greatest, delta_t)?,
+        jd_tt: greatest,
+        magnitude,
+        penumbral_magnitude: None,
+        gamma: shadow.gamma,
+        contacts: Vec::new(),
+        local: observer.and_then(|observer| local_solar(greatest, observer, delta_t)),
+    })
+}
+
+/// 观测者所见日月的站心几何
+struct TopocentricSunMoon {
+    /// 日月中心角距 (弧度)
+    separation: f64,
+    /// 太阳视半径 (弧度)
+    sun_radius: f64,
+    /// 月亮视半径 (弧度)
+    moon_radius: f64,
+    /// 太阳地平高度 (度)
+    sun_altitude: f64,
+}
+
+fn topocentric_sun_moon(
+    jde: f64,
+    observer: &Observer,
+    delta_t: &dyn DeltaTProvider,
+) -> TopocentricSunMoon {
+    let scales = TimeScales::from_jd_tt_with(jde, delta_t);
+    let site = observer.geocentric_position(&scales);
+    let SunMoon { sun, moon } = sun_moon(jde);
+    let sun = sub(sun, site);
+    let moon = sub(moon, site);
+    TopocentricSunMoon {
+        separation: angle_between(sun, moon),
+        sun_radius: (SUN_RADIUS_KM / norm(sun)).asin(),
+        moon_radius: (MOON_RADIUS_KM / norm(moon)).asin(),
+        sun_altitude: altitude(sun, observer.zenith(&scales)),
+    }
+}
+
+fn local_solar(
+    greatest: f64,
+    observer: &Observer,
+    delta_t: &dyn DeltaTProvider,
+) -> Option<LocalCircumstances> {
+    let geometry = |t: f64| topocentric_sun_moon(t, observer, delta_t);
+    let maximum = minimize(
+        |t| geometry(t).separation,
+        greatest - SEARCH_WINDOW,
+        greatest + SEARCH_WINDOW,
+    );
+    let at_max = geometry(maximum);
+    if at_max.separation >= at_max.sun_radius + at_max.moon_radius {
+        return None;
+    }
+
+    let separation = |t: f64| geometry(t).separation;
+    let altitude_at = |t: f64| Some(geometry(t).sun_altitude);
+    let mut contacts = Vec::new();
+    let outer = contact_pair(
+        &separation,
+        &|t| {
+            let g = geometry(t);
+            g.sun_radius + g.moon_radius
+        },
+        maximum,
+    );
+    let inner = contact_pair(
+        &separation,
+        &|t| {
+            let g = geometry(t);
+            (g.sun_radius - g.moon_radius).abs()
+        },
+        maximum,
+    );
+    if let Some((c1, c4)) = outer {
+        contacts.extend(contact("C1", c1, altitude_at(c1), delta_t));
+        if let Some((c2, c3)) = inner {
+            contacts.extend(contact("C2", c2, altitude_at(c2), delta_t));
+            contacts.extend(contact("C3", c3, altitude_at(c3), delta_t));
+        }
+        contacts.extend(contact("C4", c4, altitude_at(c4), delta_t));
+    }
+
+    let visible = at_max.sun_altitude > 0.0
+        || contacts
+            .iter()
+            .any(|c| c.altitude_deg.is_some_and(|alt| alt > 0.0));
+    Some(LocalCircumstances {
+        observer: *observer,
+        visible,
+        maximum: tt_to_utc_with(maximum, delta_t)?,
+        magnitude: (at_max.sun_radius + at_max.moon_radius - at_max.separation)
+            / (2.0 * at_max.sun_radius),
+        altitude_deg: at_max.sun_altitude,
+        contacts,
+    })
+}
+
+/// 月食时地影与月亮的地心几何（角度均为度）
+struct LunarShadow {
+    /// 月心与地影中心的角距
+    separation: f64,
+    /// 半影半径
+    penumbra: f64,
+    /// 本影半径
+    umbra: f64,
+    /// 月亮视半径
+    moon_radius: f64,
+    /// 月心相对地影中心的南北方向，北正
+    north: f64,
+    /// 地月距离 (km)
+    moon_distance: f64,
+}
+
+fn lunar_shadow(jde: f64) -> LunarShadow {
+    let SunMoon { sun, moon } = sun_moon(jde);
+    let moon_distance = norm(moon);
+    let sun_distance = norm(sun);
+    let moon_parallax = (EARTH_EQUATORIAL_RADIUS_KM / moon_distance)
+        .asin()
+        .to_degrees();
+    let sun_parallax = (EARTH_EQUATORIAL_RADIUS_KM / sun_distance)
+        .asin()
+        .to_degrees();
+    let sun_radius = (SUN_RADIUS_KM / sun_distance).asin().to_degrees();
+    // Danjon 法：地球半径放大 1% 以计大气
+    let earth = 1.01 * moon_parallax + sun_parallax;
+
+    let shadow_center = scale(sun, -1.0);
+    let north = moon[2] / moon_distance - shadow_center[2] / sun_distance;
+    LunarShadow {
+        separation: angle_between(moon, shadow_center).to_degrees(),
+        penumbra: earth + sun_radius,
+        umbra: earth - sun_radius,
+        moon_radius: (MOON_RADIUS_KM / moon_distance).asin().to_degrees(),
+        north,
+        moon_distance,
+    }
+}
+
+/// 接触条件：月心与地影中心角距达到此值
+type ShadowLimit = fn(&LunarShadow) -> f64;
+
+fn lunar_eclipse(
+    full_moon_jde: f64,
+    observer: Option<&Observer>,
+    delta_t: &dyn DeltaTProvider,
+) -> Option<Eclipse> {
+    let greatest = minimize(
+        |t| lunar_shadow(t).separation,
+        full_moon_jde - SEARCH_WINDOW,
+        full_moon_jde + SEARCH_WINDOW,
+    );
+    let shadow = lunar_shadow(greatest);
+    let diameter = 2.0 * shadow.moon_radius;
+    let umbral_magnitude = (shadow.umbra + shadow.moon_radius - shadow.separation) / diameter;
+    let penumbral_magnitude = (shadow.penumbra + shadow.moon_radius - shadow.separation) / diameter;
+    let eclipse_type = if umbral_magnitude >= 1.0 {
+        EclipseType::Total
+    } else if umbral_magnitude > 0.0 {
+        EclipseType::Partial
+    } else if penumbral_magnitude > 0.0 {
+        EclipseType::Penumbral
+    } else {
+        return None;
+    };
+
+    let moon_altitude = |t: f64| {
+        observer.map(|observer| {
+            let scales = TimeScales::from_jd_tt_with(t, delta_t);
+            let SunMoon { moon, .. } = sun_moon(t);
+            altitude(
+                sub(moon, observer.geocentric_position(&scales)),
+                observer.zenith(&scales),
+            )
+        })
+    };
+
+    let separation = |t: f64| lunar_shadow(t).separation;
+    let limits: [(&str, &str, ShadowLimit); 3] = [
+        ("P1", "P4", |s| s.penumbra + s.moon_radius),
+        ("U1", "U4", |s| s.umbra + s.moon_radius),
+        ("U2", "U3", |s| s.umbra - s.moon_radius),
+    ];
+    let mut starts = Vec::new();
+    let mut ends = Vec::new();
+    for (first, last, limit) in limits {
+        if let Some((begin, end)) =
+            contact_pair(&separation, &|t| limit(&lunar_shadow(t)), greatest)
+        {
+            starts.extend(contact(first, begin, moon_altitude(begin), delta_t));
+            ends.push(contact(last, end, moon_altitude(end), delta_t));
+        }
+    }
+    let contacts: Vec<EclipseContact> = starts
+        .into_iter()
+        .chain(ends.into_iter().rev().flatten())
+        .collect();
+
+    let greatest_utc = tt_to_utc_with(greatest, delta_t)?;
+    let local = match (observer, moon_altitude(greatest)) {
+        (Some(observer), Some(altitude_deg)) => Some(LocalCircumstances {
+            observer: *observer,
+            visible: altitude_deg > 0.0
+                || contacts
+                    .iter()
+                    .any(|c| c.altitude_deg.is_some_and(|alt| alt > 0.0)),
+            maximum: greatest_utc,
+            magnitude: umbral_magnitude,
+            altitude_deg,
+            contacts: contacts.clone(),
+        }),
+        _ => None,
+    };
+
+    let gamma =
+        shadow.separation.to_radians().sin() * shadow.moon_distance / EARTH_EQUATORIAL_RADIUS_KM;
+    Some(Eclipse {
+        kind: EclipseKind::Lunar,
+        eclipse_type,
+        greatest: greatest_utc,
+        jd_tt: greatest,
+        magnitude: umbral_magnitude,
+        penumbral_magnitude: Some(penumbral_magnitude),
+        gamma: if shadow.north < 0.0 { -gamma } else { gamma },
+        contacts,
+        local,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::astro::delta_t::{EspenakMeeus, FixedDeltaT};
+    use chrono::TimeZone;
+
+    fn minutes_between(a: DateTime<Utc>, b: DateTime<Utc>) -> f64 {
+        (a - b).num_seconds().abs() as f64 / 60.0
+    }
+
+    #[test]
+    fn test_eclipses_2024_2025() {
+        // NASA 日月食表：2024–2025 共 8 次交食
+        let eclipses = find_eclipses(2024, 2025, None, &EspenakMeeus).unwrap();
+        let summary: Vec<_> = eclipses
+            .iter()
+            .map(|e| (e.kind, e.eclipse_type, e.greatest.date_naive()))
+            .collect();
+        let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+        use EclipseKind::*;
+        use EclipseType::*;
+        assert_eq!(
+            summary,
+            vec![
+                (Lunar, Penumbral, date(2024, 3, 25)),
+                (Solar, Total, date(2024, 4, 8)),
+                (Lunar, Partial, date(2024, 9, 18)),
+                (Solar, Annular, date(2024, 10, 2)),
+                (Lunar, Total, date(2025, 3, 14)),
+                (Solar, Partial, date(2025, 3, 29)),
+                (Lunar, Total, date(2025, 9, 7)),
+                (Solar, Partial, date(2025, 9, 21)),
+            ]
+        );
+
+        // 2024-04-08 日全食：食甚 18:17:16 UT，食分 1.0566，γ = 0.3431
+        let tse = &eclipses[1];
+        let expected = Utc.with_ymd_and_hms(2024, 4, 8, 18, 17, 16).unwrap();
+        assert!(
+            minutes_between(tse.greatest, expected) < 2.0,
+            "{}",
+            tse.greatest
+        );
+        assert!((tse.magnitude - 1.0566).abs() < 0.005, "{}", tse.magnitude);
+        assert!((tse.gamma - 0.3431).abs() < 0.005, "{}", tse.gamma);
+
+        // 食甚在 TT 下求得；ΔT 取 0 时 UTC 即 TT，晚出一个 ΔT
+        let fixed = find_eclipses(2024, 2024, None, &FixedDeltaT { seconds: 0.0 }).unwrap();
+        assert!((fixed[1].jd_tt - tse.jd_tt).abs() * 86400.0 < 1.0);
+        let shift = (fixed[1].greatest - tse.greatest).num_seconds() as f64;
+        assert!(
+            (shift - EspenakMeeus.delta_t(tse.jd_tt)).abs() <= 2.0,
+            "shift={}",
+            shift
+        );
+
+        // 2025-03-14 月全食：食甚 06:58:43 UT，本影食分 1.178
+        let tle = &eclipses[4];
+        let expected = Utc.with_ymd_and_hms(2025, 3, 14, 6, 58, 43).unwrap();
+        assert!(
+            minutes_between(tle.greatest, expected) < 2.0,
+            "{}",
+            tle.greatest
+        );
+        assert!((tle.magnitude - 1.178).abs() < 0.02, "{}", tle.magnitude);
+        assert_eq!(
+            tle.contacts
+                .iter()
+                .map(|c| c.label.as_str())
+                .collect::<Vec<_>>(),
+            vec!["P1", "U1", "U2", "U3", "U4", "P4"]
+        );
+
+        // 2025-03-29 日偏食：γ = 1.0405，食分 0.9376
+        let pse = &eclipses[5];
+        assert!((pse.gamma - 1.0405).abs() < 0.005, "{}", pse.gamma);
+        assert!((pse.magnitude - 0.9376).abs() < 0.01, "{}", pse.magnitude);
+        assert_eq!(pse.title(), "Partial solar eclipse (Mar 29, 2025)");
+        assert_eq!(pse.to_event()["year"], 2025);
+
+        assert!(find_eclipses(2000, 2000 + MAX_ECLIPSE_YEARS, None, &EspenakMeeus).is_err());
+    }
+
+    #[test]
+    fn test_local_circumstances() {
+        // 2024-04-08 达拉斯：全食 18:40:43–18:44:35 UT
+        let dallas = Observer::new(32.7767, -96.797);
+        let eclipses = find_eclipses(2024, 2024, Some(&dallas), &EspenakMeeus).unwrap();
+        let tse = eclipses
+            .iter()
+            .find(|e| e.kind == EclipseKind::Solar && e.eclipse_type == EclipseType::Total)
+            .unwrap();
+        let local = tse.local.as_ref().unwrap();
+        assert!(local.visible);
+        assert!(local.magnitude > 1.0);
+        let labels: Vec<_> = local.contacts.iter().map(|c| c.label.as_str()).collect();
+        assert_eq!(labels, vec!["C1", "C2", "C3", "C4"]);
+        let c2 = Utc.with_ymd_and_hms(2024, 4, 8, 18, 40, 43).unwrap();
+        assert!(minutes_between(local.contacts[1].time, c2) < 1.0);
+
+        // 2025-03-14 月全食：北京为白昼不可见，纽约可见
+        let beijing = Observer::new(39.9, 116.4);
+        let new_york = Observer::new(40.71, -74.01);
+        let visible = |observer: &Observer| {
+            find_eclipses(2025, 2025, Some(observer), &EspenakMeeus)
+                .unwrap()
+                .into_iter()
+                .find(|e| e.kind == EclipseKind::Lunar)
+                .and_then(|e| e.local)
+                .map(|local| local.visible)
+        };
+        assert_eq!(visible(&beijing), Some(false));
+        assert_eq!(visible(&new_york), Some(true));
+    }
+}
@@ -0,0 +1,212 @@
+//! 视星等
+//!
+//! 参考资料：
+//! - Jean Meeus, "Astronomical Algorithms" (2nd ed.), Chapter 41（行星视星等，取《天文年历》1984 年公式）、
+//!   Chapter 45（土星光环）
+//!
+//! 行星视星等取决于日心距 r、地心距 Δ 与相角 i；土星另计光环倾角 B 与日、地土心黄经差 ΔU。
+//! 月亮取常用的相角经验式，并按地月、日月距离改正。
+
+use crate::astro::planets::Planet;
+use serde::{Deserialize, Serialize};
+
+/// 太阳在 1 AU 处的视星等
+const SUN_MAGNITUDE_AT_1AU: f64 = -26.74;
+
+/// 月亮平均地心距 (km)，月亮视星等经验式以此为准
+const MOON_MEAN_DISTANCE_KM: f64 = 384_400.0;
+
+/// 日、地、行星三角形
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct PhaseGeometry {
+    /// 日心距 r (AU)
+    pub r_au: f64,
+    /// 地心距 Δ (AU)
+    pub delta_au: f64,
+    /// 相角 i (度, 0-180)：行星处所见日地夹角
+    pub phase_angle_deg: f64,
+}
+
+impl PhaseGeometry {
+    /// 由地心距、日地距与距角 ψ（地心所见与太阳的角距）解三角形
+    pub fn from_elongation(delta_au: f64, sun_distance_au: f64, elongation_deg: f64) -> Self {
+        let (delta, big_r) = (delta_au, sun_distance_au);
+        let r = (big_r * big_r + delta * delta
+            - 2.0 * big_r * delta * elongation_deg.to_radians().cos())
+        .sqrt();
+        let cos_i = (r * r + delta * delta - big_r * big_r) / (2.0 * r * delta);
+        Self {
+            r_au: r,
+            delta_au,
+            phase_angle_deg: cos_i.clamp(-1.0, 1.0).acos().to_degrees(),
+        }
+    }
+
+    /// 照亮比例 k = (1 + cos i) / 2
+    pub fn illuminated_fraction(&self) -> f64 {
+        (1.0 + self.phase_angle_deg.to_radians().cos()) / 2.0
+    }
+}
+
+/// 土星光环的几何
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct SaturnRing {
+    /// 光环面对地球的倾角 B (度)
+    pub tilt_deg: f64,
+    /// 太阳与地球的土心黄经差 ΔU (度)
+    pub delta_u_deg: f64,
+}
+
+impl SaturnRing {
+    /// 土星光环倾角（Meeus 第 45 章）
+    ///
+    /// # 参数
+    /// - `jd_tt`: 力学时 (TT) 儒略日
+    /// - `helio_lon_deg`, `helio_lat_deg`: 土星日心黄经、黄纬 (度)
+    /// - `geo_lon_deg`, `geo_lat_deg`: 土星地心黄经、黄纬 (度)
+    pub fn new(
+        jd_tt: f64,
+        helio_lon_deg: f64,
+        helio_lat_deg: f64,
+        geo_lon_deg: f64,
+        geo_lat_deg: f64,
+    ) -> Self {
+        let t = (jd_tt - 2451545.0) / 36525.0;
+        // 光环面对当时黄道的倾角与升交点黄经
+        let i = (28.075216 - 0.012998 * t + 0.000004 * t * t).to_radians();
+        let node = (169.508470 + 1.394681 * t + 0.000412 * t * t).to_radians();
+
+        // 某方向在光环面上的土心经度 U
+        let ring_longitude = |lon_deg: f64, lat_deg: f64| {
+            let (lon, lat) = (lon_deg.to_radians() - node, lat_deg.to_radians());
+            (i.sin() * lat.sin() + i.cos() * lat.cos() * lon.sin()).atan2(lat.cos() * lon.cos())
+        };
+        let (lon, lat) = ((geo_lon_deg.to_radians() - node), geo_lat_deg.to_radians());
+        let sin_b = i.sin() * lat.cos() * lon.sin() - i.cos() * lat.sin();
+        let delta_u = (ring_longitude(helio_lon_deg, helio_lat_deg)
+            - ring_longitude(geo_lon_deg, geo_lat_deg))
+        .to_degrees()
+        .rem_euclid(360.0);
+        Self {
+            tilt_deg: sin_b.clamp(-1.0, 1.0).asin().to_degrees(),
+            delta_u_deg: delta_u.min(360.0 - delta_u),
+        }
+    }
+}
+
+/// 行星视星等（Meeus 第 41 章，《天文年历》1984 年公式）
+///
+/// 土星给出 `ring` 时计入光环；缺省时只计星体本身。
+pub fn planet_magnitude(
+    planet: Planet,
+    geometry: &PhaseGeometry,
+    ring: Option<&SaturnRing>,
+) -> f64 {
+    let i = geometry.phase_angle_deg;
+    let distance_term = 5.0 * (geometry.r_au * geometry.delta_au).log10();
+    let phase_term = match planet {
+        Planet::Mercury => -0.42 + 0.0380 * i - 0.000273 * i * i + 0.000002 * i * i * i,
+        Planet::Venus => -4.40 + 0.0009 * i + 0.000239 * i * i - 0.00000065 * i * i * i,
+        Planet::Mars => -1.52 + 0.016 * i,
+        Planet::Jupiter => -9.40 + 0.005 * i,
+        Planet::Saturn => {
+            let ring_term = ring.map_or(0.0, |ring| {
+                let sin_b = ring.tilt_deg.to_radians().sin().abs();
+                0.044 * ring.delta_u_deg - 2.60 * sin_b + 1.25 * sin_b * sin_b
+            });
+            -8.88 + ring_term
+        }
+        Planet::Uranus => -7.19,
+        Planet::Neptune => -6.87,
+    };
+    phase_term + distance_term
+}
+
+/// 太阳视星等
+pub fn sun_magnitude(distance_au: f64) -> f64 {
+    SUN_MAGNITUDE_AT_1AU + 5.0 * distance_au.log10()
+}
+
+/// 月亮视星等：平均距离处 −12.73 + 0.026|i| + 4×10⁻⁹ i⁴，再按实际地月、日月距离改正
+///
+/// # 参数
+/// - `distance_km`: 地月距离
+/// - `sun_distance_au`: 日月距离（可取日地距离）
+/// - `phase_angle_deg`: 相角 i (度)
+pub fn moon_magnitude(distance_km: f64, sun_distance_au: f64, phase_angle_deg: f64) -> f64 {
+    let i = phase_angle_deg.abs();
+    -12.73
+        + 0.026 * i
+        + 4e-9 * i.powi(4)
+        + 5.0 * (distance_km / MOON_MEAN_DISTANCE_KM * sun_distance_au).log10()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::astro::planets::planet_position;
+    use crate::astro::solar::apparent_sun;
+
+    #[test]
+    fn test_meeus_example_41a_venus() {
+        // 1992-12-20 0h TD：r = 0.724604，Δ = 0.910947，距角 ψ = 44.77°，相角 i = 72.96°
+        let geometry = PhaseGeometry::from_elongation(0.910947, 0.983824, 44.77);
+        assert!((geometry.r_au - 0.724604).abs() < 1e-3);
+        assert!((geometry.phase_angle_deg - 72.96).abs() < 0.1);
+
+        // 1984 年公式得 −4.2（Meeus 书中以旧式 Müller 公式得 −3.8）
+        let exact = PhaseGeometry {
+            r_au: 0.724604,
+            delta_au: 0.910947,
+            phase_angle_deg: 72.96,
+        };
+        let m = planet_magnitude(Planet::Venus, &exact, None);
+        assert!((m - (-4.22)).abs() < 0.01, "{}", m);
+        assert!((exact.illuminated_fraction() - 0.647).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_meeus_example_45a_saturn_ring() {
+        // 1992-12-16 0h TD：B = 16.442°，ΔU = 4.198°
+        let jd_tt = 2448972.5;
+        let saturn = planet_position(Planet::Saturn, jd_tt);
+        let sun = apparent_sun(jd_tt);
+        // 日心坐标：地心向量减去太阳的地心向量
+        let rect = |lon: f64, lat: f64, r: f64| {
+            let (lon, lat) = (lon.to_radians(), lat.to_radians());
+            [
+                r * lat.cos() * lon.cos(),
+                r * lat.cos() * lon.sin(),
+                r * lat.sin(),
+            ]
+        };
+        let p = rect(saturn.longitude, saturn.latitude, saturn.distance_au);
+        let s = rect(sun.longitude, sun.latitude, sun.radius);
+        let h = [p[0] - s[0], p[1] - s[1], p[2] - s[2]];
+        let helio_lon = h[1].atan2(h[0]).to_degrees();
+        let helio_lat = h[2].atan2((h[0] * h[0] + h[1] * h[1]).sqrt()).to_degrees();
+
+        let ring = SaturnRing::new(
+            jd_tt,
+            helio_lon,
+            helio_lat,
+            saturn.longitude,
+            saturn.latitude,
+        );
+        assert!((ring.tilt_deg - 16.442).abs() < 0.05, "{}", ring.tilt_deg);
+        assert!(
+            (ring.delta_u_deg - 4.198).abs() < 0.05,
+            "{}",
+            ring.delta_u_deg
+        );
+    }
+
+    #[test]
+    fn test_sun_and_moon() {
+        assert!((sun_magnitude(1.0) - (-26.74)).abs() < 1e-12);
+        // 平均距离处的满月约 −12.7，上弦约 −10
+        assert!((moon_magnitude(384_400.0, 1.0, 0.0) - (-12.73)).abs() < 1e-9);
+        let quarter = moon_magnitude(384_400.0, 1.0, 90.0);
+        assert!(quarter > -10.5 && quarter < -9.5, "{}", quarter);
+    }
+}
@@ -0,0 +1,233 @@
+//! 二十八宿入宿度
+//!
+//! 以各宿距星的赤经为宿界，距星由依巴谷星表取 J2000.0 位置，岁差、章动、光行差至当时；
+//! 天体赤经落在某宿距星与下一宿距星之间即入该宿，与该宿距星的赤经差为入宿度。
+//! 入宿度同时给出今度（360°）与古度（周天 365.25 度）。
+//!
+//! 距星体系：
+//! - `Historical`：汉唐石氏体系，参宿以参宿一（δ Ori）为距星。
+//!   此后岁差使觜宿距星赤经超过参宿一，近世觜宿宿度为负（"觜参颠倒"）。
+//! - `Modern`：清《仪象考成》以来的体系，参宿改以参宿三（ζ Ori）为距星，即今日通行者。
+
+use crate::astro::stars::{find_star, StarReduction};
+use serde::{Deserialize, Serialize};
+
+/// 古度一周天
+const GUDU_PER_CIRCLE: f64 = 365.25;
+
+/// 距星体系
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DeterminativeSystem {
+    /// 汉唐石氏距星
+    Historical,
+    /// 清《仪象考成》以来的今距星（默认）
+    #[default]
+    Modern,
+}
+
+impl DeterminativeSystem {
+    /// 解析请求参数：`modern`（默认）或 `historical`
+    pub fn parse(raw: &str) -> anyhow::Result<Self> {
+        match raw.trim().to_ascii_lowercase().as_str() {
+            "" | "modern" => Ok(Self::Modern),
+            "historical" | "ancient" => Ok(Self::Historical),
+            other => anyhow::bail!("无效的距星体系：{}（可选 modern、historical）", other),
+        }
+    }
+}
+
+/// 一宿：宿名、所属四象、距星名与依巴谷编号（今、古）
+struct MansionDef {
+    name: &'static str,
+    group: &'static str,
+    modern: (&'static str, u32),
+    historical: (&'static str, u32),
+}
+
+const fn mansion(
+    name: &'static str,
+    group: &'static str,
+    star: &'static str,
+    hip: u32,
+) -> MansionDef {
+    MansionDef {
+        name,
+        group,
+        modern: (star, hip),
+        historical: (star, hip),
+    }
+}
+
+/// 二十八宿，自角宿起按赤经顺序
+const MANSIONS: [MansionDef; 28] = [
+    mansion("角", "东方苍龙", "角宿一", 65474),
+    mansion("亢", "东方苍龙", "亢宿一", 69427),
+    mansion("氐", "东方苍龙", "氐宿一", 72622),
+    mansion("房", "东方苍龙", "房宿一", 78265),
+    mansion("心", "东方苍龙", "心宿一", 80112),
+    mansion("尾", "东方苍龙", "尾宿一", 82514),
+    mansion("箕", "东方苍龙", "箕宿一", 88635),
+    mansion("斗", "北方玄武", "斗宿一", 92041),
+    mansion("牛", "北方玄武", "牛宿一", 100345),
+    mansion("女", "北方玄武", "女宿一", 102618),
+    mansion("虚", "北方玄武", "虚宿一", 106278),
+    mansion("危", "北方玄武", "危宿一", 109074),
+    mansion("室", "北方玄武", "室宿一", 113963),
+    mansion("壁", "北方玄武", "壁宿一", 1067),
+    mansion("奎", "西方白虎", "奎宿二", 3693),
+    mansion("娄", "西方白虎", "娄宿一", 8903),
+    mansion("胃", "西方白虎", "胃宿一", 12719),
+    mansion("昴", "西方白虎", "昴宿一", 17499),
+    mansion("毕", "西方白虎", "毕宿一", 20889),
+    mansion("觜", "西方白虎", "觜宿一", 26207),
+    MansionDef {
+        name: "参",
+        group: "西方白虎",
+        modern: ("参宿三", 26727),
+        historical: ("参宿一", 25930),
+    },
+    mansion("井", "南方朱雀", "井宿一", 30343),
+    mansion("鬼", "南方朱雀", "鬼宿一", 41822),
+    mansion("柳", "南方朱雀", "柳宿一", 42313),
+    mansion("星", "南方朱雀", "星宿一", 46390),
+    mansion("张", "南方朱雀", "张宿一", 48356),
+    mansion("翼", "南方朱雀", "翼宿一", 53740),
+    mansion("轸", "南方朱雀", "轸宿一", 59803),
+];
+
+/// 天体所入之宿
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MansionPlacement {
+    /// 自角宿起的序号 (1–28)
+    pub index: usize,
+    /// 宿名，如 角
+    pub name: String,
+    /// 四象，如 东方苍龙
+    pub group: String,
+    /// 距星，如 角宿一
+    pub determinative: String,
+    /// 入宿度 (度)
+    pub entry_deg: f64,
+    /// 入宿度 (古度)
+    pub entry_gudu: f64,
+    /// 本宿赤道宿度 (度)
+    pub width_deg: f64,
+}
+
+/// 某一时刻、某一距星体系下的二十八宿宿界
+#[derive(Debug, Clone)]
+pub struct MansionTable {
+    system: DeterminativeSystem,
+    /// 各宿距星的视赤经 (度)
+    starts: [f64; 28],
+}
+
+impl MansionTable {
+    /// # 参数
+    /// - `jd_tt`: 力学时 (TT) 儒略日
+    pub fn new(jd_tt: f64, system: DeterminativeSystem) -> Self {
+        let reduction = StarReduction::new(jd_tt);
+        let starts = MANSIONS.map(|def| {
+            let star =
+                find_star(Self::determinative_of(&def, system).1).expect("星表缺少二十八宿距星");
+            reduction.apparent_place(star).0
+        });
+        Self { system, starts }
+    }
+
+    fn determinative_of(def: &MansionDef, system: DeterminativeSystem) -> (&'static str, u32) {
+        match system {
+            DeterminativeSystem::Historical => def.historical,
+            DeterminativeSystem::Modern => def.modern,
+        }
+    }
+
+    pub fn system(&self) -> DeterminativeSystem {
+        self.system
+    }
+
+    /// 第 `i` 宿（0 起）的赤道宿度 (度)；距星赤经逆序时为负
+    pub fn width_deg(&self, i: usize) -> f64 {
+        let next = self.starts[(i + 1) % 28];
+        let width = (next - self.starts[i]).rem_euclid(360.0);
+        // 相邻距星赤经差超过半周视为逆序
+        if width > 180.0 {
+            width - 360.0
+        } else {
+            width
+        }
+    }
+
+    /// 赤经 `ra_deg`（当时视赤经，度）所入之宿
+    ///
+    /// 取赤经不小于宿界且入宿度最小的一宿；宿度为负的宿不收天体。
+    pub fn placement(&self, ra_deg: f64) -> MansionPlacement {
+        let (i, entry) = (0..28)
+            .filter(|&i| self.width_deg(i) > 0.0)
+            .map(|i| (i, (ra_deg - self.starts[i]).rem_euclid(360.0)))
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .expect("二十八宿非空");
+        let def = &MANSIONS[i];
+        MansionPlacement {
+            index: i + 1,
+            name: def.name.to_string(),
+            group: def.group.to_string(),
+            determinative: Self::determinative_of(def, self.system).0.to_string(),
+            entry_deg: entry,
+            entry_gudu: entry * GUDU_PER_CIRCLE / 360.0,
+            width_deg: self.width_deg(i),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_modern_mansions_cover_circle() {
+        let table = MansionTable::new(2451545.0, DeterminativeSystem::Modern);
+        let total: f64 = (0..28).map(|i| table.width_deg(i)).sum();
+        assert!((total - 360.0).abs() < 1e-9);
+        assert!((0..28).all(|i| table.width_deg(i) > 0.0));
+
+        // 角宿一本身入角宿 0 度；赤经 90° 在参、井之间
+        let spica = table.placement(table.starts[0] + 0.01);
+        assert_eq!((spica.index, spica.name.as_str()), (1, "角"));
+        assert!(spica.entry_deg < 0.02);
+        let placement = table.placement(90.0);
+        assert_eq!(placement.name, "参");
+        assert_eq!(placement.determinative, "参宿三");
+        assert!((placement.entry_gudu / placement.entry_deg - 365.25 / 360.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_zi_shen_reversal() {
+        // 汉代（公元前 100 年前后）觜宿宿度为正；今日若仍以参宿一为距星则为负
+        let han = MansionTable::new(1684595.0, DeterminativeSystem::Historical);
+        let today = MansionTable::new(2460676.5, DeterminativeSystem::Historical);
+        let modern = MansionTable::new(2460676.5, DeterminativeSystem::Modern);
+        assert!(han.width_deg(19) > 0.0);
+        assert!(today.width_deg(19) < 0.0);
+        assert!(modern.width_deg(19) > 0.0);
+
+        // 宿度为负时，觜宿不收天体
+        let between = (today.starts[20] + today.starts[19]) / 2.0;
+        assert_eq!(today.placement(between).name, "参");
+        assert_eq!(today.placement(today.starts[19] + 0.1).name, "参");
+    }
+
+    #[test]
+    fn test_parse_system() {
+        assert_eq!(
+            DeterminativeSystem::parse("historical").unwrap(),
+            DeterminativeSystem::Historical
+        );
+        assert_eq!(
+            DeterminativeSystem::parse("").unwrap(),
+            DeterminativeSystem::Modern
+        );
+        assert!(DeterminativeSystem::parse("tang").is_err());
+    }
+}
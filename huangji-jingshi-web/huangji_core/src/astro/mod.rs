@@ -1,8 +1,74 @@
 //! 天文计算模块
 //! 
-//! 提供太阳位置、均时差、真太阳时等天文计算功能。
+//! 提供以下天文计算功能：
+//! - `solar`：太阳位置（VSOP87D）与均时差
+//! - `solar_time`：太阳时策略（区时/平太阳时/真太阳时）
+//! - `moon_phase`：朔望与月相
+//! - `lunar`：月球位置（ELP2000-82）
+//! - `planets`：行星位置（JPL 轨道根数）
+//! - `magnitude`：行星视星等
+//! - `stars`：恒星视位置（依巴谷星表）
+//! - `mansions`：二十八宿入宿度
+//! - `eclipse`：日月食
+//! - `rise_set`：出没与晨昏蒙影
+//! - `delta_t`：ΔT 模型
+//! - `nutation`：章动
+//! - `precession`：岁差（IAU 2006）
+//! - `constellations`：星座归属
+//! - `coords`：坐标变换（黄道/赤道/银道/地平）
+//! - `time`：时间尺度（UTC/UT1/TT）
+//! - `calendar_system`：儒略历与格里历（可配置改历日）
+//! - `sidereal`：恒星时
+//! - `observer`：观测者位置与站心视差
+//! - `refraction`：大气折射
+//! - `vsop87`：VSOP87D 地球日心坐标（截断）
+//! - `numeric`：求根、求极值与向量运算
+//!
+//! 星历函数的儒略日参数一律为力学时 (TT)，由 `time` 模块与 UTC 互换。
 //! 星空图、皇极经世、八字排盘共用此模块。
 
 pub mod solar;
+pub mod solar_time;
+pub mod moon_phase;
+pub mod lunar;
+pub mod planets;
+pub mod magnitude;
+pub mod stars;
+pub mod mansions;
+pub mod eclipse;
+pub mod rise_set;
+pub mod delta_t;
+pub mod nutation;
+pub mod precession;
+pub mod constellations;
+pub mod coords;
+pub mod time;
+pub mod calendar_system;
+pub mod sidereal;
+pub mod observer;
+pub mod refraction;
+pub mod vsop87;
+pub mod numeric;
 
 pub use solar::*;
+pub use solar_time::*;
+pub use moon_phase::*;
+pub use lunar::*;
+pub use planets::*;
+pub use magnitude::*;
+pub use stars::*;
+pub use mansions::*;
+pub use eclipse::*;
+pub use rise_set::*;
+pub use delta_t::*;
+pub use nutation::*;
+pub use precession::*;
+pub use constellations::*;
+pub use coords::*;
+pub use time::*;
+pub use calendar_system::*;
+pub use sidereal::*;
+pub use observer::*;
+pub use refraction::*;
+pub use vsop87::*;
+pub use numeric::*;
@@ -0,0 +1,337 @@
+//! 朔望时刻
+//!
+//! 参考资料：
+//! - Jean Meeus, "Astronomical Algorithms" (2nd ed.), Chapter 49 "Phases of the Moon"
+//!
+//! 朔望以 Meeus 第 49 章级数给出初值，上下弦以平月相为初值，
+//! 再按 ELP2000-82 月球位置（`lunar` 模块）求月日视黄经差恰为 0°/90°/180°/270° 的时刻。
+//! 级数与求精均以力学时儒略日 (JDE) 进行，对外经 ΔT 换算为 UTC。
+
+use crate::astro::delta_t::{DeltaTProvider, EspenakMeeus};
+use crate::astro::lunar::find_elongation_jde;
+use crate::astro::time::{jd_tt_to_utc_with, jd_utc_to_tt_with};
+use serde::{Deserialize, Serialize};
+
+/// 月相类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MoonPhase {
+    /// 朔（新月）
+    New,
+    /// 上弦
+    FirstQuarter,
+    /// 望（满月）
+    Full,
+    /// 下弦
+    LastQuarter,
+}
+
+impl MoonPhase {
+    /// 中文名称
+    pub fn name(&self) -> &'static str {
+        match self {
+            MoonPhase::New => "朔",
+            MoonPhase::FirstQuarter => "上弦",
+            MoonPhase::Full => "望",
+            MoonPhase::LastQuarter => "下弦",
+        }
+    }
+
+    /// 月日视黄经差 (度)
+    pub fn elongation(&self) -> f64 {
+        self.fraction() * 360.0
+    }
+
+    /// k 值的小数部分（朔 0，上弦 0.25，望 0.5，下弦 0.75）
+    fn fraction(&self) -> f64 {
+        match self {
+            MoonPhase::New => 0.0,
+            MoonPhase::FirstQuarter => 0.25,
+            MoonPhase::Full => 0.5,
+            MoonPhase::LastQuarter => 0.75,
+        }
+    }
+}
+
+/// 朔望时刻
+#[derive(Debug, Clone, Copy)]
+pub struct MoonPhaseEvent {
+    pub phase: MoonPhase,
+    /// 儒略日 (UTC)
+    pub jd: f64,
+}
+
+/// 朔望周期修正项系数（朔, 望），按 Meeus 表 49.A 顺序
+const PERIODIC: [(f64, f64); 25] = [
+    (-0.40720, -0.40614),
+    (0.17241, 0.17302),
+    (0.01608, 0.01614),
+    (0.01039, 0.01043),
+    (0.00739, 0.00734),
+    (-0.00514, -0.00515),
+    (0.00208, 0.00209),
+    (-0.00111, -0.00111),
+    (-0.00057, -0.00057),
+    (0.00056, 0.00056),
+    (-0.00042, -0.00042),
+    (0.00042, 0.00042),
+    (0.00038, 0.00038),
+    (-0.00024, -0.00024),
+    (-0.00017, -0.00017),
+    (-0.00007, -0.00007),
+    (0.00004, 0.00004),
+    (0.00004, 0.00004),
+    (0.00003, 0.00003),
+    (0.00003, 0.00003),
+    (-0.00003, -0.00003),
+    (0.00003, 0.00003),
+    (-0.00002, -0.00002),
+    (-0.00002, -0.00002),
+    (0.00002, 0.00002),
+];
+
+/// 行星摄动项 (A1..A14 的常数、k 系数、振幅)
+const PLANETARY: [(f64, f64, f64); 14] = [
+    (299.77, 0.107408, 0.000325),
+    (251.88, 0.016321, 0.000165),
+    (251.83, 26.651886, 0.000164),
+    (349.42, 36.412478, 0.000126),
+    (84.66, 18.206239, 0.000110),
+    (141.74, 53.303771, 0.000062),
+    (207.14, 2.453732, 0.000060),
+    (154.84, 7.306860, 0.000056),
+    (34.52, 27.261239, 0.000047),
+    (207.19, 0.121824, 0.000042),
+    (291.34, 1.844379, 0.000040),
+    (161.72, 24.198154, 0.000037),
+    (239.56, 25.513099, 0.000035),
+    (331.55, 3.592518, 0.000023),
+];
+
+/// 第 k 个平月相的儒略日 (Meeus 式 49.1)
+fn mean_phase_jde(k: f64) -> f64 {
+    let t = k / 1236.85;
+    let t2 = t * t;
+    let t3 = t2 * t;
+    let t4 = t3 * t;
+    2451550.09766 + 29.530588861 * k + 0.00015437 * t2 - 0.000000150 * t3 + 0.00000000073 * t4
+}
+
+/// 第 k 个朔望的儒略日
+///
+/// k 为整数对应朔，k + 0.5 对应望；k = 0 为 2000 年 1 月 6 日的朔。
+/// 上下弦的周期项另有一表，此处不用，退回平月相。
+fn phase_jde(k: f64, phase: MoonPhase) -> f64 {
+    let t = k / 1236.85;
+    let t2 = t * t;
+    let t3 = t2 * t;
+    let t4 = t3 * t;
+
+    let mean = mean_phase_jde(k);
+    let coeff_index = match phase {
+        MoonPhase::New => 0,
+        MoonPhase::Full => 1,
+        MoonPhase::FirstQuarter | MoonPhase::LastQuarter => return mean,
+    };
+
+    let e = 1.0 - 0.002516 * t - 0.0000074 * t2;
+    let m = (2.5534 + 29.10535670 * k - 0.0000014 * t2 - 0.00000011 * t3).to_radians();
+    let mp = (201.5643 + 385.81693528 * k + 0.0107582 * t2 + 0.00001238 * t3 - 0.000000058 * t4)
+        .to_radians();
+    let f = (160.7108 + 390.67050284 * k - 0.0016118 * t2 - 0.00000227 * t3 + 0.000000011 * t4)
+        .to_radians();
+    let omega = (124.7746 - 1.56375588 * k + 0.0020672 * t2 + 0.00000215 * t3).to_radians();
+
+    let terms = [
+        mp.sin(),
+        e * m.sin(),
+        (2.0 * mp).sin(),
+        (2.0 * f).sin(),
+        e * (mp - m).sin(),
+        e * (mp + m).sin(),
+        e * e * (2.0 * m).sin(),
+        (mp - 2.0 * f).sin(),
+        (mp + 2.0 * f).sin(),
+        e * (2.0 * mp + m).sin(),
+        (3.0 * mp).sin(),
+        e * (m + 2.0 * f).sin(),
+        e * (m - 2.0 * f).sin(),
+        e * (2.0 * mp - m).sin(),
+        omega.sin(),
+        (mp + 2.0 * m).sin(),
+        (2.0 * mp - 2.0 * f).sin(),
+        (3.0 * m).sin(),
+        (mp + m - 2.0 * f).sin(),
+        (2.0 * mp + 2.0 * f).sin(),
+        (mp + m + 2.0 * f).sin(),
+        (mp - m + 2.0 * f).sin(),
+        (mp - m - 2.0 * f).sin(),
+        (3.0 * mp + m).sin(),
+        (4.0 * mp).sin(),
+    ];
+    let periodic: f64 = PERIODIC
+        .iter()
+        .zip(terms)
+        .map(|(&(new, full), value)| {
+            let coeff = if coeff_index == 0 { new } else { full };
+            coeff * value
+        })
+        .sum();
+
+    let planetary: f64 = PLANETARY
+        .iter()
+        .enumerate()
+        .map(|(i, (base, rate, amplitude))| {
+            let mut arg = base + rate * k;
+            if i == 0 {
+                arg -= 0.009173 * t2;
+            }
+            amplitude * arg.to_radians().sin()
+        })
+        .sum();
+
+    mean + periodic + planetary
+}
+
+/// 查找区间 [start_jd, end_jd) 内的全部朔望，按时间排序
+///
+/// 区间端点与结果均为 UTC 儒略日。
+pub fn find_moon_phases(start_jd: f64, end_jd: f64) -> Vec<MoonPhaseEvent> {
+    find_moon_phases_with(start_jd, end_jd, &EspenakMeeus)
+}
+
+/// 同 [`find_moon_phases`]，UTC 与 TT 按指定 ΔT 提供者换算
+pub fn find_moon_phases_with(
+    start_jd: f64,
+    end_jd: f64,
+    delta_t: &dyn DeltaTProvider,
+) -> Vec<MoonPhaseEvent> {
+    find_phases(
+        start_jd,
+        end_jd,
+        &[MoonPhase::New, MoonPhase::Full],
+        delta_t,
+    )
+}
+
+/// 查找区间 [start_jd, end_jd) 内的朔、上弦、望、下弦，按时间排序
+///
+/// 区间端点与结果均为 UTC 儒略日。
+pub fn find_principal_phases(start_jd: f64, end_jd: f64) -> Vec<MoonPhaseEvent> {
+    find_phases(
+        start_jd,
+        end_jd,
+        &[
+            MoonPhase::New,
+            MoonPhase::FirstQuarter,
+            MoonPhase::Full,
+            MoonPhase::LastQuarter,
+        ],
+        &EspenakMeeus,
+    )
+}
+
+/// 第 k 个朔望月中的指定月相时刻 (TT)：级数初值 + ELP 求精
+fn refined_phase_jde(k: f64, phase: MoonPhase) -> f64 {
+    let guess = phase_jde(k + phase.fraction(), phase);
+    find_elongation_jde(guess, phase.elongation())
+}
+
+fn find_phases(
+    start_jd: f64,
+    end_jd: f64,
+    phases: &[MoonPhase],
+    delta_t: &dyn DeltaTProvider,
+) -> Vec<MoonPhaseEvent> {
+    const SYNODIC_MONTH: f64 = 29.530588861;
+    let start_jde = jd_utc_to_tt_with(start_jd, delta_t);
+    let end_jde = jd_utc_to_tt_with(end_jd, delta_t);
+    let mut k = ((start_jde - 2451550.09766) / SYNODIC_MONTH).floor() - 1.0;
+    let mut events = Vec::new();
+
+    loop {
+        for &phase in phases {
+            let jde = refined_phase_jde(k, phase);
+            if jde >= end_jde {
+                return events;
+            }
+            if jde >= start_jde {
+                events.push(MoonPhaseEvent {
+                    phase,
+                    jd: jd_tt_to_utc_with(jde, delta_t),
+                });
+            }
+        }
+        k += 1.0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_meeus_example_new_moon_1977() {
+        // Meeus 例 49.a：1977 年 2 月朔 JDE 2443192.65118
+        let jde = phase_jde(-283.0, MoonPhase::New);
+        assert!((jde - 2443192.65118).abs() < 0.00001, "jde={}", jde);
+    }
+
+    #[test]
+    fn test_find_moon_phases_2025_01() {
+        use crate::astro::solar::jd_to_utc;
+        use chrono::{TimeZone, Utc};
+
+        // 2025-01-13 22:27 UTC 望，2025-01-29 12:36 UTC 朔
+        let start = 2460676.5; // 2025-01-01 00:00 UTC
+        let events = find_moon_phases(start, start + 31.0);
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].phase, MoonPhase::Full);
+        assert_eq!(events[1].phase, MoonPhase::New);
+
+        let expected = [
+            Utc.with_ymd_and_hms(2025, 1, 13, 22, 27, 0).unwrap(),
+            Utc.with_ymd_and_hms(2025, 1, 29, 12, 36, 0).unwrap(),
+        ];
+        for (event, expected) in events.iter().zip(expected) {
+            let diff = (jd_to_utc(event.jd).unwrap() - expected)
+                .num_seconds()
+                .abs();
+            assert!(diff < 60, "{} 偏差 {} 秒", event.phase.name(), diff);
+        }
+    }
+
+    #[test]
+    fn test_find_principal_phases_2025_01() {
+        use crate::astro::solar::jd_to_utc;
+        use chrono::{TimeZone, Utc};
+
+        // 2025-01 上弦 6 日 23:56，望 13 日 22:27，下弦 21 日 20:31，朔 29 日 12:36 (UTC)
+        let start = 2460676.5;
+        let events = find_principal_phases(start, start + 31.0);
+        let expected = [
+            (
+                MoonPhase::FirstQuarter,
+                Utc.with_ymd_and_hms(2025, 1, 6, 23, 56, 0).unwrap(),
+            ),
+            (
+                MoonPhase::Full,
+                Utc.with_ymd_and_hms(2025, 1, 13, 22, 27, 0).unwrap(),
+            ),
+            (
+                MoonPhase::LastQuarter,
+                Utc.with_ymd_and_hms(2025, 1, 21, 20, 31, 0).unwrap(),
+            ),
+            (
+                MoonPhase::New,
+                Utc.with_ymd_and_hms(2025, 1, 29, 12, 36, 0).unwrap(),
+            ),
+        ];
+        assert_eq!(events.len(), expected.len());
+        for (event, (phase, time)) in events.iter().zip(expected) {
+            assert_eq!(event.phase, phase);
+            let diff = (jd_to_utc(event.jd).unwrap() - time).num_seconds().abs();
+            assert!(diff < 60, "{} 偏差 {} 秒", phase.name(), diff);
+        }
+    }
+}
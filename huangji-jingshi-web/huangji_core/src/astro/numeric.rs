@@ -0,0 +1,92 @@
+//! 数值工具
+//!
+//! 各天象搜索共用的一维求根、求极值与三维向量运算。
+//! 自变量均为儒略日，求精到 `TIME_TOLERANCE`（约 0.1 秒）。
+
+/// 求根、求极值精度 (日)
+pub const TIME_TOLERANCE: f64 = 1e-6;
+
+/// 把角度差归一到 (-180, 180]
+pub fn wrap180(deg: f64) -> f64 {
+    let d = deg.rem_euclid(360.0);
+    if d > 180.0 {
+        d - 360.0
+    } else {
+        d
+    }
+}
+
+/// 二分求 f 在 [a, b] 内的变号点（两端异号）
+pub fn bisect(f: impl Fn(f64) -> f64, mut a: f64, mut b: f64) -> f64 {
+    let a_negative = f(a) < 0.0;
+    while b - a > TIME_TOLERANCE {
+        let mid = (a + b) / 2.0;
+        if (f(mid) < 0.0) == a_negative {
+            a = mid;
+        } else {
+            b = mid;
+        }
+    }
+    (a + b) / 2.0
+}
+
+/// 黄金分割求 f 在 [a, b] 内的极小点（单峰）
+pub fn minimize(f: impl Fn(f64) -> f64, mut a: f64, mut b: f64) -> f64 {
+    let ratio = (5f64.sqrt() - 1.0) / 2.0;
+    let mut c = b - ratio * (b - a);
+    let mut d = a + ratio * (b - a);
+    let (mut fc, mut fd) = (f(c), f(d));
+    while b - a > TIME_TOLERANCE {
+        if fc < fd {
+            b = d;
+            d = c;
+            fd = fc;
+            c = b - ratio * (b - a);
+            fc = f(c);
+        } else {
+            a = c;
+            c = d;
+            fc = fd;
+            d = a + ratio * (b - a);
+            fd = f(d);
+        }
+    }
+    (a + b) / 2.0
+}
+
+pub fn dot(a: [f64; 3], b: [f64; 3]) -> f64 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+pub fn sub(a: [f64; 3], b: [f64; 3]) -> [f64; 3] {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
+pub fn norm(a: [f64; 3]) -> f64 {
+    dot(a, a).sqrt()
+}
+
+pub fn scale(a: [f64; 3], k: f64) -> [f64; 3] {
+    [a[0] * k, a[1] * k, a[2] * k]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wrap180() {
+        assert_eq!(wrap180(190.0), -170.0);
+        assert_eq!(wrap180(-190.0), 170.0);
+        assert_eq!(wrap180(180.0), 180.0);
+        assert_eq!(wrap180(-180.0), 180.0);
+    }
+
+    #[test]
+    fn test_bisect_and_minimize() {
+        let root = bisect(|x| x * x - 2.0, 0.0, 2.0);
+        assert!((root - 2f64.sqrt()).abs() < 1e-5);
+        let min = minimize(|x| (x - 0.3).powi(2), -1.0, 1.0);
+        assert!((min - 0.3).abs() < 1e-5);
+    }
+}
@@ -0,0 +1,330 @@
+//! 章动与黄赤交角
+//!
+//! 参考资料：
+//! - IAU 1980 章动理论 (Wahr 1981; Seidelmann 1982)
+//! - Jean Meeus, "Astronomical Algorithms" (2nd ed.), Chapter 22 "Nutation and the Obliquity of the Ecliptic"
+//!
+//! - IERS Conventions (2010), §5.5.4；McCarthy & Luzum (2003) IAU 2000B 章动
+//!
+//! `nutation` 采用 IAU 1980 全部 63 项周期项（Meeus 表 22.A），章动精度优于 0.01"，供日月行星视位置使用。
+//! `nutation_iau2000b` 采用 IAU 2000B 的 77 项日月章动加行星章动常数偏置，与 IAU 2000A 相差不超过 1 mas，
+//! 与 IAU 2006 岁差（`precession` 模块）配合构成坐标变换所用的岁差章动模型。
+
+/// 周期项：D, M, M', F, Ω 系数，Δψ = (S + S'·T)·sin(arg)，Δε = (C + C'·T)·cos(arg)，单位 0.0001"
+type Term = (i8, i8, i8, i8, i8, f64, f64, f64, f64);
+
+const TERMS: [Term; 63] = [
+    (0, 0, 0, 0, 1, -171996.0, -174.2, 92025.0, 8.9),
+    (-2, 0, 0, 2, 2, -13187.0, -1.6, 5736.0, -3.1),
+    (0, 0, 0, 2, 2, -2274.0, -0.2, 977.0, -0.5),
+    (0, 0, 0, 0, 2, 2062.0, 0.2, -895.0, 0.5),
+    (0, 1, 0, 0, 0, 1426.0, -3.4, 54.0, -0.1),
+    (0, 0, 1, 0, 0, 712.0, 0.1, -7.0, 0.0),
+    (-2, 1, 0, 2, 2, -517.0, 1.2, 224.0, -0.6),
+    (0, 0, 0, 2, 1, -386.0, -0.4, 200.0, 0.0),
+    (0, 0, 1, 2, 2, -301.0, 0.0, 129.0, -0.1),
+    (-2, -1, 0, 2, 2, 217.0, -0.5, -95.0, 0.3),
+    (-2, 0, 1, 0, 0, -158.0, 0.0, 0.0, 0.0),
+    (-2, 0, 0, 2, 1, 129.0, 0.1, -70.0, 0.0),
+    (0, 0, -1, 2, 2, 123.0, 0.0, -53.0, 0.0),
+    (2, 0, 0, 0, 0, 63.0, 0.0, 0.0, 0.0),
+    (0, 0, 1, 0, 1, 63.0, 0.1, -33.0, 0.0),
+    (2, 0, -1, 2, 2, -59.0, 0.0, 26.0, 0.0),
+    (0, 0, -1, 0, 1, -58.0, -0.1, 32.0, 0.0),
+    (0, 0, 1, 2, 1, -51.0, 0.0, 27.0, 0.0),
+    (-2, 0, 2, 0, 0, 48.0, 0.0, 0.0, 0.0),
+    (0, 0, -2, 2, 1, 46.0, 0.0, -24.0, 0.0),
+    (2, 0, 0, 2, 2, -38.0, 0.0, 16.0, 0.0),
+    (0, 0, 2, 2, 2, -31.0, 0.0, 13.0, 0.0),
+    (0, 0, 2, 0, 0, 29.0, 0.0, 0.0, 0.0),
+    (-2, 0, 1, 2, 2, 29.0, 0.0, -12.0, 0.0),
+    (0, 0, 0, 2, 0, 26.0, 0.0, 0.0, 0.0),
+    (-2, 0, 0, 2, 0, -22.0, 0.0, 0.0, 0.0),
+    (0, 0, -1, 2, 1, 21.0, 0.0, -10.0, 0.0),
+    (0, 2, 0, 0, 0, 17.0, -0.1, 0.0, 0.0),
+    (2, 0, -1, 0, 1, 16.0, 0.0, -8.0, 0.0),
+    (-2, 2, 0, 2, 2, -16.0, 0.1, 7.0, 0.0),
+    (0, 1, 0, 0, 1, -15.0, 0.0, 9.0, 0.0),
+    (-2, 0, 1, 0, 1, -13.0, 0.0, 7.0, 0.0),
+    (0, -1, 0, 0, 1, -12.0, 0.0, 6.0, 0.0),
+    (0, 0, 2, -2, 0, 11.0, 0.0, 0.0, 0.0),
+    (2, 0, -1, 2, 1, -10.0, 0.0, 5.0, 0.0),
+    (2, 0, 1, 2, 2, -8.0, 0.0, 3.0, 0.0),
+    (0, 1, 0, 2, 2, 7.0, 0.0, -3.0, 0.0),
+    (-2, 1, 1, 0, 0, -7.0, 0.0, 0.0, 0.0),
+    (0, -1, 0, 2, 2, -7.0, 0.0, 3.0, 0.0),
+    (2, 0, 0, 2, 1, -7.0, 0.0, 3.0, 0.0),
+    (2, 0, 1, 0, 0, 6.0, 0.0, 0.0, 0.0),
+    (-2, 0, 2, 2, 2, 6.0, 0.0, -3.0, 0.0),
+    (-2, 0, 1, 2, 1, 6.0, 0.0, -3.0, 0.0),
+    (2, 0, -2, 0, 1, -6.0, 0.0, 3.0, 0.0),
+    (2, 0, 0, 0, 1, -6.0, 0.0, 3.0, 0.0),
+    (0, -1, 1, 0, 0, 5.0, 0.0, 0.0, 0.0),
+    (-2, -1, 0, 2, 1, -5.0, 0.0, 3.0, 0.0),
+    (-2, 0, 0, 0, 1, -5.0, 0.0, 3.0, 0.0),
+    (0, 0, 2, 2, 1, -5.0, 0.0, 3.0, 0.0),
+    (-2, 0, 2, 0, 1, 4.0, 0.0, 0.0, 0.0),
+    (-2, 1, 0, 2, 1, 4.0, 0.0, 0.0, 0.0),
+    (0, 0, 1, -2, 0, 4.0, 0.0, 0.0, 0.0),
+    (-1, 0, 1, 0, 0, -4.0, 0.0, 0.0, 0.0),
+    (-2, 1, 0, 0, 0, -4.0, 0.0, 0.0, 0.0),
+    (1, 0, 0, 0, 0, -4.0, 0.0, 0.0, 0.0),
+    (0, 0, 1, 2, 0, 3.0, 0.0, 0.0, 0.0),
+    (0, 0, -2, 2, 2, -3.0, 0.0, 0.0, 0.0),
+    (-1, -1, 1, 0, 0, -3.0, 0.0, 0.0, 0.0),
+    (0, 1, 1, 0, 0, -3.0, 0.0, 0.0, 0.0),
+    (0, -1, 1, 2, 2, -3.0, 0.0, 0.0, 0.0),
+    (2, -1, -1, 2, 2, -3.0, 0.0, 0.0, 0.0),
+    (0, 0, 3, 2, 2, -3.0, 0.0, 0.0, 0.0),
+    (2, -1, 0, 2, 2, -3.0, 0.0, 0.0, 0.0),
+];
+
+/// IAU 2000B 周期项：l, l', F, D, Ω 系数；
+/// Δψ = (ps + pst·T)·sin(arg) + pc·cos(arg)，Δε = (ec + ect·T)·cos(arg) + es·sin(arg)，单位 0.1 μas
+type Term2000B = (i8, i8, i8, i8, i8, f64, f64, f64, f64, f64, f64);
+
+const TERMS_2000B: [Term2000B; 77] = [
+    (
+        0,
+        0,
+        0,
+        0,
+        1,
+        -172064161.0,
+        -174666.0,
+        33386.0,
+        92052331.0,
+        9086.0,
+        15377.0,
+    ),
+    (
+        0,
+        0,
+        2,
+        -2,
+        2,
+        -13170906.0,
+        -1675.0,
+        -13696.0,
+        5730336.0,
+        -3015.0,
+        -4587.0,
+    ),
+    (
+        0, 0, 2, 0, 2, -2276413.0, -234.0, 2796.0, 978459.0, -485.0, 1374.0,
+    ),
+    (
+        0, 0, 0, 0, 2, 2074554.0, 207.0, -698.0, -897492.0, 470.0, -291.0,
+    ),
+    (
+        0, 1, 0, 0, 0, 1475877.0, -3633.0, 11817.0, 73871.0, -184.0, -1924.0,
+    ),
+    (
+        0, 1, 2, -2, 2, -516821.0, 1226.0, -524.0, 224386.0, -677.0, -174.0,
+    ),
+    (1, 0, 0, 0, 0, 711159.0, 73.0, -872.0, -6750.0, 0.0, 358.0),
+    (
+        0, 0, 2, 0, 1, -387298.0, -367.0, 380.0, 200728.0, 18.0, 318.0,
+    ),
+    (
+        1, 0, 2, 0, 2, -301461.0, -36.0, 816.0, 129025.0, -63.0, 367.0,
+    ),
+    (
+        0, -1, 2, -2, 2, 215829.0, -494.0, 111.0, -95929.0, 299.0, 132.0,
+    ),
+    (0, 0, 2, -2, 1, 128227.0, 137.0, 181.0, -68982.0, -9.0, 39.0),
+    (-1, 0, 2, 0, 2, 123457.0, 11.0, 19.0, -53311.0, 32.0, -4.0),
+    (-1, 0, 0, 2, 0, 156994.0, 10.0, -168.0, -1235.0, 0.0, 82.0),
+    (1, 0, 0, 0, 1, 63110.0, 63.0, 27.0, -33228.0, 0.0, -9.0),
+    (-1, 0, 0, 0, 1, -57976.0, -63.0, -189.0, 31429.0, 0.0, -75.0),
+    (-1, 0, 2, 2, 2, -59641.0, -11.0, 149.0, 25543.0, -11.0, 66.0),
+    (1, 0, 2, 0, 1, -51613.0, -42.0, 129.0, 26366.0, 0.0, 78.0),
+    (-2, 0, 2, 0, 1, 45893.0, 50.0, 31.0, -24236.0, -10.0, 20.0),
+    (0, 0, 0, 2, 0, 63384.0, 11.0, -150.0, -1220.0, 0.0, 29.0),
+    (0, 0, 2, 2, 2, -38571.0, -1.0, 158.0, 16452.0, -11.0, 68.0),
+    (0, -2, 2, -2, 2, 32481.0, 0.0, 0.0, -13870.0, 0.0, 0.0),
+    (-2, 0, 0, 2, 0, -47722.0, 0.0, -18.0, 477.0, 0.0, -25.0),
+    (2, 0, 2, 0, 2, -31046.0, -1.0, 131.0, 13238.0, -11.0, 59.0),
+    (1, 0, 2, -2, 2, 28593.0, 0.0, -1.0, -12338.0, 10.0, -3.0),
+    (-1, 0, 2, 0, 1, 20441.0, 21.0, 10.0, -10758.0, 0.0, -3.0),
+    (2, 0, 0, 0, 0, 29243.0, 0.0, -74.0, -609.0, 0.0, 13.0),
+    (0, 0, 2, 0, 0, 25887.0, 0.0, -66.0, -550.0, 0.0, 11.0),
+    (0, 1, 0, 0, 1, -14053.0, -25.0, 79.0, 8551.0, -2.0, -45.0),
+    (-1, 0, 0, 2, 1, 15164.0, 10.0, 11.0, -8001.0, 0.0, -1.0),
+    (0, 2, 2, -2, 2, -15794.0, 72.0, -16.0, 6850.0, -42.0, -5.0),
+    (0, 0, -2, 2, 0, 21783.0, 0.0, 13.0, -167.0, 0.0, 13.0),
+    (1, 0, 0, -2, 1, -12873.0, -10.0, -37.0, 6953.0, 0.0, -14.0),
+    (0, -1, 0, 0, 1, -12654.0, 11.0, 63.0, 6415.0, 0.0, 26.0),
+    (-1, 0, 2, 2, 1, -10204.0, 0.0, 25.0, 5222.0, 0.0, 15.0),
+    (0, 2, 0, 0, 0, 16707.0, -85.0, -10.0, 168.0, -1.0, 10.0),
+    (1, 0, 2, 2, 2, -7691.0, 0.0, 44.0, 3268.0, 0.0, 19.0),
+    (-2, 0, 2, 0, 0, -11024.0, 0.0, -14.0, 104.0, 0.0, 2.0),
+    (0, 1, 2, 0, 2, 7566.0, -21.0, -11.0, -3250.0, 0.0, -5.0),
+    (0, 0, 2, 2, 1, -6637.0, -11.0, 25.0, 3353.0, 0.0, 14.0),
+    (0, -1, 2, 0, 2, -7141.0, 21.0, 8.0, 3070.0, 0.0, 4.0),
+    (0, 0, 0, 2, 1, -6302.0, -11.0, 2.0, 3272.0, 0.0, 4.0),
+    (1, 0, 2, -2, 1, 5800.0, 10.0, 2.0, -3045.0, 0.0, -1.0),
+    (2, 0, 2, -2, 2, 6443.0, 0.0, -7.0, -2768.0, 0.0, -4.0),
+    (-2, 0, 0, 2, 1, -5774.0, -11.0, -15.0, 3041.0, 0.0, -5.0),
+    (2, 0, 2, 0, 1, -5350.0, 0.0, 21.0, 2695.0, 0.0, 12.0),
+    (0, -1, 2, -2, 1, -4752.0, -11.0, -3.0, 2719.0, 0.0, -3.0),
+    (0, 0, 0, -2, 1, -4940.0, -11.0, -21.0, 2720.0, 0.0, -9.0),
+    (-1, -1, 0, 2, 0, 7350.0, 0.0, -8.0, -51.0, 0.0, 4.0),
+    (2, 0, 0, -2, 1, 4065.0, 0.0, 6.0, -2206.0, 0.0, 1.0),
+    (1, 0, 0, 2, 0, 6579.0, 0.0, -24.0, -199.0, 0.0, 2.0),
+    (0, 1, 2, -2, 1, 3579.0, 0.0, 5.0, -1900.0, 0.0, 1.0),
+    (1, -1, 0, 0, 0, 4725.0, 0.0, -6.0, -41.0, 0.0, 3.0),
+    (-2, 0, 2, 0, 2, -3075.0, 0.0, -2.0, 1313.0, 0.0, -1.0),
+    (3, 0, 2, 0, 2, -2904.0, 0.0, 15.0, 1233.0, 0.0, 7.0),
+    (0, -1, 0, 2, 0, 4348.0, 0.0, -10.0, -81.0, 0.0, 2.0),
+    (1, -1, 2, 0, 2, -2878.0, 0.0, 8.0, 1232.0, 0.0, 4.0),
+    (0, 0, 0, 1, 0, -4230.0, 0.0, 5.0, -20.0, 0.0, -2.0),
+    (-1, -1, 2, 2, 2, -2819.0, 0.0, 7.0, 1207.0, 0.0, 3.0),
+    (-1, 0, 2, 0, 0, -4056.0, 0.0, 5.0, 40.0, 0.0, -2.0),
+    (0, -1, 2, 2, 2, -2647.0, 0.0, 11.0, 1129.0, 0.0, 5.0),
+    (-2, 0, 0, 0, 1, -2294.0, 0.0, -10.0, 1266.0, 0.0, -4.0),
+    (1, 1, 2, 0, 2, 2481.0, 0.0, -7.0, -1062.0, 0.0, -3.0),
+    (2, 0, 0, 0, 1, 2179.0, 0.0, -2.0, -1129.0, 0.0, -2.0),
+    (-1, 1, 0, 1, 0, 3276.0, 0.0, 1.0, -9.0, 0.0, 0.0),
+    (1, 1, 0, 0, 0, -3389.0, 0.0, 5.0, 35.0, 0.0, -2.0),
+    (1, 0, 2, 0, 0, 3339.0, 0.0, -13.0, -107.0, 0.0, 1.0),
+    (-1, 0, 2, -2, 1, -1987.0, 0.0, -6.0, 1073.0, 0.0, -2.0),
+    (1, 0, 0, 0, 2, -1981.0, 0.0, 0.0, 854.0, 0.0, 0.0),
+    (-1, 0, 0, 1, 0, 4026.0, 0.0, -353.0, -553.0, 0.0, -139.0),
+    (0, 0, 2, 1, 2, 1660.0, 0.0, -5.0, -710.0, 0.0, -2.0),
+    (-1, 0, 2, 4, 2, -1521.0, 0.0, 9.0, 647.0, 0.0, 4.0),
+    (-1, 1, 0, 1, 1, 1314.0, 0.0, 0.0, -700.0, 0.0, 0.0),
+    (0, -2, 2, -2, 1, -1283.0, 0.0, 0.0, 672.0, 0.0, 0.0),
+    (1, 0, 2, 2, 1, -1331.0, 0.0, 8.0, 663.0, 0.0, 4.0),
+    (-2, 0, 2, 2, 2, 1383.0, 0.0, -2.0, -594.0, 0.0, -2.0),
+    (-1, 0, 0, 0, 2, 1405.0, 0.0, 4.0, -610.0, 0.0, 2.0),
+    (1, 1, 2, -2, 2, 1290.0, 0.0, 0.0, -556.0, 0.0, 0.0),
+];
+
+/// IAU 2000B 以固定偏置代替行星章动：Δψ = -0.135 mas，Δε = +0.388 mas
+const PLANETARY_OFFSET_MAS: (f64, f64) = (-0.135, 0.388);
+
+/// 章动
+#[derive(Debug, Clone, Copy)]
+pub struct Nutation {
+    /// 黄经章动 Δψ (度)
+    pub longitude: f64,
+    /// 交角章动 Δε (度)
+    pub obliquity: f64,
+}
+
+/// 计算章动（IAU 1980）
+///
+/// # 参数
+/// - `jde`: 力学时 (TT) 儒略日
+pub fn nutation(jde: f64) -> Nutation {
+    let t = (jde - 2451545.0) / 36525.0;
+    let t2 = t * t;
+    let t3 = t2 * t;
+
+    // 月日平距角、太阳平近点角、月亮平近点角、月亮纬度参数、月亮升交点平黄经
+    let d = 297.85036 + 445267.111480 * t - 0.0019142 * t2 + t3 / 189474.0;
+    let m = 357.52772 + 35999.050340 * t - 0.0001603 * t2 - t3 / 300000.0;
+    let mp = 134.96298 + 477198.867398 * t + 0.0086972 * t2 + t3 / 56250.0;
+    let f = 93.27191 + 483202.017538 * t - 0.0036825 * t2 + t3 / 327270.0;
+    let omega = 125.04452 - 1934.136261 * t + 0.0020708 * t2 + t3 / 450000.0;
+
+    let (dpsi, deps) = TERMS.iter().fold(
+        (0.0, 0.0),
+        |(dpsi, deps), &(cd, cm, cmp, cf, co, s, st, c, ct)| {
+            let arg = (cd as f64 * d
+                + cm as f64 * m
+                + cmp as f64 * mp
+                + cf as f64 * f
+                + co as f64 * omega)
+                .to_radians();
+            (
+                dpsi + (s + st * t) * arg.sin(),
+                deps + (c + ct * t) * arg.cos(),
+            )
+        },
+    );
+
+    Nutation {
+        longitude: dpsi * 0.0001 / 3600.0,
+        obliquity: deps * 0.0001 / 3600.0,
+    }
+}
+
+/// 计算章动（IAU 2000B）
+///
+/// # 参数
+/// - `jd_tt`: 力学时 (TT) 儒略日
+pub fn nutation_iau2000b(jd_tt: f64) -> Nutation {
+    let t = (jd_tt - 2451545.0) / 36525.0;
+    // 基本幅角（Simon et al. 1994），角秒
+    let arg = |a: f64, b: f64| ((a + b * t) % 1_296_000.0 / 3600.0).to_radians();
+    let l = arg(485868.249036, 1717915923.2178);
+    let lp = arg(1287104.79305, 129596581.0481);
+    let f = arg(335779.526232, 1739527262.8478);
+    let d = arg(1072260.70369, 1602961601.2090);
+    let om = arg(450160.398036, -6962890.5431);
+
+    let (dpsi, deps) = TERMS_2000B.iter().fold(
+        (0.0, 0.0),
+        |(dpsi, deps), &(cl, clp, cf, cd, co, ps, pst, pc, ec, ect, es)| {
+            let a =
+                cl as f64 * l + clp as f64 * lp + cf as f64 * f + cd as f64 * d + co as f64 * om;
+            let (sin, cos) = a.sin_cos();
+            (
+                dpsi + (ps + pst * t) * sin + pc * cos,
+                deps + (ec + ect * t) * cos + es * sin,
+            )
+        },
+    );
+
+    // 0.1 μas → 度
+    let (dpsi_planetary, deps_planetary) = PLANETARY_OFFSET_MAS;
+    Nutation {
+        longitude: (dpsi * 1e-7 + dpsi_planetary * 1e-3) / 3600.0,
+        obliquity: (deps * 1e-7 + deps_planetary * 1e-3) / 3600.0,
+    }
+}
+
+/// 平黄赤交角 ε0（度，IAU 1980，Meeus 式 22.2）
+pub fn mean_obliquity(jde: f64) -> f64 {
+    let t = (jde - 2451545.0) / 36525.0;
+    let seconds = 21.448 - 46.8150 * t - 0.00059 * t * t + 0.001813 * t * t * t;
+    23.0 + 26.0 / 60.0 + seconds / 3600.0
+}
+
+/// 真黄赤交角 ε = ε0 + Δε（度）
+pub fn true_obliquity(jde: f64) -> f64 {
+    mean_obliquity(jde) + nutation(jde).obliquity
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_meeus_example_22a() {
+        // Meeus 例 22.a：1987 年 4 月 10 日 0h TD
+        // Δψ = -3.788"，Δε = +9.443"，ε = 23°26'36.850"
+        let jde = 2446895.5;
+        let n = nutation(jde);
+        assert!((n.longitude * 3600.0 - (-3.788)).abs() < 0.01);
+        assert!((n.obliquity * 3600.0 - 9.443).abs() < 0.01);
+        let eps = true_obliquity(jde);
+        let expected = 23.0 + 26.0 / 60.0 + 36.850 / 3600.0;
+        assert!((eps - expected).abs() * 3600.0 < 0.01, "ε={}", eps);
+    }
+
+    #[test]
+    fn test_iau2000b_against_sofa() {
+        // SOFA iauNut00b(2400000.5, 53736.0)：Δψ = -0.9632552291148362783e-5 rad，Δε = 0.4063197106621159367e-4 rad
+        let n = nutation_iau2000b(2400000.5 + 53736.0);
+        assert!((n.longitude.to_radians() - (-0.9632552291148363e-5)).abs() < 1e-13);
+        assert!((n.obliquity.to_radians() - 0.4063197106621159e-4).abs() < 1e-13);
+
+        // 与 IAU 1980 相差在 0.05" 以内
+        let old = nutation(2400000.5 + 53736.0);
+        assert!((n.longitude - old.longitude).abs() * 3600.0 < 0.05);
+        assert!((n.obliquity - old.obliquity).abs() * 3600.0 < 0.05);
+    }
+}
@@ -0,0 +1,133 @@
+//! 观测者位置
+//!
+//! 参考资料：
+//! - Jean Meeus, "Astronomical Algorithms" (2nd ed.), Chapter 11 "The Earth's Globe"
+//!
+//! 地球椭球取 IAU 1976 参数：赤道半径 6378.14 km，扁率 1/298.257。
+
+use crate::astro::sidereal::local_apparent_sidereal_time;
+use crate::astro::time::TimeScales;
+use serde::{Deserialize, Serialize};
+
+/// 地球赤道半径 (km)
+pub const EARTH_EQUATORIAL_RADIUS_KM: f64 = 6378.14;
+
+/// 地球极半径与赤道半径之比 b/a
+const EARTH_AXIS_RATIO: f64 = 0.99664719;
+
+/// 地面观测者
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Observer {
+    /// 地理纬度 (度)，北正南负
+    pub lat_deg: f64,
+    /// 地理经度 (度)，东正西负
+    pub lon_deg: f64,
+    /// 海拔 (米)
+    #[serde(default)]
+    pub elevation_m: f64,
+}
+
+impl Observer {
+    /// 海拔为 0 的观测者
+    pub fn new(lat_deg: f64, lon_deg: f64) -> Self {
+        Self {
+            lat_deg,
+            lon_deg,
+            elevation_m: 0.0,
+        }
+    }
+
+    /// 地心距在赤道面与自转轴上的分量 (ρ·cos φ', ρ·sin φ')，以赤道半径为单位（Meeus 第 11 章）
+    pub fn geocentric_components(&self) -> (f64, f64) {
+        let phi = self.lat_deg.to_radians();
+        let u = (EARTH_AXIS_RATIO * phi.tan()).atan();
+        let h = self.elevation_m / (EARTH_EQUATORIAL_RADIUS_KM * 1000.0);
+        (
+            u.cos() + h * phi.cos(),
+            EARTH_AXIS_RATIO * u.sin() + h * phi.sin(),
+        )
+    }
+
+    /// 观测者的地心赤道直角坐标 (km)，瞬时真赤道与春分点
+    pub fn geocentric_position(&self, scales: &TimeScales) -> [f64; 3] {
+        let (rho_cos, rho_sin) = self.geocentric_components();
+        let theta = local_apparent_sidereal_time(scales, self.lon_deg).to_radians();
+        [
+            EARTH_EQUATORIAL_RADIUS_KM * rho_cos * theta.cos(),
+            EARTH_EQUATORIAL_RADIUS_KM * rho_cos * theta.sin(),
+            EARTH_EQUATORIAL_RADIUS_KM * rho_sin,
+        ]
+    }
+
+    /// 地心赤道坐标改正为站心赤道坐标（周日视差，Meeus 式 40.2）
+    ///
+    /// # 参数
+    /// - `ra_deg`, `dec_deg`: 地心赤经、赤纬 (度)
+    /// - `distance_km`: 地心距 (km)
+    /// - `hour_angle_deg`: 地心时角 (度)，即地方恒星时减赤经
+    ///
+    /// 返回站心 (赤经, 赤纬) (度)；站心时角为地方恒星时减站心赤经。
+    pub fn topocentric_equatorial(
+        &self,
+        ra_deg: f64,
+        dec_deg: f64,
+        distance_km: f64,
+        hour_angle_deg: f64,
+    ) -> (f64, f64) {
+        let (rho_cos, rho_sin) = self.geocentric_components();
+        let sin_pi = EARTH_EQUATORIAL_RADIUS_KM / distance_km;
+        let (dec, h) = (dec_deg.to_radians(), hour_angle_deg.to_radians());
+
+        let denominator = dec.cos() - rho_cos * sin_pi * h.cos();
+        let delta_ra = (-rho_cos * sin_pi * h.sin()).atan2(denominator);
+        let topo_dec = ((dec.sin() - rho_sin * sin_pi) * delta_ra.cos()).atan2(denominator);
+        (
+            (ra_deg + delta_ra.to_degrees()).rem_euclid(360.0),
+            topo_dec.to_degrees(),
+        )
+    }
+
+    /// 当地天顶（大地垂线）方向的单位向量，赤道直角坐标
+    pub fn zenith(&self, scales: &TimeScales) -> [f64; 3] {
+        let phi = self.lat_deg.to_radians();
+        let theta = local_apparent_sidereal_time(scales, self.lon_deg).to_radians();
+        [phi.cos() * theta.cos(), phi.cos() * theta.sin(), phi.sin()]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::astro::coords::AU_KM;
+
+    #[test]
+    fn test_meeus_example_11a() {
+        // Meeus 例 11.a：帕洛玛山天文台 φ = 33°21'22"，H = 1706 m
+        // ρ sin φ' = 0.546861，ρ cos φ' = 0.836339
+        let observer = Observer {
+            lat_deg: 33.0 + 21.0 / 60.0 + 22.0 / 3600.0,
+            lon_deg: -116.8625,
+            elevation_m: 1706.0,
+        };
+        let (rho_cos, rho_sin) = observer.geocentric_components();
+        assert!((rho_sin - 0.546861).abs() < 1e-6);
+        assert!((rho_cos - 0.836339).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_meeus_example_40a() {
+        // Meeus 例 40.a：2003-08-28 3h17m UT 帕洛玛山观测火星
+        // α = 339.530208°，δ = -15.771083°，Δ = 0.37276 AU，H = 288.7958°
+        // 站心 α' = 22h38m08.54s，δ' = -15°46'30.0"
+        let observer = Observer {
+            lat_deg: 33.0 + 21.0 / 60.0 + 22.0 / 3600.0,
+            lon_deg: -116.8625,
+            elevation_m: 1706.0,
+        };
+        let (ra, dec) =
+            observer.topocentric_equatorial(339.530208, -15.771083, 0.37276 * AU_KM, 288.7958);
+        let expected_ra = (22.0 + 38.0 / 60.0 + 8.54 / 3600.0) * 15.0;
+        assert!((ra - expected_ra).abs() * 3600.0 < 0.2);
+        assert!((dec - (-(15.0 + 46.0 / 60.0 + 30.0 / 3600.0))).abs() * 3600.0 < 0.2);
+    }
+}
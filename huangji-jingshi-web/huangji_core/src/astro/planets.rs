@@ -0,0 +1,327 @@
+//! 行星轨道根数与地心位置
+//!
+//! 参考资料：
+//! - E. M. Standish, "Keplerian Elements for Approximate Positions of the Major Planets" (JPL),
+//!   Table 1：J2000.0 根数及其每儒略世纪变化率，适用 1800–2050 年；
+//!   Table 2a/2b：适用公元前 3000 年至公元 3000 年，木、土、天王、海王四星另加
+//!   平近点角附加项 b·T² + c·cos(fT) + s·sin(fT)
+//! - Jean Meeus, "Astronomical Algorithms" (2nd ed.), Chapter 33（光行时）
+//!
+//! 1800–2050 年取星图数据 `planets.json`（与前端共用）中的 Table 1 根数，其余年代取
+//! `data/planet_elements_3000bc_3000ad.json` 中的 Table 2a/2b 根数，此即 Standish 的建议用法。
+//! 地球位置取 VSOP87D，行星日心坐标由 J2000 黄道岁差（IAU 2006）至瞬时黄道后相减，
+//! 再以光行时迭代；地球同样取推迟时刻，从而一并计入周年光行差（Meeus 第 33 章）。
+//! 1800–2050 年内黄经误差约 1′ 以内；其外至公元前 3000 年、公元 3000 年误差为角分量级
+//! （土星、天王星最大约 10′），再往外误差继续增大，不宜再用。
+
+use crate::astro::coords::apply;
+use crate::astro::numeric::{norm, sub};
+use crate::astro::nutation::nutation;
+use crate::astro::precession::ecliptic_precession_matrix;
+use crate::astro::vsop87::earth_heliocentric;
+use once_cell::sync::Lazy;
+use serde::Deserialize;
+use std::collections::HashMap;
+
+/// 光行 1 AU 所需时间 (日)
+const LIGHT_TIME_DAYS_PER_AU: f64 = 0.0057755183;
+
+/// 大行星（不含地球）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Planet {
+    Mercury,
+    Venus,
+    Mars,
+    Jupiter,
+    Saturn,
+    Uranus,
+    Neptune,
+}
+
+impl Planet {
+    pub const ALL: [Planet; 7] = [
+        Planet::Mercury,
+        Planet::Venus,
+        Planet::Mars,
+        Planet::Jupiter,
+        Planet::Saturn,
+        Planet::Uranus,
+        Planet::Neptune,
+    ];
+
+    /// `planets.json` 与 `planet_elements_3000bc_3000ad.json` 中的键
+    pub fn key(&self) -> &'static str {
+        match self {
+            Planet::Mercury => "mer",
+            Planet::Venus => "ven",
+            Planet::Mars => "mar",
+            Planet::Jupiter => "jup",
+            Planet::Saturn => "sat",
+            Planet::Uranus => "ura",
+            Planet::Neptune => "nep",
+        }
+    }
+}
+
+/// J2000.0 平黄道与春分点下的开普勒根数及每儒略世纪变化率
+#[derive(Debug, Clone, Copy, Deserialize)]
+pub struct KeplerElements {
+    /// 半长轴 (AU)
+    pub a: f64,
+    /// 偏心率
+    pub e: f64,
+    /// 轨道倾角 (度)
+    pub i: f64,
+    /// 平黄经 (度)
+    #[serde(rename = "L")]
+    pub l: f64,
+    /// 近日点黄经 ϖ (度)
+    #[serde(rename = "W")]
+    pub w: f64,
+    /// 升交点黄经 Ω (度)
+    #[serde(rename = "N")]
+    pub n: f64,
+    pub da: f64,
+    pub de: f64,
+    pub di: f64,
+    #[serde(rename = "dL")]
+    pub dl: f64,
+    #[serde(rename = "dW")]
+    pub dw: f64,
+    #[serde(rename = "dN")]
+    pub dn: f64,
+    /// 平近点角附加项 b·T² (度)，仅木、土、天王、海王四星
+    #[serde(default)]
+    pub b: f64,
+    /// 平近点角附加项 c·cos(fT) (度)
+    #[serde(default)]
+    pub c: f64,
+    /// 平近点角附加项 s·sin(fT) (度)
+    #[serde(default)]
+    pub s: f64,
+    /// 附加项频率 f (度/儒略世纪)
+    #[serde(default)]
+    pub f: f64,
+}
+
+impl KeplerElements {
+    /// 日心黄道直角坐标 (AU)，J2000.0 平黄道与春分点
+    ///
+    /// # 参数
+    /// - `jd_tt`: 力学时 (TT) 儒略日
+    pub fn heliocentric_j2000(&self, jd_tt: f64) -> [f64; 3] {
+        let t = (jd_tt - 2451545.0) / 36525.0;
+        let a = self.a + self.da * t;
+        let e = self.e + self.de * t;
+        let i = (self.i + self.di * t).to_radians();
+        let l = self.l + self.dl * t;
+        let w = self.w + self.dw * t;
+        let omega = self.n + self.dn * t;
+
+        let ft = (self.f * t).to_radians();
+        let m = (l - w + self.b * t * t + self.c * ft.cos() + self.s * ft.sin()).rem_euclid(360.0);
+        let big_e = solve_kepler(m, e);
+        // 真近点角与向径
+        let v = 2.0 * (((1.0 + e) / (1.0 - e)).sqrt() * (big_e / 2.0).tan()).atan();
+        let r = a * (1.0 - e * big_e.cos());
+
+        let u = v + (w - omega).to_radians();
+        let omega = omega.to_radians();
+        [
+            r * (u.cos() * omega.cos() - u.sin() * omega.sin() * i.cos()),
+            r * (u.cos() * omega.sin() + u.sin() * omega.cos() * i.cos()),
+            r * u.sin() * i.sin(),
+        ]
+    }
+}
+
+/// Table 1 根数的适用区间 (TT 儒略日)：1800-01-01 至 2050-01-01
+const TABLE1_JD_RANGE: std::ops::Range<f64> = 2378496.5..2469807.5;
+
+#[derive(Deserialize)]
+struct LongTermElements {
+    elements: HashMap<String, KeplerElements>,
+}
+
+/// Table 1 根数，1800–2050 年
+static PLANET_ELEMENTS: Lazy<HashMap<Planet, KeplerElements>> = Lazy::new(|| {
+    // planets.json 另含日、月等形制不同的条目，只严格解析七大行星
+    let records: HashMap<String, serde_json::Value> = serde_json::from_str(include_str!(
+        "../../../backend/backend/data/celestial/planets.json"
+    ))
+    .expect("planets.json 解析失败");
+    Planet::ALL
+        .iter()
+        .map(|&planet| {
+            let value = records
+                .get(planet.key())
+                .and_then(|record| record["elements"].get(0))
+                .unwrap_or_else(|| panic!("planets.json 缺少 {} 的轨道根数", planet.key()));
+            let elements = KeplerElements::deserialize(value).unwrap_or_else(|err| {
+                panic!("planets.json 中 {} 的轨道根数有误: {}", planet.key(), err)
+            });
+            (planet, elements)
+        })
+        .collect()
+});
+
+/// Table 2a/2b 根数，公元前 3000 年至公元 3000 年
+static LONG_TERM_ELEMENTS: Lazy<HashMap<Planet, KeplerElements>> = Lazy::new(|| {
+    let file: LongTermElements = serde_json::from_str(include_str!(
+        "../../data/planet_elements_3000bc_3000ad.json"
+    ))
+    .expect("planet_elements_3000bc_3000ad.json 解析失败");
+    Planet::ALL
+        .iter()
+        .map(|&planet| {
+            let elements = file.elements.get(planet.key()).unwrap_or_else(|| {
+                panic!(
+                    "planet_elements_3000bc_3000ad.json 缺少 {} 的轨道根数",
+                    planet.key()
+                )
+            });
+            (planet, *elements)
+        })
+        .collect()
+});
+
+/// 行星在 `jd_tt` 时刻适用的轨道根数：1800–2050 年取 Table 1，其余取 Table 2a/2b
+pub fn planet_elements(planet: Planet, jd_tt: f64) -> KeplerElements {
+    if TABLE1_JD_RANGE.contains(&jd_tt) {
+        PLANET_ELEMENTS[&planet]
+    } else {
+        LONG_TERM_ELEMENTS[&planet]
+    }
+}
+
+/// 行星地心视黄道坐标
+#[derive(Debug, Clone, Copy)]
+pub struct PlanetPosition {
+    /// 视黄经 (度)，含光行时、光行差与章动
+    pub longitude: f64,
+    /// 视黄纬 (度)
+    pub latitude: f64,
+    /// 地心距 (AU)，即光行距离
+    pub distance_au: f64,
+    /// 光行时 (日)
+    pub light_time_days: f64,
+}
+
+/// 解开普勒方程 M = E - e·sin E，返回偏近点角 E (弧度)
+fn solve_kepler(m_deg: f64, e: f64) -> f64 {
+    let m_rad = m_deg.to_radians();
+    let mut e_rad = m_rad;
+    for _ in 0..10 {
+        let delta = (m_rad - (e_rad - e * e_rad.sin())) / (1.0 - e * e_rad.cos());
+        e_rad += delta;
+        if delta.abs() < 1e-9 {
+            break;
+        }
+    }
+    e_rad
+}
+
+/// 行星日心直角坐标 (AU)，瞬时平黄道与平春分点
+fn heliocentric_of_date(elements: &KeplerElements, jd_tt: f64) -> [f64; 3] {
+    apply(
+        &ecliptic_precession_matrix(jd_tt),
+        &elements.heliocentric_j2000(jd_tt),
+    )
+}
+
+/// 地球日心直角坐标 (AU)，VSOP87D 瞬时平黄道
+fn earth_rectangular(jd_tt: f64) -> [f64; 3] {
+    let earth = earth_heliocentric(jd_tt);
+    [
+        earth.radius * earth.latitude.cos() * earth.longitude.cos(),
+        earth.radius * earth.latitude.cos() * earth.longitude.sin(),
+        earth.radius * earth.latitude.sin(),
+    ]
+}
+
+/// 行星地心视位置
+///
+/// # 参数
+/// - `jd_tt`: 力学时 (TT) 儒略日
+pub fn planet_position(planet: Planet, jd_tt: f64) -> PlanetPosition {
+    let elements = planet_elements(planet, jd_tt);
+    let earth_now = earth_rectangular(jd_tt);
+
+    // 光行时迭代：行星取 t - τ，τ 由行星在 t - τ 与地球在 t 的距离决定
+    let mut tau = 0.0;
+    let mut distance_au = 0.0;
+    for _ in 0..4 {
+        let planet_then = heliocentric_of_date(&elements, jd_tt - tau);
+        distance_au = norm(sub(planet_then, earth_now));
+        let next = distance_au * LIGHT_TIME_DAYS_PER_AU;
+        let converged = (next - tau).abs() < 1e-8;
+        tau = next;
+        if converged {
+            break;
+        }
+    }
+
+    // 地球与行星同取推迟时刻，合并计入光行时与周年光行差
+    let [x, y, z] = sub(
+        heliocentric_of_date(&elements, jd_tt - tau),
+        earth_rectangular(jd_tt - tau),
+    );
+    let longitude = y.atan2(x).to_degrees() + nutation(jd_tt).longitude;
+    PlanetPosition {
+        longitude: longitude.rem_euclid(360.0),
+        latitude: z.atan2((x * x + y * y).sqrt()).to_degrees(),
+        distance_au,
+        light_time_days: tau,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_all_planets_loaded() {
+        for planet in Planet::ALL {
+            assert!(planet_elements(planet, 2451545.0).a > 0.0, "{:?}", planet);
+            assert!(planet_elements(planet, 0.0).a > 0.0, "{:?}", planet);
+        }
+        // Table 2b 附加项仅木、土、天王、海王四星，且只在 1800–2050 年之外启用
+        let ancient = 1721057.5; // 公元 0 年
+        assert_eq!(planet_elements(Planet::Mars, ancient).f, 0.0);
+        assert!(planet_elements(Planet::Saturn, ancient).f > 0.0);
+        assert_eq!(planet_elements(Planet::Saturn, 2451545.0).f, 0.0);
+    }
+
+    #[test]
+    fn test_table_switch_is_continuous() {
+        // 两套根数在 1800、2050 年交界处的行星位置应相差不足 0.5°
+        for jd in [TABLE1_JD_RANGE.start, TABLE1_JD_RANGE.end] {
+            for planet in Planet::ALL {
+                let before = planet_position(planet, jd - 0.01).longitude;
+                let after = planet_position(planet, jd + 0.01).longitude;
+                let diff = ((after - before + 540.0).rem_euclid(360.0) - 180.0).abs();
+                assert!(diff < 0.5, "{:?} {} {}", planet, jd, diff);
+            }
+        }
+    }
+
+    #[test]
+    fn test_meeus_example_33a_venus() {
+        // Meeus 例 33.a：1992-12-20 0h TD 金星
+        // 视黄经 313.08102°，视黄纬 -2.08474°，Δ = 0.910947 AU，τ = 0.0052612 日
+        let venus = planet_position(Planet::Venus, 2448976.5);
+        assert!((venus.longitude - 313.08102).abs() < 0.01);
+        assert!((venus.latitude - (-2.08474)).abs() < 0.01);
+        assert!((venus.distance_au - 0.910947).abs() < 1e-4);
+        assert!((venus.light_time_days - 0.0052612).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_precession_identity_at_j2000() {
+        let v = [0.3, -0.8, 0.1];
+        let precessed = apply(&ecliptic_precession_matrix(2451545.0), &v);
+        for (a, b) in precessed.iter().zip(v.iter()) {
+            assert!((a - b).abs() < 1e-12);
+        }
+    }
+}
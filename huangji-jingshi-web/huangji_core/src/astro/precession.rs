@@ -0,0 +1,234 @@
+//! 岁差（IAU 2006）
+//!
+//! 参考资料：
+//! - Capitaine, Wallace & Chapront (2003), "Expressions for IAU 2000 precession quantities" (P03)
+//! - IERS Conventions (2010), §5.6.4 式 (5.39)、(5.40)
+//!
+//! 赤道岁差角 ζA、zA、θA 与平黄赤交角 εA 均取 P03 五次多项式。
+//! 岁差矩阵 P = R3(-zA)·R2(θA)·R3(-ζA) 将 J2000.0 平赤道坐标转到瞬时平赤道；
+//! 章动矩阵 N = R1(-(εA+Δε))·R3(-Δψ)·R1(εA) 再转到瞬时真赤道，章动取 IAU 2000B。
+//! 未计 ICRS 与 J2000.0 平赤道之间的框架偏差（约 0.02"）。
+
+use crate::astro::coords::{multiply, rotate_x, rotate_y, rotate_z, transpose, RotationMatrix};
+use crate::astro::nutation::{nutation_iau2000b, Nutation};
+use serde::{Deserialize, Serialize};
+
+/// J2000.0 平黄赤交角 ε0 (角秒)
+pub const J2000_OBLIQUITY_ARCSEC: f64 = 84381.406;
+
+/// 赤道岁差角 (角秒)
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct PrecessionAngles {
+    pub zeta_arcsec: f64,
+    pub z_arcsec: f64,
+    pub theta_arcsec: f64,
+}
+
+fn centuries(jd_tt: f64) -> f64 {
+    (jd_tt - 2451545.0) / 36525.0
+}
+
+/// 赤道岁差角 ζA、zA、θA（IAU 2006）
+///
+/// # 参数
+/// - `jd_tt`: 力学时 (TT) 儒略日
+pub fn precession_angles_iau2006(jd_tt: f64) -> PrecessionAngles {
+    let t = centuries(jd_tt);
+    let poly = |c: [f64; 6]| c.iter().rev().fold(0.0, |acc, &k| acc * t + k);
+    PrecessionAngles {
+        zeta_arcsec: poly([
+            2.650545,
+            2306.083227,
+            0.2988499,
+            0.01801828,
+            -0.000005971,
+            -0.0000003173,
+        ]),
+        z_arcsec: poly([
+            -2.650545,
+            2306.077181,
+            1.0927348,
+            0.01826837,
+            -0.000028596,
+            -0.0000002904,
+        ]),
+        theta_arcsec: poly([
+            0.0,
+            2004.191903,
+            -0.4294934,
+            -0.04182264,
+            -0.000007089,
+            -0.0000001274,
+        ]),
+    }
+}
+
+/// 平黄赤交角 εA（度，IAU 2006）
+pub fn mean_obliquity_iau2006(jd_tt: f64) -> f64 {
+    let t = centuries(jd_tt);
+    let seconds = [
+        J2000_OBLIQUITY_ARCSEC,
+        -46.836769,
+        -0.0001831,
+        0.00200340,
+        -0.000000576,
+        -0.0000000434,
+    ]
+    .iter()
+    .rev()
+    .fold(0.0, |acc, &k| acc * t + k);
+    seconds / 3600.0
+}
+
+/// 岁差矩阵：J2000.0 平赤道 → 瞬时平赤道
+pub fn precession_matrix(jd_tt: f64) -> RotationMatrix {
+    let angles = precession_angles_iau2006(jd_tt);
+    let rad = |arcsec: f64| (arcsec / 3600.0).to_radians();
+    multiply(
+        &rotate_z(-rad(angles.z_arcsec)),
+        &multiply(
+            &rotate_y(rad(angles.theta_arcsec)),
+            &rotate_z(-rad(angles.zeta_arcsec)),
+        ),
+    )
+}
+
+/// 黄道岁差矩阵：J2000.0 平黄道 → 瞬时平黄道
+///
+/// 经赤道转换：R1(εA)·P·R1(-ε0)。
+pub fn ecliptic_precession_matrix(jd_tt: f64) -> RotationMatrix {
+    let eps0 = (J2000_OBLIQUITY_ARCSEC / 3600.0).to_radians();
+    let eps = mean_obliquity_iau2006(jd_tt).to_radians();
+    multiply(
+        &rotate_x(eps),
+        &multiply(&precession_matrix(jd_tt), &rotate_x(-eps0)),
+    )
+}
+
+/// 某一时刻的岁差章动，可对多个方向复用
+#[derive(Debug, Clone, Copy)]
+pub struct PrecessionNutation {
+    /// 平黄赤交角 εA (度)
+    pub mean_obliquity_deg: f64,
+    /// 章动（IAU 2000B）
+    pub nutation: Nutation,
+    /// J2000.0 平赤道 → 瞬时平赤道
+    pub precession: RotationMatrix,
+    /// 瞬时平赤道 → 瞬时真赤道
+    pub nutation_matrix: RotationMatrix,
+}
+
+impl PrecessionNutation {
+    /// # 参数
+    /// - `jd_tt`: 力学时 (TT) 儒略日
+    pub fn new(jd_tt: f64) -> Self {
+        let eps = mean_obliquity_iau2006(jd_tt).to_radians();
+        let nutation = nutation_iau2000b(jd_tt);
+        let (dpsi, deps) = (
+            nutation.longitude.to_radians(),
+            nutation.obliquity.to_radians(),
+        );
+        Self {
+            mean_obliquity_deg: eps.to_degrees(),
+            nutation,
+            precession: precession_matrix(jd_tt),
+            nutation_matrix: multiply(
+                &rotate_x(-(eps + deps)),
+                &multiply(&rotate_z(-dpsi), &rotate_x(eps)),
+            ),
+        }
+    }
+
+    /// 真黄赤交角 ε = εA + Δε (度)
+    pub fn true_obliquity_deg(&self) -> f64 {
+        self.mean_obliquity_deg + self.nutation.obliquity
+    }
+
+    /// J2000.0 平赤道 → 瞬时真赤道
+    pub fn matrix(&self) -> RotationMatrix {
+        multiply(&self.nutation_matrix, &self.precession)
+    }
+
+    /// 瞬时真赤道 → J2000.0 平赤道
+    pub fn inverse_matrix(&self) -> RotationMatrix {
+        transpose(&self.matrix())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::astro::coords::{apply, EquatorialCoord};
+
+    #[test]
+    fn test_obliquity_against_sofa() {
+        // SOFA iauObl06(2400000.5, 54388.0) = 0.4090749229387258204 rad
+        let eps = mean_obliquity_iau2006(2400000.5 + 54388.0).to_radians();
+        assert!((eps - 0.4090749229387258).abs() < 1e-14);
+        assert_eq!(mean_obliquity_iau2006(2451545.0) * 3600.0, 84381.406);
+    }
+
+    #[test]
+    fn test_precession_matrix_against_sofa() {
+        // SOFA iauPmat06(2400000.5, 50123.9999) 首行；其含框架偏差，与纯岁差矩阵相差约 0.02"（1e-7 rad）
+        let p = precession_matrix(2400000.5 + 50123.9999);
+        let expected = [
+            0.9999995505176007,
+            0.8695404617348208e-3,
+            0.3779735201865589e-3,
+        ];
+        for (value, expected_value) in p[0].iter().zip(expected.iter()) {
+            assert!((value - expected_value).abs() < 1e-7, "{:?}", p);
+        }
+        // 正交矩阵
+        let identity = multiply(&p, &transpose(&p));
+        for (i, row) in identity.iter().enumerate() {
+            for (j, value) in row.iter().enumerate() {
+                let expected = if i == j { 1.0 } else { 0.0 };
+                assert!((value - expected).abs() < 1e-15);
+            }
+        }
+    }
+
+    #[test]
+    fn test_meeus_example_21b() {
+        // 英仙座 θ：计入自行后的 J2000.0 位置 α0 = 41.054063°，δ0 = 49.227750°
+        // 岁差至 2028-11-13.19 TD：α = 41.547214°，δ = 49.348483°（IAU 1976，与 IAU 2006 相差远小于 0.5"）
+        let pn = PrecessionNutation::new(2462088.69);
+        let j2000 = EquatorialCoord {
+            ra_deg: 41.054063,
+            dec_deg: 49.227750,
+        };
+        let mean = EquatorialCoord::from_vector(apply(&pn.precession, &j2000.to_vector()));
+        assert!((mean.ra_deg - 41.547214).abs() * 3600.0 < 0.5, "{:?}", mean);
+        assert!(
+            (mean.dec_deg - 49.348483).abs() * 3600.0 < 0.5,
+            "{:?}",
+            mean
+        );
+
+        // 往返
+        let back = EquatorialCoord::from_vector(apply(
+            &pn.inverse_matrix(),
+            &apply(&pn.matrix(), &j2000.to_vector()),
+        ));
+        assert!((back.ra_deg - j2000.ra_deg).abs() < 1e-10);
+        assert!((back.dec_deg - j2000.dec_deg).abs() < 1e-10);
+    }
+
+    #[test]
+    fn test_ecliptic_precession_meeus_example_21c() {
+        use crate::astro::coords::EclipticCoord;
+
+        // Meeus 例 21.c：J2000.0 λ0 = 149.48194°，β0 = 1.76549°
+        // 至 -214-06-30 (JDE 1643074.5)：λ = 118.704°，β = 1.615°
+        let j2000 = EclipticCoord {
+            lon_deg: 149.48194,
+            lat_deg: 1.76549,
+        };
+        let m = ecliptic_precession_matrix(1643074.5);
+        let of_date = EclipticCoord::from_vector(apply(&m, &j2000.to_vector()));
+        assert!((of_date.lon_deg - 118.704).abs() < 0.005);
+        assert!((of_date.lat_deg - 1.615).abs() < 0.005);
+    }
+}
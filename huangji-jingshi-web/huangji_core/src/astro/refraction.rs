@@ -0,0 +1,155 @@
+//! 大气折射
+//!
+//! 参考资料：
+//! - Jean Meeus, "Astronomical Algorithms" (2nd ed.), Chapter 16 "Atmospheric Refraction"
+//! - G. G. Bennett, "The Calculation of Astronomical Refraction in Marine Navigation",
+//!   Journal of Navigation 35 (1982)
+//! - Þ. Sæmundsson, Sky and Telescope 72 (1986)
+//!
+//! 标准大气取 1010 hPa、10 °C，其他气压与气温按 (P/1010)·(283/(273+T)) 比例修正。
+//! 气温须高于绝对零度、气压不得为负，否则比例失去意义，构造时即报错。
+
+use serde::{Deserialize, Serialize};
+
+/// 低于此真高度 (度) 不再计折射：公式在地平以下迅速失真，天体已不可见
+const MIN_REFRACTION_ALTITUDE_DEG: f64 = -1.0;
+
+/// 绝对零度 (°C)
+const ABSOLUTE_ZERO_C: f64 = -273.15;
+
+/// 观测点大气状态
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "RawAtmosphere")]
+pub struct Atmosphere {
+    /// 气压 (hPa)
+    pub pressure_hpa: f64,
+    /// 气温 (°C)
+    pub temperature_c: f64,
+}
+
+impl Default for Atmosphere {
+    fn default() -> Self {
+        Self {
+            pressure_hpa: 1010.0,
+            temperature_c: 10.0,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct RawAtmosphere {
+    pressure_hpa: f64,
+    temperature_c: f64,
+}
+
+impl TryFrom<RawAtmosphere> for Atmosphere {
+    type Error = anyhow::Error;
+
+    fn try_from(raw: RawAtmosphere) -> anyhow::Result<Self> {
+        Self::new(raw.pressure_hpa, raw.temperature_c)
+    }
+}
+
+impl Atmosphere {
+    /// 由气压 (hPa) 与气温 (°C) 构造，取值无效时报错
+    pub fn new(pressure_hpa: f64, temperature_c: f64) -> anyhow::Result<Self> {
+        let atmosphere = Self {
+            pressure_hpa,
+            temperature_c,
+        };
+        atmosphere.validate()?;
+        Ok(atmosphere)
+    }
+
+    /// 检查取值：气压须为非负有限数，气温须为高于绝对零度的有限数
+    pub fn validate(&self) -> anyhow::Result<()> {
+        if !self.pressure_hpa.is_finite() || self.pressure_hpa < 0.0 {
+            anyhow::bail!("无效的气压：{} hPa（须为非负数）", self.pressure_hpa);
+        }
+        if !self.temperature_c.is_finite() || self.temperature_c <= ABSOLUTE_ZERO_C {
+            anyhow::bail!(
+                "无效的气温：{} °C（须高于绝对零度 -273.15 °C）",
+                self.temperature_c
+            );
+        }
+        Ok(())
+    }
+
+    /// 相对标准大气的折射比例
+    fn scale(&self) -> f64 {
+        (self.pressure_hpa / 1010.0) * (283.0 / (273.0 + self.temperature_c))
+    }
+
+    /// 由视高度求折射 (度)，Bennett 公式（Meeus 式 16.3），精度约 0.07′
+    pub fn refraction_from_apparent(&self, apparent_alt_deg: f64) -> f64 {
+        if apparent_alt_deg < MIN_REFRACTION_ALTITUDE_DEG {
+            return 0.0;
+        }
+        let h = apparent_alt_deg;
+        let arcmin = 1.0 / (h + 7.31 / (h + 4.4)).to_radians().tan();
+        arcmin / 60.0 * self.scale()
+    }
+
+    /// 由真（几何）高度求折射 (度)，Sæmundsson 公式（Meeus 式 16.4），与 Bennett 互逆至 0.015′
+    pub fn refraction_from_true(&self, true_alt_deg: f64) -> f64 {
+        if true_alt_deg < MIN_REFRACTION_ALTITUDE_DEG {
+            return 0.0;
+        }
+        let h = true_alt_deg;
+        let arcmin = 1.02 / (h + 10.3 / (h + 5.11)).to_radians().tan();
+        arcmin / 60.0 * self.scale()
+    }
+
+    /// 真高度对应的视高度 (度)
+    pub fn apparent_altitude(&self, true_alt_deg: f64) -> f64 {
+        true_alt_deg + self.refraction_from_true(true_alt_deg)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_standard_horizon_refraction() {
+        // 视地平处折射约 34.5′，天顶处为零
+        let atmosphere = Atmosphere::default();
+        assert!((atmosphere.refraction_from_apparent(0.0) * 60.0 - 34.5).abs() < 0.1);
+        assert!(atmosphere.refraction_from_apparent(90.0).abs() < 1e-4);
+        assert_eq!(atmosphere.apparent_altitude(-5.0), -5.0);
+    }
+
+    #[test]
+    fn test_rejects_invalid_atmosphere() {
+        assert!(Atmosphere::new(0.0, 10.0).is_ok());
+        assert!(Atmosphere::new(1010.0, -273.0).is_ok());
+        assert!(Atmosphere::new(1010.0, -273.15).is_err());
+        assert!(Atmosphere::new(1010.0, -300.0).is_err());
+        assert!(Atmosphere::new(-1.0, 10.0).is_err());
+        assert!(Atmosphere::new(f64::NAN, 10.0).is_err());
+        assert!(Atmosphere::new(1010.0, f64::INFINITY).is_err());
+        assert!(serde_json::from_str::<Atmosphere>(
+            r#"{"pressure_hpa":1010,"temperature_c":-280}"#
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_bennett_saemundsson_inverse() {
+        let atmosphere = Atmosphere::default();
+        for apparent in [0.0, 0.5, 2.0, 10.0, 45.0] {
+            let refraction = atmosphere.refraction_from_apparent(apparent);
+            let true_alt = apparent - refraction;
+            let back = atmosphere.refraction_from_true(true_alt);
+            assert!((back - refraction).abs() * 60.0 < 0.1, "{}", apparent);
+        }
+
+        // 高原低压、严寒时折射按比例变化
+        let thin = Atmosphere {
+            pressure_hpa: 505.0,
+            temperature_c: 10.0,
+        };
+        let ratio = thin.refraction_from_true(5.0) / atmosphere.refraction_from_true(5.0);
+        assert!((ratio - 0.5).abs() < 1e-12);
+    }
+}
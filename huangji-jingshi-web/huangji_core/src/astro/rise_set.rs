@@ -0,0 +1,350 @@
+//! 出没、中天与晨昏蒙影
+//!
+//! 参考资料：
+//! - Jean Meeus, "Astronomical Algorithms" (2nd ed.), Chapter 15 "Rising, Transit, and Setting"
+//! - Explanatory Supplement to the Astronomical Almanac (1992), §9 "Sunrise, Sunset, and Twilight"
+//!
+//! 在一个民用日内按固定步长扫描站心地平高度，遇变号再二分求精，
+//! 因此不依赖逐日插值，也能正确处理月亮一日之内缺少升或落的情形。
+//!
+//! - 升落：天体上缘与视地平相切，即几何高度 h0 = -(大气折射 + 视半径)
+//! - 中天：站心时角为 0 的上中天
+//! - 晨昏蒙影：太阳中心几何高度为 -6° / -12° / -18°（民用、航海、天文）
+//!
+//! 整日均在 h0 之上为拱极（不落），整日均在其下为不升。
+
+use crate::astro::delta_t::DeltaTProvider;
+use crate::astro::numeric::bisect;
+use crate::astro::observer::Observer;
+use crate::astro::sidereal::local_apparent_sidereal_time;
+use crate::astro::solar::jd_to_utc;
+use crate::astro::time::TimeScales;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// 标准大气下地平处的折射 (度)
+pub const STANDARD_REFRACTION_DEG: f64 = 34.0 / 60.0;
+
+/// 扫描步长 (日)，10 分钟
+const SCAN_STEP: f64 = 10.0 / 1440.0;
+
+/// 天体某时刻的地心视位置
+#[derive(Debug, Clone, Copy)]
+pub struct ApparentPlace {
+    /// 视赤经 (度)
+    pub ra_deg: f64,
+    /// 视赤纬 (度)
+    pub dec_deg: f64,
+    /// 地心距 (km)
+    pub distance_km: f64,
+    /// 天体半径 (km)，点源取 0
+    pub radius_km: f64,
+}
+
+/// 全日的地平状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HorizonStatus {
+    /// 当日有升或落
+    Normal,
+    /// 终日在地平之上（拱极、极昼）
+    Circumpolar,
+    /// 终日在地平之下（不升、极夜）
+    NeverRises,
+}
+
+/// 一日内的升、中天、落
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RiseSetTransit {
+    pub rise: Option<DateTime<Utc>>,
+    /// 升起方位角 (度，北起向东)
+    pub rise_azimuth_deg: Option<f64>,
+    pub transit: Option<DateTime<Utc>>,
+    /// 上中天时的地平高度 (度，未计折射)
+    pub transit_altitude_deg: Option<f64>,
+    pub set: Option<DateTime<Utc>>,
+    /// 落下方位角 (度，北起向东)
+    pub set_azimuth_deg: Option<f64>,
+    pub status: HorizonStatus,
+}
+
+/// 晨昏蒙影类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TwilightKind {
+    /// 民用晨昏蒙影，太阳在地平下 6°
+    Civil,
+    /// 航海晨昏蒙影，太阳在地平下 12°
+    Nautical,
+    /// 天文晨昏蒙影，太阳在地平下 18°
+    Astronomical,
+}
+
+impl TwilightKind {
+    pub const ALL: [TwilightKind; 3] = [
+        TwilightKind::Civil,
+        TwilightKind::Nautical,
+        TwilightKind::Astronomical,
+    ];
+
+    /// 太阳中心的俯角 (度)
+    pub fn depression_deg(&self) -> f64 {
+        match self {
+            TwilightKind::Civil => 6.0,
+            TwilightKind::Nautical => 12.0,
+            TwilightKind::Astronomical => 18.0,
+        }
+    }
+}
+
+/// 一日内的晨光始、昏影终
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Twilight {
+    pub kind: TwilightKind,
+    /// 晨光始：太阳升至俯角处
+    pub dawn: Option<DateTime<Utc>>,
+    /// 昏影终：太阳降至俯角处
+    pub dusk: Option<DateTime<Utc>>,
+    /// Circumpolar 表示终日亮于此级蒙影（如白夜），NeverRises 表示终日暗于此级
+    pub status: HorizonStatus,
+}
+
+/// 站心地平坐标
+struct Horizontal {
+    /// 几何地平高度 (度)
+    altitude: f64,
+    /// 方位角 (度，北起向东)
+    azimuth: f64,
+    /// 站心时角 (度，-180..180)
+    hour_angle: f64,
+    /// 站心视半径 (度)
+    semidiameter: f64,
+}
+
+fn horizontal(
+    place: &impl Fn(f64) -> ApparentPlace,
+    observer: &Observer,
+    jd_utc: f64,
+    provider: &dyn DeltaTProvider,
+) -> Horizontal {
+    let scales = TimeScales::from_jd_utc_with(jd_utc, provider);
+    let p = place(scales.jd_tt);
+    let (ra, dec) = (p.ra_deg.to_radians(), p.dec_deg.to_radians());
+    let site = observer.geocentric_position(&scales);
+    let v = [
+        p.distance_km * dec.cos() * ra.cos() - site[0],
+        p.distance_km * dec.cos() * ra.sin() - site[1],
+        p.distance_km * dec.sin() - site[2],
+    ];
+    let distance = (v[0] * v[0] + v[1] * v[1] + v[2] * v[2]).sqrt();
+
+    let phi = observer.lat_deg.to_radians();
+    let theta = local_apparent_sidereal_time(&scales, observer.lon_deg).to_radians();
+    let up = [phi.cos() * theta.cos(), phi.cos() * theta.sin(), phi.sin()];
+    let north = [
+        -phi.sin() * theta.cos(),
+        -phi.sin() * theta.sin(),
+        phi.cos(),
+    ];
+    let east = [-theta.sin(), theta.cos(), 0.0];
+    let along = |axis: [f64; 3]| (v[0] * axis[0] + v[1] * axis[1] + v[2] * axis[2]) / distance;
+
+    let topo_ra = v[1].atan2(v[0]);
+    Horizontal {
+        altitude: along(up).clamp(-1.0, 1.0).asin().to_degrees(),
+        azimuth: along(east)
+            .atan2(along(north))
+            .to_degrees()
+            .rem_euclid(360.0),
+        hour_angle: ((theta - topo_ra).to_degrees() + 180.0).rem_euclid(360.0) - 180.0,
+        semidiameter: (p.radius_km / distance).min(1.0).asin().to_degrees(),
+    }
+}
+
+/// [start, start + 1) 内 f 由负转正与由正转负的时刻
+fn crossings(f: &impl Fn(f64) -> f64, start_jd_utc: f64) -> (Option<f64>, Option<f64>, f64) {
+    let steps = (1.0 / SCAN_STEP).round() as usize;
+    let mut up = None;
+    let mut down = None;
+    let first = f(start_jd_utc);
+    let mut prev = (start_jd_utc, first);
+    for i in 1..=steps {
+        let t = start_jd_utc + i as f64 * SCAN_STEP;
+        let value = f(t);
+        if (prev.1 < 0.0) != (value < 0.0) {
+            let root = bisect(f, prev.0, t);
+            if value >= 0.0 {
+                up.get_or_insert(root);
+            } else {
+                down.get_or_insert(root);
+            }
+        }
+        prev = (t, value);
+    }
+    (up, down, first)
+}
+
+fn status(up: Option<f64>, down: Option<f64>, first: f64) -> HorizonStatus {
+    match (up, down) {
+        (None, None) if first >= 0.0 => HorizonStatus::Circumpolar,
+        (None, None) => HorizonStatus::NeverRises,
+        _ => HorizonStatus::Normal,
+    }
+}
+
+/// 求一日内的升、中天、落
+///
+/// # 参数
+/// - `place`: 天体地心视位置，自变量为力学时 (TT) 儒略日
+/// - `start_jd_utc`: 当地民用日零时对应的 UTC 儒略日，搜索区间为其后 24 小时
+/// - `refraction_deg`: 地平处大气折射 (度)，标准值见 `STANDARD_REFRACTION_DEG`
+pub fn rise_set_transit(
+    place: impl Fn(f64) -> ApparentPlace,
+    observer: &Observer,
+    start_jd_utc: f64,
+    refraction_deg: f64,
+    provider: &dyn DeltaTProvider,
+) -> RiseSetTransit {
+    let at = |t: f64| horizontal(&place, observer, t, provider);
+    let above_horizon = |t: f64| {
+        let h = at(t);
+        h.altitude + refraction_deg + h.semidiameter
+    };
+    let (rise, set, first) = crossings(&above_horizon, start_jd_utc);
+
+    // 上中天：时角由负转正；时角在 ±180° 处回绕（下中天）不算
+    let steps = (1.0 / SCAN_STEP).round() as usize;
+    let mut transit = None;
+    let mut prev = (start_jd_utc, at(start_jd_utc).hour_angle);
+    for i in 1..=steps {
+        let t = start_jd_utc + i as f64 * SCAN_STEP;
+        let hour_angle = at(t).hour_angle;
+        if prev.1 < 0.0 && hour_angle >= 0.0 && hour_angle - prev.1 < 90.0 {
+            transit = Some(bisect(|t| at(t).hour_angle, prev.0, t));
+            break;
+        }
+        prev = (t, hour_angle);
+    }
+
+    RiseSetTransit {
+        rise: rise.and_then(jd_to_utc),
+        rise_azimuth_deg: rise.map(|t| at(t).azimuth),
+        transit: transit.and_then(jd_to_utc),
+        transit_altitude_deg: transit.map(|t| at(t).altitude),
+        set: set.and_then(jd_to_utc),
+        set_azimuth_deg: set.map(|t| at(t).azimuth),
+        status: status(rise, set, first),
+    }
+}
+
+/// 求一日内某级晨昏蒙影的晨光始与昏影终
+///
+/// # 参数
+/// - `sun`: 太阳地心视位置，自变量为力学时 (TT) 儒略日
+/// - `start_jd_utc`: 当地民用日零时对应的 UTC 儒略日
+pub fn twilight(
+    sun: impl Fn(f64) -> ApparentPlace,
+    observer: &Observer,
+    start_jd_utc: f64,
+    kind: TwilightKind,
+    provider: &dyn DeltaTProvider,
+) -> Twilight {
+    let depression = kind.depression_deg();
+    let above = |t: f64| horizontal(&sun, observer, t, provider).altitude + depression;
+    let (dawn, dusk, first) = crossings(&above, start_jd_utc);
+    Twilight {
+        kind,
+        dawn: dawn.and_then(jd_to_utc),
+        dusk: dusk.and_then(jd_to_utc),
+        status: status(dawn, dusk, first),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::astro::coords::AU_KM;
+    use crate::astro::delta_t::EspenakMeeus;
+    use crate::astro::nutation::true_obliquity;
+    use crate::astro::solar::apparent_sun;
+    use chrono::TimeZone;
+
+    fn sun_place(jd_tt: f64) -> ApparentPlace {
+        let sun = apparent_sun(jd_tt);
+        let eps = true_obliquity(jd_tt).to_radians();
+        let lon = sun.longitude.to_radians();
+        ApparentPlace {
+            ra_deg: (eps.cos() * lon.sin()).atan2(lon.cos()).to_degrees(),
+            dec_deg: (eps.sin() * lon.sin()).asin().to_degrees(),
+            distance_km: sun.radius * AU_KM,
+            radius_km: 696_000.0,
+        }
+    }
+
+    #[test]
+    fn test_sunrise_greenwich_equinox() {
+        // 2025-03-20 格林尼治：日出 06:02，日没 18:13 (UTC)
+        let greenwich = Observer::new(51.4779, 0.0);
+        let start = 2460754.5;
+        let events = rise_set_transit(
+            sun_place,
+            &greenwich,
+            start,
+            STANDARD_REFRACTION_DEG,
+            &EspenakMeeus,
+        );
+        assert_eq!(events.status, HorizonStatus::Normal);
+        let near = |actual: Option<DateTime<Utc>>, h, m| {
+            let expected = Utc.with_ymd_and_hms(2025, 3, 20, h, m, 0).unwrap();
+            (actual.unwrap() - expected).num_seconds().abs() < 90
+        };
+        assert!(near(events.rise, 6, 2), "{:?}", events.rise);
+        assert!(near(events.set, 18, 13), "{:?}", events.set);
+        assert!(near(events.transit, 12, 7), "{:?}", events.transit);
+        // 春分日出正东略偏北
+        assert!((events.rise_azimuth_deg.unwrap() - 89.0).abs() < 1.5);
+    }
+
+    #[test]
+    fn test_polar_day_and_night() {
+        let tromso = Observer::new(69.65, 18.96);
+        // 2025-06-21 极昼：终日不落，且无任何蒙影
+        let summer = rise_set_transit(
+            sun_place,
+            &tromso,
+            2460847.5,
+            STANDARD_REFRACTION_DEG,
+            &EspenakMeeus,
+        );
+        assert_eq!(summer.status, HorizonStatus::Circumpolar);
+        assert!(summer.rise.is_none() && summer.set.is_none());
+        let civil = twilight(
+            sun_place,
+            &tromso,
+            2460847.5,
+            TwilightKind::Civil,
+            &EspenakMeeus,
+        );
+        assert_eq!(civil.status, HorizonStatus::Circumpolar);
+
+        // 2025-12-21 极夜：终日不升，但仍有民用晨昏蒙影
+        let winter = rise_set_transit(
+            sun_place,
+            &tromso,
+            2461030.5,
+            STANDARD_REFRACTION_DEG,
+            &EspenakMeeus,
+        );
+        assert_eq!(winter.status, HorizonStatus::NeverRises);
+        assert!(winter.transit_altitude_deg.unwrap() < 0.0);
+        let civil = twilight(
+            sun_place,
+            &tromso,
+            2461030.5,
+            TwilightKind::Civil,
+            &EspenakMeeus,
+        );
+        assert_eq!(civil.status, HorizonStatus::Normal);
+        assert!(civil.dawn.unwrap() < civil.dusk.unwrap());
+    }
+}
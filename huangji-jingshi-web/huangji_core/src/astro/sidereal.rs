@@ -0,0 +1,48 @@
+//! 恒星时
+//!
+//! 参考资料：
+//! - Jean Meeus, "Astronomical Algorithms" (2nd ed.), Chapter 12 "Sidereal Time at Greenwich"
+//!
+//! 恒星时以地球自转 (UT1) 计，章动改正以力学时 (TT) 求值。
+
+use crate::astro::nutation::{nutation, true_obliquity};
+use crate::astro::time::TimeScales;
+
+/// 格林尼治平恒星时（度, 0-360，Meeus 式 12.4）
+///
+/// # 参数
+/// - `jd_ut1`: UT1 儒略日
+pub fn greenwich_mean_sidereal_time(jd_ut1: f64) -> f64 {
+    let d = jd_ut1 - 2451545.0;
+    let t = d / 36525.0;
+    (280.46061837 + 360.98564736629 * d + 0.000387933 * t * t - t * t * t / 38710000.0)
+        .rem_euclid(360.0)
+}
+
+/// 格林尼治视恒星时（度, 0-360）：平恒星时加赤经章动 Δψ·cos ε
+pub fn greenwich_apparent_sidereal_time(scales: &TimeScales) -> f64 {
+    let equation_of_equinoxes =
+        nutation(scales.jd_tt).longitude * true_obliquity(scales.jd_tt).to_radians().cos();
+    (greenwich_mean_sidereal_time(scales.jd_ut1) + equation_of_equinoxes).rem_euclid(360.0)
+}
+
+/// 地方视恒星时（度, 0-360）
+///
+/// # 参数
+/// - `lon_deg`: 地理经度，东正西负
+pub fn local_apparent_sidereal_time(scales: &TimeScales, lon_deg: f64) -> f64 {
+    (greenwich_apparent_sidereal_time(scales) + lon_deg).rem_euclid(360.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_meeus_example_12a() {
+        // Meeus 例 12.a：1987 年 4 月 10 日 0h UT，平恒星时 13h10m46.3668s
+        let gmst = greenwich_mean_sidereal_time(2446895.5);
+        let expected = (13.0 + 10.0 / 60.0 + 46.3668 / 3600.0) * 15.0;
+        assert!((gmst - expected).abs() * 240.0 < 0.001, "gmst={}", gmst);
+    }
+}
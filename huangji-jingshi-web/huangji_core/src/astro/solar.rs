@@ -1,11 +1,14 @@
 //! 太阳位置与时间计算
 //!
 //! 参考资料：
-//! - Jean Meeus, "Astronomical Algorithms" (2nd ed.)
+//! - Jean Meeus, "Astronomical Algorithms" (2nd ed.), Chapter 25, 28
 //! - NOAA Solar Calculator: https://gml.noaa.gov/grad/solcalc/
 //! - Equation of Time: https://www.sws.bom.gov.au/Category/Educational/The%20Sun%20and%20Solar%20Activity/General%20Info/EquationOfTime.pdf
 
-use chrono::{DateTime, NaiveDateTime, Timelike, Utc};
+use crate::astro::nutation::{nutation, true_obliquity};
+use crate::astro::solar_time::SolarTimePolicy;
+use crate::astro::vsop87::earth_heliocentric;
+use chrono::{DateTime, FixedOffset, NaiveDateTime, Timelike, Utc};
 
 /// 太阳位置信息
 #[derive(Debug, Clone, Copy)]
@@ -30,58 +33,95 @@ pub fn utc_to_jd(dt: &DateTime<Utc>) -> f64 {
     (timestamp as f64 / 86400.0) + 2440587.5
 }
 
+/// 将儒略日转换为 DateTime<Utc>（精确到秒）
+pub fn jd_to_utc(jd: f64) -> Option<DateTime<Utc>> {
+    let seconds = ((jd - 2440587.5) * 86400.0).round() as i64;
+    DateTime::from_timestamp(seconds, 0)
+}
+
+/// 太阳地心视位置
+#[derive(Debug, Clone, Copy)]
+pub struct ApparentSun {
+    /// 视黄经 (度, 0-360)，含章动与光行差
+    pub longitude: f64,
+    /// 黄纬 (度)
+    pub latitude: f64,
+    /// 日地距离 (AU)
+    pub radius: f64,
+}
+
+/// 计算太阳地心视位置
+///
+/// 由 VSOP87D 地球日心坐标反向得到太阳几何坐标，转换到 FK5 系统，
+/// 再加 IAU 1980 黄经章动与周年光行差 (-20.4898"/R)。
+/// 见 Meeus 第 25 章 "Higher accuracy"。
+///
+/// # 参数
+/// - `jde`: 力学时 (TT) 儒略日
+pub fn apparent_sun(jde: f64) -> ApparentSun {
+    let earth = earth_heliocentric(jde);
+    let t = (jde - 2451545.0) / 36525.0;
+
+    // 几何黄经、黄纬
+    let theta = (earth.longitude.to_degrees() + 180.0).rem_euclid(360.0);
+    let beta = -earth.latitude.to_degrees();
+
+    // 转换到 FK5 系统
+    let lambda_prime = (theta - 1.397 * t - 0.00031 * t * t).to_radians();
+    let theta = theta - 0.09033 / 3600.0;
+    let beta = beta + 0.03916 * (lambda_prime.cos() - lambda_prime.sin()) / 3600.0;
+
+    // 章动与光行差
+    let aberration = -20.4898 / 3600.0 / earth.radius;
+    let longitude = (theta + nutation(jde).longitude + aberration).rem_euclid(360.0);
+
+    ApparentSun {
+        longitude,
+        latitude: beta,
+        radius: earth.radius,
+    }
+}
+
+/// 太阳视黄经（度, 0-360）
+pub fn apparent_solar_longitude(jde: f64) -> f64 {
+    apparent_sun(jde).longitude
+}
+
 /// 计算太阳位置（黄经和均时差）
 /// 
-/// 使用 VSOP87 简化算法，精度约 0.01°，足够八字排盘使用。
+/// 黄经为 VSOP87D + 章动 + 光行差的视黄经，精度约 1"，节气时刻可精确到秒级；
+/// 均时差按 Meeus 式 28.3 计算。
 /// 
 /// # 参数
-/// - `jd`: 儒略日
+/// - `jd`: 力学时 (TT) 儒略日，UTC 时刻先经 `time::utc_to_tt` 转换
 /// 
 /// # 返回
 /// - `SolarPosition`: 太阳黄经(度)和均时差(分钟)
 pub fn solar_position(jd: f64) -> SolarPosition {
-    // 儒略世纪数 (从 J2000.0 = JD 2451545.0 起算)
-    let t = (jd - 2451545.0) / 36525.0;
-    
-    // ========== 太阳黄经计算 (VSOP87 简化) ==========
-    
-    // 太阳平黄经 (Mean Longitude)
-    let l0 = (280.46646 + 36000.76983 * t + 0.0003032 * t * t).rem_euclid(360.0);
-    
-    // 太阳平近点角 (Mean Anomaly)
-    let m = (357.52911 + 35999.05029 * t - 0.0001537 * t * t).rem_euclid(360.0);
-    let m_rad = m.to_radians();
-    
-    // 地球轨道偏心率 (保留用于更精确计算)
-    let _e = 0.016708634 - 0.000042037 * t - 0.0000001267 * t * t;
-    
-    // 太阳中心差 (Equation of Center)
-    let c = (1.914602 - 0.004817 * t - 0.000014 * t * t) * m_rad.sin()
-          + (0.019993 - 0.000101 * t) * (2.0 * m_rad).sin()
-          + 0.000289 * (3.0 * m_rad).sin();
-    
-    // 太阳真黄经 (True Longitude)
-    let sun_lon = (l0 + c).rem_euclid(360.0);
-    
-    // ========== 均时差计算 (Equation of Time) ==========
-    
-    // 黄赤交角 (Obliquity of Ecliptic)
-    let epsilon = 23.439291 - 0.0130042 * t;
-    let epsilon_rad = epsilon.to_radians();
-    
-    // 太阳赤经 (Right Ascension)
-    let y = epsilon_rad.cos() * sun_lon.to_radians().sin();
-    let x = sun_lon.to_radians().cos();
-    let alpha = y.atan2(x).to_degrees().rem_euclid(360.0);
-    
-    // 均时差 = 平黄经 - 赤经 (转换为分钟)
-    let mut eot = l0 - alpha;
-    if eot > 180.0 { eot -= 360.0; }
-    if eot < -180.0 { eot += 360.0; }
+    let sun = apparent_sun(jd);
+
+    // 太阳平黄经 (Meeus 式 28.2，τ 为儒略千年数)
+    let tau = (jd - 2451545.0) / 365250.0;
+    let l0 = 280.4664567 + 360007.6982779 * tau + 0.03032028 * tau * tau
+        + tau.powi(3) / 49931.0
+        - tau.powi(4) / 15300.0
+        - tau.powi(5) / 2000000.0;
+
+    // 视赤经
+    let epsilon = true_obliquity(jd).to_radians();
+    let lambda = sun.longitude.to_radians();
+    let beta = sun.latitude.to_radians();
+    let alpha = (lambda.sin() * epsilon.cos() - beta.tan() * epsilon.sin())
+        .atan2(lambda.cos())
+        .to_degrees();
+
+    // 均时差 = 平黄经 - 0.0057183° - 赤经 + Δψ·cos ε (转换为分钟)
+    let eot = (l0 - 0.0057183 - alpha + nutation(jd).longitude * epsilon.cos()).rem_euclid(360.0);
+    let eot = if eot > 180.0 { eot - 360.0 } else { eot };
     let eot_minutes = eot * 4.0; // 1度 = 4分钟
-    
+
     SolarPosition {
-        ecliptic_longitude: sun_lon,
+        ecliptic_longitude: sun.longitude,
         equation_of_time: eot_minutes,
     }
 }
@@ -104,40 +144,21 @@ pub fn equation_of_time_spencer(day_of_year: u32) -> f64 {
     9.87 * (2.0 * b).sin() - 7.67 * (b + 78.7_f64.to_radians()).sin()
 }
 
-/// 计算真太阳时
+/// 计算真太阳时（地方视太阳时）
 /// 
-/// 真太阳时 = 标准时 + 经度修正 + 均时差
+/// 真太阳时 = UTC + 经度时差 + 均时差，与所在时区无关，
+/// 即 `SolarTimePolicy::Apparent` 以 UTC 偏移表示的本地时间。
 /// 
 /// # 参数
 /// - `dt_utc`: UTC 时间
 /// - `longitude`: 出生地经度 (东经为正)
-/// - `standard_meridian`: 标准时区中央经线 (如北京时间用 120.0)
 /// 
 /// # 返回
 /// - 真太阳时 (NaiveDateTime)
-pub fn true_solar_time(
-    dt_utc: &DateTime<Utc>,
-    longitude: f64,
-    standard_meridian: f64,
-) -> NaiveDateTime {
-    let jd = utc_to_jd(dt_utc);
-    let solar = solar_position(jd);
-    
-    // 经度修正: 每度 = 4分钟
-    // 东边比标准时区快，西边慢
-    let longitude_correction_minutes = (longitude - standard_meridian) * 4.0;
-    
-    // 总修正量 (分钟) = 经度修正 + 均时差
-    let total_correction_minutes = longitude_correction_minutes + solar.equation_of_time;
-    
-    // 转换为秒并应用
-    let correction_seconds = (total_correction_minutes * 60.0).round() as i64;
-    
-    // 先转到标准时区时间，再加修正
-    let local_standard = *dt_utc + chrono::Duration::hours((standard_meridian / 15.0).round() as i64);
-    let true_solar = local_standard + chrono::Duration::seconds(correction_seconds);
-    
-    true_solar.naive_utc()
+pub fn true_solar_time(dt_utc: &DateTime<Utc>, longitude: f64) -> NaiveDateTime {
+    SolarTimePolicy::Apparent
+        .local_time(dt_utc, longitude, FixedOffset::east_opt(0).expect("UTC 偏移有效"))
+        .naive_local()
 }
 
 /// 计算真太阳时（简化版，直接返回小时数）
@@ -149,25 +170,7 @@ pub fn true_solar_time(
 /// # 返回
 /// - 真太阳时小时数 (0.0 - 24.0)
 pub fn true_solar_hour(dt_utc: &DateTime<Utc>, longitude: f64) -> f64 {
-    let jd = utc_to_jd(dt_utc);
-    let solar = solar_position(jd);
-    
-    // UTC 时间转为小时
-    let utc_hour = dt_utc.hour() as f64 
-                 + dt_utc.minute() as f64 / 60.0 
-                 + dt_utc.second() as f64 / 3600.0;
-    
-    // 经度时差: 每15度 = 1小时
-    let longitude_hours = longitude / 15.0;
-    
-    // 均时差转小时
-    let eot_hours = solar.equation_of_time / 60.0;
-    
-    // 真太阳时 = UTC + 经度时差 + 均时差
-    let tst = utc_hour + longitude_hours + eot_hours;
-    
-    // 归一化到 0-24
-    tst.rem_euclid(24.0)
+    true_solar_time(dt_utc, longitude).num_seconds_from_midnight() as f64 / 3600.0
 }
 
 /// 根据真太阳时小时数获取时辰地支索引
@@ -202,6 +205,7 @@ pub fn hour_to_dizhi_index(true_solar_hour: f64) -> (usize, bool) {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::astro::time::utc_to_tt;
     use chrono::TimeZone;
 
     #[test]
@@ -209,8 +213,7 @@ mod tests {
         // 2024年春分: 3月20日 约 03:06 UTC
         // 太阳黄经应接近 0°
         let dt = Utc.with_ymd_and_hms(2024, 3, 20, 3, 6, 0).unwrap();
-        let jd = utc_to_jd(&dt);
-        let solar = solar_position(jd);
+        let solar = solar_position(utc_to_tt(&dt));
         println!("春分太阳黄经: {:.2}°", solar.ecliptic_longitude);
         assert!(solar.ecliptic_longitude < 1.0 || solar.ecliptic_longitude > 359.0);
     }
@@ -220,12 +223,27 @@ mod tests {
         // 2025年立春: 2月3日 约 22:10 UTC (北京时间 2月4日 06:10)
         // 太阳黄经应接近 315°
         let dt = Utc.with_ymd_and_hms(2025, 2, 3, 22, 10, 0).unwrap();
-        let jd = utc_to_jd(&dt);
-        let solar = solar_position(jd);
+        let solar = solar_position(utc_to_tt(&dt));
         println!("立春太阳黄经: {:.2}°", solar.ecliptic_longitude);
         assert!((solar.ecliptic_longitude - 315.0).abs() < 1.0);
     }
 
+    #[test]
+    fn test_meeus_example_25b_apparent_longitude() {
+        // Meeus 例 25.b：1992 年 10 月 13.0 TD，视黄经 199°54'21.818"，R = 0.99760775 AU
+        let sun = apparent_sun(2448908.5);
+        let expected = 199.0 + 54.0 / 60.0 + 21.818 / 3600.0;
+        assert!((sun.longitude - expected).abs() * 3600.0 < 0.1, "λ={}", sun.longitude);
+        assert!((sun.radius - 0.99760775).abs() < 1e-8);
+    }
+
+    #[test]
+    fn test_meeus_example_28a_equation_of_time() {
+        // Meeus 例 28.a：1992 年 10 月 13.0 TD，均时差 +13m42.6s
+        let eot = solar_position(2448908.5).equation_of_time;
+        assert!((eot - (13.0 + 42.6 / 60.0)).abs() < 0.02, "E={}", eot);
+    }
+
     #[test]
     fn test_equation_of_time() {
         // 均时差在 ±16 分钟范围内
@@ -0,0 +1,184 @@
+//! 太阳时策略
+//!
+//! 排盘、岁首与黄历取"本地时间"时，可选以下几种太阳时：
+//! - `Meridian`：区时，即以时区标准经线为参考的钟表时间，不做校正
+//! - `Mean`：地方平太阳时，区时加经度差（每度 4 分钟）
+//! - `Apparent`：地方真太阳时，平太阳时再加均时差，均时差取太阳视位置（`solar_position`）
+//! - `ApparentSpencer`：同上，均时差取 Spencer 近似（`equation_of_time_spencer`，约 30 秒）
+//!
+//! 平太阳时与真太阳时只取决于 UTC 与经度，与所在时区无关；
+//! 结果仍以区时偏移表示，便于与本地日期、时辰直接比较。
+
+use crate::astro::solar::{equation_of_time_spencer, solar_position};
+use crate::astro::time::utc_to_tt;
+use chrono::{DateTime, Datelike, Duration, FixedOffset, Utc};
+use serde::{Deserialize, Serialize};
+
+/// 计时所依据的太阳时
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SolarTimePolicy {
+    /// 区时（默认）
+    #[default]
+    Meridian,
+    /// 地方平太阳时
+    Mean,
+    /// 地方真太阳时，精确均时差
+    Apparent,
+    /// 地方真太阳时，Spencer 均时差
+    ApparentSpencer,
+}
+
+impl SolarTimePolicy {
+    /// 解析请求参数：`meridian`（兼容 `standard`）、`mean`、`apparent`（兼容 `true_solar`）、
+    /// `apparent_spencer`；空串为区时
+    pub fn parse(raw: &str) -> anyhow::Result<Self> {
+        match raw.trim().to_ascii_lowercase().as_str() {
+            "" | "meridian" | "standard" => Ok(Self::Meridian),
+            "mean" | "mean_solar" => Ok(Self::Mean),
+            "apparent" | "true_solar" | "truesolar" | "apparent_precise" => Ok(Self::Apparent),
+            "apparent_spencer" | "spencer" => Ok(Self::ApparentSpencer),
+            other => anyhow::bail!(
+                "无效的太阳时策略：{}（可选 meridian、mean、apparent、apparent_spencer）",
+                other
+            ),
+        }
+    }
+
+    /// 旧参数 `useTrueSolarTime` 的含义：历来只按经度差校正，即地方平太阳时，不计均时差
+    ///
+    /// 需计入均时差时请改用 `Apparent`（请求参数 `solarTime=apparent`）。
+    pub fn from_true_solar_flag(use_true_solar_time: bool) -> Self {
+        if use_true_solar_time {
+            Self::Mean
+        } else {
+            Self::Meridian
+        }
+    }
+
+    /// 请求参数写法，与 `parse` 互逆
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Meridian => "meridian",
+            Self::Mean => "mean",
+            Self::Apparent => "apparent",
+            Self::ApparentSpencer => "apparent_spencer",
+        }
+    }
+
+    /// 是否按出生地经度校正
+    pub fn is_local(&self) -> bool {
+        !matches!(self, Self::Meridian)
+    }
+
+    /// 均时差 (分钟)：真太阳时减平太阳时；区时与平太阳时为 0
+    pub fn equation_of_time(&self, utc: &DateTime<Utc>, longitude: f64) -> f64 {
+        match self {
+            Self::Meridian | Self::Mean => 0.0,
+            Self::Apparent => solar_position(utc_to_tt(utc)).equation_of_time,
+            Self::ApparentSpencer => {
+                // 日序取地方平太阳时的日期，与时区无关
+                let local_mean = *utc + Duration::seconds((longitude * 240.0).round() as i64);
+                equation_of_time_spencer(local_mean.ordinal())
+            }
+        }
+    }
+
+    /// 相对区时的校正量（秒）
+    ///
+    /// # 参数
+    /// - `longitude`: 出生地经度（东经为正）
+    /// - `offset`: 区时偏移，标准经线 = 15° × 偏移小时数（支持 +05:30 等非整小时时区）
+    pub fn correction_seconds(
+        &self,
+        utc: &DateTime<Utc>,
+        longitude: f64,
+        offset: FixedOffset,
+    ) -> f64 {
+        if !self.is_local() {
+            return 0.0;
+        }
+        let standard_meridian = offset.local_minus_utc() as f64 / 240.0;
+        (longitude - standard_meridian) * 240.0 + self.equation_of_time(utc, longitude) * 60.0
+    }
+
+    /// 按本策略取本地时间（精确到秒），以 `offset` 表示
+    pub fn local_time(
+        &self,
+        utc: &DateTime<Utc>,
+        longitude: f64,
+        offset: FixedOffset,
+    ) -> DateTime<FixedOffset> {
+        let correction = self.correction_seconds(utc, longitude, offset).round() as i64;
+        utc.with_timezone(&offset) + Duration::seconds(correction)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{TimeZone, Timelike};
+
+    #[test]
+    fn test_parse_and_names() {
+        for policy in [
+            SolarTimePolicy::Meridian,
+            SolarTimePolicy::Mean,
+            SolarTimePolicy::Apparent,
+            SolarTimePolicy::ApparentSpencer,
+        ] {
+            assert_eq!(SolarTimePolicy::parse(policy.name()).unwrap(), policy);
+            let json = serde_json::to_string(&policy).unwrap();
+            assert_eq!(json, format!("\"{}\"", policy.name()));
+        }
+        assert_eq!(SolarTimePolicy::parse("").unwrap(), SolarTimePolicy::Meridian);
+        assert_eq!(
+            SolarTimePolicy::parse("True_Solar").unwrap(),
+            SolarTimePolicy::Apparent
+        );
+        assert!(SolarTimePolicy::parse("sidereal").is_err());
+    }
+
+    #[test]
+    fn test_policies_agree_on_components() {
+        let beijing = FixedOffset::east_opt(8 * 3600).unwrap();
+        // 2025-11-03 前后均时差约 +16.4 分钟，为一年中最大
+        let utc = Utc.with_ymd_and_hms(2025, 11, 3, 4, 0, 0).unwrap();
+        let lon = 116.4;
+
+        let meridian = SolarTimePolicy::Meridian.local_time(&utc, lon, beijing);
+        assert_eq!(meridian.hour(), 12);
+        assert_eq!(meridian.minute(), 0);
+
+        // 平太阳时：4 × (116.4 − 120) = −14.4 分钟
+        let mean = SolarTimePolicy::Mean.local_time(&utc, lon, beijing);
+        assert_eq!((mean - meridian).num_seconds(), -864);
+
+        let precise = SolarTimePolicy::Apparent.equation_of_time(&utc, lon);
+        let spencer = SolarTimePolicy::ApparentSpencer.equation_of_time(&utc, lon);
+        assert!((precise - 16.4).abs() < 0.1, "precise={}", precise);
+        assert!((precise - spencer).abs() < 1.0, "spencer={}", spencer);
+
+        let apparent = SolarTimePolicy::Apparent.local_time(&utc, lon, beijing);
+        assert!(((apparent - mean).num_seconds() as f64 - precise * 60.0).abs() <= 1.0);
+    }
+
+    #[test]
+    fn test_local_solar_time_is_zone_independent() {
+        let utc = Utc.with_ymd_and_hms(2024, 2, 10, 15, 30, 0).unwrap();
+        let beijing = FixedOffset::east_opt(8 * 3600).unwrap();
+        let india = FixedOffset::east_opt(5 * 3600 + 1800).unwrap();
+        for policy in [
+            SolarTimePolicy::Mean,
+            SolarTimePolicy::Apparent,
+            SolarTimePolicy::ApparentSpencer,
+        ] {
+            assert_eq!(
+                policy.local_time(&utc, 87.6, beijing).naive_local(),
+                policy.local_time(&utc, 87.6, india).naive_local(),
+                "{:?}",
+                policy
+            );
+        }
+    }
+}
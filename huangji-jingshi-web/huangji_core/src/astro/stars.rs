@@ -0,0 +1,301 @@
+//! 恒星星表与视位置
+//!
+//! 参考资料：
+//! - Jean Meeus, "Astronomical Algorithms" (2nd ed.), Chapter 23（周年光行差，式 23.3）
+//! - 岁差章动取 IAU 2006/2000B，见 `precession` 模块
+//!
+//! 星表取自星图数据 `stars.6.json`（依巴谷星表至 6 等，含 B−V 色指数，与前端共用），
+//! 星名取自 `starnames.json`。坐标为 J2000.0 平赤道与春分点，先改正自行，再岁差、章动、
+//! 光行差至视位置。
+//!
+//! `stars.6.json` 不带自行，自行另取 `data/star_proper_motions.json`（依巴谷星表，
+//! 毫角秒/年，赤经分量已乘 cos δ），仅收一等亮星与大角、南门二、天津增廿九等自行较大的近邻恒星；
+//! 其余恒星自行按 0 计。多数 6 等以内恒星自行不足 0.1″/年，千年内位移在 2′ 以内。
+
+use crate::astro::coords::{apply, EquatorialCoord};
+use crate::astro::precession::PrecessionNutation;
+use crate::astro::vsop87::earth_heliocentric;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// 周年光行差常数 κ (角秒)
+const ABERRATION_CONSTANT_ARCSEC: f64 = 20.49552;
+
+/// 星表中的一颗恒星（J2000.0）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CatalogStar {
+    /// 依巴谷星表编号
+    pub hip: u32,
+    pub ra_deg: f64,
+    pub dec_deg: f64,
+    /// 视星等
+    pub mag: f64,
+    /// B−V 色指数
+    pub bv: Option<f64>,
+    /// 赤经自行 μα·cos δ (毫角秒/年)
+    pub pm_ra_mas_yr: f64,
+    /// 赤纬自行 (毫角秒/年)
+    pub pm_dec_mas_yr: f64,
+    /// 英文专名
+    pub name: Option<String>,
+    /// 中文星官名，如 天狼、勾陈一
+    pub name_zh: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct StarCollection {
+    features: Vec<StarFeature>,
+}
+
+#[derive(Deserialize)]
+struct StarFeature {
+    id: u32,
+    properties: StarProperties,
+    geometry: StarGeometry,
+}
+
+#[derive(Deserialize)]
+struct StarProperties {
+    mag: f64,
+    #[serde(default)]
+    bv: String,
+}
+
+#[derive(Deserialize)]
+struct ProperMotionTable {
+    /// 依巴谷编号 → [μα·cos δ, μδ] (毫角秒/年)
+    proper_motions: HashMap<u32, [f64; 2]>,
+}
+
+#[derive(Deserialize)]
+struct StarGeometry {
+    /// [赤经, 赤纬] (度)，赤经按 -180..180 存放
+    coordinates: [f64; 2],
+}
+
+#[derive(Deserialize)]
+struct StarName {
+    #[serde(default)]
+    name: String,
+    #[serde(default)]
+    zh: String,
+}
+
+static STAR_CATALOG: Lazy<Vec<CatalogStar>> = Lazy::new(|| {
+    let collection: StarCollection = serde_json::from_str(include_str!(
+        "../../../backend/backend/data/celestial/stars.6.json"
+    ))
+    .expect("stars.6.json 解析失败");
+    let names: HashMap<String, StarName> = serde_json::from_str(include_str!(
+        "../../../backend/backend/data/celestial/starnames.json"
+    ))
+    .expect("starnames.json 解析失败");
+    let proper_motions: ProperMotionTable =
+        serde_json::from_str(include_str!("../../data/star_proper_motions.json"))
+            .expect("star_proper_motions.json 解析失败");
+    let non_empty = |s: &str| (!s.is_empty()).then(|| s.to_string());
+
+    let mut stars: Vec<CatalogStar> = collection
+        .features
+        .into_iter()
+        .map(|feature| {
+            let name = names.get(&feature.id.to_string());
+            let [ra, dec] = feature.geometry.coordinates;
+            let [pm_ra, pm_dec] = proper_motions
+                .proper_motions
+                .get(&feature.id)
+                .copied()
+                .unwrap_or_default();
+            CatalogStar {
+                hip: feature.id,
+                ra_deg: ra.rem_euclid(360.0),
+                dec_deg: dec,
+                mag: feature.properties.mag,
+                bv: feature.properties.bv.trim().parse().ok(),
+                pm_ra_mas_yr: pm_ra,
+                pm_dec_mas_yr: pm_dec,
+                name: name.and_then(|n| non_empty(&n.name)),
+                name_zh: name.and_then(|n| non_empty(&n.zh)),
+            }
+        })
+        .collect();
+    stars.sort_by(|a, b| a.mag.total_cmp(&b.mag));
+    stars
+});
+
+/// 星表全部恒星，按星等由亮到暗排列
+pub fn star_catalog() -> &'static [CatalogStar] {
+    &STAR_CATALOG
+}
+
+/// 按依巴谷编号查星
+pub fn find_star(hip: u32) -> Option<&'static CatalogStar> {
+    STAR_CATALOG.iter().find(|star| star.hip == hip)
+}
+
+/// 某一时刻的恒星视位置归算参数（岁差章动矩阵、光行差），可对多颗星复用
+#[derive(Debug, Clone, Copy)]
+pub struct StarReduction {
+    /// 自 J2000.0 起的儒略年数
+    years: f64,
+    precession_nutation: PrecessionNutation,
+    /// 真黄赤交角 (弧度)
+    eps: f64,
+    /// 太阳真黄经 (弧度)
+    sun_longitude: f64,
+    /// 地球轨道偏心率与近日点黄经 (弧度)
+    e: f64,
+    perihelion: f64,
+}
+
+impl StarReduction {
+    /// # 参数
+    /// - `jd_tt`: 力学时 (TT) 儒略日
+    pub fn new(jd_tt: f64) -> Self {
+        let t = (jd_tt - 2451545.0) / 36525.0;
+        let precession_nutation = PrecessionNutation::new(jd_tt);
+        let earth = earth_heliocentric(jd_tt);
+        Self {
+            years: t * 100.0,
+            precession_nutation,
+            eps: precession_nutation.true_obliquity_deg().to_radians(),
+            sun_longitude: earth.longitude + std::f64::consts::PI,
+            e: 0.016708634 - 0.000042037 * t,
+            perihelion: (102.93735 + 1.71946 * t).to_radians(),
+        }
+    }
+
+    /// 自行改正后岁差至当时的平位置 (赤经, 赤纬) (度)
+    pub fn mean_place(&self, star: &CatalogStar) -> (f64, f64) {
+        let dec0 = star.dec_deg + star.pm_dec_mas_yr * self.years / 3_600_000.0;
+        let ra0 = star.ra_deg
+            + star.pm_ra_mas_yr * self.years / 3_600_000.0 / star.dec_deg.to_radians().cos();
+        let j2000 = EquatorialCoord {
+            ra_deg: ra0,
+            dec_deg: dec0,
+        };
+        let mean = EquatorialCoord::from_vector(apply(
+            &self.precession_nutation.precession,
+            &j2000.to_vector(),
+        ));
+        (mean.ra_deg, mean.dec_deg)
+    }
+
+    /// 视位置 (赤经, 赤纬) (度)：平位置加章动与周年光行差
+    pub fn apparent_place(&self, star: &CatalogStar) -> (f64, f64) {
+        let (ra, dec) = self.mean_place(star);
+        let mean = EquatorialCoord {
+            ra_deg: ra,
+            dec_deg: dec,
+        };
+        let nutated = EquatorialCoord::from_vector(apply(
+            &self.precession_nutation.nutation_matrix,
+            &mean.to_vector(),
+        ));
+        let (a, d) = (ra.to_radians(), dec.to_radians());
+        let (eps, sun) = (self.eps, self.sun_longitude);
+
+        // 周年光行差（Meeus 式 23.3）
+        let kappa = (ABERRATION_CONSTANT_ARCSEC / 3600.0).to_radians();
+        let ek = self.e * kappa;
+        let pi = self.perihelion;
+        let ab_ra = (-kappa * (a.cos() * sun.cos() * eps.cos() + a.sin() * sun.sin())
+            + ek * (a.cos() * pi.cos() * eps.cos() + a.sin() * pi.sin()))
+            / d.cos();
+        let tilt = eps.tan() * d.cos() - a.sin() * d.sin();
+        let ab_dec = -kappa * (sun.cos() * eps.cos() * tilt + a.cos() * d.sin() * sun.sin())
+            + ek * (pi.cos() * eps.cos() * tilt + a.cos() * d.sin() * pi.sin());
+
+        (
+            (nutated.ra_deg + ab_ra.to_degrees()).rem_euclid(360.0),
+            nutated.dec_deg + ab_dec.to_degrees(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Meeus 例 21.b / 23.a 的英仙座 θ（J2000.0 位置与自行）
+    fn theta_persei() -> CatalogStar {
+        let dec = 49.0 + 13.0 / 60.0 + 42.48 / 3600.0;
+        CatalogStar {
+            hip: 12777,
+            ra_deg: (2.0 + 44.0 / 60.0 + 11.986 / 3600.0) * 15.0,
+            dec_deg: dec,
+            mag: 4.1,
+            bv: None,
+            // μα = +0.03425 s/年，μδ = -0.0895″/年
+            pm_ra_mas_yr: 0.03425 * 15_000.0 * dec.to_radians().cos(),
+            pm_dec_mas_yr: -89.5,
+            name: None,
+            name_zh: None,
+        }
+    }
+
+    #[test]
+    fn test_meeus_example_21b_and_23a() {
+        // 2028-11-13.19 TD：平位置 α = 2h46m11.331s，δ = +49°20'54.54"
+        // 视位置 α = 2h46m14.390s，δ = +49°21'07.45"
+        let reduction = StarReduction::new(2462088.69);
+        let star = theta_persei();
+        let hms = |h: f64, m: f64, s: f64| (h + m / 60.0 + s / 3600.0) * 15.0;
+        let dms = |d: f64, m: f64, s: f64| d + m / 60.0 + s / 3600.0;
+
+        let (ra, dec) = reduction.mean_place(&star);
+        assert!((ra - hms(2.0, 46.0, 11.331)).abs() * 3600.0 < 0.1);
+        assert!((dec - dms(49.0, 20.0, 54.54)).abs() * 3600.0 < 0.1);
+
+        let (ra, dec) = reduction.apparent_place(&star);
+        assert!((ra - hms(2.0, 46.0, 14.390)).abs() * 3600.0 < 0.5);
+        assert!((dec - dms(49.0, 21.0, 7.45)).abs() * 3600.0 < 0.5);
+    }
+
+    #[test]
+    fn test_catalog_loaded_with_names() {
+        let stars = star_catalog();
+        assert!(stars.len() > 5000);
+        assert!(stars.windows(2).all(|w| w[0].mag <= w[1].mag));
+
+        let sirius = &stars[0];
+        assert_eq!(sirius.hip, 32349);
+        assert_eq!(sirius.name.as_deref(), Some("Sirius"));
+        assert_eq!(sirius.name_zh.as_deref(), Some("天狼"));
+
+        // 赤经统一到 0..360
+        let alkaid = find_star(67301).unwrap();
+        assert!((alkaid.ra_deg - 206.8852).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_arcturus_proper_motion() {
+        // 大角自行约 2.3″/年（μα·cos δ ≈ −1.09″，μδ ≈ −2.00″），千年间赤纬南移约 33′
+        let arcturus = find_star(69673).unwrap();
+        assert_eq!(arcturus.name.as_deref(), Some("Arcturus"));
+        assert!(arcturus.pm_dec_mas_yr < -1900.0);
+
+        let j2000 = StarReduction::new(2451545.0);
+        let ad3000 = StarReduction::new(2451545.0 + 365_250.0);
+        let (_, dec_2000) = j2000.mean_place(arcturus);
+        assert!((dec_2000 - arcturus.dec_deg).abs() < 1e-9);
+
+        // 去掉自行后两历元之差只剩岁差，差值即自行位移
+        let fixed = CatalogStar {
+            pm_ra_mas_yr: 0.0,
+            pm_dec_mas_yr: 0.0,
+            ..arcturus.clone()
+        };
+        let (ra_moving, dec_moving) = ad3000.mean_place(arcturus);
+        let (ra_fixed, dec_fixed) = ad3000.mean_place(&fixed);
+        let shift_dec = (dec_moving - dec_fixed) * 60.0;
+        let shift_ra = (ra_moving - ra_fixed) * 60.0 * dec_fixed.to_radians().cos();
+        let shift = shift_dec.hypot(shift_ra);
+        assert!((shift - 38.0).abs() < 1.5, "{}′", shift);
+
+        // 不在自行表中的恒星按 0 计
+        let dubhe = find_star(54061).unwrap();
+        assert_eq!((dubhe.pm_ra_mas_yr, dubhe.pm_dec_mas_yr), (0.0, 0.0));
+    }
+}
@@ -0,0 +1,156 @@
+//! 时间尺度：UTC、UT1、TT
+//!
+//! - UTC：民用协调世界时，`DateTime<Utc>` 与 `utc_to_jd` 均为此尺度
+//! - UT1：以地球自转定义的世界时。|UT1 - UTC| < 0.9 秒，本模块取 DUT1 = 0；
+//!   1972 年以前按 UT 理解
+//! - TT：力学时（地球时），星历（VSOP87、朔望、章动等）的自变量
+//!
+//! 三者关系：TT = UT1 + ΔT。凡星历计算一律以 TT 求值，结果再换回 UTC。
+//! ΔT 默认取 Espenak–Meeus 多项式，`*_with` 系列可指定其他提供者（见 `delta_t` 模块）；
+//! 不带 `_with` 的函数（`utc_to_tt`、`jd_utc_to_tt`、`tt_to_utc` 等）不读取已注册的 IERS 表。
+
+use crate::astro::delta_t::{DeltaTProvider, EspenakMeeus};
+use crate::astro::solar::{jd_to_utc, utc_to_jd};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// 一日的秒数
+const SECONDS_PER_DAY: f64 = 86400.0;
+
+/// 某一时刻在各时间尺度下的儒略日
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct TimeScales {
+    /// UTC 儒略日
+    pub jd_utc: f64,
+    /// UT1 儒略日
+    pub jd_ut1: f64,
+    /// TT 儒略日 (JDE)
+    pub jd_tt: f64,
+    /// ΔT = TT - UT1 (秒)
+    pub delta_t_sec: f64,
+}
+
+impl TimeScales {
+    /// 由 UTC 时刻构造
+    pub fn from_utc(dt: &DateTime<Utc>) -> Self {
+        Self::from_jd_utc(utc_to_jd(dt))
+    }
+
+    /// 由 UTC 儒略日构造
+    pub fn from_jd_utc(jd_utc: f64) -> Self {
+        Self::from_jd_utc_with(jd_utc, &EspenakMeeus)
+    }
+
+    /// 由 TT 儒略日构造
+    pub fn from_jd_tt(jd_tt: f64) -> Self {
+        Self::from_jd_tt_with(jd_tt, &EspenakMeeus)
+    }
+
+    /// 由 UTC 儒略日构造，使用指定 ΔT 提供者
+    pub fn from_jd_utc_with(jd_utc: f64, provider: &dyn DeltaTProvider) -> Self {
+        let jd_ut1 = jd_utc;
+        let delta_t_sec = provider.delta_t(jd_ut1);
+        Self {
+            jd_utc,
+            jd_ut1,
+            jd_tt: jd_ut1 + delta_t_sec / SECONDS_PER_DAY,
+            delta_t_sec,
+        }
+    }
+
+    /// 由 TT 儒略日构造，使用指定 ΔT 提供者
+    pub fn from_jd_tt_with(jd_tt: f64, provider: &dyn DeltaTProvider) -> Self {
+        // ΔT 随时间变化极慢，以 TT 估算一次后再按 UT1 修正即可
+        let jd_ut1 = jd_tt - provider.delta_t(jd_tt) / SECONDS_PER_DAY;
+        let delta_t_sec = provider.delta_t(jd_ut1);
+        let jd_ut1 = jd_tt - delta_t_sec / SECONDS_PER_DAY;
+        Self {
+            jd_utc: jd_ut1,
+            jd_ut1,
+            jd_tt,
+            delta_t_sec,
+        }
+    }
+}
+
+/// UTC 时刻对应的 TT 儒略日
+pub fn utc_to_tt(dt: &DateTime<Utc>) -> f64 {
+    utc_to_tt_with(dt, &EspenakMeeus)
+}
+
+/// UTC 儒略日转 TT 儒略日
+pub fn jd_utc_to_tt(jd_utc: f64) -> f64 {
+    jd_utc_to_tt_with(jd_utc, &EspenakMeeus)
+}
+
+/// TT 儒略日转 UTC 儒略日
+pub fn jd_tt_to_utc(jd_tt: f64) -> f64 {
+    jd_tt_to_utc_with(jd_tt, &EspenakMeeus)
+}
+
+/// TT 儒略日对应的 UTC 时刻（精确到秒）
+pub fn tt_to_utc(jd_tt: f64) -> Option<DateTime<Utc>> {
+    tt_to_utc_with(jd_tt, &EspenakMeeus)
+}
+
+/// 同 [`utc_to_tt`]，使用指定 ΔT 提供者
+pub fn utc_to_tt_with(dt: &DateTime<Utc>, provider: &dyn DeltaTProvider) -> f64 {
+    TimeScales::from_jd_utc_with(utc_to_jd(dt), provider).jd_tt
+}
+
+/// 同 [`jd_utc_to_tt`]，使用指定 ΔT 提供者
+pub fn jd_utc_to_tt_with(jd_utc: f64, provider: &dyn DeltaTProvider) -> f64 {
+    TimeScales::from_jd_utc_with(jd_utc, provider).jd_tt
+}
+
+/// 同 [`jd_tt_to_utc`]，使用指定 ΔT 提供者
+pub fn jd_tt_to_utc_with(jd_tt: f64, provider: &dyn DeltaTProvider) -> f64 {
+    TimeScales::from_jd_tt_with(jd_tt, provider).jd_utc
+}
+
+/// 同 [`tt_to_utc`]，使用指定 ΔT 提供者
+pub fn tt_to_utc_with(jd_tt: f64, provider: &dyn DeltaTProvider) -> Option<DateTime<Utc>> {
+    jd_to_utc(jd_tt_to_utc_with(jd_tt, provider))
+}
+
+/// ΔT = TT - UT1（秒），默认采用 Espenak–Meeus 多项式
+///
+/// # 参数
+/// - `jd`: 儒略日（UT 或 TT 均可，二者之差对 ΔT 的影响可忽略）
+pub fn delta_t(jd: f64) -> f64 {
+    EspenakMeeus.delta_t(jd)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_round_trip_utc_tt() {
+        let dt = Utc.with_ymd_and_hms(2024, 2, 4, 8, 27, 0).unwrap();
+        let scales = TimeScales::from_utc(&dt);
+        assert!((scales.delta_t_sec - 72.0).abs() < 5.0);
+        assert!(
+            ((scales.jd_tt - scales.jd_utc) * SECONDS_PER_DAY - scales.delta_t_sec).abs() < 1e-3
+        );
+        assert_eq!(tt_to_utc(scales.jd_tt), Some(dt));
+
+        // 古代时刻 ΔT 达数小时，往返仍须一致
+        let ancient_tt = 1_000_000.5;
+        let back = jd_utc_to_tt(jd_tt_to_utc(ancient_tt));
+        assert!((back - ancient_tt).abs() * SECONDS_PER_DAY < 0.01);
+        assert!((ancient_tt - jd_tt_to_utc(ancient_tt)) * 24.0 > 10.0);
+    }
+
+    #[test]
+    fn test_explicit_provider() {
+        use crate::astro::delta_t::FixedDeltaT;
+
+        let fixed = FixedDeltaT { seconds: 100.0 };
+        let scales = TimeScales::from_jd_utc_with(2451545.0, &fixed);
+        assert_eq!(scales.delta_t_sec, 100.0);
+        let back = TimeScales::from_jd_tt_with(scales.jd_tt, &fixed);
+        assert!((back.jd_utc - 2451545.0).abs() * SECONDS_PER_DAY < 1e-3);
+    }
+}
@@ -0,0 +1,117 @@
+//! 星历表生成器
+//!
+//! 用法：
+//!   cargo run -p huangji_core --bin ephemeris -- \
+//!     --start 2024-01-01 --end 2024-01-31 --step 1d \
+//!     --bodies sun,moon,mars --lat 39.9 --lon 116.4 --format csv --output mars.csv
+//!
+//! 时刻均为 UTC，可写作 `2024-01-01`、`2024-01-01T12:00` 或 RFC 3339。
+
+use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
+use huangji_core::astro::delta_t::DeltaTModel;
+use huangji_core::astro::observer::Observer;
+use huangji_core::astro::refraction::Atmosphere;
+use huangji_core::ephemeris::{
+    generate_ephemeris, parse_step_minutes, render_ephemeris, EphemerisFormat, EphemerisRequest,
+};
+use huangji_core::sky::SkyBody;
+use std::fs;
+
+const USAGE: &str = "用法：ephemeris --start <UTC 起始时刻> --end <UTC 终止时刻> \
+[--step 1d|6h|30m 步长] [--bodies sun,moon,... 天体] [--lat 39.9 纬度] [--lon 116.4 经度] \
+[--elevation 0 海拔米] [--pressure 1010 气压 hPa] [--temperature 10 气温 °C] \
+[--delta-t espenak_meeus|iers|fixed:<秒> ΔT 模型] [--format csv|json 格式] [--output <文件>]";
+
+fn parse_utc(raw: &str) -> anyhow::Result<DateTime<Utc>> {
+    if let Ok(dt) = DateTime::parse_from_rfc3339(raw) {
+        return Ok(dt.with_timezone(&Utc));
+    }
+    for format in [
+        "%Y-%m-%dT%H:%M:%S",
+        "%Y-%m-%d %H:%M:%S",
+        "%Y-%m-%dT%H:%M",
+        "%Y-%m-%d %H:%M",
+    ] {
+        if let Ok(naive) = NaiveDateTime::parse_from_str(raw, format) {
+            return Ok(naive.and_utc());
+        }
+    }
+    let date = NaiveDate::parse_from_str(raw, "%Y-%m-%d")
+        .map_err(|_| anyhow::anyhow!("无效的时刻：{}", raw))?;
+    Ok(date
+        .and_hms_opt(0, 0, 0)
+        .expect("零时必为有效时刻")
+        .and_utc())
+}
+
+fn parse_number(name: &str, raw: &str) -> anyhow::Result<f64> {
+    raw.parse()
+        .map_err(|_| anyhow::anyhow!("无效的 --{}：{}", name, raw))
+}
+
+fn main() -> anyhow::Result<()> {
+    let mut start = None;
+    let mut end = None;
+    let mut step_minutes = 1440;
+    let mut bodies = None;
+    let mut observer = Observer::new(39.9, 116.4);
+    let mut atmosphere = Atmosphere::default();
+    let mut delta_t_model = DeltaTModel::default();
+    let mut format = EphemerisFormat::Csv;
+    let mut output = None;
+
+    let mut args = std::env::args().skip(1);
+    while let Some(flag) = args.next() {
+        if flag == "-h" || flag == "--help" {
+            println!("{}", USAGE);
+            return Ok(());
+        }
+        let value = args
+            .next()
+            .ok_or_else(|| anyhow::anyhow!("{} 缺少取值\n{}", flag, USAGE))?;
+        match flag.as_str() {
+            "--start" => start = Some(parse_utc(&value)?),
+            "--end" => end = Some(parse_utc(&value)?),
+            "--step" => step_minutes = parse_step_minutes(&value)?,
+            "--bodies" => {
+                bodies = Some(
+                    value
+                        .split(',')
+                        .filter(|s| !s.trim().is_empty())
+                        .map(SkyBody::parse)
+                        .collect::<anyhow::Result<Vec<_>>>()?,
+                )
+            }
+            "--lat" => observer.lat_deg = parse_number("lat", &value)?,
+            "--lon" => observer.lon_deg = parse_number("lon", &value)?,
+            "--elevation" => observer.elevation_m = parse_number("elevation", &value)?,
+            "--pressure" => atmosphere.pressure_hpa = parse_number("pressure", &value)?,
+            "--temperature" => atmosphere.temperature_c = parse_number("temperature", &value)?,
+            "--delta-t" => delta_t_model = DeltaTModel::parse(&value)?,
+            "--format" => format = EphemerisFormat::parse(&value)?,
+            "--output" => output = Some(value),
+            other => anyhow::bail!("未知选项：{}\n{}", other, USAGE),
+        }
+    }
+
+    let start = start.ok_or_else(|| anyhow::anyhow!("须给出 --start\n{}", USAGE))?;
+    let rows = generate_ephemeris(&EphemerisRequest {
+        bodies,
+        start,
+        end: end.unwrap_or(start),
+        step_minutes,
+        observer,
+        atmosphere,
+        delta_t_model,
+    })?;
+    let rendered = render_ephemeris(&rows, format)?;
+
+    match output {
+        Some(path) => {
+            fs::write(&path, rendered)?;
+            eprintln!("已生成星历表：{} 行 -> {}", rows.len(), path);
+        }
+        None => print!("{}", rendered),
+    }
+    Ok(())
+}
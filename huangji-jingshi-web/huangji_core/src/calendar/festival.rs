@@ -0,0 +1,135 @@
+//! 传统节日模块
+//!
+//! 节日规则以数据形式保存在 `data/festivals.json`，本模块只负责解析与匹配。
+//! 新增或调整节日只需修改数据文件，无需改动代码。
+
+use crate::calendar::jieqi::SolarTerm;
+use chrono::{Datelike, Duration, NaiveDate};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+
+/// 节日规则
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum FestivalRule {
+    /// 农历某月某日（闰月不计）
+    Lunar { month: u8, day: u8 },
+    /// 农历某月某日的前一日（如除夕 = 正月初一前一日，可自动适应腊月大小）
+    LunarEve { month: u8, day: u8 },
+    /// 节气所在日，可带日数偏移（如寒食 = 清明前一日）
+    SolarTerm {
+        term: String,
+        #[serde(default)]
+        offset_days: i64,
+    },
+    /// 公历某月某日
+    Gregorian { month: u32, day: u32 },
+}
+
+/// 节日定义
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Festival {
+    pub name: String,
+    /// traditional | public
+    pub category: String,
+    #[serde(flatten)]
+    pub rule: FestivalRule,
+}
+
+/// 农历日期键（月序、日序、是否闰月）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LunarDayKey {
+    pub month: u8,
+    pub day: u8,
+    pub is_leap: bool,
+}
+
+/// 判断节日所需的某日历法事实
+#[derive(Debug, Clone)]
+pub struct DayFacts<'a> {
+    /// 公历日期（本地）
+    pub date: NaiveDate,
+    /// 当日农历
+    pub lunar: Option<LunarDayKey>,
+    /// 次日农历（用于"前一日"类规则）
+    pub next_lunar: Option<LunarDayKey>,
+    /// 附近节气及其本地日期
+    pub solar_terms: &'a [(SolarTerm, NaiveDate)],
+}
+
+static FESTIVALS: Lazy<Vec<Festival>> = Lazy::new(|| {
+    serde_json::from_str(include_str!("../../data/festivals.json"))
+        .expect("festivals.json 解析失败")
+});
+
+/// 获取全部节日定义
+pub fn get_festivals() -> &'static [Festival] {
+    &FESTIVALS
+}
+
+fn lunar_is(key: Option<LunarDayKey>, month: u8, day: u8) -> bool {
+    key.is_some_and(|k| !k.is_leap && k.month == month && k.day == day)
+}
+
+impl FestivalRule {
+    /// 判断某日是否命中该规则
+    pub fn matches(&self, facts: &DayFacts) -> bool {
+        match self {
+            FestivalRule::Lunar { month, day } => lunar_is(facts.lunar, *month, *day),
+            FestivalRule::LunarEve { month, day } => lunar_is(facts.next_lunar, *month, *day),
+            FestivalRule::SolarTerm { term, offset_days } => {
+                let Some(term) = SolarTerm::from_name(term) else {
+                    return false;
+                };
+                facts.solar_terms.iter().any(|(t, date)| {
+                    *t == term && *date + Duration::days(*offset_days) == facts.date
+                })
+            }
+            FestivalRule::Gregorian { month, day } => {
+                facts.date.month() == *month && facts.date.day() == *day
+            }
+        }
+    }
+}
+
+/// 列出某日命中的全部节日名称（按数据文件顺序）
+pub fn festivals_on(facts: &DayFacts) -> Vec<String> {
+    get_festivals()
+        .iter()
+        .filter(|festival| festival.rule.matches(facts))
+        .map(|festival| festival.name.clone())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_festival_data_loads() {
+        let festivals = get_festivals();
+        assert!(festivals.iter().any(|f| f.name == "春节"));
+        assert!(festivals.iter().any(|f| f.name == "除夕"));
+        for festival in festivals {
+            if let FestivalRule::SolarTerm { term, .. } = &festival.rule {
+                assert!(SolarTerm::from_name(term).is_some(), "未知节气: {}", term);
+            }
+        }
+    }
+
+    #[test]
+    fn test_leap_month_does_not_match() {
+        let facts = DayFacts {
+            date: NaiveDate::from_ymd_opt(2025, 7, 1).unwrap(),
+            lunar: Some(LunarDayKey {
+                month: 6,
+                day: 7,
+                is_leap: true,
+            }),
+            next_lunar: None,
+            solar_terms: &[],
+        };
+        let rule = FestivalRule::Lunar { month: 6, day: 7 };
+        assert!(!rule.matches(&facts));
+    }
+}
@@ -0,0 +1,467 @@
+//! iCalendar (RFC 5545) 导出
+//!
+//! 将节气、传统节日、朔望以及（可选的）皇极经世旬/世交界生成 ICS 文本，
+//! 供 Outlook、Apple 日历等订阅。
+//!
+//! - 节气、朔望为定时事件，以 UTC 写出，由客户端换算为本地时间
+//! - 节日、旬/世交界为全天事件，日期按请求时区确定
+//!
+//! 时区可为 IANA 时区：节气、朔望的本地时刻与所属年份逐事件按当时偏移（含夏令时）换算，
+//! 节日按当月月中的偏移推算当月月历。
+
+use crate::algorithm::get_hj_info;
+use crate::astro::delta_t::{DeltaTModel, DeltaTProvider};
+use crate::astro::moon_phase::{find_moon_phases_with, MoonPhase};
+use crate::astro::solar::{jd_to_utc, utc_to_jd};
+use crate::calendar::festival::get_festivals;
+use crate::calendar::jieqi::{find_solar_term_jd_with, find_solar_terms_for_year_with, SolarTerm};
+use crate::calendar::month::compute_month_calendar;
+use crate::calendar::zone::{LocalTimeResolution, TimeZoneSpec};
+use chrono::{DateTime, Datelike, Duration, NaiveDate, NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// 单次导出允许的最大年数
+pub const MAX_ICS_YEARS: i32 = 10;
+
+/// 内容行最大字节数（不含 CRLF）
+const MAX_LINE_OCTETS: usize = 75;
+
+/// ICS 导出参数
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IcsRequest {
+    /// 起始公历年（含）
+    pub start_year: i32,
+    /// 结束公历年（含）
+    pub end_year: i32,
+    /// 时区：IANA 时区（如 Asia/Shanghai）或固定偏移（如 +08:00）
+    pub zone: TimeZoneSpec,
+    /// 观测地纬度，与经度同时提供时写入事件 GEO
+    pub lat_deg: Option<f64>,
+    /// 观测地经度
+    pub lon_deg: Option<f64>,
+    /// 是否包含皇极经世旬/世交界
+    pub include_huangji: bool,
+    /// ΔT 模型，用于节气、朔望时刻与节日所据的农历干支
+    #[serde(default)]
+    pub delta_t_model: DeltaTModel,
+}
+
+/// 事件开始时间
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EventStart {
+    /// 精确时刻
+    Instant(DateTime<Utc>),
+    /// 全天（本地日期）
+    AllDay(NaiveDate),
+}
+
+/// 日历事件
+#[derive(Debug, Clone)]
+pub struct CalendarEvent {
+    pub uid: String,
+    pub summary: String,
+    pub description: String,
+    /// 节气 | 节日 | 月相 | 皇极经世
+    pub category: String,
+    pub start: EventStart,
+}
+
+impl CalendarEvent {
+    /// 本地排序键
+    fn local_start(&self, zone: &TimeZoneSpec) -> NaiveDateTime {
+        match self.start {
+            EventStart::Instant(utc) => zone.to_local(&utc).naive_local(),
+            EventStart::AllDay(date) => date.and_hms_opt(0, 0, 0).unwrap(),
+        }
+    }
+}
+
+fn format_local(utc: DateTime<Utc>, zone: &TimeZoneSpec) -> String {
+    zone.to_local(&utc)
+        .format("%Y-%m-%d %H:%M:%S (UTC%:z)")
+        .to_string()
+}
+
+fn solar_term_events(
+    req: &IcsRequest,
+    delta_t: &dyn DeltaTProvider,
+) -> anyhow::Result<Vec<CalendarEvent>> {
+    let mut events = Vec::new();
+    // 以立春起算，需从上一年取一月的小寒、大寒
+    for year in (req.start_year - 1)..=req.end_year {
+        for point in find_solar_terms_for_year_with(year, delta_t) {
+            let utc = jd_to_utc(point.jd).ok_or_else(|| {
+                anyhow::anyhow!("节气时刻超出范围：{} {}", year, point.term.name())
+            })?;
+            let local_year = req.zone.to_local(&utc).year();
+            if local_year < req.start_year || local_year > req.end_year {
+                continue;
+            }
+            let name = point.term.name();
+            events.push(CalendarEvent {
+                uid: format!(
+                    "jieqi-{}-{:02}@huangji-jingshi",
+                    local_year, point.term as u8
+                ),
+                summary: name.to_string(),
+                description: format!(
+                    "{}：太阳黄经 {}°，交节时刻 {}",
+                    name,
+                    point.longitude,
+                    format_local(utc, &req.zone)
+                ),
+                category: "节气".to_string(),
+                start: EventStart::Instant(utc),
+            });
+        }
+    }
+    Ok(events)
+}
+
+fn festival_events(
+    req: &IcsRequest,
+    delta_t: &dyn DeltaTProvider,
+) -> anyhow::Result<Vec<CalendarEvent>> {
+    let festivals = get_festivals();
+    let mut events = Vec::new();
+    for year in req.start_year..=req.end_year {
+        for month in 1..=12 {
+            let mid_month = NaiveDate::from_ymd_opt(year, month, 15)
+                .and_then(|date| date.and_hms_opt(12, 0, 0))
+                .ok_or_else(|| anyhow::anyhow!("无效年月：{}-{}", year, month))?
+                .and_utc();
+            let tz_offset_minutes = req.zone.offset_at(&mid_month).local_minus_utc() / 60;
+            let calendar = compute_month_calendar(year, month, tz_offset_minutes, None, delta_t)?;
+            for day in &calendar.days {
+                for name in &day.festivals {
+                    // 清明、冬至等与节气同名的节日已由节气事件覆盖
+                    if day
+                        .solar_term
+                        .as_ref()
+                        .is_some_and(|term| &term.name == name)
+                    {
+                        continue;
+                    }
+                    let index = festivals.iter().position(|f| &f.name == name).unwrap_or(0);
+                    events.push(CalendarEvent {
+                        uid: format!(
+                            "festival-{}-{:02}@huangji-jingshi",
+                            day.date.format("%Y%m%d"),
+                            index
+                        ),
+                        summary: name.clone(),
+                        description: format!(
+                            "{}：农历{}{}，{}日",
+                            name, day.lunar_month, day.lunar_day, day.ganzhi_day
+                        ),
+                        category: "节日".to_string(),
+                        start: EventStart::AllDay(day.date),
+                    });
+                }
+            }
+        }
+    }
+    Ok(events)
+}
+
+fn moon_phase_events(
+    req: &IcsRequest,
+    delta_t: &dyn DeltaTProvider,
+) -> anyhow::Result<Vec<CalendarEvent>> {
+    let local_new_year = |year: i32| -> anyhow::Result<f64> {
+        let local = NaiveDate::from_ymd_opt(year, 1, 1)
+            .and_then(|date| date.and_hms_opt(0, 0, 0))
+            .ok_or_else(|| anyhow::anyhow!("无效年份：{}", year))?;
+        let utc = req
+            .zone
+            .from_local(&local, LocalTimeResolution::Compatible)?;
+        Ok(utc_to_jd(&utc))
+    };
+    let start_jd = local_new_year(req.start_year)?;
+    let end_jd = local_new_year(req.end_year + 1)?;
+
+    find_moon_phases_with(start_jd, end_jd, delta_t)
+        .into_iter()
+        .map(|event| {
+            let utc = jd_to_utc(event.jd)
+                .ok_or_else(|| anyhow::anyhow!("朔望时刻超出范围：JD {}", event.jd))?;
+            let (kind, summary) = match event.phase {
+                MoonPhase::New => ("new", "朔（新月）"),
+                MoonPhase::Full => ("full", "望（满月）"),
+                MoonPhase::FirstQuarter => ("first-quarter", "上弦"),
+                MoonPhase::LastQuarter => ("last-quarter", "下弦"),
+            };
+            Ok(CalendarEvent {
+                uid: format!("moon-{}-{}@huangji-jingshi", kind, utc.format("%Y%m%d")),
+                summary: summary.to_string(),
+                description: format!("{}：{}", event.phase.name(), format_local(utc, &req.zone)),
+                category: "月相".to_string(),
+                start: EventStart::Instant(utc),
+            })
+        })
+        .collect()
+}
+
+/// 旬/世交界：经世年以立春为岁首，事件落在交界年立春当日
+fn huangji_events(
+    req: &IcsRequest,
+    delta_t: &dyn DeltaTProvider,
+) -> anyhow::Result<Vec<CalendarEvent>> {
+    let mut events = Vec::new();
+    for year in req.start_year..=req.end_year {
+        let info = get_hj_info(year);
+        let boundaries = [("shi", "世", &info.shi), ("xun", "旬", &info.xun)];
+        for (kind, label, period) in boundaries {
+            if period.start_year != year {
+                continue;
+            }
+            let lichun = jd_to_utc(find_solar_term_jd_with(year, SolarTerm::Lichun, delta_t))
+                .ok_or_else(|| anyhow::anyhow!("节气时刻超出范围：{} 立春", year))?;
+            events.push(CalendarEvent {
+                uid: format!("huangji-{}-{}@huangji-jingshi", kind, year),
+                summary: format!("{}{}始", period.name, label),
+                description: format!(
+                    "皇极经世：{}会 {}运 {}世 {}旬，第{}{}（{}–{}）自本年立春起",
+                    info.hui.name,
+                    info.yun.name,
+                    info.shi.name,
+                    info.xun.name,
+                    period.index,
+                    label,
+                    period.start_year,
+                    period.end_year
+                ),
+                category: "皇极经世".to_string(),
+                start: EventStart::AllDay(req.zone.to_local(&lichun).date_naive()),
+            });
+        }
+    }
+    Ok(events)
+}
+
+/// 收集导出范围内的全部事件，按本地时间排序
+pub fn collect_calendar_events(req: &IcsRequest) -> anyhow::Result<Vec<CalendarEvent>> {
+    if req.end_year < req.start_year {
+        anyhow::bail!(
+            "结束年份不能早于起始年份：{} > {}",
+            req.start_year,
+            req.end_year
+        );
+    }
+    if req.end_year - req.start_year + 1 > MAX_ICS_YEARS {
+        anyhow::bail!("单次最多导出 {} 年", MAX_ICS_YEARS);
+    }
+
+    let delta_t = req.delta_t_model.provider();
+    let mut events = solar_term_events(req, delta_t.as_ref())?;
+    events.extend(festival_events(req, delta_t.as_ref())?);
+    events.extend(moon_phase_events(req, delta_t.as_ref())?);
+    if req.include_huangji {
+        events.extend(huangji_events(req, delta_t.as_ref())?);
+    }
+    events.sort_by_key(|event| event.local_start(&req.zone));
+    Ok(events)
+}
+
+/// 转义 TEXT 值中的特殊字符
+fn escape_text(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace('\n', "\\n")
+}
+
+/// 按 75 字节折行（不拆分 UTF-8 字符），续行以空格开头
+fn fold_line(line: &str, out: &mut String) {
+    let mut width = 0;
+    for ch in line.chars() {
+        if width + ch.len_utf8() > MAX_LINE_OCTETS {
+            out.push_str("\r\n ");
+            width = 1;
+        }
+        out.push(ch);
+        width += ch.len_utf8();
+    }
+    out.push_str("\r\n");
+}
+
+/// 将事件渲染为 ICS 文本
+///
+/// `dtstamp` 为生成时刻，写入每个事件的 DTSTAMP。
+pub fn render_ics(req: &IcsRequest, events: &[CalendarEvent], dtstamp: DateTime<Utc>) -> String {
+    const UTC_FORMAT: &str = "%Y%m%dT%H%M%SZ";
+    let geo = req
+        .lat_deg
+        .zip(req.lon_deg)
+        .map(|(lat, lon)| format!("GEO:{:.6};{:.6}", lat, lon));
+
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        "PRODID:-//Huangji Jingshi//Calendar//ZH".to_string(),
+        "CALSCALE:GREGORIAN".to_string(),
+        "METHOD:PUBLISH".to_string(),
+        "X-WR-CALNAME:皇极经世历".to_string(),
+        format!(
+            "X-WR-CALDESC:{}",
+            escape_text(&format!(
+                "节气、节日与朔望 {}–{}",
+                req.start_year, req.end_year
+            ))
+        ),
+    ];
+    for event in events {
+        lines.push("BEGIN:VEVENT".to_string());
+        lines.push(format!("UID:{}", event.uid));
+        lines.push(format!("DTSTAMP:{}", dtstamp.format(UTC_FORMAT)));
+        match event.start {
+            EventStart::Instant(utc) => {
+                lines.push(format!("DTSTART:{}", utc.format(UTC_FORMAT)));
+            }
+            EventStart::AllDay(date) => {
+                lines.push(format!("DTSTART;VALUE=DATE:{}", date.format("%Y%m%d")));
+                lines.push(format!(
+                    "DTEND;VALUE=DATE:{}",
+                    (date + Duration::days(1)).format("%Y%m%d")
+                ));
+            }
+        }
+        lines.push(format!("SUMMARY:{}", escape_text(&event.summary)));
+        lines.push(format!("DESCRIPTION:{}", escape_text(&event.description)));
+        lines.push(format!("CATEGORIES:{}", escape_text(&event.category)));
+        if let Some(geo) = &geo {
+            lines.push(geo.clone());
+        }
+        lines.push("TRANSP:TRANSPARENT".to_string());
+        lines.push("END:VEVENT".to_string());
+    }
+    lines.push("END:VCALENDAR".to_string());
+
+    let mut out = String::new();
+    for line in &lines {
+        fold_line(line, &mut out);
+    }
+    out
+}
+
+/// 生成 ICS 日历文本
+///
+/// # 参数
+/// - `req`: 年份范围、时区、位置及是否包含旬/世交界
+///
+/// # 返回
+/// - RFC 5545 文本（CRLF 换行）
+pub fn generate_ics(req: &IcsRequest) -> anyhow::Result<String> {
+    let events = collect_calendar_events(req)?;
+    Ok(render_ics(req, &events, Utc::now()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn request(start_year: i32, end_year: i32, include_huangji: bool) -> IcsRequest {
+        IcsRequest {
+            start_year,
+            end_year,
+            zone: TimeZoneSpec::default(),
+            lat_deg: Some(39.9),
+            lon_deg: Some(116.4),
+            include_huangji,
+            delta_t_model: DeltaTModel::default(),
+        }
+    }
+
+    #[test]
+    fn test_collect_events_2025() {
+        let events = collect_calendar_events(&request(2025, 2025, false)).unwrap();
+        let count = |category: &str| events.iter().filter(|e| e.category == category).count();
+        assert_eq!(count("节气"), 24);
+        assert!(count("月相") >= 24);
+
+        let spring = events.iter().find(|e| e.summary == "春节").unwrap();
+        assert_eq!(
+            spring.start,
+            EventStart::AllDay(NaiveDate::from_ymd_opt(2025, 1, 29).unwrap())
+        );
+        // 清明、冬至只以节气事件出现
+        assert_eq!(events.iter().filter(|e| e.summary == "清明").count(), 1);
+
+        let mut uids: Vec<_> = events.iter().map(|e| e.uid.as_str()).collect();
+        uids.sort();
+        uids.dedup();
+        assert_eq!(uids.len(), events.len());
+    }
+
+    #[test]
+    fn test_iana_zone_follows_dst() {
+        let req = IcsRequest {
+            zone: TimeZoneSpec::parse("America/New_York").unwrap(),
+            ..request(2025, 2025, false)
+        };
+        let events = collect_calendar_events(&req).unwrap();
+        let description = |summary: &str| {
+            events
+                .iter()
+                .find(|e| e.summary == summary)
+                .map(|e| e.description.clone())
+                .unwrap()
+        };
+        // 2025 春分 3月20日 09:01 UTC（夏令时 -04:00），冬至 12月21日约 15:03 UTC（-05:00）
+        assert!(description("春分").contains("2025-03-20 05:01"));
+        assert!(description("春分").contains("(UTC-04:00)"));
+        assert!(description("冬至").contains("2025-12-21 10:"));
+        assert!(description("冬至").contains("(UTC-05:00)"));
+    }
+
+    #[test]
+    fn test_huangji_boundaries() {
+        // 十年内必有一次旬交界
+        let events = collect_calendar_events(&request(2020, 2029, true)).unwrap();
+        let boundaries: Vec<_> = events.iter().filter(|e| e.category == "皇极经世").collect();
+        assert!(!boundaries.is_empty());
+        for event in boundaries {
+            let EventStart::AllDay(date) = event.start else {
+                panic!("旬/世交界应为全天事件");
+            };
+            let info = get_hj_info(date.year());
+            assert!(info.xun.start_year == date.year() || info.shi.start_year == date.year());
+        }
+    }
+
+    #[test]
+    fn test_render_ics_format() {
+        let req = request(2025, 2025, false);
+        let events = collect_calendar_events(&req).unwrap();
+        let stamp = Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap();
+        let ics = render_ics(&req, &events, stamp);
+
+        assert!(ics.starts_with("BEGIN:VCALENDAR\r\n"));
+        assert!(ics.ends_with("END:VCALENDAR\r\n"));
+        assert_eq!(ics.matches("BEGIN:VEVENT").count(), events.len());
+        assert!(ics.contains("DTSTART;VALUE=DATE:20250129\r\n"));
+        assert!(ics.contains("GEO:39.900000;116.400000\r\n"));
+        for line in ics.split("\r\n") {
+            assert!(line.len() <= MAX_LINE_OCTETS, "行过长: {}", line);
+        }
+    }
+
+    #[test]
+    fn test_escape_and_fold() {
+        assert_eq!(escape_text("a,b;c\\d\ne"), "a\\,b\\;c\\\\d\\ne");
+        let mut out = String::new();
+        fold_line(&"节".repeat(40), &mut out);
+        let lines: Vec<_> = out.trim_end_matches("\r\n").split("\r\n").collect();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[1].starts_with(' '));
+        assert_eq!(lines.concat().replace(' ', ""), "节".repeat(40));
+    }
+
+    #[test]
+    fn test_rejects_invalid_range() {
+        assert!(collect_calendar_events(&request(2026, 2025, false)).is_err());
+        assert!(collect_calendar_events(&request(2000, 2020, false)).is_err());
+    }
+}
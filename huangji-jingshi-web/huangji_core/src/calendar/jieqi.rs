@@ -2,13 +2,22 @@
 //!
 //! 节气以太阳黄经为准，每 15° 一个节气。
 //! 八字排盘中，年以立春(315°)换年，月以"节"换月。
+//! 太阳视黄经取自 VSOP87D + 章动 + 光行差（见 `astro::solar::apparent_sun`），
+//! 以力学时 (TT) 求解，对外的儒略日均为 UTC。
+//! 求根先外扩出黄经差异号的区间再二分（见 `solve_solar_longitude`），任意年份（含公元前）均不漏根；
+//! `solar_terms_of_year` 按所选时区（固定偏移或 IANA）与历法给出一年内各节气的本地时刻。
 //!
 //! 参考资料：
 //! - 《天文年历》
 //! - 中国科学院紫金山天文台
 
-use crate::astro::solar::{solar_position, datetime_to_jd};
-use chrono::NaiveDateTime;
+use crate::astro::calendar_system::{Calendar, CalendarDate, CalendarSystem};
+use crate::astro::solar::apparent_solar_longitude;
+use crate::astro::delta_t::{DeltaTProvider, EspenakMeeus};
+use crate::astro::time::{jd_tt_to_utc_with, jd_utc_to_tt_with};
+use crate::calendar::zone::TimeZoneSpec;
+use chrono::{FixedOffset, NaiveTime};
+use serde::{Deserialize, Serialize};
 
 /// 二十四节气枚举
 /// 
@@ -125,6 +134,13 @@ impl SolarTerm {
         }
     }
     
+    /// 从中文名称获取节气
+    pub fn from_name(name: &str) -> Option<Self> {
+        (0..24)
+            .filter_map(Self::from_index)
+            .find(|term| term.name() == name.trim())
+    }
+    
     /// 从黄经度数获取当前所处的节气
     pub fn from_longitude(longitude: f64) -> Self {
         let normalized = longitude.rem_euclid(360.0);
@@ -133,6 +149,15 @@ impl SolarTerm {
     }
 }
 
+/// 求根收敛的时间容差（日），约合 0.01 秒
+const TIME_TOLERANCE_DAYS: f64 = 1e-7;
+
+/// 外扩求根区间的步长（日）：太阳每日行约 1°，远小于黄经差折返所需的半年
+const BRACKET_STEP_DAYS: f64 = 4.0;
+
+/// 可求节气的年份范围（天文纪年），即 VSOP87 的适用年代
+pub const SOLAR_TERM_YEARS: std::ops::RangeInclusive<i32> = -4000..=8000;
+
 /// 节气时刻点
 #[derive(Debug, Clone)]
 pub struct SolarTermPoint {
@@ -149,17 +174,60 @@ pub fn current_solar_term(longitude: f64) -> SolarTerm {
     SolarTerm::from_longitude(longitude)
 }
 
+/// 太阳视黄经与 `target_lon` 之差 (度)，归一到 (-180, 180]
+fn longitude_offset(jde: f64, target_lon: f64) -> f64 {
+    let diff = (apparent_solar_longitude(jde) - target_lon).rem_euclid(360.0);
+    if diff > 180.0 {
+        diff - 360.0
+    } else {
+        diff
+    }
+}
+
+/// 求太阳视黄经达到 `target_lon` 的力学时 (TT)
+///
+/// 太阳视黄经单调增加，故黄经差在根两侧各半年内异号。自估计值 `jde_guess` 起按
+/// `BRACKET_STEP_DAYS` 逐步外扩，直至区间一端落后于目标、一端超过目标，再二分至
+/// `TIME_TOLERANCE_DAYS`。步长远小于半年，不会越过节气；估计值偏差不足半年即得所求之根。
+pub fn solve_solar_longitude(target_lon: f64, jde_guess: f64) -> f64 {
+    let mut low = jde_guess;
+    let mut high = jde_guess;
+    while longitude_offset(low, target_lon) > 0.0 {
+        high = low;
+        low -= BRACKET_STEP_DAYS;
+    }
+    while longitude_offset(high, target_lon) <= 0.0 {
+        low = high;
+        high += BRACKET_STEP_DAYS;
+    }
+
+    while high - low > TIME_TOLERANCE_DAYS {
+        let mid = (low + high) / 2.0;
+        if longitude_offset(mid, target_lon) > 0.0 {
+            high = mid;
+        } else {
+            low = mid;
+        }
+    }
+    (low + high) / 2.0
+}
+
 /// 计算某个节气的精确时刻 (使用二分法)
 /// 
+/// 搜索在力学时 (TT) 下进行，结果经 ΔT 换回 UTC。
+/// 
 /// # 参数
 /// - `year`: 公历年份
 /// - `term`: 目标节气
 /// 
 /// # 返回
-/// - 该节气的儒略日
+/// - 该节气的儒略日 (UTC)
 pub fn find_solar_term_jd(year: i32, term: SolarTerm) -> f64 {
-    let target_lon = term.longitude();
-    
+    find_solar_term_jd_with(year, term, &EspenakMeeus)
+}
+
+/// 同 [`find_solar_term_jd`]，UTC 与 TT 按指定 ΔT 提供者换算
+pub fn find_solar_term_jd_with(year: i32, term: SolarTerm, delta_t: &dyn DeltaTProvider) -> f64 {
     // 估算起始搜索点
     // 立春约在2月4日，索引21，每个节气约15天
     let term_idx = term as u8;
@@ -169,44 +237,29 @@ pub fn find_solar_term_jd(year: i32, term: SolarTerm) -> f64 {
         (term_idx as f64 + 3.0) * 15.22
     };
     
-    // 该年立春约在2月4日，JD约 = 年初 + 35天
-    let year_start_jd = datetime_to_jd(&NaiveDateTime::parse_from_str(
-        &format!("{}-01-01 00:00:00", year), "%Y-%m-%d %H:%M:%S"
-    ).unwrap());
-    
-    let mut jd_low = year_start_jd + days_from_lichun - 20.0;
-    let mut jd_high = year_start_jd + days_from_lichun + 20.0;
-    
-    // 二分法查找
-    for _ in 0..50 {
-        let jd_mid = (jd_low + jd_high) / 2.0;
-        let lon = solar_position(jd_mid).ecliptic_longitude;
-        
-        // 处理黄经跨越 0°/360° 的情况
-        let diff = (lon - target_lon).rem_euclid(360.0);
-        let diff = if diff > 180.0 { diff - 360.0 } else { diff };
-        
-        if diff.abs() < 0.0001 {
-            return jd_mid;
-        }
-        
-        if diff > 0.0 {
-            jd_high = jd_mid;
-        } else {
-            jd_low = jd_mid;
-        }
-    }
-    
-    (jd_low + jd_high) / 2.0
+    // 该年立春约在2月4日，JD约 = 年初 + 35天（外推格里历，公元前亦可）
+    let year_start_jd = Calendar::Gregorian.jdn(year, 1, 1) as f64 - 0.5;
+    let lichun_jde = jd_utc_to_tt_with(year_start_jd + 35.0, delta_t);
+
+    let jde = solve_solar_longitude(term.longitude(), lichun_jde + days_from_lichun);
+    jd_tt_to_utc_with(jde, delta_t)
 }
 
 /// 查找某年的所有节气时刻
 pub fn find_solar_terms_for_year(year: i32) -> Vec<SolarTermPoint> {
+    find_solar_terms_for_year_with(year, &EspenakMeeus)
+}
+
+/// 同 [`find_solar_terms_for_year`]，使用指定 ΔT 提供者
+pub fn find_solar_terms_for_year_with(
+    year: i32,
+    delta_t: &dyn DeltaTProvider,
+) -> Vec<SolarTermPoint> {
     let mut terms = Vec::with_capacity(24);
     
     for i in 0..24 {
         if let Some(term) = SolarTerm::from_index(i) {
-            let jd = find_solar_term_jd(year, term);
+            let jd = find_solar_term_jd_with(year, term, delta_t);
             terms.push(SolarTermPoint {
                 term,
                 jd,
@@ -224,12 +277,18 @@ pub fn find_solar_terms_for_year(year: i32) -> Vec<SolarTermPoint> {
 /// 查找给定时刻之后的下一个"节"
 /// 
 /// # 参数
-/// - `jd`: 当前儒略日
+/// - `jd`: 当前儒略日 (UTC)
 /// 
 /// # 返回
-/// - (下一个节的儒略日, 节气)
+/// - (下一个节的儒略日 (UTC), 节气)
 pub fn find_next_jie(jd: f64) -> (f64, SolarTerm) {
-    let current_lon = solar_position(jd).ecliptic_longitude;
+    find_next_jie_with(jd, &EspenakMeeus)
+}
+
+/// 同 [`find_next_jie`]，使用指定 ΔT 提供者
+pub fn find_next_jie_with(jd: f64, delta_t: &dyn DeltaTProvider) -> (f64, SolarTerm) {
+    let jde = jd_utc_to_tt_with(jd, delta_t);
+    let current_lon = apparent_solar_longitude(jde);
     let current_term = SolarTerm::from_longitude(current_lon);
     
     // 找下一个"节"
@@ -242,30 +301,10 @@ pub fn find_next_jie(jd: f64) -> (f64, SolarTerm) {
             let lon_diff = (target_lon - current_lon).rem_euclid(360.0);
             let approx_days = lon_diff / 0.9856; // 太阳每天移动约 0.9856°
             
-            let approx_jd = jd + approx_days;
+            let approx_jde = jde + approx_days;
             
-            // 使用二分法精确查找
-            let mut jd_low = approx_jd - 5.0;
-            let mut jd_high = approx_jd + 5.0;
-            
-            for _ in 0..30 {
-                let jd_mid = (jd_low + jd_high) / 2.0;
-                let lon = solar_position(jd_mid).ecliptic_longitude;
-                let diff = (lon - target_lon).rem_euclid(360.0);
-                let diff = if diff > 180.0 { diff - 360.0 } else { diff };
-                
-                if diff.abs() < 0.0001 {
-                    return (jd_mid, term);
-                }
-                
-                if diff > 0.0 {
-                    jd_high = jd_mid;
-                } else {
-                    jd_low = jd_mid;
-                }
-            }
-            
-            return ((jd_low + jd_high) / 2.0, term);
+            let found = solve_solar_longitude(target_lon, approx_jde);
+            return (jd_tt_to_utc_with(found, delta_t), term);
         }
         next_idx = (next_idx + 1) % 24;
     }
@@ -274,12 +313,18 @@ pub fn find_next_jie(jd: f64) -> (f64, SolarTerm) {
 /// 查找给定时刻之前的上一个"节"
 /// 
 /// # 参数
-/// - `jd`: 当前儒略日
+/// - `jd`: 当前儒略日 (UTC)
 /// 
 /// # 返回
-/// - (上一个节的儒略日, 节气)
+/// - (上一个节的儒略日 (UTC), 节气)
 pub fn find_prev_jie(jd: f64) -> (f64, SolarTerm) {
-    let current_lon = solar_position(jd).ecliptic_longitude;
+    find_prev_jie_with(jd, &EspenakMeeus)
+}
+
+/// 同 [`find_prev_jie`]，使用指定 ΔT 提供者
+pub fn find_prev_jie_with(jd: f64, delta_t: &dyn DeltaTProvider) -> (f64, SolarTerm) {
+    let jde = jd_utc_to_tt_with(jd, delta_t);
+    let current_lon = apparent_solar_longitude(jde);
     
     // 找上一个"节"
     let mut prev_idx = ((SolarTerm::from_longitude(current_lon) as i8 - 1 + 24) % 24) as u8;
@@ -290,34 +335,148 @@ pub fn find_prev_jie(jd: f64) -> (f64, SolarTerm) {
             let lon_diff = (current_lon - target_lon).rem_euclid(360.0);
             let approx_days = lon_diff / 0.9856;
             
-            let approx_jd = jd - approx_days;
-            
-            let mut jd_low = approx_jd - 5.0;
-            let mut jd_high = approx_jd + 5.0;
-            
-            for _ in 0..30 {
-                let jd_mid = (jd_low + jd_high) / 2.0;
-                let lon = solar_position(jd_mid).ecliptic_longitude;
-                let diff = (lon - target_lon).rem_euclid(360.0);
-                let diff = if diff > 180.0 { diff - 360.0 } else { diff };
-                
-                if diff.abs() < 0.0001 {
-                    return (jd_mid, term);
-                }
-                
-                if diff > 0.0 {
-                    jd_high = jd_mid;
-                } else {
-                    jd_low = jd_mid;
-                }
-            }
+            let approx_jde = jde - approx_days;
             
-            return ((jd_low + jd_high) / 2.0, term);
+            let found = solve_solar_longitude(target_lon, approx_jde);
+            return (jd_tt_to_utc_with(found, delta_t), term);
         }
         prev_idx = ((prev_idx as i8 - 1 + 24) % 24) as u8;
     }
 }
 
+/// 给定时刻之后的下一个节气
+///
+/// # 参数
+/// - `jd`: 儒略日 (UTC)
+pub fn next_solar_term(jd: f64) -> SolarTermPoint {
+    next_solar_term_with(jd, &EspenakMeeus)
+}
+
+/// 同 [`next_solar_term`]，使用指定 ΔT 提供者
+pub fn next_solar_term_with(jd: f64, delta_t: &dyn DeltaTProvider) -> SolarTermPoint {
+    let jde = jd_utc_to_tt_with(jd, delta_t);
+    let current_lon = apparent_solar_longitude(jde);
+    let term = SolarTerm::from_index((SolarTerm::from_longitude(current_lon) as u8 + 1) % 24)
+        .expect("节气索引在 0-23 之内");
+    let approx_days = (term.longitude() - current_lon).rem_euclid(360.0) / 0.9856;
+    let found = solve_solar_longitude(term.longitude(), jde + approx_days);
+    SolarTermPoint {
+        term,
+        jd: jd_tt_to_utc_with(found, delta_t),
+        longitude: term.longitude(),
+    }
+}
+
+/// 节气时刻（按所选时区与历法）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LocalSolarTerm {
+    pub name: String,
+    /// 太阳黄经 (度)
+    pub longitude: f64,
+    pub is_jie: bool,
+    /// 儒略日 (UTC)
+    pub jd: f64,
+    /// 儒略日 (TT)
+    pub jd_tt: f64,
+    /// 交节时当地的 UTC 偏移（秒，东为正）
+    pub utc_offset_seconds: i32,
+    /// 本地日期
+    pub date: CalendarDate,
+    /// 本地时刻（精确到秒）
+    pub time: NaiveTime,
+    /// ISO 8601 本地时刻，年份为天文纪年，如 -0720-02-05T10:21:37+08:00
+    pub local: String,
+    /// 中文表述，如 公元前721年2月5日（儒略历）10:21:37
+    pub label: String,
+}
+
+/// 一个本地历年（元旦至除夕）内交节的全部节气
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SolarTermYear {
+    /// 天文纪年：0 = 公元前 1 年
+    pub year: i32,
+    pub zone: TimeZoneSpec,
+    /// 历法，见 `CalendarSystem::name`
+    pub calendar: String,
+    /// 按时间先后排列，通常 24 个
+    pub terms: Vec<LocalSolarTerm>,
+    /// 实际使用的 ΔT 提供者
+    pub delta_t_provider: String,
+}
+
+fn local_solar_term(
+    point: &SolarTermPoint,
+    zone: &TimeZoneSpec,
+    calendar: &CalendarSystem,
+    delta_t: &dyn DeltaTProvider,
+) -> anyhow::Result<LocalSolarTerm> {
+    let utc_offset_seconds = zone.offset_seconds_at_jd(point.jd)?;
+    let (date, time) = calendar.date_time_of_jd(point.jd + utc_offset_seconds as f64 / 86400.0);
+    let offset = FixedOffset::east_opt(utc_offset_seconds).expect("时区偏移不足一日");
+    let clock = time.format("%H:%M:%S");
+    Ok(LocalSolarTerm {
+        name: point.term.name().to_string(),
+        longitude: point.longitude,
+        is_jie: point.term.is_jie(),
+        jd: point.jd,
+        jd_tt: jd_utc_to_tt_with(point.jd, delta_t),
+        utc_offset_seconds,
+        date,
+        time,
+        local: format!("{}T{}{}", date, clock, offset),
+        label: format!("{} {}", date.label_zh(), clock),
+    })
+}
+
+/// 某年的全部节气，按本地日期归年
+///
+/// 与 [`find_solar_terms_for_year`] 的立春年不同，此处以所选历法的元旦至除夕为一年，
+/// 节气按交节时刻在 `zone` 下的本地日期归入；IANA 时区按当时的历史偏移换算。
+///
+/// # 参数
+/// - `year`: 天文纪年（0 = 公元前 1 年，-720 = 公元前 721 年），须在 [`SOLAR_TERM_YEARS`] 内
+/// - `zone`: 时区
+/// - `calendar`: 本地日期所用历法
+/// - `delta_t`: ΔT 提供者，交节时刻在 TT 下求得后按其换回 UTC
+pub fn solar_terms_of_year(
+    year: i32,
+    zone: &TimeZoneSpec,
+    calendar: &CalendarSystem,
+    delta_t: &dyn DeltaTProvider,
+) -> anyhow::Result<SolarTermYear> {
+    if !SOLAR_TERM_YEARS.contains(&year) {
+        anyhow::bail!(
+            "年份超出范围：{}（支持 {} 至 {}）",
+            year,
+            SOLAR_TERM_YEARS.start(),
+            SOLAR_TERM_YEARS.end()
+        );
+    }
+    let first_jdn = calendar.date(year, 1, 1)?.jdn();
+    let next_first_jdn = calendar.date(year + 1, 1, 1)?.jdn();
+
+    // 时区偏移不足一日：自元旦前一日逐个求节气，至次年元旦后一日止
+    let end = next_first_jdn as f64 + 0.5;
+    let mut terms = Vec::with_capacity(24);
+    let mut point = next_solar_term_with(first_jdn as f64 - 1.5, delta_t);
+    while point.jd < end {
+        let local = local_solar_term(&point, zone, calendar, delta_t)?;
+        if (first_jdn..next_first_jdn).contains(&local.date.jdn()) {
+            terms.push(local);
+        }
+        // 相邻节气相隔约 15 日，跨过一日即不会重复求得同一节气
+        point = next_solar_term_with(point.jd + 1.0, delta_t);
+    }
+
+    Ok(SolarTermYear {
+        year,
+        zone: *zone,
+        calendar: calendar.name(),
+        terms,
+        delta_t_provider: delta_t.name().to_string(),
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -339,6 +498,143 @@ mod tests {
         assert!(!SolarTerm::Chunfen.is_jie()); // 春分是气
     }
 
+    #[test]
+    fn test_find_solar_term_jd_2024() {
+        use crate::astro::solar::jd_to_utc;
+        use chrono::{Datelike, Timelike};
+
+        // 2024 立春: 2月4日 16:27 北京时间 (08:27 UTC)
+        let lichun = jd_to_utc(find_solar_term_jd(2024, SolarTerm::Lichun)).unwrap();
+        assert_eq!((lichun.month(), lichun.day(), lichun.hour()), (2, 4, 8));
+
+        // 2024 冬至: 12月21日 17:21 北京时间 (09:21 UTC)
+        let dongzhi = jd_to_utc(find_solar_term_jd(2024, SolarTerm::Dongzhi)).unwrap();
+        assert_eq!((dongzhi.month(), dongzhi.day(), dongzhi.hour()), (12, 21, 9));
+    }
+
+    #[test]
+    fn test_historical_term_applies_delta_t() {
+        use crate::astro::time::{delta_t, jd_utc_to_tt};
+
+        // 公元 1000 年 ΔT 约 26 分钟：UTC 结果换回 TT 后黄经须恰为 270°
+        let jd = find_solar_term_jd(1000, SolarTerm::Dongzhi);
+        let lon = apparent_solar_longitude(jd_utc_to_tt(jd));
+        assert!((lon - 270.0).abs() < 1e-5, "λ={}", lon);
+        assert!(delta_t(jd) > 1500.0);
+    }
+
+    #[test]
+    fn test_solver_brackets_far_guesses() {
+        use crate::astro::time::jd_utc_to_tt;
+
+        // 估计值偏离数月仍收敛到离其最近的那次交节
+        let jde = jd_utc_to_tt(find_solar_term_jd(2024, SolarTerm::Dongzhi));
+        for guess in [jde - 150.0, jde - 3.0, jde, jde + 0.5, jde + 120.0] {
+            let found = solve_solar_longitude(270.0, guess);
+            assert!(
+                (found - jde).abs() < 1e-6,
+                "guess={} found={}",
+                guess,
+                found
+            );
+        }
+        // 跨越 0° 的春分
+        let chunfen = solve_solar_longitude(0.0, jde + 80.0);
+        assert!(longitude_offset(chunfen, 0.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn test_bce_year_terms() {
+        // 公元前 721 年（天文纪年 -720）
+        assert!(find_solar_term_jd(-720, SolarTerm::Lichun).is_finite());
+
+        let year = solar_terms_of_year(
+            -720,
+            &TimeZoneSpec::default(),
+            &CalendarSystem::default(),
+            &EspenakMeeus,
+        )
+        .unwrap();
+        assert_eq!(year.terms.len(), 24);
+        for pair in year.terms.windows(2) {
+            assert!(pair[1].jd - pair[0].jd > 13.0 && pair[1].jd - pair[0].jd < 17.0);
+            assert_eq!(
+                (pair[0].longitude + 15.0).rem_euclid(360.0),
+                pair[1].longitude
+            );
+        }
+        for term in &year.terms {
+            assert_eq!(term.date.year, -720);
+            assert_eq!(term.date.calendar, Calendar::Julian);
+            let offset = longitude_offset(term.jd_tt, term.longitude);
+            assert!(offset.abs() < 1e-5, "{} Δλ={}", term.name, offset);
+            assert!(term.local.starts_with("-0720-"), "{}", term.local);
+            assert!(term.label.starts_with("公元前721年"), "{}", term.label);
+        }
+        assert!(solar_terms_of_year(
+            -5000,
+            &TimeZoneSpec::default(),
+            &CalendarSystem::default(),
+            &EspenakMeeus
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_local_terms_follow_zone() {
+        let calendar = CalendarSystem::default();
+        let find = |zone: &str, year: i32, name: &str| {
+            solar_terms_of_year(year, &TimeZoneSpec::parse(zone).unwrap(), &calendar, &EspenakMeeus)
+                .unwrap()
+                .terms
+                .into_iter()
+                .find(|t| t.name == name)
+                .unwrap()
+        };
+
+        // 2024 立春：北京时间 2 月 4 日 16:27
+        let lichun = find("Asia/Shanghai", 2024, "立春");
+        assert_eq!((lichun.date.month, lichun.date.day), (2, 4));
+        assert_eq!(lichun.time.format("%H:%M").to_string(), "16:27");
+        assert!(lichun.local.starts_with("2024-02-04T16:27:"));
+        assert!(lichun.local.ends_with("+08:00"));
+
+        // 2024 春分 03:06 UTC，纽约夏令时 (-04:00) 尚在 3 月 19 日
+        let chunfen = find("America/New_York", 2024, "春分");
+        assert_eq!((chunfen.date.month, chunfen.date.day), (3, 19));
+        assert_eq!(chunfen.utc_offset_seconds, -4 * 3600);
+        assert_eq!(find("+08:00", 2024, "春分").date.day, 20);
+
+        // 1988 年中国夏令时：夏至按 Asia/Shanghai 比 +08:00 晚一小时
+        let dst = find("Asia/Shanghai", 1988, "夏至");
+        let fixed = find("+08:00", 1988, "夏至");
+        assert_eq!(dst.jd, fixed.jd);
+        assert_eq!(dst.utc_offset_seconds - fixed.utc_offset_seconds, 3600);
+        assert_eq!((dst.time - fixed.time).num_seconds(), 3600);
+    }
+
+    #[test]
+    fn test_terms_follow_delta_t_provider() {
+        use crate::astro::delta_t::FixedDeltaT;
+
+        let terms = |delta_t: &dyn DeltaTProvider| {
+            solar_terms_of_year(2024, &TimeZoneSpec::default(), &CalendarSystem::default(), delta_t)
+                .unwrap()
+        };
+        let polynomial = terms(&EspenakMeeus);
+        let fixed = terms(&FixedDeltaT { seconds: 0.0 });
+        assert_eq!(polynomial.delta_t_provider, "espenak_meeus");
+        assert_eq!(fixed.delta_t_provider, "fixed");
+
+        // 交节的 TT 时刻不变，ΔT = 0 时 UTC 即 TT，晚约 74 秒
+        for (a, b) in polynomial.terms.iter().zip(&fixed.terms) {
+            assert!((a.jd_tt - b.jd_tt).abs() * 86400.0 < 0.1);
+            assert!((b.jd - b.jd_tt).abs() * 86400.0 < 0.1);
+            let shift = (b.jd - a.jd) * 86400.0;
+            assert!((shift - EspenakMeeus.delta_t(a.jd)).abs() < 0.1, "{} shift={}", a.name, shift);
+        }
+    }
+
     #[test]
     fn test_from_longitude() {
         assert_eq!(SolarTerm::from_longitude(315.0), SolarTerm::Lichun);
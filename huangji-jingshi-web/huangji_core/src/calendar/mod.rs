@@ -4,8 +4,22 @@
 
 pub mod jieqi;
 pub mod ganzhi;
+pub mod sexagenary;
 pub mod time_rule;
+pub mod festival;
+pub mod month;
+pub mod seasonal;
+pub mod ics;
+pub mod zeri;
+pub mod zone;
 
 pub use jieqi::*;
 pub use ganzhi::*;
+pub use sexagenary::*;
 pub use time_rule::*;
+pub use festival::*;
+pub use month::*;
+pub use seasonal::*;
+pub use ics::*;
+pub use zeri::*;
+pub use zone::*;
@@ -0,0 +1,363 @@
+//! 月历模块
+//!
+//! 按公历月份一次性生成逐日历表：农历日期、日干支、节气精确时刻、
+//! 建除十二神与传统节日，避免前端逐日调用 `/api/sky-and-fortune`。
+//! 给出观测地时附逐日日出日落。
+
+use crate::astro::coords::AU_KM;
+use crate::astro::delta_t::DeltaTProvider;
+use crate::astro::observer::Observer;
+use crate::astro::rise_set::{rise_set_transit, ApparentPlace, STANDARD_REFRACTION_DEG};
+use crate::astro::solar::{datetime_to_jd, jd_to_utc};
+use crate::astro::solar_time::SolarTimePolicy;
+use crate::calendar::festival::{festivals_on, DayFacts, LunarDayKey};
+use crate::calendar::jieqi::{find_solar_terms_for_year_with, SolarTerm};
+use crate::calendar::seasonal::{
+    seasonal_marks_on, seasonal_periods, SeasonalMark, SeasonalPeriod,
+};
+use crate::calendar::zone::TimeZoneSpec;
+use crate::lunar::{compute_lunar, LunarInfo};
+use crate::sky::{body_equatorial, SkyBody};
+use chrono::{DateTime, Datelike, FixedOffset, NaiveDate, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+
+/// 节气时刻（本地时间）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SolarTermTime {
+    pub name: String,
+    /// 太阳黄经 (度)
+    pub longitude: f64,
+    /// 儒略日
+    pub jd: f64,
+    /// 交节时刻（按请求时区）
+    pub local_time: DateTime<FixedOffset>,
+}
+
+/// 月历中的一天
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CalendarDay {
+    pub date: NaiveDate,
+    /// ISO 星期（1=周一 ... 7=周日）
+    pub weekday: u32,
+    pub lunar_month: String,
+    pub lunar_day: String,
+    pub lunar_month_number: u8,
+    pub lunar_day_number: u8,
+    pub is_leap_month: bool,
+    pub ganzhi_day: String,
+    pub twelve_officer: String,
+    /// 当日交节的节气（无则为 None）
+    pub solar_term: Option<SolarTermTime>,
+    pub festivals: Vec<String>,
+    /// 数伏、数九、入梅出梅、社日
+    pub seasonal: Vec<SeasonalMark>,
+    /// 日出（按请求时区），仅在指定观测者时给出；极昼极夜为 None
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sunrise: Option<DateTime<FixedOffset>>,
+    /// 日落（按请求时区），同上
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sunset: Option<DateTime<FixedOffset>>,
+}
+
+/// 公历月历
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MonthCalendar {
+    pub year: i32,
+    pub month: u32,
+    pub tz_offset_minutes: i32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub observer: Option<Observer>,
+    pub days: Vec<CalendarDay>,
+    /// 与本月相交的杂节气时段
+    pub seasonal_periods: Vec<SeasonalPeriod>,
+    /// 实际使用的 ΔT 提供者
+    pub delta_t_provider: String,
+}
+
+fn lunar_key(info: &LunarInfo) -> LunarDayKey {
+    LunarDayKey {
+        month: info.lunar_month_number,
+        day: info.lunar_day_number,
+        is_leap: info.is_leap_month,
+    }
+}
+
+/// 取某本地日期正午的农历与干支信息
+///
+/// 以本地正午计算，避免 UTC 跨日；经度取时区中央经线，不做真太阳时校正。
+pub(crate) fn lunar_at_local_noon(
+    date: NaiveDate,
+    tz_offset_minutes: i32,
+    delta_t: &dyn DeltaTProvider,
+) -> anyhow::Result<LunarInfo> {
+    let offset = FixedOffset::east_opt(tz_offset_minutes * 60)
+        .ok_or_else(|| anyhow::anyhow!("无效时区偏移：tz_offset_minutes={}", tz_offset_minutes))?;
+    let noon = offset
+        .from_local_datetime(&date.and_hms_opt(12, 0, 0).unwrap())
+        .single()
+        .ok_or_else(|| anyhow::anyhow!("无法解析本地日期：{}", date))?;
+    let central_meridian = 15.0 * tz_offset_minutes as f64 / 60.0;
+    compute_lunar(
+        &noon.with_timezone(&Utc),
+        &TimeZoneSpec::Fixed(offset),
+        central_meridian,
+        SolarTimePolicy::Meridian,
+        delta_t,
+    )
+}
+
+/// 计算覆盖某公历年的节气时刻（本地）
+///
+/// `find_solar_terms_for_year` 以立春起算，故同时取上一年以覆盖一月的小寒、大寒。
+fn solar_terms_around(
+    year: i32,
+    offset: &FixedOffset,
+    delta_t: &dyn DeltaTProvider,
+) -> Vec<(SolarTerm, SolarTermTime)> {
+    [year - 1, year]
+        .into_iter()
+        .flat_map(|y| find_solar_terms_for_year_with(y, delta_t))
+        .filter_map(|point| {
+            let utc = jd_to_utc(point.jd)?;
+            Some((
+                point.term,
+                SolarTermTime {
+                    name: point.term.name().to_string(),
+                    longitude: point.longitude,
+                    jd: point.jd,
+                    local_time: utc.with_timezone(offset),
+                },
+            ))
+        })
+        .collect()
+}
+
+/// 某本地日期的日出、日落（太阳上缘切于视地平，标准折射 34'）
+fn sunrise_sunset(
+    date: NaiveDate,
+    offset: &FixedOffset,
+    observer: &Observer,
+    delta_t: &dyn DeltaTProvider,
+) -> (Option<DateTime<FixedOffset>>, Option<DateTime<FixedOffset>>) {
+    let local_midnight = date.and_hms_opt(0, 0, 0).unwrap();
+    let start_jd = datetime_to_jd(&local_midnight) - offset.local_minus_utc() as f64 / 86400.0;
+    let sun = |jd_tt: f64| {
+        let position = body_equatorial(SkyBody::Sun, jd_tt);
+        ApparentPlace {
+            ra_deg: position.ra_deg,
+            dec_deg: position.dec_deg,
+            distance_km: position.distance_au * AU_KM,
+            radius_km: SkyBody::Sun.radius_km(),
+        }
+    };
+    let events = rise_set_transit(sun, observer, start_jd, STANDARD_REFRACTION_DEG, delta_t);
+    (
+        events.rise.map(|t| t.with_timezone(offset)),
+        events.set.map(|t| t.with_timezone(offset)),
+    )
+}
+
+/// 生成某公历月的逐日历表
+///
+/// 农历、干支与节日只取决于时区；观测地仅用于日出日落。
+///
+/// # 参数
+/// - `year`, `month`: 公历年月
+/// - `tz_offset_minutes`: 时区偏移（分钟），东为正 UTC+8=+480
+/// - `observer`: 观测地，给出时附逐日日出日落
+/// - `delta_t`: ΔT 提供者，用于节气时刻、干支（太阳黄经）与日出日落
+///
+/// # 返回
+/// - `MonthCalendar`: 当月每日的农历、干支、节气、建除、节日与杂节气
+pub fn compute_month_calendar(
+    year: i32,
+    month: u32,
+    tz_offset_minutes: i32,
+    observer: Option<Observer>,
+    delta_t: &dyn DeltaTProvider,
+) -> anyhow::Result<MonthCalendar> {
+    let offset = FixedOffset::east_opt(tz_offset_minutes * 60)
+        .ok_or_else(|| anyhow::anyhow!("无效时区偏移：tz_offset_minutes={}", tz_offset_minutes))?;
+    if let Some(observer) = &observer {
+        if !(-90.0..=90.0).contains(&observer.lat_deg) {
+            anyhow::bail!("纬度超出范围：{}", observer.lat_deg);
+        }
+        if !(-180.0..=180.0).contains(&observer.lon_deg) {
+            anyhow::bail!("经度超出范围：{}", observer.lon_deg);
+        }
+    }
+    let first = NaiveDate::from_ymd_opt(year, month, 1)
+        .ok_or_else(|| anyhow::anyhow!("无效年月：{}-{}", year, month))?;
+    let next_first = if month == 12 {
+        NaiveDate::from_ymd_opt(year + 1, 1, 1)
+    } else {
+        NaiveDate::from_ymd_opt(year, month + 1, 1)
+    }
+    .ok_or_else(|| anyhow::anyhow!("无效年月：{}-{}", year, month))?;
+
+    let lunar_at = |date: NaiveDate| lunar_at_local_noon(date, tz_offset_minutes, delta_t);
+
+    let terms = solar_terms_around(year, &offset, delta_t);
+    let term_dates: Vec<(SolarTerm, NaiveDate)> = terms
+        .iter()
+        .map(|(term, time)| (*term, time.local_time.date_naive()))
+        .collect();
+
+    let last = next_first.pred_opt().unwrap();
+    let periods: Vec<SeasonalPeriod> = seasonal_periods(year, tz_offset_minutes, delta_t)?
+        .into_iter()
+        .filter(|p| p.start <= last && p.end >= first)
+        .collect();
+
+    let dates: Vec<NaiveDate> = first.iter_days().take_while(|d| *d < next_first).collect();
+    let lunars = dates
+        .iter()
+        .map(|date| lunar_at(*date))
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    // 月末次日仅用于"前一日"类节日，超出农历库范围时忽略
+    let lunar_after = lunar_at(next_first).ok();
+
+    let days = dates
+        .iter()
+        .zip(&lunars)
+        .enumerate()
+        .map(|(i, (date, lunar))| {
+            let next_lunar = lunars.get(i + 1).or(lunar_after.as_ref());
+            let facts = DayFacts {
+                date: *date,
+                lunar: Some(lunar_key(lunar)),
+                next_lunar: next_lunar.map(lunar_key),
+                solar_terms: &term_dates,
+            };
+            let solar_term = terms
+                .iter()
+                .find(|(_, time)| time.local_time.date_naive() == *date)
+                .map(|(_, time)| time.clone());
+            let (sunrise, sunset) = observer
+                .as_ref()
+                .map(|observer| sunrise_sunset(*date, &offset, observer, delta_t))
+                .unwrap_or_default();
+
+            CalendarDay {
+                date: *date,
+                weekday: date.weekday().number_from_monday(),
+                lunar_month: lunar.lunar_month.clone(),
+                lunar_day: lunar.lunar_day.clone(),
+                lunar_month_number: lunar.lunar_month_number,
+                lunar_day_number: lunar.lunar_day_number,
+                is_leap_month: lunar.is_leap_month,
+                ganzhi_day: lunar.ganzhi_day.clone(),
+                twelve_officer: lunar.twelve_officer.clone(),
+                solar_term,
+                festivals: festivals_on(&facts),
+                seasonal: seasonal_marks_on(&periods, *date),
+                sunrise,
+                sunset,
+            }
+        })
+        .collect();
+
+    Ok(MonthCalendar {
+        year,
+        month,
+        tz_offset_minutes,
+        observer,
+        days,
+        seasonal_periods: periods,
+        delta_t_provider: delta_t.name().to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::astro::delta_t::EspenakMeeus;
+    use chrono::Timelike;
+
+    fn day(calendar: &MonthCalendar, d: u32) -> &CalendarDay {
+        &calendar.days[(d - 1) as usize]
+    }
+
+    #[test]
+    fn test_month_calendar_spring_festival_2025() {
+        let calendar = compute_month_calendar(2025, 1, 480, None, &EspenakMeeus).unwrap();
+        assert_eq!(calendar.days.len(), 31);
+
+        assert_eq!(day(&calendar, 1).festivals, vec!["元旦"]);
+        assert_eq!(day(&calendar, 1).weekday, 3);
+        assert!(day(&calendar, 7).festivals.contains(&"腊八".to_string()));
+        assert!(day(&calendar, 28).festivals.contains(&"除夕".to_string()));
+        assert!(day(&calendar, 29).festivals.contains(&"春节".to_string()));
+        assert_eq!(day(&calendar, 29).lunar_month_number, 1);
+        assert_eq!(day(&calendar, 29).lunar_day_number, 1);
+    }
+
+    #[test]
+    fn test_month_calendar_solar_terms_local_time() {
+        let calendar = compute_month_calendar(2025, 1, 480, None, &EspenakMeeus).unwrap();
+        let xiaohan = day(&calendar, 5).solar_term.as_ref().expect("小寒");
+        assert_eq!(xiaohan.name, "小寒");
+        assert_eq!(xiaohan.local_time.hour(), 10);
+
+        let dahan = day(&calendar, 20).solar_term.as_ref().expect("大寒");
+        assert_eq!(dahan.name, "大寒");
+
+        let term_days = calendar
+            .days
+            .iter()
+            .filter(|d| d.solar_term.is_some())
+            .count();
+        assert_eq!(term_days, 2);
+    }
+
+    #[test]
+    fn test_month_calendar_mid_autumn_and_dragon_boat() {
+        let october = compute_month_calendar(2025, 10, 480, None, &EspenakMeeus).unwrap();
+        assert!(day(&october, 1).festivals.contains(&"国庆节".to_string()));
+        assert!(day(&october, 6).festivals.contains(&"中秋".to_string()));
+
+        let may = compute_month_calendar(2025, 5, 480, None, &EspenakMeeus).unwrap();
+        assert!(day(&may, 31).festivals.contains(&"端午".to_string()));
+    }
+
+    #[test]
+    fn test_month_calendar_seasonal_marks() {
+        let july = compute_month_calendar(2025, 7, 480, None, &EspenakMeeus).unwrap();
+        let chufu = &day(&july, 20).seasonal;
+        assert_eq!(chufu.len(), 1);
+        assert_eq!((chufu[0].name.as_str(), chufu[0].day), ("初伏", 1));
+        assert!(july.seasonal_periods.iter().any(|p| p.name == "中伏"));
+        assert!(day(&july, 19).seasonal.iter().all(|m| m.group != "三伏"));
+    }
+
+    #[test]
+    fn test_month_calendar_sunrise_sunset() {
+        // 北京 2025-06-21：日出约 04:46，日落约 19:46（北京时间）
+        let beijing = Observer::new(39.9042, 116.4074);
+        let june = compute_month_calendar(2025, 6, 480, Some(beijing), &EspenakMeeus).unwrap();
+        let solstice = day(&june, 21);
+        let sunrise = solstice.sunrise.expect("日出");
+        let sunset = solstice.sunset.expect("日落");
+        assert_eq!((sunrise.hour(), sunrise.minute() / 10), (4, 4));
+        assert_eq!((sunset.hour(), sunset.minute() / 10), (19, 4));
+        assert_eq!(sunrise.date_naive(), solstice.date);
+
+        let plain = compute_month_calendar(2025, 6, 480, None, &EspenakMeeus).unwrap();
+        assert!(plain.days.iter().all(|d| d.sunrise.is_none()));
+        assert_eq!(plain.days[20].ganzhi_day, solstice.ganzhi_day);
+        assert_eq!(plain.delta_t_provider, "espenak_meeus");
+    }
+
+    #[test]
+    fn test_month_calendar_rejects_invalid_month() {
+        assert!(compute_month_calendar(2025, 13, 480, None, &EspenakMeeus).is_err());
+        assert!(compute_month_calendar(
+            2025,
+            1,
+            480,
+            Some(Observer::new(91.0, 0.0)),
+            &EspenakMeeus
+        )
+        .is_err());
+    }
+}
@@ -0,0 +1,232 @@
+//! 杂节气（数伏、数九、入梅出梅、社日）
+//!
+//! 这些日期由节气时刻与日干支共同决定：
+//! - 三伏：夏至起第三个庚日为初伏，第四个庚日为中伏，立秋起第一个庚日为末伏
+//! - 九九：冬至起每九日为一九，共九九八十一日
+//! - 入梅/出梅：芒种起第一个丙日入梅，小暑起第一个未日出梅
+//! - 社日：立春起第五个戊日为春社，立秋起第五个戊日为秋社
+//!
+//! 计数均含节气当日（节气当日即为所求干支时记为第一个）。
+
+use crate::astro::delta_t::DeltaTProvider;
+use crate::astro::solar::jd_to_utc;
+use crate::calendar::jieqi::{find_solar_term_jd_with, SolarTerm};
+use crate::calendar::sexagenary::Sexagenary;
+use chrono::{Duration, FixedOffset, NaiveDate};
+use serde::{Deserialize, Serialize};
+
+const GENG: usize = 6; // 庚
+const BING: usize = 2; // 丙
+const WU: usize = 4; // 戊
+const WEI: usize = 7; // 未
+
+const NINE_NAMES: [&str; 9] = [
+    "一九", "二九", "三九", "四九", "五九", "六九", "七九", "八九", "九九",
+];
+
+/// 杂节气时段（单日事件的起止为同一天）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SeasonalPeriod {
+    /// 名称，如 初伏、三九、入梅、春社
+    pub name: String,
+    /// 分组：三伏 | 九九 | 梅雨 | 社日
+    pub group: String,
+    pub start: NaiveDate,
+    /// 结束日（含）
+    pub end: NaiveDate,
+}
+
+/// 某日所处的杂节气标记
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SeasonalMark {
+    pub name: String,
+    pub group: String,
+    /// 该时段第几天（从 1 起）
+    pub day: u32,
+}
+
+impl SeasonalPeriod {
+    fn new(name: &str, group: &str, start: NaiveDate, end: NaiveDate) -> Self {
+        Self {
+            name: name.to_string(),
+            group: group.to_string(),
+            start,
+            end,
+        }
+    }
+
+    /// 某日是否落在该时段内
+    pub fn contains(&self, date: NaiveDate) -> bool {
+        self.start <= date && date <= self.end
+    }
+
+    /// 某日在该时段中的标记
+    pub fn mark_on(&self, date: NaiveDate) -> Option<SeasonalMark> {
+        self.contains(date).then(|| SeasonalMark {
+            name: self.name.clone(),
+            group: self.group.clone(),
+            day: (date - self.start).num_days() as u32 + 1,
+        })
+    }
+}
+
+/// 某公历日的日干支索引 (天干, 地支)
+fn day_stem_branch(date: NaiveDate) -> (usize, usize) {
+    Sexagenary::day_pillar(date).pair()
+}
+
+/// 从 `start`（含）起第 `n` 个满足条件的日期
+fn nth_day_from(start: NaiveDate, n: usize, pred: impl Fn((usize, usize)) -> bool) -> NaiveDate {
+    start
+        .iter_days()
+        .filter(|date| pred(day_stem_branch(*date)))
+        .nth(n - 1)
+        .unwrap()
+}
+
+/// 节气在指定时区下的本地日期
+fn term_local_date(
+    year: i32,
+    term: SolarTerm,
+    offset: &FixedOffset,
+    delta_t: &dyn DeltaTProvider,
+) -> anyhow::Result<NaiveDate> {
+    let jd = find_solar_term_jd_with(year, term, delta_t);
+    let utc = jd_to_utc(jd)
+        .ok_or_else(|| anyhow::anyhow!("节气时刻超出范围：{} {}", year, term.name()))?;
+    Ok(utc.with_timezone(offset).date_naive())
+}
+
+fn nine_nines(dongzhi: NaiveDate) -> Vec<SeasonalPeriod> {
+    NINE_NAMES
+        .iter()
+        .enumerate()
+        .map(|(i, name)| {
+            let start = dongzhi + Duration::days(9 * i as i64);
+            SeasonalPeriod::new(name, "九九", start, start + Duration::days(8))
+        })
+        .collect()
+}
+
+/// 计算与某公历年相关的全部杂节气
+///
+/// 包含上一年冬至起的九九（延续到本年初春）及本年冬至起的九九。
+///
+/// # 参数
+/// - `year`: 公历年份
+/// - `tz_offset_minutes`: 时区偏移（分钟），东为正 UTC+8=+480
+/// - `delta_t`: ΔT 提供者，用于求节气时刻
+pub fn seasonal_periods(
+    year: i32,
+    tz_offset_minutes: i32,
+    delta_t: &dyn DeltaTProvider,
+) -> anyhow::Result<Vec<SeasonalPeriod>> {
+    let offset = FixedOffset::east_opt(tz_offset_minutes * 60)
+        .ok_or_else(|| anyhow::anyhow!("无效时区偏移：tz_offset_minutes={}", tz_offset_minutes))?;
+    let term = |y: i32, t: SolarTerm| term_local_date(y, t, &offset, delta_t);
+
+    let mut periods = nine_nines(term(year - 1, SolarTerm::Dongzhi)?);
+
+    // 社日
+    let chunshe = nth_day_from(term(year, SolarTerm::Lichun)?, 5, |(g, _)| g == WU);
+    periods.push(SeasonalPeriod::new("春社", "社日", chunshe, chunshe));
+
+    // 入梅 / 出梅
+    let rumei = nth_day_from(term(year, SolarTerm::Mangzhong)?, 1, |(g, _)| g == BING);
+    let chumei = nth_day_from(term(year, SolarTerm::Xiaoshu)?, 1, |(_, z)| z == WEI);
+    periods.push(SeasonalPeriod::new("入梅", "梅雨", rumei, rumei));
+    periods.push(SeasonalPeriod::new("出梅", "梅雨", chumei, chumei));
+
+    // 三伏
+    let xiazhi = term(year, SolarTerm::Xiazhi)?;
+    let liqiu = term(year, SolarTerm::Liqiu)?;
+    let chufu = nth_day_from(xiazhi, 3, |(g, _)| g == GENG);
+    let zhongfu = nth_day_from(xiazhi, 4, |(g, _)| g == GENG);
+    let mofu = nth_day_from(liqiu, 1, |(g, _)| g == GENG);
+    periods.push(SeasonalPeriod::new(
+        "初伏",
+        "三伏",
+        chufu,
+        zhongfu - Duration::days(1),
+    ));
+    periods.push(SeasonalPeriod::new(
+        "中伏",
+        "三伏",
+        zhongfu,
+        mofu - Duration::days(1),
+    ));
+    periods.push(SeasonalPeriod::new(
+        "末伏",
+        "三伏",
+        mofu,
+        mofu + Duration::days(9),
+    ));
+
+    let qiushe = nth_day_from(liqiu, 5, |(g, _)| g == WU);
+    periods.push(SeasonalPeriod::new("秋社", "社日", qiushe, qiushe));
+
+    periods.extend(nine_nines(term(year, SolarTerm::Dongzhi)?));
+
+    Ok(periods)
+}
+
+/// 列出某日所处的杂节气标记
+pub fn seasonal_marks_on(periods: &[SeasonalPeriod], date: NaiveDate) -> Vec<SeasonalMark> {
+    periods.iter().filter_map(|p| p.mark_on(date)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::astro::delta_t::EspenakMeeus;
+
+    fn find<'a>(periods: &'a [SeasonalPeriod], name: &str) -> &'a SeasonalPeriod {
+        periods.iter().find(|p| p.name == name).unwrap()
+    }
+
+    fn ymd(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn test_sanfu_2025() {
+        let periods = seasonal_periods(2025, 480, &EspenakMeeus).unwrap();
+        assert_eq!(find(&periods, "初伏").start, ymd(2025, 7, 20));
+        assert_eq!(find(&periods, "中伏").start, ymd(2025, 7, 30));
+        assert_eq!(find(&periods, "末伏").start, ymd(2025, 8, 9));
+        assert_eq!(find(&periods, "末伏").end, ymd(2025, 8, 18));
+    }
+
+    #[test]
+    fn test_sanfu_2024_long_zhongfu() {
+        // 2024 年中伏 20 天
+        let periods = seasonal_periods(2024, 480, &EspenakMeeus).unwrap();
+        let zhongfu = find(&periods, "中伏");
+        assert_eq!(zhongfu.start, ymd(2024, 7, 25));
+        assert_eq!((zhongfu.end - zhongfu.start).num_days() + 1, 20);
+        assert_eq!(find(&periods, "末伏").start, ymd(2024, 8, 14));
+    }
+
+    #[test]
+    fn test_nine_nines_from_dongzhi() {
+        let periods = seasonal_periods(2025, 480, &EspenakMeeus).unwrap();
+        let marks = seasonal_marks_on(&periods, ymd(2024, 12, 21));
+        assert_eq!(marks.len(), 1);
+        assert_eq!(marks[0].name, "一九");
+        assert_eq!(marks[0].day, 1);
+
+        let marks = seasonal_marks_on(&periods, ymd(2025, 1, 8));
+        assert_eq!(marks[0].name, "三九");
+        assert_eq!(marks[0].day, 1);
+    }
+
+    #[test]
+    fn test_day_stem_rules() {
+        let periods = seasonal_periods(2025, 480, &EspenakMeeus).unwrap();
+        for name in ["春社", "秋社"] {
+            assert_eq!(day_stem_branch(find(&periods, name).start).0, WU);
+        }
+        assert_eq!(day_stem_branch(find(&periods, "入梅").start).0, BING);
+        assert_eq!(day_stem_branch(find(&periods, "出梅").start).1, WEI);
+    }
+}
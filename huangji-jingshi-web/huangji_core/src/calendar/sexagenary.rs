@@ -0,0 +1,343 @@
+//! 干支历统一服务
+//!
+//! 年、月、日、时四柱的唯一推算入口。黄历（`lunar`）、运势（`fortune`）、
+//! 经世年（`time_rule`）、八字排盘（`ganzhi` 与后端 `bazi::legacy`）均经由此处，
+//! 保证立春换年、节气换月、子时换日在各模块之间一致。
+//!
+//! 规则：
+//! - 年：以立春（太阳黄经 315°）为岁首
+//! - 月：以十二节为界，月支由太阳黄经决定，月干按五虎遁
+//! - 日：按推算所用的本地日期的儒略日数连续计日，JD 2451545（2000-01-01）= 戊午；
+//!   可选子初（23 时）换日。古代日期须先按所用历法（儒略历/格里历）折算为儒略日数
+//! - 时：时支由本地（或真太阳）时刻决定，时干按五鼠遁
+
+use crate::astro::calendar_system::{naive_to_jdn, CalendarDate};
+use crate::astro::solar::{apparent_solar_longitude, hour_to_dizhi_index};
+use crate::astro::delta_t::{DeltaTProvider, EspenakMeeus};
+use crate::astro::time::utc_to_tt_with;
+use crate::calendar::ganzhi::{DIZHI, TIANGAN};
+use chrono::{DateTime, Datelike, NaiveDate, NaiveDateTime, Timelike, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// 立春黄经
+const LICHUN_LONGITUDE: f64 = 315.0;
+
+/// 干支（天干索引, 地支索引）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GanZhi {
+    pub gan: usize,
+    pub zhi: usize,
+}
+
+impl GanZhi {
+    /// 由六十甲子序号构造（0 = 甲子）
+    pub fn from_cycle(index: i64) -> Self {
+        let index = index.rem_euclid(60);
+        Self {
+            gan: (index % 10) as usize,
+            zhi: (index % 12) as usize,
+        }
+    }
+
+    /// 六十甲子序号（0 = 甲子）
+    pub fn cycle_index(&self) -> usize {
+        (6 * self.gan + 55 * self.zhi) % 60
+    }
+
+    pub fn gan_name(&self) -> &'static str {
+        TIANGAN[self.gan % 10]
+    }
+
+    pub fn zhi_name(&self) -> &'static str {
+        DIZHI[self.zhi % 12]
+    }
+
+    /// 索引元组，兼容旧接口
+    pub fn pair(&self) -> (usize, usize) {
+        (self.gan, self.zhi)
+    }
+}
+
+impl fmt::Display for GanZhi {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", self.gan_name(), self.zhi_name())
+    }
+}
+
+/// 换日规则
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DayRollover {
+    /// 子初换日：23:00 起日柱按次日（八字常用）
+    #[default]
+    ZiChu23,
+    /// 子正换日：00:00 换日（与民用日期一致，黄历使用）
+    ZiZheng00,
+}
+
+impl DayRollover {
+    pub fn parse(raw: Option<&str>) -> Self {
+        match raw
+            .unwrap_or("zi_chu_23")
+            .trim()
+            .to_ascii_lowercase()
+            .as_str()
+        {
+            "zi_zheng_00" | "zizheng00" | "00:00" => Self::ZiZheng00,
+            _ => Self::ZiChu23,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::ZiChu23 => "zi_chu_23",
+            Self::ZiZheng00 => "zi_zheng_00",
+        }
+    }
+}
+
+/// 四柱
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SexagenaryPillars {
+    pub year: GanZhi,
+    pub month: GanZhi,
+    pub day: GanZhi,
+    pub hour: GanZhi,
+    /// 太阳黄经 (度)
+    pub solar_longitude: f64,
+    /// 是否晚子时（23:00-24:00）
+    pub is_late_zi: bool,
+}
+
+/// 干支历推算服务
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Sexagenary {
+    pub day_rollover: DayRollover,
+}
+
+impl Sexagenary {
+    pub fn new(day_rollover: DayRollover) -> Self {
+        Self { day_rollover }
+    }
+
+    /// 以立春为岁首的干支纪年年份（天文纪年，公元前 1 年 = 0）
+    ///
+    /// 黄经 [270°, 315°) 介于冬至与立春之间；只有在公历一、二月时才属立春前，
+    /// 十二月下旬虽同在此区间，但尚未跨入新年，不应减一。
+    ///
+    /// # 参数
+    /// - `year`, `month`: 推算时刻的公历年、月
+    /// - `solar_longitude`: 太阳黄经
+    pub fn solar_year(year: i32, month: u32, solar_longitude: f64) -> i32 {
+        let before_lichun = month <= 2 && (270.0..LICHUN_LONGITUDE).contains(&solar_longitude);
+        if before_lichun {
+            year - 1
+        } else {
+            year
+        }
+    }
+
+    /// 年柱：year_gan = (年 - 4) mod 10，year_zhi = (年 - 4) mod 12
+    pub fn year_pillar(year: i32, month: u32, solar_longitude: f64) -> GanZhi {
+        GanZhi::from_cycle(Self::solar_year(year, month, solar_longitude) as i64 - 4)
+    }
+
+    /// 月柱
+    ///
+    /// 月支：315° = 寅月，每 30° 一月；月干按五虎遁。
+    ///
+    /// # 五虎遁口诀
+    /// 甲己之年丙作首，乙庚之岁戊为头，
+    /// 丙辛必定寻庚起，丁壬壬位顺行流，
+    /// 若问戊癸何方发，甲寅之上好追求。
+    pub fn month_pillar(year_gan: usize, solar_longitude: f64) -> GanZhi {
+        let month_offset =
+            ((solar_longitude - LICHUN_LONGITUDE).rem_euclid(360.0) / 30.0).floor() as usize % 12;
+        // 正月（寅月）天干 = (年干 % 5) * 2 + 2
+        let yin_month_gan = (year_gan % 5) * 2 + 2;
+        GanZhi {
+            gan: (yin_month_gan + month_offset) % 10,
+            zhi: (month_offset + 2) % 12,
+        }
+    }
+
+    /// 日柱：`date` 为外推格里历日期（chrono 口径）
+    pub fn day_pillar(date: NaiveDate) -> GanZhi {
+        Self::day_pillar_jdn(naive_to_jdn(date))
+    }
+
+    /// 日柱：任一历法下的日期（如改历前的儒略历日期）
+    pub fn day_pillar_of(date: &CalendarDate) -> GanZhi {
+        Self::day_pillar_jdn(date.jdn())
+    }
+
+    /// 日柱：由儒略日数推算，JDN 2451545（2000-01-01）= 戊午（六十甲子第 54）
+    pub fn day_pillar_jdn(jdn: i64) -> GanZhi {
+        GanZhi::from_cycle(jdn + 49)
+    }
+
+    /// 时柱
+    ///
+    /// # 五鼠遁口诀
+    /// 甲己还加甲，乙庚丙作初，
+    /// 丙辛从戊起，丁壬庚子居，
+    /// 戊癸何方发，壬子是真途。
+    pub fn hour_pillar(day_gan: usize, hour_zhi: usize) -> GanZhi {
+        GanZhi {
+            gan: ((day_gan % 5) * 2 + hour_zhi) % 10,
+            zhi: hour_zhi % 12,
+        }
+    }
+
+    /// 推算四柱
+    ///
+    /// # 参数
+    /// - `instant`: 推算时刻（UTC），用于太阳黄经（立春换年、节气换月）
+    /// - `basis_local`: 同一时刻的本地时间（标准时或真太阳时），用于日柱与时柱
+    pub fn pillars(
+        &self,
+        instant: &DateTime<Utc>,
+        basis_local: &NaiveDateTime,
+    ) -> SexagenaryPillars {
+        self.pillars_with(instant, basis_local, &EspenakMeeus)
+    }
+
+    /// 同 [`Self::pillars`]，太阳黄经按指定 ΔT 提供者换算到 TT 后求得
+    pub fn pillars_with(
+        &self,
+        instant: &DateTime<Utc>,
+        basis_local: &NaiveDateTime,
+        delta_t: &dyn DeltaTProvider,
+    ) -> SexagenaryPillars {
+        let solar_longitude = apparent_solar_longitude(utc_to_tt_with(instant, delta_t));
+
+        let hour = basis_local.hour() as f64
+            + basis_local.minute() as f64 / 60.0
+            + basis_local.second() as f64 / 3600.0;
+        let (hour_zhi, late_zi) = hour_to_dizhi_index(hour);
+        let is_late_zi = late_zi && self.day_rollover == DayRollover::ZiChu23;
+
+        let mut date = basis_local.date();
+        if is_late_zi {
+            date = date.succ_opt().unwrap_or(date);
+        }
+
+        let year = Self::year_pillar(basis_local.year(), basis_local.month(), solar_longitude);
+        let month = Self::month_pillar(year.gan, solar_longitude);
+        let day = Self::day_pillar(date);
+        let hour = Self::hour_pillar(day.gan, hour_zhi);
+
+        SexagenaryPillars {
+            year,
+            month,
+            day,
+            hour,
+            solar_longitude,
+            is_late_zi,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn ymd(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn test_cycle_index_roundtrip() {
+        for i in 0..60 {
+            assert_eq!(GanZhi::from_cycle(i).cycle_index(), i as usize);
+        }
+        assert_eq!(GanZhi::from_cycle(-1).to_string(), "癸亥");
+    }
+
+    #[test]
+    fn test_year_pillar_around_lichun() {
+        // 2025年2月3日 (立春前) 应为甲辰年，2月5日 (立春后) 应为乙巳年
+        assert_eq!(Sexagenary::year_pillar(2025, 2, 313.0).to_string(), "甲辰");
+        assert_eq!(Sexagenary::year_pillar(2025, 2, 316.0).to_string(), "乙巳");
+        assert_eq!(Sexagenary::year_pillar(2025, 1, 290.0).to_string(), "甲辰");
+    }
+
+    #[test]
+    fn test_year_pillar_late_december() {
+        // 冬至后、元旦前仍属当年
+        assert_eq!(Sexagenary::year_pillar(2024, 12, 275.0).to_string(), "甲辰");
+        assert_eq!(Sexagenary::solar_year(2024, 12, 275.0), 2024);
+    }
+
+    #[test]
+    fn test_year_pillar_bce() {
+        // 天文纪年 0 年 = 公元前 1 年 = 庚申
+        assert_eq!(Sexagenary::year_pillar(0, 6, 90.0).to_string(), "庚申");
+    }
+
+    #[test]
+    fn test_month_pillar_formula() {
+        // 315° = 寅月, 345° = 卯月, 15° = 辰月
+        assert_eq!(Sexagenary::month_pillar(0, 315.0).zhi_name(), "寅");
+        assert_eq!(Sexagenary::month_pillar(0, 345.0).zhi_name(), "卯");
+        assert_eq!(Sexagenary::month_pillar(0, 15.0).zhi_name(), "辰");
+        assert_eq!(Sexagenary::month_pillar(0, 300.0).zhi_name(), "丑");
+    }
+
+    #[test]
+    fn test_five_tigers() {
+        // 甲/己年正月 -> 丙寅，乙/庚年正月 -> 戊寅
+        assert_eq!(Sexagenary::month_pillar(0, 315.0).to_string(), "丙寅");
+        assert_eq!(Sexagenary::month_pillar(1, 315.0).to_string(), "戊寅");
+        assert_eq!(Sexagenary::month_pillar(4, 315.0).to_string(), "甲寅");
+    }
+
+    #[test]
+    fn test_day_pillar_anchors() {
+        assert_eq!(Sexagenary::day_pillar(ymd(2000, 1, 1)).to_string(), "戊午");
+        assert_eq!(Sexagenary::day_pillar(ymd(1970, 1, 1)).to_string(), "辛巳");
+        // 2025 年春节 乙巳年正月初一 戊戌日
+        assert_eq!(Sexagenary::day_pillar(ymd(2025, 1, 29)).to_string(), "戊戌");
+    }
+
+    #[test]
+    fn test_day_pillar_ancient_julian_dates() {
+        use crate::astro::calendar_system::CalendarSystem;
+        let reform = CalendarSystem::default();
+        // 《春秋》隐公三年"二月己巳，日有食之"：公元前 720 年 2 月 22 日（儒略历）
+        let eclipse = reform.date(-719, 2, 22).unwrap();
+        assert_eq!(Sexagenary::day_pillar_of(&eclipse).to_string(), "己巳");
+        let naive = reform.to_naive(-719, 2, 22).unwrap();
+        assert_eq!(Sexagenary::day_pillar(naive).to_string(), "己巳");
+        // 改历前后干支连续：儒略历 1582-10-04 与格里历 1582-10-15 相邻
+        let before = Sexagenary::day_pillar_of(&reform.date(1582, 10, 4).unwrap());
+        let after = Sexagenary::day_pillar_of(&reform.date(1582, 10, 15).unwrap());
+        assert_eq!((before.cycle_index() + 1) % 60, after.cycle_index());
+    }
+
+    #[test]
+    fn test_five_rats() {
+        // 甲日子时 -> 甲子，乙日子时 -> 丙子，戊/癸日子时 -> 壬子
+        assert_eq!(Sexagenary::hour_pillar(0, 0).to_string(), "甲子");
+        assert_eq!(Sexagenary::hour_pillar(1, 0).to_string(), "丙子");
+        assert_eq!(Sexagenary::hour_pillar(9, 0).to_string(), "壬子");
+    }
+
+    #[test]
+    fn test_pillars_day_rollover() {
+        // 北京时间 2025-06-15 23:30
+        let instant = Utc.with_ymd_and_hms(2025, 6, 15, 15, 30, 0).unwrap();
+        let local = ymd(2025, 6, 15).and_hms_opt(23, 30, 0).unwrap();
+
+        let zi_chu = Sexagenary::new(DayRollover::ZiChu23).pillars(&instant, &local);
+        assert!(zi_chu.is_late_zi);
+        assert_eq!(zi_chu.day, Sexagenary::day_pillar(ymd(2025, 6, 16)));
+        assert_eq!(zi_chu.hour.zhi_name(), "子");
+
+        let zi_zheng = Sexagenary::new(DayRollover::ZiZheng00).pillars(&instant, &local);
+        assert!(!zi_zheng.is_late_zi);
+        assert_eq!(zi_zheng.day, Sexagenary::day_pillar(ymd(2025, 6, 15)));
+    }
+}
@@ -2,30 +2,34 @@
 //! 
 //! 提供 UTC 时间到规则时间（rule_dt）的转换，以及规则时间到经世年（hj_year）的转换。
 //! 
+//! 时区取 `TimeZoneSpec`：固定偏移或 IANA 时区，后者按出生时刻取历史偏移
+//! （如 1986–1991 年中国夏令时、1901 年以前上海的地方平时）。
+//!
 //! 符号约定：
 //! - tzOffsetMinutes: 时区偏移（分钟），东为正 UTC+8=+480, 西为负 UTC-5=-300
 //! - 注意：与 JS Date.getTimezoneOffset() 符号相反！
 
-use chrono::{DateTime, Utc, FixedOffset, Datelike, Duration};
-use crate::astro::solar::{solar_position, utc_to_jd};
+use chrono::{DateTime, Utc, FixedOffset, Datelike};
+use crate::astro::calendar_system::CalendarSystem;
+use crate::astro::solar::apparent_solar_longitude;
+use crate::astro::solar_time::SolarTimePolicy;
+use crate::astro::delta_t::{DeltaTProvider, EspenakMeeus};
+use crate::astro::time::utc_to_tt_with;
+use crate::calendar::sexagenary::Sexagenary;
+use crate::calendar::zone::TimeZoneSpec;
 use serde::{Deserialize, Serialize};
 
 /// 岁首模式
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum YearStartMode {
-    /// 公历岁首模式：以公历 1 月 1 日为年份切换点
+    /// 公历岁首模式：以公历 1 月 1 日为年份切换点（改历前为儒略历 1 月 1 日）
     GregorianNewYear,
     /// 立春岁首模式：以立春节气时刻为年份切换点（预留）
+    #[default]
     Lichun,
 }
 
-impl Default for YearStartMode {
-    fn default() -> Self {
-        YearStartMode::Lichun
-    }
-}
-
 fn chrono_year_to_historical(year: i32) -> i32 {
     if year <= 0 {
         year - 1
@@ -38,44 +42,25 @@ fn chrono_year_to_historical(year: i32) -> i32 {
 /// 
 /// # Arguments
 /// * `utc` - UTC 时间
-/// * `tz_offset_minutes` - 时区偏移（分钟），东为正 UTC+8=+480, 西为负 UTC-5=-300
-/// * `lon` - 经度（用于真太阳时校正）
-/// * `use_true_solar_time` - 是否使用真太阳时
+/// * `zone` - 时区，区时偏移取 `utc` 时刻的历史偏移（含夏令时）
+/// * `lon` - 经度（用于太阳时校正）
+/// * `solar_time` - 太阳时策略：区时、平太阳时或真太阳时
 /// 
 /// # Returns
 /// 规则时间（带固定时区偏移）
 /// 
-/// # 真太阳时校正
-/// 以所选时区中央经线为基准：
-/// - `tz_offset_hours = tz_offset_minutes as f64 / 60.0`（支持非整小时时区如 +5:30）
-/// - `central_meridian = 15.0 * tz_offset_hours`
-/// - `delta_minutes = 4.0 * (lon - central_meridian)`
+/// # 太阳时校正
+/// 以该时刻区时偏移对应的中央经线为基准（见 `SolarTimePolicy::correction_seconds`）：
+/// - `central_meridian = 15.0 * offset_minutes / 60`（支持非整小时时区如 +5:30）
+/// - 平太阳时：`delta_minutes = 4.0 * (lon - central_meridian)`
+/// - 真太阳时：再加均时差
 pub fn to_rule_datetime(
     utc: DateTime<Utc>,
-    tz_offset_minutes: i32,
+    zone: &TimeZoneSpec,
     lon: f64,
-    use_true_solar_time: bool,
+    solar_time: SolarTimePolicy,
 ) -> DateTime<FixedOffset> {
-    // 创建固定偏移时区
-    let offset = FixedOffset::east_opt(tz_offset_minutes * 60)
-        .unwrap_or_else(|| FixedOffset::east_opt(8 * 3600).unwrap()); // 默认 UTC+8
-    
-    // 转换为本地时间
-    let local_dt = utc.with_timezone(&offset);
-    
-    if use_true_solar_time {
-        // 真太阳时校正
-        // 使用浮点除法支持非整小时时区（如 +5:30 = +330 分钟）
-        let tz_offset_hours: f64 = tz_offset_minutes as f64 / 60.0;
-        let central_meridian: f64 = 15.0 * tz_offset_hours;
-        let delta_minutes: f64 = 4.0 * (lon - central_meridian);
-        
-        // 应用真太阳时校正
-        let delta_seconds = (delta_minutes * 60.0).round() as i64;
-        local_dt + Duration::seconds(delta_seconds)
-    } else {
-        local_dt
-    }
+    solar_time.local_time(&utc, lon, zone.offset_at(&utc))
 }
 
 /// 将规则时间转换为经世年（hj_year）
@@ -88,26 +73,41 @@ pub fn to_rule_datetime(
 /// 经世年（历史纪年，无公元 0 年）
 /// 
 /// # 注意
-/// - 公历岁首模式：直接使用公历年份
+/// - 公历岁首模式：按默认改历（1582-10-15）取公历年份，改历前以儒略历 1 月 1 日换年。
+///   早先版本一律按外推格里历元旦换年，改历前元旦附近（儒略历 1 月 1 日前后约十天）
+///   的日期因此可能差一年；需旧行为时用 [`datetime_to_hj_year_in`] 并传 `CalendarSystem::Gregorian`
 /// - 立春岁首模式：根据立春时刻判断年份归属（预留）
 pub fn datetime_to_hj_year(
     rule_dt: DateTime<FixedOffset>,
     mode: YearStartMode,
 ) -> i32 {
-    let year = rule_dt.year();
+    datetime_to_hj_year_in(rule_dt, mode, &CalendarSystem::default())
+}
+
+/// 同 [`datetime_to_hj_year`]，公历岁首模式按指定历法（改历日）取年份
+pub fn datetime_to_hj_year_in(
+    rule_dt: DateTime<FixedOffset>,
+    mode: YearStartMode,
+    calendar: &CalendarSystem,
+) -> i32 {
+    datetime_to_hj_year_with(rule_dt, mode, calendar, &EspenakMeeus)
+}
+
+/// 同 [`datetime_to_hj_year_in`]，立春岁首模式按指定 ΔT 提供者求太阳黄经
+pub fn datetime_to_hj_year_with(
+    rule_dt: DateTime<FixedOffset>,
+    mode: YearStartMode,
+    calendar: &CalendarSystem,
+    delta_t: &dyn DeltaTProvider,
+) -> i32 {
     match mode {
-        YearStartMode::GregorianNewYear => {
-            chrono_year_to_historical(year)
-        }
+        YearStartMode::GregorianNewYear => calendar
+            .from_naive(rule_dt.date_naive())
+            .historical_year(),
         YearStartMode::Lichun => {
             let utc_dt = rule_dt.with_timezone(&Utc);
-            let jd = utc_to_jd(&utc_dt);
-            let solar_lon = solar_position(jd).ecliptic_longitude;
-            let adjusted = if (270.0..315.0).contains(&solar_lon) {
-                year - 1
-            } else {
-                year
-            };
+            let solar_lon = apparent_solar_longitude(utc_to_tt_with(&utc_dt, delta_t));
+            let adjusted = Sexagenary::solar_year(rule_dt.year(), rule_dt.month(), solar_lon);
             chrono_year_to_historical(adjusted)
         }
     }
@@ -117,22 +117,26 @@ pub fn datetime_to_hj_year(
 /// 
 /// # Arguments
 /// * `utc` - UTC 时间
-/// * `tz_offset_minutes` - 时区偏移（分钟），东为正 UTC+8=+480, 西为负 UTC-5=-300
+/// * `zone` - 时区
 /// * `lon` - 经度
-/// * `use_true_solar_time` - 是否使用真太阳时
+/// * `solar_time` - 太阳时策略
 /// * `mode` - 岁首模式
+/// * `calendar` - 公历岁首模式下取年份的历法（改历日）
+/// * `delta_t` - ΔT 提供者，立春岁首模式下用于求太阳黄经
 /// 
 /// # Returns
 /// 经世年（历史纪年，无公元 0 年）
 pub fn utc_to_hj_year(
     utc: DateTime<Utc>,
-    tz_offset_minutes: i32,
+    zone: &TimeZoneSpec,
     lon: f64,
-    use_true_solar_time: bool,
+    solar_time: SolarTimePolicy,
     mode: YearStartMode,
+    calendar: &CalendarSystem,
+    delta_t: &dyn DeltaTProvider,
 ) -> i32 {
-    let rule_dt = to_rule_datetime(utc, tz_offset_minutes, lon, use_true_solar_time);
-    datetime_to_hj_year(rule_dt, mode)
+    let rule_dt = to_rule_datetime(utc, zone, lon, solar_time);
+    datetime_to_hj_year_with(rule_dt, mode, calendar, delta_t)
 }
 
 #[cfg(test)]
@@ -140,12 +144,16 @@ mod tests {
     use super::*;
     use chrono::TimeZone;
     use chrono::Timelike;
+
+    fn zone(tz_offset_minutes: i32) -> TimeZoneSpec {
+        TimeZoneSpec::from_offset_minutes(tz_offset_minutes).unwrap()
+    }
     
     #[test]
     fn test_to_rule_datetime_utc8() {
         // UTC 2025-12-18T13:48:00Z + UTC+8 = 2025-12-18T21:48:00+08:00
         let utc = Utc.with_ymd_and_hms(2025, 12, 18, 13, 48, 0).unwrap();
-        let rule_dt = to_rule_datetime(utc, 480, 116.4, false);
+        let rule_dt = to_rule_datetime(utc, &zone(480), 116.4, SolarTimePolicy::Meridian);
         
         assert_eq!(rule_dt.year(), 2025);
         assert_eq!(rule_dt.month(), 12);
@@ -158,7 +166,7 @@ mod tests {
     fn test_to_rule_datetime_utc_minus_5() {
         // UTC 2025-12-18T13:00:00Z + UTC-5 = 2025-12-18T08:00:00-05:00
         let utc = Utc.with_ymd_and_hms(2025, 12, 18, 13, 0, 0).unwrap();
-        let rule_dt = to_rule_datetime(utc, -300, -75.0, false);
+        let rule_dt = to_rule_datetime(utc, &zone(-300), -75.0, SolarTimePolicy::Meridian);
         
         assert_eq!(rule_dt.year(), 2025);
         assert_eq!(rule_dt.month(), 12);
@@ -188,11 +196,23 @@ mod tests {
         assert_eq!(datetime_to_hj_year(dt, YearStartMode::GregorianNewYear), -2);
     }
 
+    #[test]
+    fn test_datetime_to_hj_year_julian_new_year() {
+        let offset = FixedOffset::east_opt(8 * 3600).unwrap();
+        // 外推格里历 1000-01-03 = 儒略历 999-12-29，改历前仍属 999 年
+        let dt = offset.with_ymd_and_hms(1000, 1, 3, 12, 0, 0).unwrap();
+        assert_eq!(datetime_to_hj_year(dt, YearStartMode::GregorianNewYear), 999);
+        assert_eq!(
+            datetime_to_hj_year_in(dt, YearStartMode::GregorianNewYear, &CalendarSystem::Gregorian),
+            1000
+        );
+    }
+
     #[test]
     fn test_datetime_to_hj_year_lichun_boundary() {
         // 2025-02-03 12:00 UTC，立春前，按立春岁首应归上一年（甲辰）
         let dt_before = Utc.with_ymd_and_hms(2025, 2, 3, 12, 0, 0).unwrap();
-        let rule_before = to_rule_datetime(dt_before, 480, 116.4, false);
+        let rule_before = to_rule_datetime(dt_before, &zone(480), 116.4, SolarTimePolicy::Meridian);
         assert_eq!(
             datetime_to_hj_year(rule_before, YearStartMode::Lichun),
             2024
@@ -200,12 +220,20 @@ mod tests {
 
         // 2025-02-05 12:00 UTC，立春后，按立春岁首应归当年（乙巳）
         let dt_after = Utc.with_ymd_and_hms(2025, 2, 5, 12, 0, 0).unwrap();
-        let rule_after = to_rule_datetime(dt_after, 480, 116.4, false);
+        let rule_after = to_rule_datetime(dt_after, &zone(480), 116.4, SolarTimePolicy::Meridian);
         assert_eq!(
             datetime_to_hj_year(rule_after, YearStartMode::Lichun),
             2025
         );
     }
+
+    #[test]
+    fn test_datetime_to_hj_year_lichun_late_december() {
+        // 冬至后、元旦前（黄经约 274°）仍属当年
+        let dt = Utc.with_ymd_and_hms(2024, 12, 25, 4, 0, 0).unwrap();
+        let rule_dt = to_rule_datetime(dt, &zone(480), 116.4, SolarTimePolicy::Meridian);
+        assert_eq!(datetime_to_hj_year(rule_dt, YearStartMode::Lichun), 2024);
+    }
     
     #[test]
     fn test_true_solar_time_correction() {
@@ -213,11 +241,12 @@ mod tests {
         // 中央经线 = 15 * 8 = 120°E
         // delta = 4 * (116.4 - 120) = -14.4 分钟
         let utc = Utc.with_ymd_and_hms(2025, 12, 18, 12, 0, 0).unwrap();
-        let rule_dt = to_rule_datetime(utc, 480, 116.4, true);
+        let rule_dt = to_rule_datetime(utc, &zone(480), 116.4, SolarTimePolicy::from_true_solar_flag(true));
         
-        // 本地时间 20:00，真太阳时校正 -14.4 分钟 ≈ 19:45:36
+        // 本地时间 20:00，真太阳时校正 -14.4 分钟 = 19:45:36（旧参数只按经度校正，不计均时差）
         assert_eq!(rule_dt.hour(), 19);
-        assert!(rule_dt.minute() < 50); // 应该在 45-46 分钟左右
+        assert_eq!(rule_dt.minute(), 45);
+        assert_eq!(rule_dt.second(), 36);
     }
     
     #[test]
@@ -225,10 +254,54 @@ mod tests {
         // 印度时区 UTC+5:30 = +330 分钟
         // 中央经线 = 15 * 5.5 = 82.5°E
         let utc = Utc.with_ymd_and_hms(2025, 12, 18, 12, 0, 0).unwrap();
-        let rule_dt = to_rule_datetime(utc, 330, 82.5, false);
+        let rule_dt = to_rule_datetime(utc, &zone(330), 82.5, SolarTimePolicy::Meridian);
         
         // UTC 12:00 + 5:30 = 17:30
         assert_eq!(rule_dt.hour(), 17);
         assert_eq!(rule_dt.minute(), 30);
     }
+
+    #[test]
+    fn test_iana_zone_uses_historical_offset() {
+        let shanghai = TimeZoneSpec::parse("Asia/Shanghai").unwrap();
+        // 1988 年夏令时：UTC 1988-07-01 15:30 = 北京夏令时 00:30（次日），区时 +8 仍为前一日 23:30
+        let utc = Utc.with_ymd_and_hms(1988, 7, 1, 15, 30, 0).unwrap();
+        let rule_dt = to_rule_datetime(utc, &shanghai, 116.4, SolarTimePolicy::Meridian);
+        assert_eq!(rule_dt.offset().local_minus_utc(), 9 * 3600);
+        assert_eq!((rule_dt.day(), rule_dt.hour(), rule_dt.minute()), (2, 0, 30));
+        let fixed = to_rule_datetime(utc, &zone(480), 116.4, SolarTimePolicy::Meridian);
+        assert_eq!((fixed.day(), fixed.hour()), (1, 23));
+
+        // 平太阳时只取决于经度，与夏令时无关
+        let mean = to_rule_datetime(utc, &shanghai, 116.4, SolarTimePolicy::Mean);
+        let mean_fixed = to_rule_datetime(utc, &zone(480), 116.4, SolarTimePolicy::Mean);
+        assert_eq!(mean.naive_local(), mean_fixed.naive_local());
+
+        // 1901 年以前按地方平时 +08:05:43：UTC 1890-12-31 15:57 为当地 1891-01-01 00:02:43
+        let lmt = Utc.with_ymd_and_hms(1890, 12, 31, 15, 57, 0).unwrap();
+        assert_eq!(
+            utc_to_hj_year(
+                lmt,
+                &shanghai,
+                116.4,
+                SolarTimePolicy::Meridian,
+                YearStartMode::GregorianNewYear,
+                &CalendarSystem::default(),
+                &EspenakMeeus
+            ),
+            1891
+        );
+        assert_eq!(
+            utc_to_hj_year(
+                lmt,
+                &zone(480),
+                116.4,
+                SolarTimePolicy::Meridian,
+                YearStartMode::GregorianNewYear,
+                &CalendarSystem::default(),
+                &EspenakMeeus
+            ),
+            1890
+        );
+    }
 }
@@ -0,0 +1,309 @@
+//! 择日模块
+//!
+//! 在给定日期范围内逐日取黄历信息（`lunar::compute_lunar`），按约束筛选并排序：
+//! - 硬约束：事项须在当日"宜"且不在"忌"；日支不冲命主年支；可选避开月破、岁破
+//! - 软约束：首选建除十二神、日支与命主年支六合/三合，命中者加分
+//!
+//! 每个候选日附带理由说明，便于前端展示。
+
+use crate::astro::delta_t::DeltaTModel;
+use crate::calendar::ganzhi::{zhi_chong, zhi_index, zhi_liuhe, zhi_sanhe, DIZHI};
+use crate::calendar::month::lunar_at_local_noon;
+use crate::lunar::{LunarInfo, OFFICERS};
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+/// 单次择日允许的最大天数
+pub const MAX_ZERI_DAYS: i64 = 366;
+
+const SCORE_PREFERRED_OFFICER: i32 = 2;
+const SCORE_LIUHE: i32 = 2;
+const SCORE_SANHE: i32 = 1;
+
+/// 择日请求
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ZeriRequest {
+    /// 起始日期（含，本地）
+    pub start_date: NaiveDate,
+    /// 结束日期（含，本地）
+    pub end_date: NaiveDate,
+    /// 时区偏移（分钟），东为正 UTC+8=+480
+    pub tz_offset_minutes: i32,
+    /// 所办事项（须在当日"宜"中），如 嫁娶、移徙
+    pub activity: Option<String>,
+    /// 命主出生年地支或生肖，如 "午" 或 "马"；日支与之相冲则排除
+    pub birth_branch: Option<String>,
+    /// 首选建除十二神，如 ["成", "开"]
+    #[serde(default)]
+    pub preferred_officers: Vec<String>,
+    /// 是否避开月破（日支冲月支）
+    #[serde(default)]
+    pub avoid_month_break: bool,
+    /// 是否避开岁破（日支冲年支）
+    #[serde(default)]
+    pub avoid_year_break: bool,
+    /// 最多返回的候选数（默认全部）
+    pub limit: Option<usize>,
+    /// ΔT 模型，用于逐日干支的太阳黄经
+    #[serde(default)]
+    pub delta_t_model: DeltaTModel,
+}
+
+/// 候选日
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ZeriCandidate {
+    pub date: NaiveDate,
+    pub lunar_month: String,
+    pub lunar_day: String,
+    pub ganzhi_year: String,
+    pub ganzhi_month: String,
+    pub ganzhi_day: String,
+    pub twelve_officer: String,
+    pub yi: Vec<String>,
+    pub ji: Vec<String>,
+    /// 得分（越高越好）
+    pub score: i32,
+    /// 入选与加分理由
+    pub reasons: Vec<String>,
+}
+
+/// 择日结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ZeriResult {
+    /// 考察的天数
+    pub examined_days: usize,
+    /// 通过硬约束的天数（截断前）
+    pub matched_days: usize,
+    /// 按得分降序、日期升序排列的候选日
+    pub candidates: Vec<ZeriCandidate>,
+    /// 实际使用的 ΔT 提供者
+    pub delta_t_provider: String,
+}
+
+/// 干支字符串的地支索引（如 "甲午" -> 6）
+fn branch_of(ganzhi: &str) -> Option<usize> {
+    let branch: String = ganzhi.chars().skip(1).take(1).collect();
+    DIZHI.iter().position(|z| *z == branch)
+}
+
+/// 评估某日；不满足硬约束返回 None
+fn evaluate(
+    req: &ZeriRequest,
+    birth_branch: Option<usize>,
+    date: NaiveDate,
+    lunar: LunarInfo,
+) -> anyhow::Result<Option<ZeriCandidate>> {
+    let branch =
+        |ganzhi: &str| branch_of(ganzhi).ok_or_else(|| anyhow::anyhow!("无法解析干支：{}", ganzhi));
+    let day_branch = branch(&lunar.ganzhi_day)?;
+    let month_branch = branch(&lunar.ganzhi_month)?;
+    let year_branch = branch(&lunar.ganzhi_year)?;
+
+    let mut score = 0;
+    let mut reasons = Vec::new();
+
+    if let Some(activity) = &req.activity {
+        if !lunar.yi.contains(activity) || lunar.ji.contains(activity) {
+            return Ok(None);
+        }
+        reasons.push(format!("宜{}", activity));
+    }
+
+    if let Some(birth) = birth_branch {
+        if zhi_chong(day_branch, birth) {
+            return Ok(None);
+        }
+        reasons.push(format!("日支{}不冲命支{}", DIZHI[day_branch], DIZHI[birth]));
+        if zhi_liuhe(day_branch, birth) {
+            score += SCORE_LIUHE;
+            reasons.push(format!(
+                "日支{}与命支{}六合",
+                DIZHI[day_branch], DIZHI[birth]
+            ));
+        } else if zhi_sanhe(day_branch, birth) {
+            score += SCORE_SANHE;
+            reasons.push(format!(
+                "日支{}与命支{}三合",
+                DIZHI[day_branch], DIZHI[birth]
+            ));
+        }
+    }
+
+    if req.avoid_month_break {
+        if zhi_chong(day_branch, month_branch) {
+            return Ok(None);
+        }
+        reasons.push("非月破".to_string());
+    }
+
+    if req.avoid_year_break {
+        if zhi_chong(day_branch, year_branch) {
+            return Ok(None);
+        }
+        reasons.push("非岁破".to_string());
+    }
+
+    if req.preferred_officers.contains(&lunar.twelve_officer) {
+        score += SCORE_PREFERRED_OFFICER;
+        reasons.push(format!("值{}日，为首选建除", lunar.twelve_officer));
+    }
+
+    Ok(Some(ZeriCandidate {
+        date,
+        lunar_month: lunar.lunar_month,
+        lunar_day: lunar.lunar_day,
+        ganzhi_year: lunar.ganzhi_year,
+        ganzhi_month: lunar.ganzhi_month,
+        ganzhi_day: lunar.ganzhi_day,
+        twelve_officer: lunar.twelve_officer,
+        yi: lunar.yi,
+        ji: lunar.ji,
+        score,
+        reasons,
+    }))
+}
+
+/// 在日期范围内择日
+///
+/// # 参数
+/// - `req`: 日期范围与约束条件
+///
+/// # 返回
+/// - `ZeriResult`: 按得分降序（同分按日期升序）排列的候选日
+pub fn select_dates(req: &ZeriRequest) -> anyhow::Result<ZeriResult> {
+    if req.end_date < req.start_date {
+        anyhow::bail!(
+            "结束日期不能早于起始日期：{} > {}",
+            req.start_date,
+            req.end_date
+        );
+    }
+    if (req.end_date - req.start_date).num_days() + 1 > MAX_ZERI_DAYS {
+        anyhow::bail!("单次择日最多 {} 天", MAX_ZERI_DAYS);
+    }
+    let birth_branch = req
+        .birth_branch
+        .as_deref()
+        .map(|name| zhi_index(name).ok_or_else(|| anyhow::anyhow!("无效的命主年支：{}", name)))
+        .transpose()?;
+    if let Some(officer) = req
+        .preferred_officers
+        .iter()
+        .find(|o| !OFFICERS.contains(&o.as_str()))
+    {
+        anyhow::bail!("无效的建除十二神：{}", officer);
+    }
+
+    let delta_t = req.delta_t_model.provider();
+    let mut examined_days = 0;
+    let mut candidates = Vec::new();
+    for date in req
+        .start_date
+        .iter_days()
+        .take_while(|d| *d <= req.end_date)
+    {
+        examined_days += 1;
+        let lunar = lunar_at_local_noon(date, req.tz_offset_minutes, delta_t.as_ref())?;
+        if let Some(candidate) = evaluate(req, birth_branch, date, lunar)? {
+            candidates.push(candidate);
+        }
+    }
+
+    let matched_days = candidates.len();
+    candidates.sort_by(|a, b| b.score.cmp(&a.score).then(a.date.cmp(&b.date)));
+    if let Some(limit) = req.limit {
+        candidates.truncate(limit);
+    }
+
+    Ok(ZeriResult {
+        examined_days,
+        matched_days,
+        candidates,
+        delta_t_provider: delta_t.name().to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(start: (i32, u32, u32), end: (i32, u32, u32)) -> ZeriRequest {
+        ZeriRequest {
+            start_date: NaiveDate::from_ymd_opt(start.0, start.1, start.2).unwrap(),
+            end_date: NaiveDate::from_ymd_opt(end.0, end.1, end.2).unwrap(),
+            tz_offset_minutes: 480,
+            activity: None,
+            birth_branch: None,
+            preferred_officers: Vec::new(),
+            avoid_month_break: false,
+            avoid_year_break: false,
+            limit: None,
+            delta_t_model: DeltaTModel::default(),
+        }
+    }
+
+    #[test]
+    fn test_activity_must_be_yi() {
+        let mut req = request((2025, 5, 1), (2025, 5, 31));
+        req.activity = Some("嫁娶".to_string());
+        let result = select_dates(&req).unwrap();
+        assert_eq!(result.examined_days, 31);
+        assert!(!result.candidates.is_empty());
+        for candidate in &result.candidates {
+            assert!(candidate.yi.contains(&"嫁娶".to_string()));
+            assert!(candidate.reasons.contains(&"宜嫁娶".to_string()));
+        }
+    }
+
+    #[test]
+    fn test_birth_branch_clash_and_breaks() {
+        let mut req = request((2025, 3, 1), (2025, 4, 30));
+        req.birth_branch = Some("马".to_string());
+        req.avoid_month_break = true;
+        req.avoid_year_break = true;
+        let result = select_dates(&req).unwrap();
+        assert!(result.matched_days < result.examined_days);
+        for candidate in &result.candidates {
+            let day = branch_of(&candidate.ganzhi_day).unwrap();
+            assert!(!zhi_chong(day, 6), "{} 冲午", candidate.date);
+            assert!(!zhi_chong(day, branch_of(&candidate.ganzhi_month).unwrap()));
+            assert!(!zhi_chong(day, branch_of(&candidate.ganzhi_year).unwrap()));
+            // 月破日即建除之"破"
+            assert_ne!(candidate.twelve_officer, "破");
+        }
+    }
+
+    #[test]
+    fn test_ranking_prefers_officers() {
+        let mut req = request((2025, 6, 1), (2025, 6, 30));
+        req.preferred_officers = vec!["成".to_string(), "开".to_string()];
+        req.limit = Some(5);
+        let result = select_dates(&req).unwrap();
+        assert_eq!(result.matched_days, 30);
+        assert_eq!(result.candidates.len(), 5);
+        for candidate in &result.candidates {
+            assert!(["成", "开"].contains(&candidate.twelve_officer.as_str()));
+        }
+        assert!(result
+            .candidates
+            .windows(2)
+            .all(|w| w[0].score > w[1].score || w[0].date < w[1].date));
+    }
+
+    #[test]
+    fn test_rejects_invalid_request() {
+        let mut req = request((2025, 6, 1), (2025, 5, 1));
+        assert!(select_dates(&req).is_err());
+
+        req = request((2025, 1, 1), (2026, 6, 1));
+        assert!(select_dates(&req).is_err());
+
+        req = request((2025, 1, 1), (2025, 1, 2));
+        req.birth_branch = Some("甲".to_string());
+        assert!(select_dates(&req).is_err());
+
+        req.birth_branch = None;
+        req.preferred_officers = vec!["吉".to_string()];
+        assert!(select_dates(&req).is_err());
+    }
+}
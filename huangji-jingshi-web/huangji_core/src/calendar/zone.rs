@@ -0,0 +1,379 @@
+//! 时区
+//!
+//! 本地时间既可按固定偏移（如 +08:00）也可按 IANA 时区（如 Asia/Shanghai）计算。
+//! IANA 时区按 tzdb 给出各时刻的历史偏移（含夏令时与早年的地方平时），
+//! 早于 tzdb 首条记录的时刻一律取其最早偏移。
+//!
+//! 本地时间换算为 UTC 时，夏令时回拨造成的重复时刻与拨快造成的空缺时刻
+//! 按 `LocalTimeResolution` 明确处理。
+
+use crate::astro::solar::jd_to_utc;
+use chrono::{DateTime, Duration, FixedOffset, LocalResult, NaiveDateTime, Offset, TimeZone, Utc};
+use chrono_tz::Tz;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// 时区：固定偏移或 IANA 时区
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum TimeZoneSpec {
+    Fixed(FixedOffset),
+    Iana(Tz),
+}
+
+impl Default for TimeZoneSpec {
+    /// 北京时间 +08:00
+    fn default() -> Self {
+        Self::Fixed(FixedOffset::east_opt(8 * 3600).expect("+08:00 有效"))
+    }
+}
+
+/// 本地时间不唯一时的取舍
+///
+/// - 重复时刻（夏令时回拨，同一钟点出现两次）：`Earlier` 取前一次，`Later` 取后一次
+/// - 空缺时刻（夏令时拨快，钟点不存在）：`Earlier` 按拨快后的偏移换算（落在空缺之前），
+///   `Later` 按拨快前的偏移换算（落在空缺之后）
+/// - `Compatible`：重复取前、空缺取后，与多数日历软件一致
+/// - `Reject`：均报错
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LocalTimeResolution {
+    #[default]
+    Compatible,
+    Earlier,
+    Later,
+    Reject,
+}
+
+impl LocalTimeResolution {
+    /// 解析请求参数：compatible、earlier、later、reject；空串为 compatible
+    pub fn parse(raw: &str) -> anyhow::Result<Self> {
+        match raw.trim().to_ascii_lowercase().as_str() {
+            "" | "compatible" => Ok(Self::Compatible),
+            "earlier" | "earliest" => Ok(Self::Earlier),
+            "later" | "latest" => Ok(Self::Later),
+            "reject" | "strict" => Ok(Self::Reject),
+            other => anyhow::bail!(
+                "无效的本地时间取舍：{}（可选 compatible、earlier、later、reject）",
+                other
+            ),
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Compatible => "compatible",
+            Self::Earlier => "earlier",
+            Self::Later => "later",
+            Self::Reject => "reject",
+        }
+    }
+}
+
+/// 解析 `±HH:MM`、`±HH` 形式的偏移（秒）
+fn parse_signed_hours(raw: &str) -> Option<i32> {
+    let (sign, body) = match raw.as_bytes().first()? {
+        b'+' => (1, &raw[1..]),
+        b'-' => (-1, &raw[1..]),
+        _ => return None,
+    };
+    let (h, m) = body.split_once(':').unwrap_or((body, "00"));
+    let digits = |s: &str| !s.is_empty() && s.len() <= 2 && s.bytes().all(|b| b.is_ascii_digit());
+    if !digits(h) || !digits(m) {
+        return None;
+    }
+    let (h, m) = (h.parse::<i32>().ok()?, m.parse::<i32>().ok()?);
+    (m < 60).then_some(sign * (h * 3600 + m * 60))
+}
+
+impl TimeZoneSpec {
+    /// 固定偏移（分钟，东为正）
+    pub fn from_offset_minutes(minutes: i32) -> anyhow::Result<Self> {
+        FixedOffset::east_opt(minutes * 60)
+            .map(Self::Fixed)
+            .ok_or_else(|| anyhow::anyhow!("无效时区偏移：{} 分钟", minutes))
+    }
+
+    /// 解析请求参数：
+    /// - IANA 时区名，如 `Asia/Shanghai`
+    /// - `UTC`、`Z`，或 `±HH:MM`、`UTC±H[:MM]`、`GMT±H[:MM]`
+    /// - 整数按分钟计（东为正），与各接口的 `tz` 参数一致，如 `480`、`-300`；
+    ///   按小时须写作 `UTC+8`
+    /// - 空串为北京时间 +08:00
+    pub fn parse(raw: &str) -> anyhow::Result<Self> {
+        let raw = raw.trim();
+        if raw.is_empty() {
+            return Ok(Self::default());
+        }
+        let upper = raw.to_ascii_uppercase();
+        if matches!(upper.as_str(), "UTC" | "GMT" | "Z") {
+            return Self::from_offset_minutes(0);
+        }
+        if let Ok(minutes) = raw.parse::<i32>() {
+            return Self::from_offset_minutes(minutes);
+        }
+        let hours = upper
+            .strip_prefix("UTC")
+            .or_else(|| upper.strip_prefix("GMT"))
+            .unwrap_or(&upper);
+        if hours.contains(':') || hours.len() < upper.len() {
+            let seconds = parse_signed_hours(hours)
+                .ok_or_else(|| anyhow::anyhow!("无效时区偏移：{}", raw))?;
+            return FixedOffset::east_opt(seconds)
+                .map(Self::Fixed)
+                .ok_or_else(|| anyhow::anyhow!("无效时区偏移：{}", raw));
+        }
+        raw.parse::<Tz>().map(Self::Iana).map_err(|_| {
+            anyhow::anyhow!(
+                "无效时区：{}（可用 IANA 时区名如 Asia/Shanghai，或 +08:00、UTC+8、480）",
+                raw
+            )
+        })
+    }
+
+    /// 请求参数写法：IANA 时区名或 `±HH:MM`
+    pub fn name(&self) -> String {
+        match self {
+            Self::Fixed(offset) => offset.to_string(),
+            Self::Iana(tz) => tz.name().to_string(),
+        }
+    }
+
+    /// 某一 UTC 时刻的偏移
+    pub fn offset_at(&self, utc: &DateTime<Utc>) -> FixedOffset {
+        match self {
+            Self::Fixed(offset) => *offset,
+            Self::Iana(tz) => tz.offset_from_utc_datetime(&utc.naive_utc()).fix(),
+        }
+    }
+
+    /// 某一儒略日 (UTC) 的偏移（秒，东为正）
+    ///
+    /// IANA 时区须把儒略日换算为 UTC 时刻再查历史偏移，超出可表示的时刻范围时报错。
+    pub fn offset_seconds_at_jd(&self, jd: f64) -> anyhow::Result<i32> {
+        match self {
+            Self::Fixed(offset) => Ok(offset.local_minus_utc()),
+            Self::Iana(tz) => {
+                let utc = jd_to_utc(jd).ok_or_else(|| {
+                    anyhow::anyhow!(
+                        "儒略日 {} 超出可表示的时刻范围，无法取 {} 的历史偏移",
+                        jd,
+                        tz.name()
+                    )
+                })?;
+                Ok(self.offset_at(&utc).local_minus_utc())
+            }
+        }
+    }
+
+    /// UTC 时刻转本地时间
+    pub fn to_local(&self, utc: &DateTime<Utc>) -> DateTime<FixedOffset> {
+        utc.with_timezone(&self.offset_at(utc))
+    }
+
+    /// 本地时间转 UTC 时刻；重复或空缺的钟点按 `resolution` 取舍
+    pub fn from_local(
+        &self,
+        local: &NaiveDateTime,
+        resolution: LocalTimeResolution,
+    ) -> anyhow::Result<DateTime<Utc>> {
+        let tz = match self {
+            Self::Fixed(offset) => {
+                return Ok((*local - Duration::seconds(offset.local_minus_utc() as i64)).and_utc())
+            }
+            Self::Iana(tz) => tz,
+        };
+        match tz.from_local_datetime(local) {
+            LocalResult::Single(dt) => Ok(dt.with_timezone(&Utc)),
+            LocalResult::Ambiguous(first, second) => match resolution {
+                LocalTimeResolution::Compatible | LocalTimeResolution::Earlier => {
+                    Ok(first.with_timezone(&Utc))
+                }
+                LocalTimeResolution::Later => Ok(second.with_timezone(&Utc)),
+                LocalTimeResolution::Reject => anyhow::bail!(
+                    "本地时间 {} 在 {} 出现两次（夏令时回拨），请指定 earlier 或 later",
+                    local,
+                    self
+                ),
+            },
+            LocalResult::None => {
+                // 空缺前后的偏移：相邻两次换时至少相隔数日
+                let probe = local.and_utc();
+                let before = self
+                    .offset_at(&(probe - Duration::days(1)))
+                    .local_minus_utc();
+                let after = self
+                    .offset_at(&(probe + Duration::days(1)))
+                    .local_minus_utc();
+                let offset = match resolution {
+                    LocalTimeResolution::Earlier => after,
+                    LocalTimeResolution::Compatible | LocalTimeResolution::Later => before,
+                    LocalTimeResolution::Reject => anyhow::bail!(
+                        "本地时间 {} 在 {} 不存在（夏令时拨快），请指定 earlier 或 later",
+                        local,
+                        self
+                    ),
+                };
+                Ok((*local - Duration::seconds(offset as i64)).and_utc())
+            }
+        }
+    }
+}
+
+impl fmt::Display for TimeZoneSpec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.name())
+    }
+}
+
+impl From<TimeZoneSpec> for String {
+    fn from(zone: TimeZoneSpec) -> Self {
+        zone.name()
+    }
+}
+
+impl TryFrom<String> for TimeZoneSpec {
+    type Error = anyhow::Error;
+
+    fn try_from(raw: String) -> anyhow::Result<Self> {
+        Self::parse(&raw)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let beijing = FixedOffset::east_opt(8 * 3600).unwrap();
+        for raw in ["", "+08:00", "UTC+8", "gmt+08:00", "480"] {
+            assert_eq!(
+                TimeZoneSpec::parse(raw).unwrap(),
+                TimeZoneSpec::Fixed(beijing),
+                "{}",
+                raw
+            );
+        }
+        let india = TimeZoneSpec::parse("UTC+5:30").unwrap();
+        assert_eq!(india.name(), "+05:30");
+        assert_eq!(TimeZoneSpec::parse("-300").unwrap().name(), "-05:00");
+        assert_eq!(TimeZoneSpec::parse("Z").unwrap().name(), "+00:00");
+        assert_eq!(
+            TimeZoneSpec::parse(" America/New_York ").unwrap(),
+            TimeZoneSpec::Iana(chrono_tz::America::New_York)
+        );
+        for raw in ["Mars/Olympus", "+8:75", "UTC+", "UTC+123", "100000"] {
+            assert!(TimeZoneSpec::parse(raw).is_err(), "{}", raw);
+        }
+    }
+
+    #[test]
+    fn test_historical_offsets() {
+        let shanghai = TimeZoneSpec::parse("Asia/Shanghai").unwrap();
+        // 1988 年夏令时 +09:00，冬季 +08:00
+        let summer = Utc.with_ymd_and_hms(1988, 7, 1, 0, 0, 0).unwrap();
+        let winter = Utc.with_ymd_and_hms(1988, 12, 1, 0, 0, 0).unwrap();
+        assert_eq!(shanghai.offset_at(&summer).local_minus_utc(), 9 * 3600);
+        assert_eq!(shanghai.offset_at(&winter).local_minus_utc(), 8 * 3600);
+        // 1901 年以前取地方平时 +08:05:43
+        let lmt = Utc.with_ymd_and_hms(1890, 1, 1, 0, 0, 0).unwrap();
+        assert_eq!(
+            shanghai.offset_at(&lmt).local_minus_utc(),
+            8 * 3600 + 5 * 60 + 43
+        );
+
+        // 儒略日取偏移：固定偏移不限范围，IANA 时区超出可表示的时刻即报错
+        let jd_1988_summer = 2447343.5;
+        assert_eq!(
+            shanghai.offset_seconds_at_jd(jd_1988_summer).unwrap(),
+            9 * 3600
+        );
+        assert!(shanghai.offset_seconds_at_jd(1e12).is_err());
+        assert_eq!(
+            TimeZoneSpec::default().offset_seconds_at_jd(1e12).unwrap(),
+            8 * 3600
+        );
+
+        let zone: TimeZoneSpec = serde_json::from_str("\"Asia/Shanghai\"").unwrap();
+        assert_eq!(zone, shanghai);
+        assert_eq!(serde_json::to_string(&zone).unwrap(), "\"Asia/Shanghai\"");
+    }
+
+    #[test]
+    fn test_from_local_resolution() {
+        let shanghai = TimeZoneSpec::parse("Asia/Shanghai").unwrap();
+        let naive = |raw: &str| NaiveDateTime::parse_from_str(raw, "%Y-%m-%dT%H:%M").unwrap();
+        let utc = |raw: &str| naive(raw).and_utc();
+
+        // 1988-04-17 02:00 拨快至 03:00，02:30 不存在
+        let gap = naive("1988-04-17T02:30");
+        assert_eq!(
+            shanghai
+                .from_local(&gap, LocalTimeResolution::Earlier)
+                .unwrap(),
+            utc("1988-04-16T17:30")
+        );
+        assert_eq!(
+            shanghai
+                .from_local(&gap, LocalTimeResolution::Later)
+                .unwrap(),
+            utc("1988-04-16T18:30")
+        );
+        assert_eq!(
+            shanghai
+                .from_local(&gap, LocalTimeResolution::Compatible)
+                .unwrap(),
+            utc("1988-04-16T18:30")
+        );
+        assert!(shanghai
+            .from_local(&gap, LocalTimeResolution::Reject)
+            .is_err());
+
+        // 1988-09-11 02:00 回拨至 01:00，01:30 出现两次
+        let fold = naive("1988-09-11T01:30");
+        assert_eq!(
+            shanghai
+                .from_local(&fold, LocalTimeResolution::Compatible)
+                .unwrap(),
+            utc("1988-09-10T16:30")
+        );
+        assert_eq!(
+            shanghai
+                .from_local(&fold, LocalTimeResolution::Later)
+                .unwrap(),
+            utc("1988-09-10T17:30")
+        );
+        assert!(shanghai
+            .from_local(&fold, LocalTimeResolution::Reject)
+            .is_err());
+
+        // 夏令时内与固定偏移均唯一
+        let summer = naive("1988-07-01T08:00");
+        assert_eq!(
+            shanghai
+                .from_local(&summer, LocalTimeResolution::Reject)
+                .unwrap(),
+            utc("1988-06-30T23:00")
+        );
+        assert_eq!(
+            TimeZoneSpec::default()
+                .from_local(&summer, LocalTimeResolution::Reject)
+                .unwrap(),
+            utc("1988-07-01T00:00")
+        );
+
+        for resolution in [
+            LocalTimeResolution::Compatible,
+            LocalTimeResolution::Earlier,
+            LocalTimeResolution::Later,
+            LocalTimeResolution::Reject,
+        ] {
+            assert_eq!(
+                LocalTimeResolution::parse(resolution.name()).unwrap(),
+                resolution
+            );
+        }
+        assert!(LocalTimeResolution::parse("nearest").is_err());
+    }
+}
@@ -0,0 +1,369 @@
+//! 星历表
+//!
+//! `compute_sky` 只给出一个时刻的星空；本模块按起止时刻与步长逐行列出指定天体的
+//! 地心视赤经赤纬、视黄经黄纬、站心地平坐标、地心距、距角、相角与视星等，
+//! 可导出 CSV 或 JSON，用于制作教学资料或与其他天文软件比对。
+
+use crate::astro::coords::{EquatorialCoord, AU_KM};
+use crate::astro::delta_t::DeltaTModel;
+use crate::astro::magnitude::{
+    moon_magnitude, planet_magnitude, sun_magnitude, PhaseGeometry, SaturnRing,
+};
+use crate::astro::observer::Observer;
+use crate::astro::planets::Planet;
+use crate::astro::refraction::Atmosphere;
+use crate::astro::sidereal::local_apparent_sidereal_time;
+use crate::astro::solar::{jd_to_utc, utc_to_jd};
+use crate::astro::time::TimeScales;
+use crate::sky::{body_ecliptic, body_equatorial, EclipticPosition, SkyBody};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// 单张星历表的最大行数
+pub const MAX_EPHEMERIS_ROWS: usize = 100_000;
+
+const MINUTES_PER_DAY: f64 = 1440.0;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EphemerisRequest {
+    /// 列出的天体，缺省为日、月与七大行星
+    #[serde(default)]
+    pub bodies: Option<Vec<SkyBody>>,
+    /// 起止时刻（UTC，含两端）
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+    /// 步长 (分钟)
+    pub step_minutes: u32,
+    pub observer: Observer,
+    /// 折射所用的气压与气温
+    #[serde(default)]
+    pub atmosphere: Atmosphere,
+    /// ΔT 模型（默认 Espenak–Meeus）
+    #[serde(default)]
+    pub delta_t_model: DeltaTModel,
+}
+
+/// 星历表的一行：某一时刻的一个天体
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EphemerisRow {
+    pub datetime: DateTime<Utc>,
+    pub jd_tt: f64,
+    /// 英文名，与 `CelestialBody::name` 一致
+    pub body: String,
+    /// 地心视赤经、赤纬 (度)
+    pub ra_deg: f64,
+    pub dec_deg: f64,
+    /// 地心视黄经、黄纬 (度)
+    pub ecl_lon_deg: f64,
+    pub ecl_lat_deg: f64,
+    /// 站心几何高度与方位（北起向东）(度)
+    pub alt_deg: f64,
+    pub az_deg: f64,
+    /// 视高度：几何高度加大气折射
+    pub apparent_alt_deg: f64,
+    /// 地心距 (AU)
+    pub distance_au: f64,
+    /// 距角：地心所见与太阳的角距 (度)，太阳为 0
+    pub elongation_deg: f64,
+    /// 相角 (度)；太阳为 `None`
+    pub phase_angle_deg: Option<f64>,
+    /// 视星等
+    pub magnitude: f64,
+}
+
+/// 导出格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EphemerisFormat {
+    #[default]
+    Csv,
+    Json,
+}
+
+impl EphemerisFormat {
+    /// 解析请求参数：`csv`（默认）或 `json`
+    pub fn parse(raw: &str) -> anyhow::Result<Self> {
+        match raw.trim().to_ascii_lowercase().as_str() {
+            "" | "csv" => Ok(Self::Csv),
+            "json" => Ok(Self::Json),
+            other => anyhow::bail!("无效的导出格式：{}（可选 csv、json）", other),
+        }
+    }
+}
+
+/// 解析步长：`30m`、`6h`、`1d`，不带单位按分钟
+pub fn parse_step_minutes(raw: &str) -> anyhow::Result<u32> {
+    let raw = raw.trim();
+    let (number, unit) = match raw.char_indices().last() {
+        Some((i, c)) if c.is_ascii_alphabetic() => (&raw[..i], c.to_ascii_lowercase()),
+        _ => (raw, 'm'),
+    };
+    let value: u32 = number
+        .trim()
+        .parse()
+        .map_err(|_| anyhow::anyhow!("无效的步长：{}", raw))?;
+    let minutes = match unit {
+        'm' => Some(value),
+        'h' => value.checked_mul(60),
+        'd' => value.checked_mul(1440),
+        _ => anyhow::bail!("无效的步长单位：{}（可选 m、h、d）", raw),
+    };
+    match minutes {
+        Some(minutes) if minutes > 0 => Ok(minutes),
+        _ => anyhow::bail!("步长须大于 0 且不超过 {} 分钟", u32::MAX),
+    }
+}
+
+/// 黄道球坐标转直角坐标
+fn rectangular(position: &EclipticPosition) -> [f64; 3] {
+    let (lon, lat) = (position.lon_deg.to_radians(), position.lat_deg.to_radians());
+    let r = position.distance_au;
+    [
+        r * lat.cos() * lon.cos(),
+        r * lat.cos() * lon.sin(),
+        r * lat.sin(),
+    ]
+}
+
+/// 土星光环几何：日心方向由地心向量减去太阳的地心向量
+fn saturn_ring(jd_tt: f64, saturn: &EclipticPosition, sun: &EclipticPosition) -> SaturnRing {
+    let (p, s) = (rectangular(saturn), rectangular(sun));
+    let h = [p[0] - s[0], p[1] - s[1], p[2] - s[2]];
+    SaturnRing::new(
+        jd_tt,
+        h[1].atan2(h[0]).to_degrees(),
+        h[2].atan2((h[0] * h[0] + h[1] * h[1]).sqrt()).to_degrees(),
+        saturn.lon_deg,
+        saturn.lat_deg,
+    )
+}
+
+/// 生成星历表，按时刻、再按请求中天体的顺序排列
+pub fn generate_ephemeris(req: &EphemerisRequest) -> anyhow::Result<Vec<EphemerisRow>> {
+    if req.end < req.start {
+        anyhow::bail!("结束时刻 {} 早于起始时刻 {}", req.end, req.start);
+    }
+    if req.step_minutes == 0 {
+        anyhow::bail!("步长须大于 0");
+    }
+    req.atmosphere.validate()?;
+    let bodies = req.bodies.as_deref().unwrap_or(&SkyBody::ALL);
+    if bodies.is_empty() {
+        anyhow::bail!("至少须指定一个天体");
+    }
+    let start = utc_to_jd(&req.start);
+    let step = req.step_minutes as f64 / MINUTES_PER_DAY;
+    let epochs = ((utc_to_jd(&req.end) - start) / step + 1e-9).floor() as usize + 1;
+    if epochs.saturating_mul(bodies.len()) > MAX_EPHEMERIS_ROWS {
+        anyhow::bail!(
+            "星历表共 {} 行，超过上限 {} 行，请增大步长或缩短时段",
+            epochs.saturating_mul(bodies.len()),
+            MAX_EPHEMERIS_ROWS
+        );
+    }
+
+    let provider = req.delta_t_model.provider();
+    let mut rows = Vec::with_capacity(epochs * bodies.len());
+    for k in 0..epochs {
+        let scales = TimeScales::from_jd_utc_with(start + k as f64 * step, provider.as_ref());
+        let Some(datetime) = jd_to_utc(scales.jd_utc) else {
+            continue;
+        };
+        let jd_tt = scales.jd_tt;
+        let lst_deg = local_apparent_sidereal_time(&scales, req.observer.lon_deg);
+        let sun = body_ecliptic(SkyBody::Sun, jd_tt);
+
+        for &body in bodies {
+            let ecliptic = body_ecliptic(body, jd_tt);
+            let equatorial = body_equatorial(body, jd_tt);
+            let (topo_ra, topo_dec) = req.observer.topocentric_equatorial(
+                equatorial.ra_deg,
+                equatorial.dec_deg,
+                equatorial.distance_au * AU_KM,
+                lst_deg - equatorial.ra_deg,
+            );
+            let horizontal = EquatorialCoord {
+                ra_deg: topo_ra,
+                dec_deg: topo_dec,
+            }
+            .to_horizontal(req.observer.lat_deg, lst_deg);
+            let (alt_deg, az_deg) = (horizontal.alt_deg, horizontal.az_deg);
+
+            let elongation_deg = if body == SkyBody::Sun {
+                0.0
+            } else {
+                ecliptic.separation(&sun)
+            };
+            let geometry = PhaseGeometry::from_elongation(
+                ecliptic.distance_au,
+                sun.distance_au,
+                elongation_deg,
+            );
+            let (phase_angle_deg, magnitude) = match body {
+                SkyBody::Sun => (None, sun_magnitude(sun.distance_au)),
+                SkyBody::Moon => (
+                    Some(geometry.phase_angle_deg),
+                    moon_magnitude(
+                        ecliptic.distance_au * AU_KM,
+                        geometry.r_au,
+                        geometry.phase_angle_deg,
+                    ),
+                ),
+                planet => {
+                    let planet = planet.planet().expect("行星均有轨道根数");
+                    let ring =
+                        (planet == Planet::Saturn).then(|| saturn_ring(jd_tt, &ecliptic, &sun));
+                    (
+                        Some(geometry.phase_angle_deg),
+                        planet_magnitude(planet, &geometry, ring.as_ref()),
+                    )
+                }
+            };
+
+            rows.push(EphemerisRow {
+                datetime,
+                jd_tt,
+                body: body.name().to_string(),
+                ra_deg: equatorial.ra_deg,
+                dec_deg: equatorial.dec_deg,
+                ecl_lon_deg: ecliptic.lon_deg,
+                ecl_lat_deg: ecliptic.lat_deg,
+                alt_deg,
+                az_deg,
+                apparent_alt_deg: req.atmosphere.apparent_altitude(alt_deg),
+                distance_au: ecliptic.distance_au,
+                elongation_deg,
+                phase_angle_deg,
+                magnitude,
+            });
+        }
+    }
+    Ok(rows)
+}
+
+/// CSV 表头
+const CSV_HEADER: &str = "datetime_utc,jd_tt,body,ra_deg,dec_deg,ecl_lon_deg,ecl_lat_deg,\
+alt_deg,az_deg,apparent_alt_deg,distance_au,elongation_deg,phase_angle_deg,magnitude";
+
+/// 导出为 CSV 或 JSON 文本
+pub fn render_ephemeris(rows: &[EphemerisRow], format: EphemerisFormat) -> anyhow::Result<String> {
+    match format {
+        EphemerisFormat::Json => Ok(serde_json::to_string_pretty(rows)?),
+        EphemerisFormat::Csv => {
+            let mut out = String::from(CSV_HEADER);
+            out.push('\n');
+            for row in rows {
+                let phase = row
+                    .phase_angle_deg
+                    .map(|i| format!("{:.4}", i))
+                    .unwrap_or_default();
+                out.push_str(&format!(
+                    "{},{:.6},{},{:.6},{:.6},{:.6},{:.6},{:.4},{:.4},{:.4},{:.8},{:.4},{},{:.2}\n",
+                    row.datetime.format("%Y-%m-%dT%H:%M:%SZ"),
+                    row.jd_tt,
+                    row.body,
+                    row.ra_deg,
+                    row.dec_deg,
+                    row.ecl_lon_deg,
+                    row.ecl_lat_deg,
+                    row.alt_deg,
+                    row.az_deg,
+                    row.apparent_alt_deg,
+                    row.distance_au,
+                    row.elongation_deg,
+                    phase,
+                    row.magnitude,
+                ));
+            }
+            Ok(out)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn request(bodies: &[SkyBody], days: u32, step_minutes: u32) -> EphemerisRequest {
+        let start = Utc.with_ymd_and_hms(2020, 3, 24, 0, 0, 0).unwrap();
+        EphemerisRequest {
+            bodies: Some(bodies.to_vec()),
+            start,
+            end: start + chrono::Duration::days(days as i64),
+            step_minutes,
+            observer: Observer::new(39.9, 116.4),
+            atmosphere: Atmosphere::default(),
+            delta_t_model: DeltaTModel::default(),
+        }
+    }
+
+    #[test]
+    fn test_ephemeris_rows_and_values() {
+        // 2020-03-24 金星东大距 46.1°，视星等约 −4.4
+        let rows = generate_ephemeris(&request(
+            &[SkyBody::Sun, SkyBody::Venus, SkyBody::Saturn],
+            2,
+            720,
+        ))
+        .unwrap();
+        assert_eq!(rows.len(), 5 * 3);
+        assert_eq!(rows[0].body, "Sun");
+        assert_eq!(rows[1].body, "Venus");
+        assert!(rows[3].datetime > rows[0].datetime);
+
+        let venus = &rows[1];
+        assert!((venus.elongation_deg - 46.08).abs() < 0.1);
+        assert!(
+            (venus.magnitude - (-4.4)).abs() < 0.2,
+            "{}",
+            venus.magnitude
+        );
+        let sun = &rows[0];
+        assert_eq!(sun.elongation_deg, 0.0);
+        assert!(sun.phase_angle_deg.is_none());
+        assert!((sun.magnitude - (-26.74)).abs() < 0.03);
+        // 2020 年春土星约 +0.7 等
+        let saturn = &rows[2];
+        assert!((saturn.magnitude - 0.7).abs() < 0.3, "{}", saturn.magnitude);
+
+        // 北京 0h UTC（当地 8 时）太阳已升起，位于东南
+        assert!(sun.alt_deg > 15.0 && sun.az_deg > 90.0 && sun.az_deg < 135.0);
+        assert!(sun.apparent_alt_deg > sun.alt_deg);
+    }
+
+    #[test]
+    fn test_render_csv_and_json() {
+        let rows = generate_ephemeris(&request(&[SkyBody::Moon, SkyBody::Mars], 1, 1440)).unwrap();
+        let csv = render_ephemeris(&rows, EphemerisFormat::Csv).unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 1 + 4);
+        assert_eq!(lines[0], CSV_HEADER);
+        assert!(lines[1].starts_with("2020-03-24T00:00:00Z,"));
+        assert_eq!(lines[1].split(',').count(), CSV_HEADER.split(',').count());
+
+        let json = render_ephemeris(&rows, EphemerisFormat::Json).unwrap();
+        let parsed: Vec<EphemerisRow> = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.len(), 4);
+    }
+
+    #[test]
+    fn test_step_and_validation() {
+        assert_eq!(parse_step_minutes("30").unwrap(), 30);
+        assert_eq!(parse_step_minutes("6h").unwrap(), 360);
+        assert_eq!(parse_step_minutes("1D").unwrap(), 1440);
+        assert!(parse_step_minutes("0m").is_err());
+        assert!(parse_step_minutes("1w").is_err());
+        assert_eq!(
+            EphemerisFormat::parse("JSON").unwrap(),
+            EphemerisFormat::Json
+        );
+
+        assert!(generate_ephemeris(&request(&[SkyBody::Sun], 1, 0)).is_err());
+        assert!(generate_ephemeris(&request(&[], 1, 60)).is_err());
+        assert!(generate_ephemeris(&request(&SkyBody::ALL, 3650, 1)).is_err());
+        let mut reversed = request(&[SkyBody::Sun], 1, 60);
+        std::mem::swap(&mut reversed.start, &mut reversed.end);
+        assert!(generate_ephemeris(&reversed).is_err());
+    }
+}
@@ -0,0 +1,368 @@
+//! 分野
+//!
+//! 以七政所在之宿（赤道入宿）与十二次（黄经每 30° 一次，星纪起冬至）推其所应之国与州，
+//! 并结合 `huangji-stars.json` 中皇极星官的 `fenye`/`gua` 属性，给出某一时刻"何地受应"的报告。
+//!
+//! 宿与十二次的分野表保存在 `data/fenye.json`：二十八宿之国取《汉书·地理志》、州取《史记·天官书》，
+//! 十二次分野取《晋书·天文志》。调整分野只需修改数据文件。
+
+use crate::astro::delta_t::DeltaTModel;
+use crate::astro::mansions::{DeterminativeSystem, MansionPlacement, MansionTable};
+use crate::astro::solar::utc_to_jd;
+use crate::astro::time::TimeScales;
+use crate::sky::{body_ecliptic, body_equatorial, SkyBody};
+use chrono::{DateTime, Utc};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+
+/// 分野所指的国与州
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Region {
+    /// 列国，如 郑
+    pub state: String,
+    /// 九州，如 兖州
+    pub province: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct MansionFenye {
+    mansion: String,
+    #[serde(flatten)]
+    region: Region,
+}
+
+/// 十二次
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Station {
+    pub name: String,
+    /// 起始黄经 (度)
+    pub start_longitude: f64,
+    #[serde(flatten)]
+    pub region: Region,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct FenyeTable {
+    mansions: Vec<MansionFenye>,
+    stations: Vec<Station>,
+}
+
+static FENYE: Lazy<FenyeTable> = Lazy::new(|| {
+    let table: FenyeTable =
+        serde_json::from_str(include_str!("../data/fenye.json")).expect("fenye.json 解析失败");
+    assert_eq!(table.mansions.len(), 28, "fenye.json 应含二十八宿");
+    assert_eq!(table.stations.len(), 12, "fenye.json 应含十二次");
+    table
+});
+
+/// 皇极星官（`huangji-stars.json`）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HuangjiStar {
+    pub id: String,
+    pub name_cn: String,
+    pub name_hj: String,
+    pub hip: Option<u32>,
+    /// 所配之卦，如 坤
+    pub gua: String,
+    /// 所主分野：某宿（如 女宿）或 中宫
+    pub fenye: String,
+    #[serde(default)]
+    pub note: String,
+}
+
+#[derive(Deserialize)]
+struct HuangjiStarCollection {
+    features: Vec<HuangjiStarFeature>,
+}
+
+#[derive(Deserialize)]
+struct HuangjiStarFeature {
+    id: String,
+    properties: HuangjiStarProperties,
+}
+
+#[derive(Deserialize)]
+struct HuangjiStarProperties {
+    name_cn: String,
+    name_hj: String,
+    hip: Option<u32>,
+    #[serde(default)]
+    gua: String,
+    #[serde(default)]
+    fenye: String,
+    #[serde(default)]
+    note: String,
+}
+
+static HUANGJI_STARS: Lazy<Vec<HuangjiStar>> = Lazy::new(|| {
+    let collection: HuangjiStarCollection = serde_json::from_str(include_str!(
+        "../../backend/data/celestial/cultures/huangji-stars.json"
+    ))
+    .expect("huangji-stars.json 解析失败");
+    collection
+        .features
+        .into_iter()
+        .map(|feature| HuangjiStar {
+            id: feature.id,
+            name_cn: feature.properties.name_cn,
+            name_hj: feature.properties.name_hj,
+            hip: feature.properties.hip,
+            gua: feature.properties.gua,
+            fenye: feature.properties.fenye,
+            note: feature.properties.note,
+        })
+        .collect()
+});
+
+/// 全部皇极星官
+pub fn huangji_stars() -> &'static [HuangjiStar] {
+    &HUANGJI_STARS
+}
+
+/// 某宿的分野
+pub fn mansion_region(mansion: &str) -> Option<&'static Region> {
+    FENYE
+        .mansions
+        .iter()
+        .find(|m| m.mansion == mansion)
+        .map(|m| &m.region)
+}
+
+/// 黄经所在的十二次
+pub fn station_at(longitude: f64) -> Option<&'static Station> {
+    FENYE.stations.iter().min_by(|a, b| {
+        let da = (longitude - a.start_longitude).rem_euclid(360.0);
+        let db = (longitude - b.start_longitude).rem_euclid(360.0);
+        da.total_cmp(&db)
+    })
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FenyeRequest {
+    pub datetime: DateTime<Utc>,
+    /// 二十八宿距星体系（默认今距星）
+    #[serde(default)]
+    pub mansion_system: DeterminativeSystem,
+    /// ΔT 模型（默认 Espenak–Meeus）
+    #[serde(default)]
+    pub delta_t_model: DeltaTModel,
+}
+
+/// 一政所在之宿、次及其分野
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BodyFenye {
+    /// 英文名，与 `CelestialBody::name` 一致
+    pub body: String,
+    /// 中文古名，如 荧惑
+    pub name_zh: String,
+    /// 视黄经、视赤经 (度)
+    pub ecliptic_lon_deg: f64,
+    pub ra_deg: f64,
+    pub mansion: MansionPlacement,
+    pub mansion_region: Option<Region>,
+    /// 十二次，如 星纪
+    pub station: Option<String>,
+    pub station_region: Option<Region>,
+}
+
+/// 皇极星官与当时七政的关联
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StarFenye {
+    #[serde(flatten)]
+    pub star: HuangjiStar,
+    /// 星官所主之宿的分野；主中宫者为 `None`
+    pub region: Option<Region>,
+    /// 此刻居于该宿的七政
+    pub activated_by: Vec<String>,
+}
+
+/// 受应之地
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RegionImpact {
+    #[serde(flatten)]
+    pub region: Region,
+    /// 所临七政（中文古名）
+    pub bodies: Vec<String>,
+    /// 经由之宿
+    pub mansions: Vec<String>,
+    /// 经由之次
+    pub stations: Vec<String>,
+    /// 相关皇极星官所配之卦
+    pub gua: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FenyeReport {
+    pub datetime: DateTime<Utc>,
+    pub jd_tt: f64,
+    pub mansion_system: DeterminativeSystem,
+    /// 实际使用的 ΔT 提供者
+    pub delta_t_provider: String,
+    /// 七政所在
+    pub bodies: Vec<BodyFenye>,
+    /// 皇极星官
+    pub stars: Vec<StarFenye>,
+    /// 受应之地，按所临七政数由多到少
+    pub regions: Vec<RegionImpact>,
+}
+
+fn push_unique(list: &mut Vec<String>, value: &str) {
+    if !list.iter().any(|v| v == value) {
+        list.push(value.to_string());
+    }
+}
+
+fn region_entry<'a>(regions: &'a mut Vec<RegionImpact>, region: &Region) -> &'a mut RegionImpact {
+    let index = match regions.iter().position(|r| r.region == *region) {
+        Some(index) => index,
+        None => {
+            regions.push(RegionImpact {
+                region: region.clone(),
+                bodies: Vec::new(),
+                mansions: Vec::new(),
+                stations: Vec::new(),
+                gua: Vec::new(),
+            });
+            regions.len() - 1
+        }
+    };
+    &mut regions[index]
+}
+
+/// 计算某一时刻的分野报告
+pub fn compute_fenye(req: &FenyeRequest) -> FenyeReport {
+    let provider = req.delta_t_model.provider();
+    let scales = TimeScales::from_jd_utc_with(utc_to_jd(&req.datetime), provider.as_ref());
+    let jd_tt = scales.jd_tt;
+    let mansions = MansionTable::new(jd_tt, req.mansion_system);
+
+    let bodies: Vec<BodyFenye> = SkyBody::SEVEN_LUMINARIES
+        .iter()
+        .map(|&body| {
+            let ecliptic = body_ecliptic(body, jd_tt);
+            let ra_deg = body_equatorial(body, jd_tt).ra_deg;
+            let mansion = mansions.placement(ra_deg);
+            let station = station_at(ecliptic.lon_deg);
+            BodyFenye {
+                body: body.name().to_string(),
+                name_zh: body.name_zh().to_string(),
+                ecliptic_lon_deg: ecliptic.lon_deg,
+                ra_deg,
+                mansion_region: mansion_region(&mansion.name).cloned(),
+                mansion,
+                station: station.map(|s| s.name.clone()),
+                station_region: station.map(|s| s.region.clone()),
+            }
+        })
+        .collect();
+
+    let stars: Vec<StarFenye> = huangji_stars()
+        .iter()
+        .map(|star| {
+            let mansion = star.fenye.strip_suffix('宿');
+            let activated_by = bodies
+                .iter()
+                .filter(|b| mansion == Some(b.mansion.name.as_str()))
+                .map(|b| b.name_zh.clone())
+                .collect();
+            StarFenye {
+                star: star.clone(),
+                region: mansion.and_then(mansion_region).cloned(),
+                activated_by,
+            }
+        })
+        .collect();
+
+    let mut regions: Vec<RegionImpact> = Vec::new();
+    for body in &bodies {
+        if let Some(region) = &body.mansion_region {
+            let impact = region_entry(&mut regions, region);
+            push_unique(&mut impact.bodies, &body.name_zh);
+            push_unique(&mut impact.mansions, &body.mansion.name);
+        }
+        if let (Some(region), Some(station)) = (&body.station_region, &body.station) {
+            let impact = region_entry(&mut regions, region);
+            push_unique(&mut impact.bodies, &body.name_zh);
+            push_unique(&mut impact.stations, station);
+        }
+    }
+    for star in stars.iter().filter(|s| !s.activated_by.is_empty()) {
+        if let Some(region) = &star.region {
+            push_unique(&mut region_entry(&mut regions, region).gua, &star.star.gua);
+        }
+    }
+    regions.sort_by_key(|r| std::cmp::Reverse(r.bodies.len()));
+
+    FenyeReport {
+        datetime: req.datetime,
+        jd_tt,
+        mansion_system: req.mansion_system,
+        delta_t_provider: provider.name().to_string(),
+        bodies,
+        stars,
+        regions,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_fenye_tables() {
+        assert_eq!(FENYE.mansions.len(), 28);
+        assert_eq!(FENYE.stations.len(), 12);
+        let jiao = mansion_region("角").unwrap();
+        assert_eq!(
+            (jiao.state.as_str(), jiao.province.as_str()),
+            ("郑", "兖州")
+        );
+        assert_eq!(station_at(275.0).unwrap().name, "星纪");
+        assert_eq!(station_at(359.9).unwrap().name, "娵訾");
+        assert_eq!(station_at(0.0).unwrap().name, "降娄");
+
+        let vega = huangji_stars().iter().find(|s| s.id == "hj_vega").unwrap();
+        assert_eq!((vega.fenye.as_str(), vega.gua.as_str()), ("女宿", "离"));
+    }
+
+    #[test]
+    fn test_great_conjunction_2020() {
+        // 2020-12-21 木土相合于斗宿、玄枵：吴（斗宿）与齐（玄枵）同受岁星、镇星
+        let report = compute_fenye(&FenyeRequest {
+            datetime: Utc.with_ymd_and_hms(2020, 12, 21, 18, 0, 0).unwrap(),
+            mansion_system: DeterminativeSystem::Modern,
+            delta_t_model: DeltaTModel::default(),
+        });
+        assert_eq!(report.bodies.len(), 7);
+        let jupiter = report.bodies.iter().find(|b| b.name_zh == "岁星").unwrap();
+        assert_eq!(jupiter.mansion.name, "斗");
+        assert_eq!(jupiter.station.as_deref(), Some("玄枵"));
+
+        let wu = report
+            .regions
+            .iter()
+            .find(|r| r.region.state == "吴")
+            .unwrap();
+        assert_eq!(wu.bodies, ["岁星", "镇星"]);
+        assert_eq!(wu.mansions, ["斗"]);
+        let qi = report
+            .regions
+            .iter()
+            .find(|r| r.region.state == "齐")
+            .unwrap();
+        assert_eq!(qi.stations, ["玄枵"]);
+        assert!(report
+            .regions
+            .windows(2)
+            .all(|w| w[0].bodies.len() >= w[1].bodies.len()));
+
+        // 北极一主中宫，不落列国
+        let polaris = report
+            .stars
+            .iter()
+            .find(|s| s.star.id == "hj_polaris")
+            .unwrap();
+        assert!(polaris.region.is_none());
+    }
+}
@@ -1,4 +1,9 @@
-use crate::calendar::time_rule::{utc_to_hj_year, YearStartMode};
+use crate::astro::delta_t::DeltaTModel;
+use crate::astro::solar_time::SolarTimePolicy;
+use crate::calendar::sexagenary::Sexagenary;
+use crate::astro::calendar_system::CalendarSystem;
+use crate::calendar::time_rule::{datetime_to_hj_year_with, to_rule_datetime, YearStartMode};
+use crate::calendar::zone::TimeZoneSpec;
 use crate::{algorithm, huangji_table, lunar, table_engine};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
@@ -52,21 +57,33 @@ pub struct FortuneRequest {
     /// 时区偏移（分钟），东为正 UTC+8=+480, 西为负 UTC-5=-300
     #[serde(default)]
     pub tz_offset_minutes: Option<i32>,
+    /// 时区（IANA 时区名或 ±HH:MM），按出生时刻取历史偏移；给出时优先于 `tz_offset_minutes`
+    #[serde(default)]
+    pub timezone: Option<TimeZoneSpec>,
     /// 经度（用于真太阳时校正）
     #[serde(default)]
     pub lon: Option<f64>,
-    /// 是否使用真太阳时（默认 false）
+    /// 是否使用真太阳时（默认 false）；旧参数，`true` 等同 `solar_time = mean`（只按经度校正）
     #[serde(default)]
     pub use_true_solar_time: Option<bool>,
+    /// 太阳时策略：meridian|mean|apparent|apparent_spencer，给出时优先于 `use_true_solar_time`
+    #[serde(default)]
+    pub solar_time: Option<SolarTimePolicy>,
     /// 计算模式：algorithm|table|compare（默认 compare）
     #[serde(default)]
     pub mode: Option<CalcMode>,
     /// 年界模式：lichun|gregorian（默认 lichun）
     #[serde(default)]
     pub year_start: Option<YearStartMode>,
+    /// 公历岁首模式下取年份的历法（默认 1582-10-15 改历，改历前以儒略历元旦换年）
+    #[serde(default)]
+    pub calendar: Option<CalendarSystem>,
     /// compare 模式下主值来源（默认 algorithm）
     #[serde(default)]
     pub primary: Option<PrimaryMode>,
+    /// ΔT 模型，用于立春岁首与黄历四柱的太阳黄经
+    #[serde(default)]
+    pub delta_t_model: DeltaTModel,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -99,6 +116,13 @@ pub struct FortuneCalcMeta {
     pub primary: PrimaryMode,
     pub year_start: String,
     pub hj_year: i32,
+    /// 取本地时间所用的太阳时策略
+    pub solar_time: SolarTimePolicy,
+    /// 所用时区及该时刻的区时偏移（秒，东为正）
+    pub timezone: TimeZoneSpec,
+    pub utc_offset_seconds: i32,
+    /// 实际使用的 ΔT 提供者
+    pub delta_t_provider: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -416,27 +440,29 @@ pub fn compute_fortune(req: &FortuneRequest) -> FortuneResponse {
     let year_start = req.year_start.unwrap_or_default();
 
     // 用统一时间规则把 UTC 转换为经世年（无公元0年）
-    let tz_offset_minutes = req.tz_offset_minutes.unwrap_or(480);
+    let zone = req.timezone.unwrap_or_else(|| {
+        TimeZoneSpec::from_offset_minutes(req.tz_offset_minutes.unwrap_or(480)).unwrap_or_default()
+    });
     let lon = req.lon.unwrap_or(116.4);
-    let use_true_solar_time = req.use_true_solar_time.unwrap_or(false);
-    let year = utc_to_hj_year(
-        req.datetime,
-        tz_offset_minutes,
-        lon,
-        use_true_solar_time,
-        year_start,
-    );
+    let solar_time = req.solar_time.unwrap_or_else(|| {
+        SolarTimePolicy::from_true_solar_flag(req.use_true_solar_time.unwrap_or(false))
+    });
+    let rule_dt = to_rule_datetime(req.datetime, &zone, lon, solar_time);
+    let calendar = req.calendar.unwrap_or_default();
+    let delta_t = req.delta_t_model.provider();
+    let year = datetime_to_hj_year_with(rule_dt, year_start, &calendar, delta_t.as_ref());
 
     let algo_info = algorithm::get_hj_info(year);
     let mapping_record = huangji_table::get_year_record(year);
     let mapping_record_normalized = huangji_table::get_year_record_normalized(year);
 
     let lunar_info =
-        lunar::compute_lunar(&req.datetime, tz_offset_minutes, lon, use_true_solar_time).ok();
-    let ganzhi = lunar_info
-        .as_ref()
-        .map(|l| l.ganzhi_year.clone())
-        .unwrap_or_else(|| "未知".to_string());
+        lunar::compute_lunar(&req.datetime, &zone, lon, solar_time, delta_t.as_ref()).ok();
+    // 年干支与黄历、八字同源（立春岁首），农历库不支持的年份也能给出
+    let ganzhi = Sexagenary::default()
+        .pillars_with(&req.datetime, &rule_dt.naive_local(), delta_t.as_ref())
+        .year
+        .to_string();
 
     let algorithm_variant = build_variant_from_algorithm(
         "algorithm",
@@ -546,6 +572,10 @@ pub fn compute_fortune(req: &FortuneRequest) -> FortuneResponse {
             primary: resolved_primary,
             year_start: year_start_label(year_start).to_string(),
             hj_year: year,
+            solar_time,
+            timezone: zone,
+            utc_offset_seconds: rule_dt.offset().local_minus_utc(),
+            delta_t_provider: delta_t.name().to_string(),
         }),
         variants: Some(FortuneVariants {
             algorithm: algorithm_variant,
@@ -567,11 +597,15 @@ mod tests {
         let req = FortuneRequest {
             datetime: Utc.with_ymd_and_hms(2026, 6, 1, 0, 0, 0).unwrap(),
             tz_offset_minutes: Some(480),
+            timezone: None,
             lon: Some(116.4),
             use_true_solar_time: Some(false),
+            solar_time: None,
             mode: None,
             year_start: None,
+            calendar: None,
             primary: None,
+            delta_t_model: DeltaTModel::default(),
         };
         let resp = compute_fortune(&req);
         assert!(resp.calc_meta.is_some());
@@ -589,11 +623,15 @@ mod tests {
         let before = FortuneRequest {
             datetime: Utc.with_ymd_and_hms(2025, 2, 3, 12, 0, 0).unwrap(),
             tz_offset_minutes: Some(480),
+            timezone: None,
             lon: Some(116.4),
             use_true_solar_time: Some(false),
+            solar_time: None,
             mode: Some(CalcMode::Algorithm),
             year_start: Some(YearStartMode::Lichun),
+            calendar: None,
             primary: Some(PrimaryMode::Algorithm),
+            delta_t_model: DeltaTModel::default(),
         };
         let after = FortuneRequest {
             datetime: Utc.with_ymd_and_hms(2025, 2, 5, 12, 0, 0).unwrap(),
@@ -613,6 +651,117 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_compute_fortune_delta_t_model() {
+        use crate::astro::solar::jd_to_utc;
+        use crate::calendar::jieqi::{find_solar_term_jd, SolarTerm};
+
+        // 2025 立春前半小时：按 ΔT 取一小时换算到 TT 已过立春
+        let lichun = jd_to_utc(find_solar_term_jd(2025, SolarTerm::Lichun)).unwrap();
+        let base = FortuneRequest {
+            datetime: lichun - chrono::Duration::minutes(30),
+            tz_offset_minutes: Some(480),
+            timezone: None,
+            lon: Some(116.4),
+            use_true_solar_time: None,
+            solar_time: None,
+            mode: Some(CalcMode::Algorithm),
+            year_start: Some(YearStartMode::Lichun),
+            calendar: None,
+            primary: None,
+            delta_t_model: DeltaTModel::default(),
+        };
+        let meta = |req: &FortuneRequest| compute_fortune(req).calc_meta.expect("calc_meta");
+
+        let polynomial = meta(&base);
+        assert_eq!(
+            (polynomial.hj_year, polynomial.delta_t_provider.as_str()),
+            (2024, "espenak_meeus")
+        );
+        let fixed = meta(&FortuneRequest {
+            delta_t_model: DeltaTModel::Fixed { seconds: 3600.0 },
+            ..base.clone()
+        });
+        assert_eq!((fixed.hj_year, fixed.delta_t_provider.as_str()), (2025, "fixed"));
+    }
+
+    #[test]
+    fn test_compute_fortune_solar_time_policy() {
+        // 北京时间 2025-01-01 00:05；东经 116.4° 的平太阳时、真太阳时仍在 2024 年除夕
+        let base = FortuneRequest {
+            datetime: Utc.with_ymd_and_hms(2024, 12, 31, 16, 5, 0).unwrap(),
+            tz_offset_minutes: Some(480),
+            timezone: None,
+            lon: Some(116.4),
+            use_true_solar_time: None,
+            solar_time: None,
+            mode: Some(CalcMode::Algorithm),
+            year_start: Some(YearStartMode::GregorianNewYear),
+            calendar: None,
+            primary: None,
+            delta_t_model: DeltaTModel::default(),
+        };
+        let meta = |req: &FortuneRequest| compute_fortune(req).calc_meta.expect("calc_meta");
+
+        let meridian = meta(&base);
+        assert_eq!(
+            (meridian.solar_time, meridian.hj_year),
+            (SolarTimePolicy::Meridian, 2025)
+        );
+        for policy in [
+            SolarTimePolicy::Mean,
+            SolarTimePolicy::Apparent,
+            SolarTimePolicy::ApparentSpencer,
+        ] {
+            let local = meta(&FortuneRequest {
+                solar_time: Some(policy),
+                ..base.clone()
+            });
+            assert_eq!((local.solar_time, local.hj_year), (policy, 2024));
+        }
+        // 旧参数 useTrueSolarTime 沿用历来的经度校正，即地方平太阳时
+        let legacy = meta(&FortuneRequest {
+            use_true_solar_time: Some(true),
+            ..base.clone()
+        });
+        assert_eq!(legacy.solar_time, SolarTimePolicy::Mean);
+    }
+
+    #[test]
+    fn test_compute_fortune_iana_timezone() {
+        // UTC 1890-12-31 15:57：上海地方平时 (+08:05:43) 已入 1891 年，+08:00 仍在 1890 年
+        let base = FortuneRequest {
+            datetime: Utc.with_ymd_and_hms(1890, 12, 31, 15, 57, 0).unwrap(),
+            tz_offset_minutes: Some(480),
+            timezone: None,
+            lon: Some(121.5),
+            use_true_solar_time: None,
+            solar_time: None,
+            mode: Some(CalcMode::Algorithm),
+            year_start: Some(YearStartMode::GregorianNewYear),
+            calendar: None,
+            primary: None,
+            delta_t_model: DeltaTModel::default(),
+        };
+        let fixed = compute_fortune(&base).calc_meta.expect("calc_meta");
+        assert_eq!(fixed.hj_year, 1890);
+        assert_eq!(fixed.utc_offset_seconds, 8 * 3600);
+
+        let req: FortuneRequest = serde_json::from_value(serde_json::json!({
+            "datetime": "1890-12-31T15:57:00Z",
+            "tz_offset_minutes": 480,
+            "timezone": "Asia/Shanghai",
+            "lon": 121.5,
+            "mode": "algorithm",
+            "year_start": "gregoriannewyear",
+        }))
+        .expect("request");
+        let zoned = compute_fortune(&req).calc_meta.expect("calc_meta");
+        assert_eq!(zoned.hj_year, 1891);
+        assert_eq!(zoned.timezone.name(), "Asia/Shanghai");
+        assert_eq!(zoned.utc_offset_seconds, 8 * 3600 + 5 * 60 + 43);
+    }
+
     #[test]
     fn test_requires_table_source() {
         assert!(requires_table_source(
@@ -631,11 +780,15 @@ mod tests {
         let req = FortuneRequest {
             datetime: Utc.with_ymd_and_hms(2026, 6, 1, 0, 0, 0).unwrap(),
             tz_offset_minutes: Some(480),
+            timezone: None,
             lon: Some(116.4),
             use_true_solar_time: Some(false),
+            solar_time: None,
             mode: Some(CalcMode::Table),
             year_start: Some(YearStartMode::GregorianNewYear),
+            calendar: None,
             primary: Some(PrimaryMode::Table),
+            delta_t_model: DeltaTModel::default(),
         };
 
         let resp = compute_fortune(&req);
@@ -661,11 +814,15 @@ mod tests {
         let req = FortuneRequest {
             datetime: Utc.with_ymd_and_hms(1600, 6, 1, 0, 0, 0).unwrap(),
             tz_offset_minutes: Some(480),
+            timezone: None,
             lon: Some(116.4),
             use_true_solar_time: Some(false),
+            solar_time: None,
             mode: Some(CalcMode::Table),
             year_start: Some(YearStartMode::GregorianNewYear),
+            calendar: None,
             primary: Some(PrimaryMode::Table),
+            delta_t_model: DeltaTModel::default(),
         };
 
         let resp = compute_fortune(&req);
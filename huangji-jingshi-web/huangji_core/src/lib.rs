@@ -1,8 +1,12 @@
 pub mod algorithm;
 pub mod data;
+pub mod ephemeris;
+pub mod fenye;
 pub mod fortune;
 pub mod huangji_table;
 pub mod lunar;
+pub mod phenomena;
+pub mod qizheng;
 pub mod sky;
 pub mod table_engine;
 
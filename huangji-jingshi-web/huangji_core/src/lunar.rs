@@ -1,14 +1,22 @@
 use serde::{Deserialize, Serialize};
 use chinese_lunisolar_calendar::{LunisolarDate, SolarDate};
-use chrono::{Datelike, DateTime, Timelike, Utc};
+use chrono::{Datelike, DateTime, Utc};
 // use astro::*; // Unused
+use crate::astro::delta_t::DeltaTProvider;
+use crate::calendar::jieqi::SolarTerm;
+use crate::calendar::sexagenary::{DayRollover, Sexagenary};
+use crate::astro::solar_time::SolarTimePolicy;
 use crate::calendar::time_rule::to_rule_datetime;
+use crate::calendar::zone::TimeZoneSpec;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LunarInfo {
     pub lunar_year: String,
     pub lunar_month: String,
     pub lunar_day: String,
+    pub lunar_month_number: u8, // 农历月序（1-12，闰月同序）
+    pub lunar_day_number: u8,   // 农历日序（1-30）
+    pub is_leap_month: bool,
     pub ganzhi_year: String,
     pub ganzhi_month: String,
     pub ganzhi_day: String,
@@ -21,78 +29,8 @@ pub struct LunarInfo {
     pub ji: Vec<String>,        // 忌
 }
 
-const STEMS: [&str; 10] = ["甲", "乙", "丙", "丁", "戊", "己", "庚", "辛", "壬", "癸"];
-const BRANCHES: [&str; 12] = ["子", "丑", "寅", "卯", "辰", "巳", "午", "未", "申", "酉", "戌", "亥"];
-const OFFICERS: [&str; 12] = ["建", "除", "满", "平", "定", "执", "破", "危", "成", "收", "开", "闭"];
-
-// 简单的干支计算（基于索引）
-fn get_ganzhi(stem_idx: usize, branch_idx: usize) -> String {
-    format!("{}{}", STEMS[stem_idx % 10], BRANCHES[branch_idx % 12])
-}
-
-// 计算日干支（基于 Julian Day）
-fn get_ganzhi_day(jd: f64) -> (usize, usize, String) {
-    // Adjust JD to local midnight (approximate for date calculation)
-    // JD 2451545.0 is 2000-01-01 12:00:00 UTC
-    // We want the integer part representing the day.
-    // But Chinese day starts at 23:00 (Zi hour).
-    // Let's assume input JD corresponds to the queried UTC time.
-    // To get the "Day" stem/branch, we usually use the day at 00:00 or noon.
-    // JD calculation:
-    // 2451545.0 -> 戊午
-    // Offset days = round(jd - 2451545.0) ? 
-    // If JD is 2451545.2 (16:48), it is still 戊午.
-    // If JD is 2451545.8 (next day 07:12), it is 己未.
-    // Boundary is midnight?
-    // JD days start at noon.
-    // Midnight is .5
-    // So 2000-01-01 00:00 is JD 2451544.5
-    // 2000-01-01 23:59 is JD 2451545.49
-    // So [2451544.5, 2451545.5) is 2000-01-01.
-    // Let's shift JD by +0.5 to align with midnight-to-midnight days.
-    // floor(jd + 0.5) should give the integer day number.
-    // 2000-01-01 (noon 2451545.0) + 0.5 = 2451545.5 -> floor -> 2451545.
-    // 2451545 corresponds to 戊午.
-    
-    let day_number = (jd + 0.5).floor() as i64;
-    let epoch_day = 2451545; // 2000-01-01
-    let offset = day_number - epoch_day;
-    
-    let stem_idx = (4 + offset).rem_euclid(10) as usize;
-    let branch_idx = (6 + offset).rem_euclid(12) as usize;
-    (stem_idx, branch_idx, get_ganzhi(stem_idx, branch_idx))
-}
-
-// 计算太阳黄经
-fn get_solar_lambda(jd: f64) -> f64 {
-    let d_jc = (jd - 2451545.0) / 36525.0;
-    let l0 = 280.46646 + 36000.76983 * d_jc;
-    let m = 357.52911 + 35999.05029 * d_jc;
-    let m_rad = m.to_radians();
-    let c = (1.914602 - 0.004817 * d_jc) * m_rad.sin()
-          + 0.019993 * (2.0 * m_rad).sin();
-    (l0 + c).rem_euclid(360.0)
-}
-
-// 计算节气与月建（月支）
-fn get_solar_term_and_month_branch(jd: f64) -> (Option<String>, usize, String) {
-    let lambda = get_solar_lambda(jd);
-    
-    // 315 deg = 立春 (Start of Spring) -> Month Branch 寅 (Tiger, Index 2)
-    let month_idx_raw = ((lambda + 45.0) / 30.0).floor() as usize;
-    let month_branch_idx = (month_idx_raw + 2) % 12;
-    
-    let term_names = [
-        "春分", "清明", "谷雨", "立夏", "小满", "芒种",
-        "夏至", "小暑", "大暑", "立秋", "处暑", "白露",
-        "秋分", "寒露", "霜降", "立冬", "小雪", "大雪",
-        "冬至", "小寒", "大寒", "立春", "雨水", "惊蛰"
-    ];
-    let term_idx = (lambda / 15.0).floor() as usize % 24;
-    let current_term = term_names[term_idx].to_string();
-    
-    (Some(current_term), month_branch_idx, BRANCHES[month_branch_idx].to_string())
-}
+/// 建除十二神（建日起依次排列）
+pub const OFFICERS: [&str; 12] = ["建", "除", "满", "平", "定", "执", "破", "危", "成", "收", "开", "闭"];
 
 // 十二建除
 fn get_twelve_officer(month_branch_idx: usize, day_branch_idx: usize) -> String {
@@ -125,15 +63,19 @@ fn get_yi_ji(officer: &str) -> (Vec<String>, Vec<String>) {
     )
 }
 
+/// 计算某一时刻的农历、干支与黄历
+///
+/// `delta_t` 用于求太阳黄经（立春换年、节气换月与当前节气）；农历日期由查表得出，与 ΔT 无关。
 pub fn compute_lunar(
     datetime_utc: &DateTime<Utc>,
-    tz_offset_minutes: i32,
+    zone: &TimeZoneSpec,
     lon: f64,
-    use_true_solar_time: bool,
+    solar_time: SolarTimePolicy,
+    delta_t: &dyn DeltaTProvider,
 ) -> anyhow::Result<LunarInfo> {
-    // 使用“规则时间”（时区 + 可选真太阳时校正）来取本地年月日/时，
+    // 使用“规则时间”（时区 + 太阳时策略）来取本地年月日/时，
     // 避免 UTC 跨日导致的农历/干支显示错误。
-    let rule_dt = to_rule_datetime(*datetime_utc, tz_offset_minutes, lon, use_true_solar_time);
+    let rule_dt = to_rule_datetime(*datetime_utc, zone, lon, solar_time);
 
     // chinese-lunisolar-calendar 目前只支持公元正数年份（u16）
     let year_i32 = rule_dt.year();
@@ -152,76 +94,19 @@ pub fn compute_lunar(
     let lunar_year_str = lunar_year.to_string();
     let _lunar_year_int = lunar_year_str.parse::<u16>().unwrap_or(year);
 
-    // 2. Ganzhi Day & JD
-    // JD 以“同一瞬时”的 UTC 为准（与时区无关）
-    let timestamp = datetime_utc.timestamp();
-    let jd = (timestamp as f64 / 86400.0) + 2440587.5;
-    let (day_stem_idx, day_branch_idx, ganzhi_day) = get_ganzhi_day(jd);
-    
-    // 3. Solar Term & Month Branch
-    let lambda = get_solar_lambda(jd);
-    let (solar_term, month_branch_idx, month_branch_char) = get_solar_term_and_month_branch(jd);
-    
-    // 1. Ganzhi Year (Bazi Year based on LiChun 315°)
-    // If current date is before LiChun (lambda in [270, 315)), it belongs to previous year.
-    // Note: This simple check handles the transition around Feb 4th correctly.
-    // 315 (LiChun) starts the year.
-    // 270 (Winter Solstice) -> 300 (DaHan) -> 315 (LiChun)
-    // So if lambda is between 270 and 315, we are in the end of the solar cycle? 
-    // No, lambda resets at 0 (Spring Equinox). 
-    // LiChun is 315.
-    // Cycle: 0 -> ... -> 315 (LiChun) -> 360/0
-    // Wait. Vernal Equinox (0) is usually March 20.
-    // LiChun (315) is Feb 4.
-    // So: 
-    // Feb 4 (315) -> Mar 20 (0) -> ... -> Feb 4 (315)
-    // If we are in [315, 360) or [0, 315), we are in the "New Year" relative to LiChun?
-    // No.
-    // Solar Year starts at LiChun (315).
-    // So if lambda >= 315, it's the current Gregorian year's stem/branch (mostly).
-    // If lambda < 315, it depends on whether we passed 0 (March 20).
-    // Case 1: Jan 1 (lambda ~280) -> Before LiChun -> Previous Year.
-    // Case 2: Feb 5 (lambda ~316) -> After LiChun -> Current Year.
-    // Case 3: Mar 21 (lambda ~1) -> After LiChun -> Current Year.
-    // Case 4: Dec 31 (lambda ~280) -> Before NEXT LiChun, but After THIS LiChun? No.
-    // Lambda is cyclic 0-360.
-    // Let's use year offset.
-    // Generally: 
-    // If month is Jan, year = gregorian - 1.
-    // If month is Feb: check lambda. If lambda < 315, year = gregorian - 1.
-    // Else year = gregorian.
-    
-    let mut bazi_year = year;
-    if month == 1 {
-        bazi_year = year - 1;
-    } else if month == 2 && lambda < 315.0 && lambda > 270.0 {
-        // 270 is Winter Solstice, safe buffer
-        bazi_year = year - 1;
-    }
-    // For other months (3-12), bazi_year = year.
-    
-    // Calculate Stem/Branch for Bazi Year
-    let year_stem_idx = (bazi_year as isize - 4).rem_euclid(10) as usize;
-    let year_branch_idx = (bazi_year as isize - 4).rem_euclid(12) as usize;
-    let ganzhi_year = get_ganzhi(year_stem_idx, year_branch_idx);
-    
-    // 4. Ganzhi Month (Five Tigers)
-    // Formula: (YearStem%5 * 2 + 2) % 10 -> Stem of Month 2 (寅)
-    let first_month_stem_idx = (year_stem_idx % 5 * 2 + 2) % 10;
-    let month_offset = (month_branch_idx as isize - 2 + 12).rem_euclid(12) as usize;
-    let current_month_stem_idx = (first_month_stem_idx + month_offset) % 10;
-    let ganzhi_month = format!("{}{}", STEMS[current_month_stem_idx], month_branch_char);
-
-    // 5. Ganzhi Hour (Five Rats)
-    // Formula: (DayStem%5 * 2 + HourBranch) % 10
-    // Hour Branch: (H+1)/2 % 12 (traditional formula)
-    let hour = rule_dt.hour();
-    #[allow(clippy::manual_div_ceil)]
-    let hour_branch_idx = ((hour + 1) / 2 % 24 % 12) as usize;
-    let hour_stem_idx = (day_stem_idx % 5 * 2 + hour_branch_idx) % 10;
-    let ganzhi_hour = get_ganzhi(hour_stem_idx, hour_branch_idx);
-
-    // 6. Twelve Officer
+    // 2. 四柱统一由干支历服务推算：黄经取同一瞬时（UTC），日、时取规则时间；
+    //    黄历按民用日期换日（子正）
+    let pillars = Sexagenary::new(DayRollover::ZiZheng00)
+        .pillars_with(datetime_utc, &rule_dt.naive_local(), delta_t);
+    let ganzhi_year = pillars.year.to_string();
+    let ganzhi_month = pillars.month.to_string();
+    let ganzhi_day = pillars.day.to_string();
+    let ganzhi_hour = pillars.hour.to_string();
+    let solar_term = Some(SolarTerm::from_longitude(pillars.solar_longitude).name().to_string());
+    let month_branch_idx = pillars.month.zhi;
+    let day_branch_idx = pillars.day.zhi;
+
+    // 3. Twelve Officer
     let officer = get_twelve_officer(month_branch_idx, day_branch_idx);
     let (yi, ji) = get_yi_ji(&officer);
 
@@ -238,6 +123,9 @@ pub fn compute_lunar(
         lunar_year: lunar_year.to_string(),
         lunar_month: lunar_month_str,
         lunar_day: lunar_date.to_lunar_day().to_string(),
+        lunar_month_number: lunar_month_val.to_u8(),
+        lunar_day_number: lunar_date.to_lunar_day().to_u8(),
+        is_leap_month: lunar_month_val.is_leap_month(),
         ganzhi_year,
         ganzhi_month, 
         ganzhi_day,
@@ -0,0 +1,651 @@
+//! 行星天象：合、冲、大距、留与五星聚
+//!
+//! 在 `sky` 星历（地心视黄道坐标）上逐日采样，遇变号后以二分法或黄金分割精化至约 0.1 秒：
+//! - 合：两天体视黄经相等（日月相合即朔，不在此列）；
+//! - 冲：外行星与太阳黄经差 180°；
+//! - 大距：水星、金星与太阳角距的极大，分东、西；
+//! - 留：行星黄经变化率为零，分顺转逆、逆转顺；
+//! - 五星聚：五星（或其中若干颗）同处于一段不超过给定跨度的黄经弧内，给出起止与跨度最小的时刻。
+
+use crate::astro::delta_t::{DeltaTModel, DeltaTProvider};
+use crate::astro::numeric::{bisect, minimize, wrap180, TIME_TOLERANCE};
+use crate::astro::solar::{datetime_to_jd, jd_to_utc};
+use crate::astro::time::TimeScales;
+use crate::sky::{body_ecliptic, EclipticPosition, SkyBody};
+use chrono::{DateTime, Datelike, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+/// 单次最多查询的天数（约 20 年）
+pub const MAX_PHENOMENA_DAYS: i64 = 7305;
+
+/// 五星（古称五纬）
+const FIVE_PLANETS: [SkyBody; 5] = [
+    SkyBody::Mercury,
+    SkyBody::Venus,
+    SkyBody::Mars,
+    SkyBody::Jupiter,
+    SkyBody::Saturn,
+];
+
+/// 逐日采样步长 (日)
+const STEP: f64 = 1.0;
+
+/// 天象类别
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PhenomenonKind {
+    /// 合（黄经合）
+    Conjunction,
+    /// 冲日
+    Opposition,
+    /// 东大距
+    GreatestElongationEast,
+    /// 西大距
+    GreatestElongationWest,
+    /// 留：由顺行转逆行
+    StationRetrograde,
+    /// 留：由逆行转顺行
+    StationDirect,
+    /// 五星聚（多星同聚）
+    Grouping,
+}
+
+/// 一次天象
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Phenomenon {
+    pub kind: PhenomenonKind,
+    /// 中文名称，如 岁星合镇星、荧惑冲日、太白东大距、五星连珠
+    pub name: String,
+    /// 精确时刻（UTC）；五星聚为跨度最小的时刻
+    pub time: DateTime<Utc>,
+    pub jd_tt: f64,
+    /// 参与天体（英文名，与 `CelestialBody::name` 一致）
+    pub bodies: Vec<String>,
+    /// 首个天体的视黄经 (度)；五星聚为所占黄经弧的起点
+    pub longitude_deg: f64,
+    /// 合：两天体的角距；冲：与太阳的角距；大距：大距角；留：无；五星聚：最小黄经跨度 (度)
+    pub separation_deg: Option<f64>,
+    /// 五星聚的起止时刻
+    pub start: Option<DateTime<Utc>>,
+    pub end: Option<DateTime<Utc>>,
+}
+
+impl Phenomenon {
+    /// 转为 major_events.json 的事件条目
+    pub fn to_event(&self) -> serde_json::Value {
+        let detail = match self.separation_deg {
+            Some(separation) => format!("; separation {:.2}°", separation),
+            None => String::new(),
+        };
+        serde_json::json!({
+            "year": self.time.year(),
+            "title": self.name,
+            "description": format!(
+                "{} at {} UTC ({}){}.",
+                self.name,
+                self.time.format("%Y-%m-%d %H:%M"),
+                self.bodies.join(", "),
+                detail
+            ),
+            "category": "Astronomy",
+            "tags": ["Planetary", "Computed"],
+        })
+    }
+}
+
+/// 五星聚的判定条件
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct GroupingOptions {
+    /// 至少几颗星同聚 (3–5)
+    pub min_planets: usize,
+    /// 最大黄经跨度 (度)，默认 30°（一次）
+    pub max_span_deg: f64,
+}
+
+impl Default for GroupingOptions {
+    fn default() -> Self {
+        Self {
+            min_planets: 5,
+            max_span_deg: 30.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PhenomenaRequest {
+    /// 起止日期（UTC，含两端）
+    pub start: NaiveDate,
+    pub end: NaiveDate,
+    /// 参与搜索的天体，缺省为七政
+    #[serde(default)]
+    pub bodies: Option<Vec<SkyBody>>,
+    #[serde(default)]
+    pub grouping: GroupingOptions,
+    /// ΔT 模型（默认 Espenak–Meeus）
+    #[serde(default)]
+    pub delta_t_model: DeltaTModel,
+}
+
+/// 在采样点 `(times, values)` 中找变号区间，以 f 二分精化，返回根及变号方向（true 为由负转正）
+///
+/// 差值跨越 ±180° 的跳变（|f| 均大于 90）不视为变号。
+fn roots(times: &[f64], values: &[f64], f: impl Fn(f64) -> f64) -> Vec<(f64, bool)> {
+    (1..values.len())
+        .filter(|&i| {
+            let (prev, value) = (values[i - 1], values[i]);
+            (prev < 0.0) != (value < 0.0) && prev.abs() < 90.0 && value.abs() < 90.0
+        })
+        .map(|i| (bisect(&f, times[i - 1], times[i]), values[i] >= 0.0))
+        .collect()
+}
+
+/// 黄经变化率 (度/日)
+fn longitude_rate(body: SkyBody, jd_tt: f64) -> f64 {
+    let h = 0.01;
+    wrap180(body_ecliptic(body, jd_tt + h).lon_deg - body_ecliptic(body, jd_tt - h).lon_deg)
+        / (2.0 * h)
+}
+
+/// 若干黄经所能容纳 `count` 颗的最短弧：(跨度, 弧起点, 所含序号)
+fn tightest_arc(longitudes: &[f64], count: usize) -> (f64, f64, Vec<usize>) {
+    let mut order: Vec<usize> = (0..longitudes.len()).collect();
+    order.sort_by(|&a, &b| longitudes[a].total_cmp(&longitudes[b]));
+    let n = order.len();
+    (0..n)
+        .map(|i| {
+            let members: Vec<usize> = (0..count).map(|k| order[(i + k) % n]).collect();
+            let first = longitudes[members[0]];
+            let last = longitudes[members[count - 1]];
+            ((last - first).rem_euclid(360.0), first, members)
+        })
+        .min_by(|a, b| a.0.total_cmp(&b.0))
+        .expect("至少一颗星")
+}
+
+struct Finder {
+    provider: Arc<dyn DeltaTProvider>,
+    /// 逐日采样时刻 (TT 儒略日)，首尾即搜索起止
+    times: Vec<f64>,
+    /// 各天体在采样时刻的视黄道坐标
+    samples: Vec<(SkyBody, Vec<EclipticPosition>)>,
+}
+
+impl Finder {
+    fn new(start: f64, end: f64, provider: Arc<dyn DeltaTProvider>) -> Self {
+        let days = ((end - start) / STEP).ceil() as usize;
+        let times = (0..=days)
+            .map(|i| (start + i as f64 * STEP).min(end))
+            .collect();
+        Self {
+            provider,
+            times,
+            samples: Vec::new(),
+        }
+    }
+
+    /// 预先采样所需天体，逐日搜索只读缓存，精化时才重新计算星历
+    fn sample(&mut self, body: SkyBody) {
+        if self.samples.iter().all(|(b, _)| *b != body) {
+            let positions = self.times.iter().map(|&t| body_ecliptic(body, t)).collect();
+            self.samples.push((body, positions));
+        }
+    }
+
+    fn positions(&self, body: SkyBody) -> &[EclipticPosition] {
+        &self
+            .samples
+            .iter()
+            .find(|(b, _)| *b == body)
+            .expect("天体须先采样")
+            .1
+    }
+
+    fn to_utc(&self, jd_tt: f64) -> Option<DateTime<Utc>> {
+        jd_to_utc(TimeScales::from_jd_tt_with(jd_tt, self.provider.as_ref()).jd_utc)
+    }
+
+    fn phenomenon(
+        &self,
+        kind: PhenomenonKind,
+        name: String,
+        jd_tt: f64,
+        bodies: &[SkyBody],
+        separation_deg: Option<f64>,
+    ) -> Option<Phenomenon> {
+        Some(Phenomenon {
+            kind,
+            name,
+            time: self.to_utc(jd_tt)?,
+            jd_tt,
+            bodies: bodies.iter().map(|b| b.name().to_string()).collect(),
+            longitude_deg: body_ecliptic(bodies[0], jd_tt).lon_deg,
+            separation_deg,
+            start: None,
+            end: None,
+        })
+    }
+
+    fn conjunctions(&self, a: SkyBody, b: SkyBody) -> Vec<Phenomenon> {
+        let values: Vec<f64> = self
+            .positions(a)
+            .iter()
+            .zip(self.positions(b))
+            .map(|(pa, pb)| wrap180(pa.lon_deg - pb.lon_deg))
+            .collect();
+        let f = |t: f64| wrap180(body_ecliptic(a, t).lon_deg - body_ecliptic(b, t).lon_deg);
+        roots(&self.times, &values, f)
+            .into_iter()
+            .filter_map(|(t, _)| {
+                let (pa, pb) = (body_ecliptic(a, t), body_ecliptic(b, t));
+                let name = match (a, b) {
+                    (SkyBody::Sun, planet) | (planet, SkyBody::Sun)
+                        if matches!(planet, SkyBody::Mercury | SkyBody::Venus) =>
+                    {
+                        let sun_distance = body_ecliptic(SkyBody::Sun, t).distance_au;
+                        let planet_distance = body_ecliptic(planet, t).distance_au;
+                        let which = if planet_distance < sun_distance {
+                            "下合"
+                        } else {
+                            "上合"
+                        };
+                        format!("{}合日（{}）", planet.name_zh(), which)
+                    }
+                    (SkyBody::Sun, other) | (other, SkyBody::Sun) => {
+                        format!("{}合日", other.name_zh())
+                    }
+                    _ => format!("{}合{}", a.name_zh(), b.name_zh()),
+                };
+                let separation = pa.separation(&pb);
+                self.phenomenon(
+                    PhenomenonKind::Conjunction,
+                    name,
+                    t,
+                    &[a, b],
+                    Some(separation),
+                )
+            })
+            .collect()
+    }
+
+    fn oppositions(&self, planet: SkyBody) -> Vec<Phenomenon> {
+        let values: Vec<f64> = self
+            .positions(planet)
+            .iter()
+            .zip(self.positions(SkyBody::Sun))
+            .map(|(p, sun)| wrap180(p.lon_deg - sun.lon_deg - 180.0))
+            .collect();
+        let f = |t: f64| {
+            wrap180(
+                body_ecliptic(planet, t).lon_deg - body_ecliptic(SkyBody::Sun, t).lon_deg - 180.0,
+            )
+        };
+        roots(&self.times, &values, f)
+            .into_iter()
+            .filter_map(|(t, _)| {
+                let separation =
+                    body_ecliptic(planet, t).separation(&body_ecliptic(SkyBody::Sun, t));
+                self.phenomenon(
+                    PhenomenonKind::Opposition,
+                    format!("{}冲日", planet.name_zh()),
+                    t,
+                    &[planet, SkyBody::Sun],
+                    Some(separation),
+                )
+            })
+            .collect()
+    }
+
+    fn elongations(&self, planet: SkyBody) -> Vec<Phenomenon> {
+        let elongation =
+            |t: f64| body_ecliptic(planet, t).separation(&body_ecliptic(SkyBody::Sun, t));
+        let sampled: Vec<f64> = self
+            .positions(planet)
+            .iter()
+            .zip(self.positions(SkyBody::Sun))
+            .map(|(p, sun)| p.separation(sun))
+            .collect();
+        // 采样角距的局部极大，再在前后一日内精化
+        (1..sampled.len().saturating_sub(1))
+            .filter(|&i| sampled[i] > sampled[i - 1] && sampled[i] >= sampled[i + 1])
+            .filter_map(|i| {
+                let t = minimize(|x| -elongation(x), self.times[i - 1], self.times[i + 1]);
+                let east = wrap180(
+                    body_ecliptic(planet, t).lon_deg - body_ecliptic(SkyBody::Sun, t).lon_deg,
+                ) > 0.0;
+                let (kind, label) = if east {
+                    (PhenomenonKind::GreatestElongationEast, "东大距")
+                } else {
+                    (PhenomenonKind::GreatestElongationWest, "西大距")
+                };
+                self.phenomenon(
+                    kind,
+                    format!("{}{}", planet.name_zh(), label),
+                    t,
+                    &[planet],
+                    Some(elongation(t)),
+                )
+            })
+            .collect()
+    }
+
+    fn stations(&self, planet: SkyBody) -> Vec<Phenomenon> {
+        // 相邻采样的黄经差即该日中点的平均速度
+        let positions = self.positions(planet);
+        let midpoints: Vec<f64> = self.times.windows(2).map(|w| (w[0] + w[1]) / 2.0).collect();
+        let rates: Vec<f64> = positions
+            .windows(2)
+            .map(|w| wrap180(w[1].lon_deg - w[0].lon_deg))
+            .collect();
+        roots(&midpoints, &rates, |t| longitude_rate(planet, t))
+            .into_iter()
+            .filter_map(|(t, direct)| {
+                let (kind, label) = if direct {
+                    (PhenomenonKind::StationDirect, "留（逆转顺）")
+                } else {
+                    (PhenomenonKind::StationRetrograde, "留（顺转逆）")
+                };
+                self.phenomenon(
+                    kind,
+                    format!("{}{}", planet.name_zh(), label),
+                    t,
+                    &[planet],
+                    None,
+                )
+            })
+            .collect()
+    }
+
+    fn groupings(&self, options: &GroupingOptions) -> Vec<Phenomenon> {
+        let count = options.min_planets;
+        let arc = |t: f64| {
+            let longitudes: Vec<f64> = FIVE_PLANETS
+                .iter()
+                .map(|&p| body_ecliptic(p, t).lon_deg)
+                .collect();
+            tightest_arc(&longitudes, count)
+        };
+        let inside: Vec<bool> = (0..self.times.len())
+            .map(|i| {
+                let longitudes: Vec<f64> = FIVE_PLANETS
+                    .iter()
+                    .map(|&p| self.positions(p)[i].lon_deg)
+                    .collect();
+                tightest_arc(&longitudes, count).0 <= options.max_span_deg
+            })
+            .collect();
+
+        // 连续落在跨度内的采样段
+        let mut found = Vec::new();
+        let mut i = 0;
+        while i < inside.len() {
+            if !inside[i] {
+                i += 1;
+                continue;
+            }
+            let first = i;
+            while i + 1 < inside.len() && inside[i + 1] {
+                i += 1;
+            }
+            found.extend(self.grouping(first, i, count, &arc, options));
+            i += 1;
+        }
+        found
+    }
+
+    /// 精化一段五星聚：起止以二分求跨度恰为上限之时，并取跨度最小的时刻
+    fn grouping(
+        &self,
+        first: usize,
+        last: usize,
+        count: usize,
+        arc: &impl Fn(f64) -> (f64, f64, Vec<usize>),
+        options: &GroupingOptions,
+    ) -> Option<Phenomenon> {
+        let excess = |t: f64| arc(t).0 - options.max_span_deg;
+        let times = &self.times;
+        let start = if first > 0 {
+            bisect(excess, times[first - 1], times[first])
+        } else {
+            times[first]
+        };
+        let end = if last + 1 < times.len() {
+            bisect(excess, times[last], times[last + 1])
+        } else {
+            times[last]
+        };
+        let best = minimize(|t| arc(t).0, start, end.max(start + TIME_TOLERANCE));
+        let (span, arc_start, members) = arc(best);
+        let mut bodies: Vec<SkyBody> = members.iter().map(|&i| FIVE_PLANETS[i]).collect();
+        bodies.sort_by_key(|b| FIVE_PLANETS.iter().position(|p| p == b));
+        let name = match count {
+            5 => "五星连珠".to_string(),
+            4 => "四星聚".to_string(),
+            _ => "三星聚".to_string(),
+        };
+        Some(Phenomenon {
+            kind: PhenomenonKind::Grouping,
+            name,
+            time: self.to_utc(best)?,
+            jd_tt: best,
+            bodies: bodies.iter().map(|b| b.name().to_string()).collect(),
+            longitude_deg: arc_start,
+            separation_deg: Some(span),
+            start: self.to_utc(start),
+            end: self.to_utc(end),
+        })
+    }
+}
+
+/// 搜索日期范围内的行星天象，按时间排序
+pub fn find_phenomena(req: &PhenomenaRequest) -> anyhow::Result<Vec<Phenomenon>> {
+    if req.end < req.start {
+        anyhow::bail!("结束日期 {} 早于起始日期 {}", req.end, req.start);
+    }
+    if (req.end - req.start).num_days() + 1 > MAX_PHENOMENA_DAYS {
+        anyhow::bail!("单次最多查询 {} 天", MAX_PHENOMENA_DAYS);
+    }
+    if !(3..=5).contains(&req.grouping.min_planets) {
+        anyhow::bail!("五星聚的星数须在 3 到 5 之间");
+    }
+    if !(req.grouping.max_span_deg > 0.0 && req.grouping.max_span_deg < 180.0) {
+        anyhow::bail!("五星聚的黄经跨度须在 0° 到 180° 之间");
+    }
+
+    let provider = req.delta_t_model.provider();
+    let midnight = |date: NaiveDate| {
+        let jd_utc = datetime_to_jd(&date.and_hms_opt(0, 0, 0).expect("零时有效"));
+        TimeScales::from_jd_utc_with(jd_utc, provider.as_ref()).jd_tt
+    };
+    let mut finder = Finder::new(
+        midnight(req.start),
+        midnight(req.end + chrono::Duration::days(1)),
+        provider.clone(),
+    );
+
+    let mut bodies: Vec<SkyBody> = Vec::new();
+    for body in req.bodies.as_deref().unwrap_or(&SkyBody::SEVEN_LUMINARIES) {
+        if !bodies.contains(body) {
+            bodies.push(*body);
+        }
+    }
+    for body in bodies.iter().copied().chain(FIVE_PLANETS) {
+        finder.sample(body);
+    }
+    finder.sample(SkyBody::Sun);
+
+    let mut found = Vec::new();
+    for (i, &a) in bodies.iter().enumerate() {
+        for &b in &bodies[i + 1..] {
+            let sun_moon = matches!(
+                (a, b),
+                (SkyBody::Sun, SkyBody::Moon) | (SkyBody::Moon, SkyBody::Sun)
+            );
+            if !sun_moon {
+                found.extend(finder.conjunctions(a, b));
+            }
+        }
+    }
+    for &body in &bodies {
+        match body {
+            SkyBody::Sun | SkyBody::Moon => {}
+            SkyBody::Mercury | SkyBody::Venus => {
+                found.extend(finder.elongations(body));
+                found.extend(finder.stations(body));
+            }
+            _ => {
+                found.extend(finder.oppositions(body));
+                found.extend(finder.stations(body));
+            }
+        }
+    }
+    found.extend(finder.groupings(&req.grouping));
+    found.sort_by(|a, b| a.jd_tt.total_cmp(&b.jd_tt));
+    Ok(found)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn request(
+        start: (i32, u32, u32),
+        end: (i32, u32, u32),
+        bodies: &[SkyBody],
+    ) -> PhenomenaRequest {
+        PhenomenaRequest {
+            start: NaiveDate::from_ymd_opt(start.0, start.1, start.2).unwrap(),
+            end: NaiveDate::from_ymd_opt(end.0, end.1, end.2).unwrap(),
+            bodies: Some(bodies.to_vec()),
+            grouping: GroupingOptions::default(),
+            delta_t_model: DeltaTModel::default(),
+        }
+    }
+
+    fn near(time: DateTime<Utc>, expected: DateTime<Utc>, minutes: i64) -> bool {
+        (time - expected).num_minutes().abs() <= minutes
+    }
+
+    #[test]
+    fn test_great_conjunction_2020() {
+        // 2020-12-21 约 13:30 UT 木土黄经合（赤经合在 18:20 UT），角距约 0.10°；
+        // 行星取 JPL 近似根数，两星相对运动仅 0.1°/日，时刻容差放宽至 18 小时
+        let found = find_phenomena(&request(
+            (2020, 12, 1),
+            (2020, 12, 31),
+            &[SkyBody::Jupiter, SkyBody::Saturn],
+        ))
+        .unwrap();
+        let conjunction = found
+            .iter()
+            .find(|p| p.kind == PhenomenonKind::Conjunction)
+            .unwrap();
+        assert_eq!(conjunction.name, "岁星合镇星");
+        let expected = Utc.with_ymd_and_hms(2020, 12, 21, 13, 30, 0).unwrap();
+        assert!(
+            near(conjunction.time, expected, 18 * 60),
+            "{}",
+            conjunction.time
+        );
+        let separation = conjunction.separation_deg.unwrap();
+        assert!((separation - 0.10).abs() < 0.03, "{}", separation);
+    }
+
+    #[test]
+    fn test_mars_opposition_and_stations_2020() {
+        // 2020 年火星：9-09 留（顺转逆）、10-13 冲日、11-14 留（逆转顺）
+        let found = find_phenomena(&request(
+            (2020, 8, 1),
+            (2020, 12, 1),
+            &[SkyBody::Sun, SkyBody::Mars],
+        ))
+        .unwrap();
+        let find = |kind| found.iter().find(|p| p.kind == kind).unwrap();
+        let day = |m, d| Utc.with_ymd_and_hms(2020, m, d, 12, 0, 0).unwrap();
+        assert!(near(
+            find(PhenomenonKind::StationRetrograde).time,
+            day(9, 9),
+            24 * 60
+        ));
+        assert!(near(
+            find(PhenomenonKind::Opposition).time,
+            day(10, 13),
+            24 * 60
+        ));
+        assert!(near(
+            find(PhenomenonKind::StationDirect).time,
+            day(11, 14),
+            24 * 60
+        ));
+        assert_eq!(find(PhenomenonKind::Opposition).name, "荧惑冲日");
+    }
+
+    #[test]
+    fn test_venus_elongation_2020() {
+        // 2020-03-24 金星东大距 46.1°，6-03 下合，8-13 西大距 45.8°
+        let found = find_phenomena(&request(
+            (2020, 1, 1),
+            (2020, 9, 1),
+            &[SkyBody::Sun, SkyBody::Venus],
+        ))
+        .unwrap();
+        let east = found
+            .iter()
+            .find(|p| p.kind == PhenomenonKind::GreatestElongationEast)
+            .unwrap();
+        assert_eq!(
+            east.time.date_naive(),
+            NaiveDate::from_ymd_opt(2020, 3, 24).unwrap()
+        );
+        assert!((east.separation_deg.unwrap() - 46.1).abs() < 0.1);
+        let west = found
+            .iter()
+            .find(|p| p.kind == PhenomenonKind::GreatestElongationWest)
+            .unwrap();
+        assert_eq!(
+            west.time.date_naive(),
+            NaiveDate::from_ymd_opt(2020, 8, 13).unwrap()
+        );
+        let inferior = found
+            .iter()
+            .find(|p| p.kind == PhenomenonKind::Conjunction)
+            .unwrap();
+        assert_eq!(inferior.name, "太白合日（下合）");
+        assert_eq!(
+            inferior.time.date_naive(),
+            NaiveDate::from_ymd_opt(2020, 6, 3).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_grouping_and_validation() {
+        // 2022 年 6 月下旬五星依次排列于晨空，跨度约 90° 以上；以 110° 为限应检出一段
+        let mut req = request((2022, 6, 1), (2022, 7, 15), &[]);
+        req.grouping.max_span_deg = 110.0;
+        let found = find_phenomena(&req).unwrap();
+        let grouping: Vec<_> = found
+            .iter()
+            .filter(|p| p.kind == PhenomenonKind::Grouping)
+            .collect();
+        assert_eq!(grouping.len(), 1);
+        let g = grouping[0];
+        assert_eq!(g.bodies.len(), 5);
+        assert!(g.separation_deg.unwrap() <= 110.0);
+        assert!(g.start.unwrap() <= g.time && g.time <= g.end.unwrap());
+
+        // 默认 30° 时无五星聚
+        let found = find_phenomena(&request((2022, 6, 1), (2022, 7, 15), &[])).unwrap();
+        assert!(found.iter().all(|p| p.kind != PhenomenonKind::Grouping));
+
+        assert_eq!(SkyBody::parse("岁星").unwrap(), SkyBody::Jupiter);
+        assert_eq!(SkyBody::parse(" Saturn").unwrap(), SkyBody::Saturn);
+        assert!(SkyBody::parse("pluto").is_err());
+
+        let mut invalid = request((2020, 1, 1), (2019, 1, 1), &[]);
+        assert!(find_phenomena(&invalid).is_err());
+        invalid.end = NaiveDate::from_ymd_opt(2020, 2, 1).unwrap();
+        invalid.grouping.min_planets = 6;
+        assert!(find_phenomena(&invalid).is_err());
+    }
+}
@@ -0,0 +1,286 @@
+//! 七政四余
+//!
+//! 七政为日、月与五星，四余为罗睺、计都、月孛、紫气（见 `sky::siyu_longitude`；
+//! 紫气须给出起元方才排入）。
+//! 十一曜各取所入之宿（赤道入宿）与所在之宫：宫即十二次（黄经每 30° 一宫，星纪为丑宫），
+//! 以出生时东方地平所升之宫为命宫，自命宫起按黄经增加方向依次为财帛、兄弟……相貌十二宫。
+
+use crate::astro::coords::EclipticCoord;
+use crate::astro::delta_t::DeltaTModel;
+use crate::astro::mansions::{DeterminativeSystem, MansionPlacement, MansionTable};
+use crate::astro::nutation::true_obliquity;
+use crate::astro::sidereal::local_apparent_sidereal_time;
+use crate::astro::solar::utc_to_jd;
+use crate::astro::time::TimeScales;
+use crate::calendar::ganzhi::DIZHI;
+use crate::fenye::station_at;
+use crate::sky::{
+    body_ecliptic, body_equatorial, siyu_longitude, SiyuConvention, SiyuPoint, SkyBody,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// 十二宫，自命宫起
+pub const HOUSE_NAMES: [&str; 12] = [
+    "命宫", "财帛", "兄弟", "田宅", "男女", "奴仆", "夫妻", "疾厄", "迁移", "官禄", "福德", "相貌",
+];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QizhengRequest {
+    /// 出生时刻
+    pub datetime: DateTime<Utc>,
+    /// 出生地纬度、经度（度，北、东为正），用于命宫
+    pub lat_deg: f64,
+    pub lon_deg: f64,
+    #[serde(default)]
+    pub mansion_system: DeterminativeSystem,
+    #[serde(default)]
+    pub delta_t_model: DeltaTModel,
+    #[serde(default)]
+    pub siyu: SiyuConvention,
+}
+
+/// 一宫
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct House {
+    /// 自命宫起的序号 (1–12)
+    pub index: usize,
+    /// 宫名，如 命宫、财帛
+    pub name: String,
+    /// 地支宫，如 子
+    pub branch: String,
+    /// 十二次，如 玄枵
+    pub station: String,
+    /// 起始黄经 (度)
+    pub start_longitude: f64,
+}
+
+/// 一曜的宿与宫
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QizhengBody {
+    pub name: String,
+    pub name_zh: String,
+    /// 七政或四余
+    pub group: String,
+    /// 视黄经（瞬时真春分点）
+    pub ecliptic_lon_deg: f64,
+    pub ra_deg: f64,
+    /// 所入之宿与入宿度
+    pub mansion: MansionPlacement,
+    /// 地支宫
+    pub branch: String,
+    /// 十二次
+    pub station: String,
+    /// 所落之宫，如 财帛
+    pub house: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QizhengChart {
+    pub datetime: DateTime<Utc>,
+    pub jd_tt: f64,
+    /// 地方视恒星时 (度)
+    pub local_sidereal_deg: f64,
+    /// 东升点视黄经 (度)
+    pub ascendant_lon_deg: f64,
+    /// 十二宫，首项为命宫
+    pub houses: Vec<House>,
+    /// 七政在前、四余在后（未给出紫气起元时无紫气）
+    pub bodies: Vec<QizhengBody>,
+    pub mansion_system: DeterminativeSystem,
+    pub siyu_convention: SiyuConvention,
+    pub delta_t_provider: String,
+}
+
+/// 东升点黄经 (度, 0-360)：黄道与东方地平的交点
+///
+/// # 参数
+/// - `local_sidereal_deg`: 地方视恒星时 (度)
+/// - `obliquity_deg`: 真黄赤交角 (度)
+pub fn ascendant_longitude(local_sidereal_deg: f64, lat_deg: f64, obliquity_deg: f64) -> f64 {
+    let theta = local_sidereal_deg.to_radians();
+    let eps = obliquity_deg.to_radians();
+    let phi = lat_deg.to_radians();
+    theta
+        .cos()
+        .atan2(-(theta.sin() * eps.cos() + phi.tan() * eps.sin()))
+        .to_degrees()
+        .rem_euclid(360.0)
+}
+
+/// 黄经所在之宫：自星纪（丑宫）起的序号 (0–11) 与地支
+fn palace_of(longitude: f64) -> (usize, &'static str) {
+    let start = station_at(longitude).map_or(longitude, |s| s.start_longitude);
+    let k = ((start - 270.0) / 30.0).round().rem_euclid(12.0) as usize;
+    (k, DIZHI[(13 - k) % 12])
+}
+
+pub fn compute_qizheng(req: &QizhengRequest) -> QizhengChart {
+    let provider = req.delta_t_model.provider();
+    let scales = TimeScales::from_jd_utc_with(utc_to_jd(&req.datetime), provider.as_ref());
+    let jd_tt = scales.jd_tt;
+    let mansions = MansionTable::new(jd_tt, req.mansion_system);
+    let obliquity = true_obliquity(jd_tt);
+    let local_sidereal_deg = local_apparent_sidereal_time(&scales, req.lon_deg);
+    let ascendant_lon_deg = ascendant_longitude(local_sidereal_deg, req.lat_deg, obliquity);
+
+    let (ming, _) = palace_of(ascendant_lon_deg);
+    let houses: Vec<House> = (0..12)
+        .map(|i| {
+            let start = 270.0 + 30.0 * ((ming + i) % 12) as f64;
+            let (_, branch) = palace_of(start);
+            House {
+                index: i + 1,
+                name: HOUSE_NAMES[i].to_string(),
+                branch: branch.to_string(),
+                station: station_at(start)
+                    .map(|s| s.name.clone())
+                    .unwrap_or_default(),
+                start_longitude: start.rem_euclid(360.0),
+            }
+        })
+        .collect();
+
+    let place = |name: &str, name_zh: &str, group: &str, lon: f64, ra: f64| {
+        let (k, branch) = palace_of(lon);
+        QizhengBody {
+            name: name.to_string(),
+            name_zh: name_zh.to_string(),
+            group: group.to_string(),
+            ecliptic_lon_deg: lon,
+            ra_deg: ra,
+            mansion: mansions.placement(ra),
+            branch: branch.to_string(),
+            station: station_at(lon).map(|s| s.name.clone()).unwrap_or_default(),
+            house: HOUSE_NAMES[(k + 12 - ming) % 12].to_string(),
+        }
+    };
+
+    let mut bodies: Vec<QizhengBody> = SkyBody::SEVEN_LUMINARIES
+        .iter()
+        .map(|&body| {
+            let lon = body_ecliptic(body, jd_tt).lon_deg;
+            let ra = body_equatorial(body, jd_tt).ra_deg;
+            place(body.name(), body.name_zh(), "七政", lon, ra)
+        })
+        .collect();
+    bodies.extend(SiyuPoint::ALL.iter().filter_map(|&point| {
+        let lon = siyu_longitude(point, jd_tt, &req.siyu)?;
+        let ra = EclipticCoord {
+            lon_deg: lon,
+            lat_deg: 0.0,
+        }
+        .to_equatorial(obliquity)
+        .ra_deg;
+        Some(place(point.name(), point.name_zh(), "四余", lon, ra))
+    }));
+
+    QizhengChart {
+        datetime: req.datetime,
+        jd_tt,
+        local_sidereal_deg,
+        ascendant_lon_deg,
+        houses,
+        bodies,
+        mansion_system: req.mansion_system,
+        siyu_convention: req.siyu,
+        delta_t_provider: provider.name().to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sky::ZiqiEpoch;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_ascendant_on_eastern_horizon() {
+        let eps = 23.44;
+        for (lst, lat) in [(0.0, 39.9), (75.0, 39.9), (200.0, 22.3), (310.0, -33.9)] {
+            let asc = ascendant_longitude(lst, lat, eps);
+            let equatorial = EclipticCoord {
+                lon_deg: asc,
+                lat_deg: 0.0,
+            }
+            .to_equatorial(eps);
+            let horizontal = equatorial.to_horizontal(lat, lst);
+            assert!(horizontal.alt_deg.abs() < 1e-9, "{:?}", horizontal);
+            assert!(horizontal.az_deg > 0.0 && horizontal.az_deg < 180.0);
+        }
+        // 赤道上、恒星时 0 时东升点为黄经 90°
+        assert!((ascendant_longitude(0.0, 0.0, eps) - 90.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_palaces() {
+        assert_eq!(palace_of(275.0), (0, "丑"));
+        assert_eq!(palace_of(305.0), (1, "子"));
+        assert_eq!(palace_of(335.0), (2, "亥"));
+        assert_eq!(palace_of(5.0), (3, "戌"));
+        assert_eq!(palace_of(245.0), (11, "寅"));
+    }
+
+    #[test]
+    fn test_chart() {
+        let request = QizhengRequest {
+            datetime: Utc.with_ymd_and_hms(2020, 12, 21, 18, 0, 0).unwrap(),
+            lat_deg: 39.9,
+            lon_deg: 116.4,
+            mansion_system: DeterminativeSystem::Modern,
+            delta_t_model: DeltaTModel::default(),
+            siyu: SiyuConvention::default(),
+        };
+        // 未给出紫气起元：只排十曜
+        let without_ziqi = compute_qizheng(&request);
+        assert_eq!(without_ziqi.bodies.len(), 10);
+        assert!(without_ziqi.bodies.iter().all(|b| b.name_zh != "紫气"));
+
+        let chart = compute_qizheng(&QizhengRequest {
+            siyu: SiyuConvention {
+                ziqi_epoch: Some(ZiqiEpoch {
+                    jd_tt: 2451545.0,
+                    longitude_deg: 0.0,
+                }),
+                ..SiyuConvention::default()
+            },
+            ..request
+        });
+        assert_eq!(chart.bodies.len(), 11);
+        assert_eq!(chart.houses.len(), 12);
+        assert_eq!(chart.houses[0].name, "命宫");
+        let mut branches: Vec<&str> = chart.houses.iter().map(|h| h.branch.as_str()).collect();
+        branches.sort();
+        branches.dedup();
+        assert_eq!(branches.len(), 12);
+
+        // 命宫含东升点；各曜所落之宫与其地支宫一致
+        let ming = &chart.houses[0];
+        assert!((chart.ascendant_lon_deg - ming.start_longitude).rem_euclid(360.0) < 30.0);
+        for body in &chart.bodies {
+            let house = chart.houses.iter().find(|h| h.name == body.house).unwrap();
+            assert_eq!(house.branch, body.branch);
+        }
+
+        // 木土相合于玄枵（子宫）
+        let jupiter = chart.bodies.iter().find(|b| b.name_zh == "岁星").unwrap();
+        assert_eq!(
+            (jupiter.station.as_str(), jupiter.branch.as_str()),
+            ("玄枵", "子")
+        );
+        let luohou = chart.bodies.iter().find(|b| b.name_zh == "罗睺").unwrap();
+        let jidu = chart.bodies.iter().find(|b| b.name_zh == "计都").unwrap();
+        assert_eq!(luohou.group, "四余");
+        let luohou_house = chart
+            .houses
+            .iter()
+            .position(|h| h.name == luohou.house)
+            .unwrap();
+        let jidu_house = chart
+            .houses
+            .iter()
+            .position(|h| h.name == jidu.house)
+            .unwrap();
+        assert_eq!((luohou_house + 6) % 12, jidu_house);
+    }
+}
@@ -1,485 +1,1029 @@
-use chrono::{DateTime, Utc, Datelike};
+use chrono::{DateTime, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
-use std::f64::consts::PI;
-use std::collections::HashMap;
-use std::sync::RwLock;
-use std::fs::File;
-use std::io::BufReader;
-use once_cell::sync::Lazy;
-use std::env;
+use crate::astro::constellations::{ConstellationLocator, ConstellationPlacement};
+use crate::astro::coords::{angular_separation, EclipticCoord, EquatorialCoord, AU_KM};
+use crate::astro::delta_t::DeltaTModel;
+use crate::astro::observer::Observer;
+use crate::astro::mansions::{DeterminativeSystem, MansionPlacement, MansionTable};
+use crate::astro::refraction::Atmosphere;
+use crate::astro::stars::{find_star, star_catalog, CatalogStar, StarReduction};
+use crate::astro::rise_set::{
+    rise_set_transit, twilight, ApparentPlace, RiseSetTransit, Twilight, TwilightKind,
+    STANDARD_REFRACTION_DEG,
+};
+use crate::astro::solar::datetime_to_jd;
+use crate::astro::lunar::{
+    mean_ascending_node, mean_perigee, moon_illumination, moon_position, true_ascending_node,
+    MoonIllumination,
+};
+use crate::astro::nutation::{nutation, true_obliquity};
+use crate::astro::solar::apparent_sun;
+use crate::astro::planets::{planet_position, Planet};
+use crate::astro::sidereal::{greenwich_mean_sidereal_time, local_apparent_sidereal_time};
+use crate::astro::time::TimeScales;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SkyRequest {
     pub datetime: DateTime<Utc>,
     pub lat_deg: f64, // 纬度：北正南负
     pub lon_deg: f64, // 经度：东正西负
-    pub delta_t_provider: Option<String>,
+    /// 海拔（米），用于站心视差
+    #[serde(default)]
+    pub elevation_m: f64,
+    /// 气压 (hPa)，缺省 1010，不得为负
+    #[serde(default)]
+    pub pressure_hpa: Option<f64>,
+    /// 气温 (°C)，缺省 10，须高于绝对零度
+    #[serde(default)]
+    pub temperature_c: Option<f64>,
+    /// ΔT 模型（默认 Espenak–Meeus）
+    #[serde(default)]
+    pub delta_t_model: DeltaTModel,
     pub accuracy: Option<String>,
+    /// 星表极限星等；缺省时不返回 `stars`
+    #[serde(default)]
+    pub star_mag_limit: Option<f64>,
+    /// 只返回视高度不低于此值 (度) 的恒星，如 0 表示地平以上
+    #[serde(default)]
+    pub star_min_alt_deg: Option<f64>,
+    /// 二十八宿距星体系（默认今距星）
+    #[serde(default)]
+    pub mansion_system: DeterminativeSystem,
+    /// 四余的取法（罗睺、计都所配交点等）
+    #[serde(default)]
+    pub siyu: SiyuConvention,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CelestialBody {
     pub name: String,
+    /// 几何站心高度（已计周日视差，未计折射）
     pub alt_deg: f64,
     pub az_deg: f64,
+    /// 视高度：几何高度加大气折射
+    pub apparent_alt_deg: f64,
+    /// 视方位；折射只抬升高度，与几何方位相同
+    pub apparent_az_deg: f64,
     pub distance_au: Option<f64>,
+    /// 地心视赤经、赤纬
     pub ra_deg: Option<f64>,
     pub dec_deg: Option<f64>,
+    /// 所入之宿与入宿度（按地心视赤经）
+    pub mansion: Option<MansionPlacement>,
+    /// 所在星座：IAU 星座及有界线的中国星官（按地心视赤经、赤纬）
+    pub constellation: Option<ConstellationPlacement>,
+}
+
+/// 星表恒星的视位置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SkyStar {
+    /// 依巴谷星表编号
+    pub hip: u32,
+    pub name: Option<String>,
+    pub name_zh: Option<String>,
+    pub mag: f64,
+    /// B−V 色指数
+    pub bv: Option<f64>,
+    /// 视赤经、赤纬（已计自行、岁差、章动、光行差）
+    pub ra_deg: f64,
+    pub dec_deg: f64,
+    pub alt_deg: f64,
+    pub az_deg: f64,
+    pub apparent_alt_deg: f64,
+}
+
+/// 四余的位置：黄道上的虚点，黄纬取 0
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SiyuBody {
+    pub name: String,
+    pub name_zh: String,
+    /// 视黄经（瞬时真春分点）
+    pub ecliptic_lon_deg: f64,
+    pub ra_deg: f64,
+    pub dec_deg: f64,
+    /// 地心高度与方位（虚点不计视差与折射）
+    pub alt_deg: f64,
+    pub az_deg: f64,
+    /// 所入之宿与入宿度
+    pub mansion: MansionPlacement,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SkyResponse {
     pub bodies: Vec<CelestialBody>,
+    /// 月相：相角、照亮比例与中文名称
+    pub moon_phase: MoonIllumination,
+    /// 星表恒星，由亮到暗；仅在请求 `star_mag_limit` 时返回
+    #[serde(default)]
+    pub stars: Vec<SkyStar>,
+    /// 四余：罗睺、计都、月孛，给出紫气起元时另有紫气
+    #[serde(default)]
+    pub siyu: Vec<SiyuBody>,
+    /// 四余所用的取法
+    #[serde(default)]
+    pub siyu_convention: SiyuConvention,
+    pub observer: Observer,
+    /// 折射所用的气压与气温
+    pub atmosphere: Atmosphere,
+    /// 入宿所用的距星体系
+    pub mansion_system: DeterminativeSystem,
     pub note: String,
     pub jd: f64,
+    /// 力学时儒略日 (JDE = JD + ΔT)，星历以此求值
+    pub jd_tt: f64,
     pub lst_deg: f64,
     pub gmst_deg: f64,
     pub delta_t_sec: f64,
+    /// 实际使用的 ΔT 提供者
+    pub delta_t_provider: String,
 }
 
-pub fn compute_sky(req: &SkyRequest) -> SkyResponse {
-    let timestamp = req.datetime.timestamp();
-    // JD calculation from unix timestamp
-    // JD = (timestamp / 86400) + 2440587.5
-    let jd = (timestamp as f64 / 86400.0) + 2440587.5;
-    
-    // Astro coords
-    let lat_rad = req.lat_deg.to_radians();
-    let _lon_rad = req.lon_deg.to_radians();
-    
-    // Sidereal time (Meeus approximation with higher-order terms)
-    // T = (JD - 2451545.0) / 36525.0
-    // GMST = 280.46061837 + 360.98564736629*(JD - 2451545.0) + 0.000387933*T^2 - T^3/38710000
-    let d_jc = (jd - 2451545.0) / 36525.0;
-    let mut gmst = 280.46061837 + 360.98564736629 * (jd - 2451545.0) + 0.000387933 * d_jc * d_jc - (d_jc * d_jc * d_jc) / 38710000.0;
-    gmst %= 360.0;
-    if gmst < 0.0 { gmst += 360.0; }
-
-    // ΔT estimation (TT - UT1) in seconds (approximate; polynomial fit)
-    // Source: NASA polynomial (valid for 2000–2100)
-    let y = req.datetime.year() as f64;
-    let u = y - 2000.0;
-    #[derive(Deserialize)]
-    struct IersDeltaT { year: i32, delta_t_sec: f64 }
-    static IERS_DELTA_T: Lazy<RwLock<HashMap<i32, f64>>> = Lazy::new(|| RwLock::new(HashMap::new()));
-    static IERS_DELTA_T_SERIES: Lazy<RwLock<Vec<(f64, f64)>>> = Lazy::new(|| RwLock::new(Vec::new()));
-    fn load_iers_delta_t_once() {
-        let mut map = IERS_DELTA_T.write().unwrap();
-        let mut series = IERS_DELTA_T_SERIES.write().unwrap();
-        if !map.is_empty() || !series.is_empty() { return; }
-        let json_path = env::var("IERS_DELTA_T_PATH").ok().unwrap_or_else(|| "huangji_core/data/iers_delta_t.json".to_string());
-        if let Ok(file) = File::open(json_path) {
-            let reader = BufReader::new(file);
-            if let Ok(records) = serde_json::from_reader::<_, Vec<IersDeltaT>>(reader) {
-                for r in records {
-                    map.insert(r.year, r.delta_t_sec);
-                    series.push((r.year as f64, r.delta_t_sec));
-                }
-            }
+/// 按星历逐时计算的天体：日、月与七大行星
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SkyBody {
+    Sun,
+    Moon,
+    Mercury,
+    Venus,
+    Mars,
+    Jupiter,
+    Saturn,
+    Uranus,
+    Neptune,
+}
+
+impl SkyBody {
+    pub const ALL: [SkyBody; 9] = [
+        SkyBody::Sun,
+        SkyBody::Moon,
+        SkyBody::Mercury,
+        SkyBody::Venus,
+        SkyBody::Mars,
+        SkyBody::Jupiter,
+        SkyBody::Saturn,
+        SkyBody::Uranus,
+        SkyBody::Neptune,
+    ];
+
+    /// 七政：日、月与五星
+    pub const SEVEN_LUMINARIES: [SkyBody; 7] = [
+        SkyBody::Sun,
+        SkyBody::Moon,
+        SkyBody::Mercury,
+        SkyBody::Venus,
+        SkyBody::Mars,
+        SkyBody::Jupiter,
+        SkyBody::Saturn,
+    ];
+
+    /// 英文名，与 `CelestialBody::name` 一致
+    pub fn name(&self) -> &'static str {
+        match self {
+            SkyBody::Sun => "Sun",
+            SkyBody::Moon => "Moon",
+            SkyBody::Mercury => "Mercury",
+            SkyBody::Venus => "Venus",
+            SkyBody::Mars => "Mars",
+            SkyBody::Jupiter => "Jupiter",
+            SkyBody::Saturn => "Saturn",
+            SkyBody::Uranus => "Uranus",
+            SkyBody::Neptune => "Neptune",
         }
-        let csv_path = env::var("IERS_DELTA_T_CSV").ok();
-        if let Some(p) = csv_path {
-            if let Ok(content) = std::fs::read_to_string(p) {
-                for line in content.lines() {
-                    let line = line.trim();
-                    if line.is_empty() { continue; }
-                    let parts: Vec<&str> = line.split([',', ';', '\t', ' ']).filter(|s| !s.is_empty()).collect();
-                    if parts.len() < 2 { continue; }
-                    if let (Ok(y), Ok(v)) = (parts[0].parse::<f64>(), parts[1].parse::<f64>()) {
-                        series.push((y, v));
-                    }
-                }
-            }
+    }
+
+    /// 中文古名：日、月与五星（辰星、太白、荧惑、岁星、镇星）
+    pub fn name_zh(&self) -> &'static str {
+        match self {
+            SkyBody::Sun => "日",
+            SkyBody::Moon => "月",
+            SkyBody::Mercury => "辰星",
+            SkyBody::Venus => "太白",
+            SkyBody::Mars => "荧惑",
+            SkyBody::Jupiter => "岁星",
+            SkyBody::Saturn => "镇星",
+            SkyBody::Uranus => "天王星",
+            SkyBody::Neptune => "海王星",
         }
-        if !series.is_empty() {
-            series.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal));
+    }
+
+    /// 解析请求参数：英文名（不分大小写）或中文古名，如 `jupiter`、`岁星`
+    pub fn parse(raw: &str) -> anyhow::Result<Self> {
+        let raw = raw.trim();
+        Self::ALL
+            .into_iter()
+            .find(|body| body.name().eq_ignore_ascii_case(raw) || body.name_zh() == raw)
+            .ok_or_else(|| anyhow::anyhow!("无效的天体：{}", raw))
+    }
+
+    /// 天体半径 (km)，用于视半径
+    pub fn radius_km(&self) -> f64 {
+        match self {
+            SkyBody::Sun => 696_000.0,
+            SkyBody::Moon => 1737.4,
+            SkyBody::Mercury => 2439.7,
+            SkyBody::Venus => 6051.8,
+            SkyBody::Mars => 3389.5,
+            SkyBody::Jupiter => 69_911.0,
+            SkyBody::Saturn => 58_232.0,
+            SkyBody::Uranus => 25_362.0,
+            SkyBody::Neptune => 24_622.0,
         }
     }
-    fn delta_t_iers_interp(year: f64) -> Option<f64> {
-        load_iers_delta_t_once();
-        let series = IERS_DELTA_T_SERIES.read().unwrap();
-        if series.len() >= 2 {
-            if year <= series[0].0 { return Some(series[0].1); }
-            if year >= series[series.len()-1].0 { return Some(series[series.len()-1].1); }
-            for i in 0..(series.len()-1) {
-                let (y0, v0) = series[i];
-                let (y1, v1) = series[i+1];
-                if year >= y0 && year <= y1 {
-                    let t = (year - y0) / (y1 - y0);
-                    return Some(v0 + t * (v1 - v0));
-                }
-            }
+
+    /// 对应的大行星；日、月为 `None`
+    pub fn planet(&self) -> Option<Planet> {
+        match self {
+            SkyBody::Sun | SkyBody::Moon => None,
+            SkyBody::Mercury => Some(Planet::Mercury),
+            SkyBody::Venus => Some(Planet::Venus),
+            SkyBody::Mars => Some(Planet::Mars),
+            SkyBody::Jupiter => Some(Planet::Jupiter),
+            SkyBody::Saturn => Some(Planet::Saturn),
+            SkyBody::Uranus => Some(Planet::Uranus),
+            SkyBody::Neptune => Some(Planet::Neptune),
         }
-        let map = IERS_DELTA_T.read().unwrap();
-        if let Some(v) = map.get(&(year.round() as i32)).copied() { return Some(v); }
-        None
     }
-    fn delta_t_segmented(y: f64) -> f64 {
-        if (1986.0..2005.0).contains(&y) {
-            let x = y - 2000.0;
-            63.86 + 0.3345 * x - 0.060374 * x * x + 0.0017275 * x * x * x + 0.000651814 * x.powi(4) + 0.00002373599 * x.powi(5)
-        } else if (1900.0..1986.0).contains(&y) {
-            let x = y - 1900.0;
-            -2.79 + 1.494119 * x - 0.0598939 * x * x + 0.0061966 * x.powi(3) - 0.000197 * x.powi(4)
-        } else {
-            // For y >= 2005.0 or y < 1900.0
-            let x = y - 2000.0;
-            62.92 + 0.32217 * x + 0.005589 * x * x
+}
+
+/// 四余：罗睺、计都、月孛、紫气，皆无实体的虚点
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SiyuPoint {
+    Luohou,
+    Jidu,
+    Yuebei,
+    Ziqi,
+}
+
+impl SiyuPoint {
+    pub const ALL: [SiyuPoint; 4] = [
+        SiyuPoint::Luohou,
+        SiyuPoint::Jidu,
+        SiyuPoint::Yuebei,
+        SiyuPoint::Ziqi,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            SiyuPoint::Luohou => "Luohou",
+            SiyuPoint::Jidu => "Jidu",
+            SiyuPoint::Yuebei => "Yuebei",
+            SiyuPoint::Ziqi => "Ziqi",
         }
     }
-    let provider = req.delta_t_provider.as_deref().unwrap_or("segmented");
-    let delta_t_sec = match provider {
-        "iers" => delta_t_iers_interp(y).unwrap_or_else(|| delta_t_segmented(y)),
-        "nasa" => 62.92 + 0.32217 * u + 0.005589 * u * u,
-        _ => delta_t_segmented(y),
-    };
 
-    let eps0_arcsec = 84381.406 - 46.836769 * d_jc - 0.0001831 * d_jc * d_jc + 0.00200340 * d_jc * d_jc * d_jc - 0.000000576 * d_jc.powi(4) - 0.000001578 * d_jc.powi(5);
-    let eps0_rad = (eps0_arcsec / 3600.0).to_radians();
+    pub fn name_zh(&self) -> &'static str {
+        match self {
+            SiyuPoint::Luohou => "罗睺",
+            SiyuPoint::Jidu => "计都",
+            SiyuPoint::Yuebei => "月孛",
+            SiyuPoint::Ziqi => "紫气",
+        }
+    }
+}
+
+/// 罗睺、计都与黄白交点的配法
+///
+/// 唐代译入的《七曜攘灾诀》等以罗睺为升交点（同印度 Rāhu），计都为降交点；
+/// 后世星命家亦有互换者，以罗睺为降交点。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NodeConvention {
+    /// 罗睺为升交点，计都为降交点（默认）
+    #[default]
+    LuohouAscending,
+    /// 罗睺为降交点，计都为升交点
+    LuohouDescending,
+}
+
+impl NodeConvention {
+    /// 解析请求参数：`ascending`（默认）或 `descending`，指罗睺所在的交点
+    pub fn parse(raw: &str) -> anyhow::Result<Self> {
+        match raw.trim().to_ascii_lowercase().as_str() {
+            "" | "ascending" | "luohou_ascending" => Ok(Self::LuohouAscending),
+            "descending" | "luohou_descending" => Ok(Self::LuohouDescending),
+            other => anyhow::bail!("无效的罗睺配法：{}（可选 ascending、descending）", other),
+        }
+    }
+}
 
-    let m_sun = 357.52911 + 35999.05029 * d_jc - 0.0001537 * d_jc * d_jc;
-    let l_moon = 134.963 + 477198.8676 * d_jc;
-    let omega = 125.045 - 1934.136 * d_jc;
-    let d_moon = 297.850 + 445267.1115 * d_jc;
+/// 交点取平交点或真交点
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NodeModel {
+    /// 平交点，匀速逆行（默认，传统星历所用）
+    #[default]
+    Mean,
+    /// 真交点，计入主要周期项
+    True,
+}
 
-    let m_sun_rad = m_sun.to_radians();
-    let l_moon_rad = l_moon.to_radians();
-    let omega_rad = omega.to_radians();
-    let _d_moon_rad_unused = d_moon.to_radians();
+impl NodeModel {
+    /// 解析请求参数：`mean`（默认）或 `true`
+    pub fn parse(raw: &str) -> anyhow::Result<Self> {
+        match raw.trim().to_ascii_lowercase().as_str() {
+            "" | "mean" => Ok(Self::Mean),
+            "true" | "osculating" => Ok(Self::True),
+            other => anyhow::bail!("无效的交点模型：{}（可选 mean、true）", other),
+        }
+    }
+}
 
-    let dpsi_arcsec = -17.20 * omega_rad.sin() - 1.32 * (2.0 * m_sun_rad).sin() - 0.23 * (2.0 * l_moon_rad).sin() + 0.21 * (2.0 * omega_rad).sin();
-    let deps_arcsec = 9.20 * omega_rad.cos() + 0.57 * (2.0 * m_sun_rad).cos() + 0.10 * (2.0 * l_moon_rad).cos() - 0.09 * (2.0 * omega_rad).cos();
-    let dpsi_deg = dpsi_arcsec / 3600.0;
-    let deps_deg = deps_arcsec / 3600.0;
-    let ee_deg = dpsi_deg * eps0_rad.cos().to_degrees();
+/// 紫气起元：某一时刻紫气所在的平黄经
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ZiqiEpoch {
+    /// 起元时刻，力学时 (TT) 儒略日
+    pub jd_tt: f64,
+    /// 起元时紫气的平黄经 (度)
+    pub longitude_deg: f64,
+}
 
-    let mut gast = gmst + ee_deg;
-    gast = gast.rem_euclid(360.0);
-    let lst_deg = (gast + req.lon_deg).rem_euclid(360.0);
-    let lst_rad = lst_deg.to_radians();
+/// 四余的取法
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub struct SiyuConvention {
+    #[serde(default)]
+    pub node_convention: NodeConvention,
+    #[serde(default)]
+    pub node_model: NodeModel,
+    /// 紫气起元。紫气无实体天象，各家起元不一，本库不设默认；未给出时不计紫气
+    #[serde(default)]
+    pub ziqi_epoch: Option<ZiqiEpoch>,
+}
 
-    let mut bodies = Vec::new();
+/// 紫气二十八年一周天（儒略年）
+const ZIQI_PERIOD_DAYS: f64 = 28.0 * 365.25;
 
-    // Helper to convert RA/Dec to Alt/Az
-    // RA, Dec in radians
-    let to_alt_az = |ra: f64, dec: f64| -> (f64, f64) {
-        let ha = lst_rad - ra;
-        let sin_alt = lat_rad.sin() * dec.sin() + lat_rad.cos() * dec.cos() * ha.cos();
-        let alt = sin_alt.asin();
-        
-        let cos_az = (dec.sin() - lat_rad.sin() * alt.sin()) / (lat_rad.cos() * alt.cos());
-        // Handle precision issues
-        let cos_az = cos_az.clamp(-1.0, 1.0);
-        let mut az = cos_az.acos();
-        
-        if ha.sin() > 0.0 {
-            az = 2.0 * PI - az;
+/// 四余的视黄经 (度, 0-360，瞬时真春分点)
+///
+/// - 罗睺、计都：黄白交点，按 `convention` 配升、降交点，平交点或真交点
+/// - 月孛：月球平远地点（平近地点之对冲）
+/// - 紫气：自 `convention.ziqi_epoch` 起二十八年匀速顺行一周；未给出起元时返回 `None`
+///
+/// # 参数
+/// - `jd_tt`: 力学时 (TT) 儒略日
+pub fn siyu_longitude(point: SiyuPoint, jd_tt: f64, convention: &SiyuConvention) -> Option<f64> {
+    let ascending = match convention.node_model {
+        NodeModel::Mean => mean_ascending_node(jd_tt),
+        NodeModel::True => true_ascending_node(jd_tt),
+    };
+    let luohou = match convention.node_convention {
+        NodeConvention::LuohouAscending => ascending,
+        NodeConvention::LuohouDescending => ascending + 180.0,
+    };
+    let mean_longitude = match point {
+        SiyuPoint::Luohou => luohou,
+        SiyuPoint::Jidu => luohou + 180.0,
+        SiyuPoint::Yuebei => mean_perigee(jd_tt) + 180.0,
+        SiyuPoint::Ziqi => {
+            let epoch = convention.ziqi_epoch?;
+            epoch.longitude_deg + (jd_tt - epoch.jd_tt) / ZIQI_PERIOD_DAYS * 360.0
         }
-        
-        (alt.to_degrees(), az.to_degrees())
     };
+    Some((mean_longitude + nutation(jd_tt).longitude).rem_euclid(360.0))
+}
 
-    // 1. Sun (using astro crate or simple implementation if astro is complex)
-    // astro crate usage:
-    // let sun_coords = sun::geocentric_ecliptic_coordinates(jd);
-    // let sun_eq = coords::ecliptic_to_equatorial(sun_coords.long, sun_coords.lat, obliq);
-    // For simplicity and verification, we use a robust simplified VSOP or similar if astro is tricky.
-    // But since we imported `astro`, let's try to use it.
-    // Actually, the crate `astro` v2.0.0 API needs checking.
-    // Let's use basic calculation if we are not sure about astro API, 
-    // OR assume standard names.
-    
-    // Using `astro` crate:
-    // It usually provides `sun::coordinates(jd)` returning (long, lat, radius)
-    // We need to convert to Equatorial (RA, Dec) then to Horizontal (Alt, Az).
-    
-    // Let's use a simplified Meeus algorithm directly here to ensure "Real Data" without fighting crate API docs blindly.
-    // Wait, user wants "Real API". `astro` IS a real implementation of Meeus.
-    // Let's blindly try to use `astro` assuming standard modules.
-    
-    // Note: `astro` crate usually has modules like `sun`, `moon`, `planet`.
-    // Let's implement a fallback or use `astro` if we recall the API.
-    // astro::sun::sun_position(jd) -> (lon, lat, rad) ?
-    
-    // Let's stick to writing our own Meeus implementation for Sun/Moon to be safe and "Have Reason (Code)".
-    
-    // --- SUN ---
-    // Mean longitude
-    let l0 = 280.46646 + 36000.76983 * d_jc + 0.0003032 * d_jc * d_jc;
-    // Mean anomaly
-    let m = 357.52911 + 35999.05029 * d_jc - 0.0001537 * d_jc * d_jc;
-    let m_rad = m.to_radians();
-    // Equation of center
-    let c = (1.914602 - 0.004817 * d_jc - 0.000014 * d_jc * d_jc) * m_rad.sin()
-          + (0.019993 - 0.000101 * d_jc) * (2.0 * m_rad).sin()
-          + 0.000289 * (3.0 * m_rad).sin();
-    // True longitude
-    let true_long = l0 + c;
-    // Obliquity of ecliptic
-    let epsilon = eps0_arcsec / 3600.0 + deps_deg;
-    let eps_rad = epsilon.to_radians();
-    let lambda_rad = (true_long + dpsi_deg).to_radians();
-    
-    // Right Ascension
-    let _alpha = (lambda_rad.cos() * eps_rad.tan()).atan().to_degrees(); 
-    // Handle quadrant
-    let _alpha_rad = lambda_rad.cos().atan2(lambda_rad.sin() * eps_rad.cos()); // Wait, atan2(y, x) -> atan2(cos(eps)*sin(lam), cos(lam))
-    let ra_rad = (eps_rad.cos() * lambda_rad.sin()).atan2(lambda_rad.cos());
-    
-    // Declination
-    let dec_rad = (eps_rad.sin() * lambda_rad.sin()).asin();
-    
-    let (alt, az) = to_alt_az(ra_rad, dec_rad);
-    
-    bodies.push(CelestialBody {
-        name: "Sun".to_string(),
-        alt_deg: alt,
-        az_deg: az,
-        distance_au: Some(1.0),
-        ra_deg: Some(ra_rad.to_degrees()),
-        dec_deg: Some(dec_rad.to_degrees()),
-    });
-
-    // --- MOON ---
-    // 使用简单的 Meeus 算法近似计算月球位置，以避免外部库依赖问题并保证"有理可依"
-    
-    // Mean longitude
-    let l_moon = 218.316 + 481267.8813 * d_jc;
-    // Mean anomaly
-    let m_moon = 134.963 + 477198.8676 * d_jc;
-    // Mean distance of Moon from ascending node
-    let f_moon = 93.272 + 483202.0175 * d_jc;
-    // Mean elongation
-    let d_moon = 297.850 + 445267.1115 * d_jc;
-    // Mean longitude of ascending node
-    let _omega_moon = 125.045 - 1934.136 * d_jc;
-    
-    let _l_rad = l_moon.to_radians();
-    let m_rad = m_moon.to_radians(); // Corrected variable name usage from m_rad to m_moon_rad if needed, but here m_rad is Sun's m?
-    // Wait, m_rad was defined earlier as Sun's Mean Anomaly.
-    // Moon calculation uses Moon's mean anomaly mm_rad.
-    // Let's check lines 143.
-    let mm_rad = m_moon.to_radians();
-    let f_rad = f_moon.to_radians();
-    let d_rad = d_moon.to_radians();
-    
-    // Longitude terms (simplified)
-    let sigma_l = 6.289 * mm_rad.sin() 
-                - 1.274 * (mm_rad - 2.0 * d_rad).sin() 
-                + 0.658 * (2.0 * d_rad).sin()
-                + 0.214 * (2.0 * mm_rad).sin()
-                - 0.186 * m_rad.sin(); // Sun's mean anomaly m used earlier
-                
-    let lam_moon = l_moon + sigma_l;
-    
-    // Latitude terms
-    let sigma_b = 5.128 * f_rad.sin()
-                + 0.280 * (mm_rad + f_rad).sin()
-                + 0.278 * (mm_rad - f_rad).sin()
-                + 0.173 * (f_rad - 2.0 * d_rad).sin();
-                
-    let beta_moon = sigma_b;
-    
-    // Convert Ecliptic (lam_moon, beta_moon) to Equatorial
-    let lam_m_rad = (lam_moon + dpsi_deg).to_radians();
-    let beta_m_rad = beta_moon.to_radians();
-    
-    // Proper conversion:
-    let ra_moon_rad = (lam_m_rad.sin() * eps_rad.cos() - beta_m_rad.tan() * eps_rad.sin()).atan2(lam_m_rad.cos());
-    let dec_moon_rad = (beta_m_rad.sin() * eps_rad.cos() + beta_m_rad.cos() * eps_rad.sin() * lam_m_rad.sin()).asin();
-    
-    let (alt_moon, az_moon) = to_alt_az(ra_moon_rad, dec_moon_rad);
-    
-    bodies.push(CelestialBody {
-        name: "Moon".to_string(),
-        alt_deg: alt_moon,
-        az_deg: az_moon,
-        distance_au: Some(0.00257),
-        ra_deg: Some(ra_moon_rad.to_degrees()),
-        dec_deg: Some(dec_moon_rad.to_degrees()),
-    });
-    
-    // --- PLANETS (Meeus Simplified) ---
-    // For planets, full Meeus VSOP87 is complex.
-    // We will use a lower precision approximation suitable for visualization.
-    // Elements from "Astronomical Algorithms", J. Meeus, Chapter 31 (Elements of Planetary Orbits)
-    // Mean orbital elements for J2000.0
-    // We calculate Heliocentric coords (L, B, R) -> Geocentric (lambda, beta, delta) -> Equatorial (alpha, delta) -> Horizontal (alt, az).
-    
-    #[allow(dead_code)]
-    struct OrbitalElements {
-        l: f64, // Mean longitude
-        a: f64, // Semi-major axis
-        e: f64, // Eccentricity
-        i: f64, // Inclination
-        omega: f64, // Longitude of ascending node
-        pi: f64, // Longitude of perihelion
-        m: f64, // Mean anomaly (calculated)
+/// 地心视赤道坐标（瞬时真赤道与春分点）
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct EquatorialPosition {
+    pub ra_deg: f64,
+    pub dec_deg: f64,
+    pub distance_au: f64,
+}
+
+/// 北极星与北斗七星：(名称, 依巴谷编号)
+const LANDMARK_STARS: [(&str, u32); 8] = [
+    ("Polaris", 11767),
+    ("Dubhe", 54061),
+    ("Merak", 53910),
+    ("Phecda", 58001),
+    ("Megrez", 59774),
+    ("Alioth", 62956),
+    ("Mizar", 65378),
+    ("Alkaid", 67301),
+];
+
+/// 地心视黄道坐标（瞬时真黄道与春分点）
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct EclipticPosition {
+    pub lon_deg: f64,
+    pub lat_deg: f64,
+    pub distance_au: f64,
+}
+
+impl EclipticPosition {
+    /// 与另一位置的角距 (度)
+    pub fn separation(&self, other: &EclipticPosition) -> f64 {
+        angular_separation(self.lon_deg, self.lat_deg, other.lon_deg, other.lat_deg)
     }
-    
-    // Helper to solve Kepler Equation: M = E - e*sin(E)
-    fn solve_kepler(m: f64, e: f64) -> f64 {
-        let ee = m;
-        let m_rad = m.to_radians();
-        let mut e_rad = ee.to_radians();
-        for _ in 0..10 {
-            let delta = (m_rad - (e_rad - e * e_rad.sin())) / (1.0 - e * e_rad.cos());
-            e_rad += delta;
-            if delta.abs() < 1e-6 { break; }
+}
+
+/// 天体的地心视黄道坐标
+///
+/// 太阳取 VSOP87D，月亮取 ELP2000-82，行星取 JPL 轨道根数（1800–2050 年用 Table 1，其余至公元前 3000 年、公元 3000 年用 Table 2a）并计入光行时。
+///
+/// # 参数
+/// - `jd_tt`: 力学时 (TT) 儒略日
+pub fn body_ecliptic(body: SkyBody, jd_tt: f64) -> EclipticPosition {
+    let (lon_deg, lat_deg, distance_au) = match body {
+        SkyBody::Sun => {
+            let sun = apparent_sun(jd_tt);
+            (sun.longitude, sun.latitude, sun.radius)
         }
-        e_rad // return in radians
-    }
-    
-    let calc_planet = |name: &str, l0: f64, a: f64, e: f64, i_deg: f64, omega_deg: f64, pi_deg: f64, n: f64| -> CelestialBody {
-        // Calculate Mean Anomaly M
-        let mut m = (l0 + n * d_jc - pi_deg) % 360.0;
-        if m < 0.0 { m += 360.0; }
-        
-        // Eccentric Anomaly E (radians)
-        let big_e_rad = solve_kepler(m, e);
-        
-        // True Anomaly v
-        // tan(v/2) = sqrt((1+e)/(1-e)) * tan(E/2)
-        let v_rad = 2.0 * (( (1.0+e)/(1.0-e) ).sqrt() * (big_e_rad / 2.0).tan()).atan();
-        
-        // Radius vector r
-        let r = a * (1.0 - e * big_e_rad.cos());
-        
-        // Heliocentric Longitude L and Latitude B
-        // u = v + pi - omega
-        let u_rad = v_rad + (pi_deg - omega_deg).to_radians();
-        let i_rad = i_deg.to_radians();
-        let omega_rad = omega_deg.to_radians();
-        
-        // Heliocentric coords
-        let l_helio_rad = (u_rad.cos() * omega_rad.cos() - u_rad.sin() * omega_rad.sin() * i_rad.cos()).atan2(
-                           u_rad.cos() * omega_rad.sin() + u_rad.sin() * omega_rad.cos() * i_rad.cos()
-                          );
-        let b_helio_rad = (u_rad.sin() * i_rad.sin()).asin();
-        
-        // Convert to Geocentric (using Sun's coords calculated previously)
-        // We need Sun's Geocentric Longitude (Theta) and Radius (R).
-        // Sun's Heliocentric Longitude is Earth's + 180.
-        // Let's re-use Sun's true_long (Geocentric) and radius (approx 1.0).
-        let l_sun_rad = (true_long + 180.0).to_radians(); // Earth's Helio Longitude approx
-        let r_earth = 1.0; // Approx
-        
-        // Rectangular coords
-        let x = r * b_helio_rad.cos() * l_helio_rad.cos() - r_earth * l_sun_rad.cos();
-        let y = r * b_helio_rad.cos() * l_helio_rad.sin() - r_earth * l_sun_rad.sin();
-        let z = r * b_helio_rad.sin();
-        
-        let lambda_geo = y.atan2(x); // Geocentric Longitude
-        let beta_geo = z.atan2((x*x + y*y).sqrt()); // Geocentric Latitude
-        
-        // To Equatorial
-        let eps = eps_rad; // from Sun calc
-        let ra_rad = (lambda_geo.sin() * eps.cos() - beta_geo.tan() * eps.sin()).atan2(lambda_geo.cos());
-        let dec_rad = (beta_geo.sin() * eps.cos() + beta_geo.cos() * eps.sin() * lambda_geo.sin()).asin();
-        
-        let (alt, az) = to_alt_az(ra_rad, dec_rad);
-        
-        CelestialBody {
-            name: name.to_string(),
-            alt_deg: alt,
-            az_deg: az,
-            distance_au: Some((x*x + y*y + z*z).sqrt()),
-            ra_deg: Some(ra_rad.to_degrees()),
-            dec_deg: Some(dec_rad.to_degrees()),
+        SkyBody::Moon => {
+            let moon = moon_position(jd_tt);
+            (moon.longitude, moon.latitude, moon.distance_au())
+        }
+        planet => {
+            let planet = planet.planet().expect("行星均有轨道根数");
+            let position = planet_position(planet, jd_tt);
+            (position.longitude, position.latitude, position.distance_au)
         }
     };
+    EclipticPosition {
+        lon_deg,
+        lat_deg,
+        distance_au,
+    }
+}
+
+/// 天体的地心视赤道坐标
+///
+/// # 参数
+/// - `jd_tt`: 力学时 (TT) 儒略日
+pub fn body_equatorial(body: SkyBody, jd_tt: f64) -> EquatorialPosition {
+    let ecliptic = body_ecliptic(body, jd_tt);
+    let equatorial = EclipticCoord {
+        lon_deg: ecliptic.lon_deg,
+        lat_deg: ecliptic.lat_deg,
+    }
+    .to_equatorial(true_obliquity(jd_tt));
+    EquatorialPosition {
+        ra_deg: equatorial.ra_deg,
+        dec_deg: equatorial.dec_deg,
+        distance_au: ecliptic.distance_au,
+    }
+}
+
+impl SkyRequest {
+    /// 折射所用的大气，缺省项取标准大气；气压或气温无效时报错
+    pub fn atmosphere(&self) -> anyhow::Result<Atmosphere> {
+        let defaults = Atmosphere::default();
+        Atmosphere::new(
+            self.pressure_hpa.unwrap_or(defaults.pressure_hpa),
+            self.temperature_c.unwrap_or(defaults.temperature_c),
+        )
+    }
+}
+
+/// 某一时刻某地的天象
+///
+/// 气压或气温无效（见 `Atmosphere::validate`）时报错。
+pub fn compute_sky(req: &SkyRequest) -> anyhow::Result<SkyResponse> {
+    let timestamp = req.datetime.timestamp();
+    // JD calculation from unix timestamp
+    // JD = (timestamp / 86400) + 2440587.5
+    let jd = (timestamp as f64 / 86400.0) + 2440587.5;
     
-    // Elements (approximate)
-    // Mercury
-    bodies.push(calc_planet("Mercury", 252.25, 0.3871, 0.2056, 7.005, 48.331, 77.456, 149472.67));
-    // Venus
-    bodies.push(calc_planet("Venus", 181.98, 0.7233, 0.0068, 3.395, 76.680, 131.533, 58517.81));
-    // Mars
-    bodies.push(calc_planet("Mars", 355.43, 1.5237, 0.0934, 1.850, 49.558, 336.041, 19140.29));
-    // Jupiter
-    bodies.push(calc_planet("Jupiter", 34.35, 5.2026, 0.0485, 1.303, 100.464, 14.331, 3034.90));
-    // Saturn
-    bodies.push(calc_planet("Saturn", 50.08, 9.5549, 0.0555, 2.489, 113.666, 92.097, 1222.11));
-    
-    // --- Polaris & Big Dipper (Stars) ---
-    // These are fixed stars (relatively), we just calculate Alt/Az from RA/Dec.
-    // Precession should be applied for high accuracy over centuries, but for "Daoist" vibe and 100-year span, J2000 is acceptable?
-    // User wants "Advanced Algorithm". We should apply precession.
-    // Precession approx: 50.3 arcsec/year.
-    // Let's implement basic precession from J2000 to current epoch.
-    
-    let precess = |ra0_deg: f64, dec0_deg: f64| -> (f64, f64) {
-        let t = (jd - 2451545.0) / 36525.0;
-        let zeta_arcsec = 2306.083227 * t + 0.2988499 * t * t + 0.018018 * t * t * t;
-        let z_arcsec = 2306.077181 * t + 1.0927348 * t * t + 0.018268 * t * t * t;
-        let theta_arcsec = 2004.191903 * t - 0.4294934 * t * t - 0.041833 * t * t * t;
-        let zeta = (zeta_arcsec / 3600.0).to_radians();
-        let z = (z_arcsec / 3600.0).to_radians();
-        let theta = (theta_arcsec / 3600.0).to_radians();
-
-        let a = ra0_deg.to_radians();
-        let d = dec0_deg.to_radians();
-
-        let x0 = d.cos() * a.cos();
-        let y0 = d.cos() * a.sin();
-        let z0 = d.sin();
-
-        let x1 = x0 * zeta.cos() + y0 * zeta.sin();
-        let y1 = -x0 * zeta.sin() + y0 * zeta.cos();
-        let z1 = z0;
-
-        let x2 = x1;
-        let y2 = y1 * theta.cos() + z1 * theta.sin();
-        let z2 = -y1 * theta.sin() + z1 * theta.cos();
-
-        let x3 = x2 * z.cos() + y2 * z.sin();
-        let y3 = -x2 * z.sin() + y2 * z.cos();
-        let z3 = z2;
-
-        let ra = y3.atan2(x3);
-        let dec = z3.atan2((x3 * x3 + y3 * y3).sqrt());
-        (ra.to_degrees().rem_euclid(360.0), dec.to_degrees())
+    let observer = Observer {
+        lat_deg: req.lat_deg,
+        lon_deg: req.lon_deg,
+        elevation_m: req.elevation_m,
     };
+    let atmosphere = req.atmosphere()?;
     
-    let add_star = |name: &str, ra2000: f64, dec2000: f64| -> CelestialBody {
-        let (ra, dec) = precess(ra2000, dec2000);
-        let (alt, az) = to_alt_az(ra.to_radians(), dec.to_radians());
-        CelestialBody {
+    // ΔT (TT - UT1)：按请求选择的模型
+    let delta_t_provider = req.delta_t_model.provider();
+    let scales = TimeScales::from_jd_utc_with(jd, delta_t_provider.as_ref());
+    let delta_t_sec = scales.delta_t_sec;
+
+    // 以下星历（章动、日月行星、岁差）均以力学时 (TT) 求值；恒星时按 UT1
+    let jd_tt = scales.jd_tt;
+    let gmst = greenwich_mean_sidereal_time(scales.jd_ut1);
+    let lst_deg = local_apparent_sidereal_time(&scales, req.lon_deg);
+
+    let mut bodies = Vec::new();
+
+    // 瞬时真赤道坐标换算地平坐标
+    let to_alt_az = |ra_deg: f64, dec_deg: f64| -> (f64, f64) {
+        let horizontal = EquatorialCoord { ra_deg, dec_deg }.to_horizontal(req.lat_deg, lst_deg);
+        (horizontal.alt_deg, horizontal.az_deg)
+    };
+
+    let mansions = MansionTable::new(jd_tt, req.mansion_system);
+    let constellations = ConstellationLocator::new(jd_tt);
+
+    // 日、月、七大行星：地心视赤道坐标改正周日视差后换算地平坐标
+    for body in SkyBody::ALL {
+        let position = body_equatorial(body, jd_tt);
+        let (topo_ra, topo_dec) = observer.topocentric_equatorial(
+            position.ra_deg,
+            position.dec_deg,
+            position.distance_au * AU_KM,
+            lst_deg - position.ra_deg,
+        );
+        let (alt, az) = to_alt_az(topo_ra, topo_dec);
+        bodies.push(CelestialBody {
+            name: body.name().to_string(),
+            alt_deg: alt,
+            az_deg: az,
+            apparent_alt_deg: atmosphere.apparent_altitude(alt),
+            apparent_az_deg: az,
+            distance_au: Some(position.distance_au),
+            ra_deg: Some(position.ra_deg),
+            dec_deg: Some(position.dec_deg),
+            mansion: Some(mansions.placement(position.ra_deg)),
+            constellation: Some(constellations.locate(position.ra_deg, position.dec_deg)),
+        });
+    }
+    let moon_phase = moon_illumination(jd_tt);
+
+    // 四余：黄道上的虚点
+    let obliquity = true_obliquity(jd_tt);
+    let siyu = SiyuPoint::ALL
+        .iter()
+        .filter_map(|&point| {
+            let lon_deg = siyu_longitude(point, jd_tt, &req.siyu)?;
+            let equatorial = EclipticCoord {
+                lon_deg,
+                lat_deg: 0.0,
+            }
+            .to_equatorial(obliquity);
+            let (alt, az) = to_alt_az(equatorial.ra_deg, equatorial.dec_deg);
+            Some(SiyuBody {
+                name: point.name().to_string(),
+                name_zh: point.name_zh().to_string(),
+                ecliptic_lon_deg: lon_deg,
+                ra_deg: equatorial.ra_deg,
+                dec_deg: equatorial.dec_deg,
+                alt_deg: alt,
+                az_deg: az,
+                mansion: mansions.placement(equatorial.ra_deg),
+            })
+        })
+        .collect();
+
+    // 北极星与北斗七星（依巴谷编号），沿用原有 bodies 输出
+    let reduction = StarReduction::new(jd_tt);
+    let star_horizontal = |star: &CatalogStar| {
+        let (ra, dec) = reduction.apparent_place(star);
+        let (alt, az) = to_alt_az(ra, dec);
+        (ra, dec, alt, az)
+    };
+    for (name, hip) in LANDMARK_STARS {
+        let Some(star) = find_star(hip) else {
+            continue;
+        };
+        let (ra, dec, alt, az) = star_horizontal(star);
+        bodies.push(CelestialBody {
             name: name.to_string(),
             alt_deg: alt,
             az_deg: az,
+            apparent_alt_deg: atmosphere.apparent_altitude(alt),
+            apparent_az_deg: az,
             distance_au: None,
             ra_deg: Some(ra),
             dec_deg: Some(dec),
-        }
+            mansion: Some(mansions.placement(ra)),
+            constellation: Some(constellations.locate(ra, dec)),
+        });
+    }
+
+    // 星表：按星等与视高度过滤
+    let stars = match req.star_mag_limit {
+        Some(mag_limit) => star_catalog()
+            .iter()
+            .take_while(|star| star.mag <= mag_limit)
+            .filter_map(|star| {
+                let (ra, dec, alt, az) = star_horizontal(star);
+                let apparent_alt = atmosphere.apparent_altitude(alt);
+                if req.star_min_alt_deg.is_some_and(|min| apparent_alt < min) {
+                    return None;
+                }
+                Some(SkyStar {
+                    hip: star.hip,
+                    name: star.name.clone(),
+                    name_zh: star.name_zh.clone(),
+                    mag: star.mag,
+                    bv: star.bv,
+                    ra_deg: ra,
+                    dec_deg: dec,
+                    alt_deg: alt,
+                    az_deg: az,
+                    apparent_alt_deg: apparent_alt,
+                })
+            })
+            .collect(),
+        None => Vec::new(),
     };
-    
-    // Polaris (Alpha Ursae Minoris)
-    // RA 2h 31m, Dec +89 15
-    bodies.push(add_star("Polaris", 37.95, 89.26));
-    
-    // Big Dipper (7 stars of Ursa Major)
-    // Dubhe (Alpha UMa): 11h 03m, +61 45
-    bodies.push(add_star("Dubhe", 165.93, 61.75));
-    // Merak (Beta UMa): 11h 01m, +56 22
-    bodies.push(add_star("Merak", 165.46, 56.38));
-    // Phecda (Gamma UMa): 11h 53m, +53 41
-    bodies.push(add_star("Phecda", 178.46, 53.69));
-    // Megrez (Delta UMa): 12h 15m, +57 01
-    bodies.push(add_star("Megrez", 183.86, 57.03));
-    // Alioth (Epsilon UMa): 12h 54m, +55 57
-    bodies.push(add_star("Alioth", 193.50, 55.96));
-    // Mizar (Zeta UMa): 13h 23m, +54 55
-    bodies.push(add_star("Mizar", 200.98, 54.92));
-    // Alkaid (Eta UMa): 13h 47m, +49 18
-    bodies.push(add_star("Alkaid", 206.88, 49.31));
-    
-    SkyResponse {
+
+    Ok(SkyResponse {
         bodies,
-        note: "Real-time Sun/Moon/Planets/Stars (Meeus/J2000)".to_string(),
+        moon_phase,
+        stars,
+        siyu,
+        siyu_convention: req.siyu,
+        observer,
+        atmosphere,
+        mansion_system: req.mansion_system,
+        note: "Real-time Sun/Moon/Planets/Stars (VSOP87/ELP2000/JPL elements, Hipparcos)".to_string(),
         jd,
+        jd_tt,
         lst_deg,
         gmst_deg: gmst,
         delta_t_sec,
+        delta_t_provider: delta_t_provider.name().to_string(),
+    })
+}
+
+/// 某地某日的出没查询
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RiseSetRequest {
+    /// 当地民用日期
+    pub date: NaiveDate,
+    /// 时区偏移（分钟），东为正
+    pub tz_offset_minutes: i32,
+    pub observer: Observer,
+    /// ΔT 模型（默认 Espenak–Meeus）
+    #[serde(default)]
+    pub delta_t_model: DeltaTModel,
+}
+
+/// 单个天体的出没
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BodyRiseSet {
+    pub name: String,
+    #[serde(flatten)]
+    pub events: RiseSetTransit,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RiseSetResponse {
+    pub date: NaiveDate,
+    pub tz_offset_minutes: i32,
+    pub observer: Observer,
+    /// 日、月、行星的升、中天、落
+    pub bodies: Vec<BodyRiseSet>,
+    /// 民用、航海、天文晨昏蒙影
+    pub twilight: Vec<Twilight>,
+    /// 实际使用的 ΔT 提供者
+    pub delta_t_provider: String,
+}
+
+/// 计算某地当地民用日内日、月、行星的升、中天、落与晨昏蒙影
+///
+/// 升落取天体上缘切于视地平（标准折射 34'），月亮计站心视差。
+pub fn compute_rise_set(req: &RiseSetRequest) -> anyhow::Result<RiseSetResponse> {
+    if !(-90.0..=90.0).contains(&req.observer.lat_deg) {
+        anyhow::bail!("纬度超出范围：{}", req.observer.lat_deg);
+    }
+    if !(-180.0..=180.0).contains(&req.observer.lon_deg) {
+        anyhow::bail!("经度超出范围：{}", req.observer.lon_deg);
+    }
+    if req.tz_offset_minutes.abs() > 18 * 60 {
+        anyhow::bail!("无效时区偏移：{} 分钟", req.tz_offset_minutes);
+    }
+    let local_midnight = req
+        .date
+        .and_hms_opt(0, 0, 0)
+        .ok_or_else(|| anyhow::anyhow!("无效日期：{}", req.date))?;
+    let start_jd = datetime_to_jd(&local_midnight) - req.tz_offset_minutes as f64 / 1440.0;
+
+    let provider = req.delta_t_model.provider();
+    let place = |body: SkyBody| {
+        move |jd_tt: f64| {
+            let position = body_equatorial(body, jd_tt);
+            ApparentPlace {
+                ra_deg: position.ra_deg,
+                dec_deg: position.dec_deg,
+                distance_km: position.distance_au * AU_KM,
+                radius_km: body.radius_km(),
+            }
+        }
+    };
+
+    let bodies = SkyBody::ALL
+        .iter()
+        .map(|&body| BodyRiseSet {
+            name: body.name().to_string(),
+            events: rise_set_transit(
+                place(body),
+                &req.observer,
+                start_jd,
+                STANDARD_REFRACTION_DEG,
+                provider.as_ref(),
+            ),
+        })
+        .collect();
+    let twilight = TwilightKind::ALL
+        .iter()
+        .map(|&kind| {
+            twilight(
+                place(SkyBody::Sun),
+                &req.observer,
+                start_jd,
+                kind,
+                provider.as_ref(),
+            )
+        })
+        .collect();
+
+    Ok(RiseSetResponse {
+        date: req.date,
+        tz_offset_minutes: req.tz_offset_minutes,
+        observer: req.observer,
+        bodies,
+        twilight,
+        delta_t_provider: provider.name().to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::astro::observer::EARTH_EQUATORIAL_RADIUS_KM;
+    use chrono::{TimeZone, Timelike};
+
+    #[test]
+    fn test_rise_set_meeus_example_15a() {
+        // Meeus 例 15.a：1988-03-20 波士顿金星，升 12h25m，中天 19h41m，落 2h55m (UT)
+        let response = compute_rise_set(&RiseSetRequest {
+            date: NaiveDate::from_ymd_opt(1988, 3, 20).unwrap(),
+            tz_offset_minutes: 0,
+            observer: Observer::new(42.3333, -71.0833),
+            delta_t_model: DeltaTModel::default(),
+        })
+        .unwrap();
+        let venus = response.bodies.iter().find(|b| b.name == "Venus").unwrap();
+        let near = |actual: Option<DateTime<Utc>>, h, m| {
+            let expected = Utc.with_ymd_and_hms(1988, 3, 20, h, m, 0).unwrap();
+            (actual.unwrap() - expected).num_seconds().abs() < 120
+        };
+        assert!(near(venus.events.rise, 12, 25));
+        assert!(near(venus.events.transit, 19, 41));
+        assert!(near(venus.events.set, 2, 55));
+        assert_eq!(response.twilight.len(), 3);
+    }
+
+    #[test]
+    fn test_neptune_discovery_position() {
+        // 1846-09-23 柏林天文台 Galle 发现海王星：赤经 21h53m25s，赤纬 -13°24′
+        let neptune = body_equatorial(SkyBody::Neptune, 2395563.46);
+        assert!((neptune.ra_deg - (21.0 + 53.42 / 60.0) * 15.0).abs() < 0.1);
+        assert!((neptune.dec_deg - (-13.4)).abs() < 0.1);
+        assert!((neptune.distance_au - 29.0).abs() < 0.5);
+    }
+
+    #[test]
+    fn test_sky_topocentric_and_refraction() {
+        let request = SkyRequest {
+            datetime: Utc.with_ymd_and_hms(2025, 1, 10, 12, 0, 0).unwrap(),
+            lat_deg: 39.9,
+            lon_deg: 116.4,
+            elevation_m: 50.0,
+            pressure_hpa: None,
+            temperature_c: None,
+            delta_t_model: DeltaTModel::default(),
+            accuracy: None,
+            star_mag_limit: None,
+            star_min_alt_deg: None,
+            mansion_system: DeterminativeSystem::default(),
+            siyu: SiyuConvention::default(),
+        };
+        let response = compute_sky(&request).expect("sky");
+        let moon = response.bodies.iter().find(|b| b.name == "Moon").unwrap();
+
+        // 由地心赤经赤纬求地心高度，与站心高度之差应为视差 π·cos h
+        let (lat, dec) = (39.9_f64.to_radians(), moon.dec_deg.unwrap().to_radians());
+        let ha = (response.lst_deg - moon.ra_deg.unwrap()).to_radians();
+        let geocentric_alt = (lat.sin() * dec.sin() + lat.cos() * dec.cos() * ha.cos())
+            .asin()
+            .to_degrees();
+        let parallax = (EARTH_EQUATORIAL_RADIUS_KM / (moon.distance_au.unwrap() * AU_KM))
+            .asin()
+            .to_degrees();
+        let expected = parallax * moon.alt_deg.to_radians().cos();
+        assert!((geocentric_alt - moon.alt_deg - expected).abs() < 0.02);
+        assert!(moon.apparent_alt_deg > moon.alt_deg || moon.alt_deg < -1.0);
+        assert_eq!(moon.apparent_az_deg, moon.az_deg);
+
+        // 真空（气压为零）时视高度即几何高度
+        let vacuum = compute_sky(&SkyRequest {
+            pressure_hpa: Some(0.0),
+            ..request.clone()
+        })
+        .expect("sky");
+        for body in &vacuum.bodies {
+            assert_eq!(body.apparent_alt_deg, body.alt_deg);
+        }
+
+        // 绝对零度以下与负气压无物理意义，报错而不计算
+        assert!(compute_sky(&SkyRequest {
+            temperature_c: Some(-273.15),
+            ..request.clone()
+        })
+        .is_err());
+        assert!(compute_sky(&SkyRequest {
+            pressure_hpa: Some(-5.0),
+            ..request
+        })
+        .is_err());
+    }
+
+    #[test]
+    fn test_sky_star_catalog_filters() {
+        let request = SkyRequest {
+            datetime: Utc.with_ymd_and_hms(2025, 1, 10, 14, 0, 0).unwrap(),
+            lat_deg: 39.9,
+            lon_deg: 116.4,
+            elevation_m: 0.0,
+            pressure_hpa: None,
+            temperature_c: None,
+            delta_t_model: DeltaTModel::default(),
+            accuracy: None,
+            star_mag_limit: None,
+            star_min_alt_deg: None,
+            mansion_system: DeterminativeSystem::default(),
+            siyu: SiyuConvention::default(),
+        };
+        let response = compute_sky(&request).expect("sky");
+        assert!(response.stars.is_empty());
+        // 北极星高度约等于地理纬度
+        let polaris = response.bodies.iter().find(|b| b.name == "Polaris").unwrap();
+        assert!((polaris.alt_deg - 39.9).abs() < 1.0);
+
+        let filtered = compute_sky(&SkyRequest {
+            star_mag_limit: Some(2.0),
+            star_min_alt_deg: Some(0.0),
+            ..request.clone()
+        })
+        .expect("sky");
+        assert!(!filtered.stars.is_empty());
+        assert!(filtered
+            .stars
+            .iter()
+            .all(|star| star.mag <= 2.0 && star.apparent_alt_deg >= 0.0));
+        // 冬夜 22 时北京可见天狼
+        assert!(filtered.stars.iter().any(|star| star.name.as_deref() == Some("Sirius")));
+
+        let all_bright = compute_sky(&SkyRequest {
+            star_mag_limit: Some(2.0),
+            ..request
+        })
+        .expect("sky");
+        assert!(all_bright.stars.len() > filtered.stars.len());
+    }
+
+    #[test]
+    fn test_sky_mansion_placement() {
+        // 2025-06-21 夏至前后太阳赤经约 90°，今距星体系入参宿
+        let request = SkyRequest {
+            datetime: Utc.with_ymd_and_hms(2025, 6, 21, 4, 0, 0).unwrap(),
+            lat_deg: 39.9,
+            lon_deg: 116.4,
+            elevation_m: 0.0,
+            pressure_hpa: None,
+            temperature_c: None,
+            delta_t_model: DeltaTModel::default(),
+            accuracy: None,
+            star_mag_limit: None,
+            star_min_alt_deg: None,
+            mansion_system: DeterminativeSystem::Modern,
+            siyu: SiyuConvention::default(),
+        };
+        let response = compute_sky(&request).expect("sky");
+        let sun = response.bodies.iter().find(|b| b.name == "Sun").unwrap();
+        let mansion = sun.mansion.as_ref().unwrap();
+        assert_eq!(mansion.name, "参");
+        assert!(mansion.entry_deg >= 0.0 && mansion.entry_deg < mansion.width_deg);
+        assert!(response.bodies.iter().all(|b| b.mansion.is_some()));
+
+        let historical = compute_sky(&SkyRequest {
+            mansion_system: DeterminativeSystem::Historical,
+            ..request
+        })
+        .expect("sky");
+        assert_eq!(historical.mansion_system, DeterminativeSystem::Historical);
+        let sun = historical.bodies.iter().find(|b| b.name == "Sun").unwrap();
+        assert_eq!(sun.mansion.as_ref().unwrap().determinative, "参宿一");
+    }
+
+    #[test]
+    fn test_siyu_conventions() {
+        // Meeus 例 47.a：1992-04-12 0h TD 平升交点 274.400656°
+        let jd_tt = 2448724.5;
+        let dpsi = nutation(jd_tt).longitude;
+        let default = SiyuConvention::default();
+        let luohou = siyu_longitude(SiyuPoint::Luohou, jd_tt, &default).unwrap();
+        let jidu = siyu_longitude(SiyuPoint::Jidu, jd_tt, &default).unwrap();
+        assert!((luohou - (274.400656 + dpsi)).abs() < 1e-5);
+        assert!(((jidu - luohou).rem_euclid(360.0) - 180.0).abs() < 1e-9);
+
+        let swapped = SiyuConvention {
+            node_convention: NodeConvention::LuohouDescending,
+            ..default
+        };
+        assert!((siyu_longitude(SiyuPoint::Luohou, jd_tt, &swapped).unwrap() - jidu).abs() < 1e-9);
+        assert!((siyu_longitude(SiyuPoint::Jidu, jd_tt, &swapped).unwrap() - luohou).abs() < 1e-9);
+        let true_node = SiyuConvention {
+            node_model: NodeModel::True,
+            ..default
+        };
+        let diff = siyu_longitude(SiyuPoint::Luohou, jd_tt, &true_node).unwrap() - luohou;
+        assert!(diff.abs() > 0.0 && diff.abs() < 1.8);
+
+        // 紫气无默认起元
+        assert_eq!(siyu_longitude(SiyuPoint::Ziqi, jd_tt, &default), None);
+        // 给出起元后，起元时刻即在所给黄经，二十八年一周天，一年约行 12.86°
+        let epoch = ZiqiEpoch {
+            jd_tt: 2415020.0,
+            longitude_deg: 100.0,
+        };
+        let with_ziqi = SiyuConvention {
+            ziqi_epoch: Some(epoch),
+            ..default
+        };
+        let ziqi = |jd: f64| siyu_longitude(SiyuPoint::Ziqi, jd, &with_ziqi).unwrap() - nutation(jd).longitude;
+        assert!((ziqi(epoch.jd_tt) - 100.0).abs() < 1e-9);
+        let step = (ziqi(epoch.jd_tt + 365.25) - ziqi(epoch.jd_tt)).rem_euclid(360.0);
+        assert!((step - 360.0 / 28.0).abs() < 1e-9);
+        assert!(((ziqi(epoch.jd_tt + ZIQI_PERIOD_DAYS) - 100.0 + 180.0).rem_euclid(360.0) - 180.0).abs() < 1e-6);
+
+        assert_eq!(NodeConvention::parse("Descending").unwrap(), NodeConvention::LuohouDescending);
+        assert_eq!(NodeModel::parse("").unwrap(), NodeModel::Mean);
+        assert!(NodeModel::parse("apparent").is_err());
+    }
+
+    #[test]
+    fn test_sky_constellations() {
+        // 2025-08-01 太阳在巨蟹座；北极星在小熊座，北斗诸星在大熊座
+        let response = compute_sky(&SkyRequest {
+            datetime: Utc.with_ymd_and_hms(2025, 8, 1, 4, 0, 0).unwrap(),
+            lat_deg: 39.9,
+            lon_deg: 116.4,
+            elevation_m: 0.0,
+            pressure_hpa: None,
+            temperature_c: None,
+            delta_t_model: DeltaTModel::default(),
+            accuracy: None,
+            star_mag_limit: None,
+            star_min_alt_deg: None,
+            mansion_system: DeterminativeSystem::Modern,
+            siyu: SiyuConvention::default(),
+        })
+        .expect("sky");
+        let constellation_of = |name: &str| {
+            let body = response.bodies.iter().find(|b| b.name == name).unwrap();
+            body.constellation.clone().unwrap()
+        };
+        // 未给出紫气起元：四余只有罗睺、计都、月孛
+        assert_eq!(response.siyu.len(), 3);
+        assert!(response.siyu.iter().all(|p| p.dec_deg.abs() < 23.5));
+        let sun = constellation_of("Sun");
+        assert_eq!(sun.iau.unwrap().id, "Cnc");
+        assert!(sun.chinese.is_some());
+        assert_eq!(constellation_of("Polaris").iau.unwrap().id, "UMi");
+        assert!(constellation_of("Polaris").chinese.is_none());
+        for (name, _) in &LANDMARK_STARS[1..] {
+            assert_eq!(constellation_of(name).iau.unwrap().id, "UMa");
+        }
+        assert!(response
+            .bodies
+            .iter()
+            .all(|b| b.constellation.as_ref().is_some_and(|c| c.iau.is_some())));
+    }
+
+    #[test]
+    fn test_rise_set_local_day_window() {
+        // 杭州 2025-01-01：所有事件均落在北京时间当日之内
+        let response = compute_rise_set(&RiseSetRequest {
+            date: NaiveDate::from_ymd_opt(2025, 1, 1).unwrap(),
+            tz_offset_minutes: 480,
+            observer: Observer::new(30.27, 120.15),
+            delta_t_model: DeltaTModel::default(),
+        })
+        .unwrap();
+        let start = Utc.with_ymd_and_hms(2024, 12, 31, 16, 0, 0).unwrap();
+        for body in &response.bodies {
+            for time in [body.events.rise, body.events.transit, body.events.set]
+                .into_iter()
+                .flatten()
+            {
+                let offset = (time - start).num_seconds();
+                assert!((0..86400).contains(&offset), "{} {}", body.name, time);
+            }
+        }
+        let sun = &response.bodies[0];
+        // 北京时间 06:55 前后日出
+        let rise_local = sun.events.rise.unwrap() + chrono::Duration::hours(8);
+        assert_eq!(rise_local.hour(), 6);
+
+        let invalid = RiseSetRequest {
+            date: NaiveDate::from_ymd_opt(2025, 1, 1).unwrap(),
+            tz_offset_minutes: 480,
+            observer: Observer::new(95.0, 0.0),
+            delta_t_model: DeltaTModel::default(),
+        };
+        assert!(compute_rise_set(&invalid).is_err());
     }
 }
@@ -0,0 +1,125 @@
+//! 干支跨模块一致性测试
+//!
+//! 黄历（lunar）、运势（fortune）、经世年（time_rule）、月历（month）与八字（ganzhi）
+//! 必须对同一时刻给出相同的年、月、日干支。
+
+use chrono::{DateTime, Datelike, TimeZone, Utc};
+use huangji_core::astro::delta_t::{DeltaTModel, EspenakMeeus};
+use huangji_core::astro::solar_time::SolarTimePolicy;
+use huangji_core::calendar::ganzhi::{calc_bazi_pillars, DIZHI, TIANGAN};
+use huangji_core::calendar::month::compute_month_calendar;
+use huangji_core::calendar::sexagenary::{GanZhi, Sexagenary};
+use huangji_core::calendar::time_rule::{datetime_to_hj_year, to_rule_datetime, YearStartMode};
+use huangji_core::calendar::zone::TimeZoneSpec;
+use huangji_core::fortune::{compute_fortune, CalcMode, FortuneRequest};
+use huangji_core::lunar::compute_lunar;
+use proptest::prelude::*;
+
+fn pillar_name(pair: (usize, usize)) -> String {
+    format!("{}{}", TIANGAN[pair.0], DIZHI[pair.1])
+}
+
+fn zone(tz_offset_minutes: i32) -> TimeZoneSpec {
+    TimeZoneSpec::from_offset_minutes(tz_offset_minutes).unwrap()
+}
+
+fn instant(seconds: i64) -> DateTime<Utc> {
+    Utc.timestamp_opt(seconds, 0).unwrap()
+}
+
+// 1901-03-01 .. 2099-12-01，农历库支持范围内
+const START: i64 = -2_172_355_200;
+const END: i64 = 4_099_766_400;
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(200))]
+
+    /// 黄历年、月干支与八字年、月柱一致（均以立春换年、节气换月）
+    #[test]
+    fn prop_lunar_matches_bazi_year_month(seconds in START..END, tz_hours in -12i32..=14) {
+        let dt = instant(seconds);
+        let lunar = compute_lunar(&dt, &zone(tz_hours * 60), tz_hours as f64 * 15.0, SolarTimePolicy::Meridian, &EspenakMeeus).unwrap();
+        let bazi = calc_bazi_pillars(&dt, tz_hours as f64 * 15.0, &EspenakMeeus);
+        prop_assert_eq!(&lunar.ganzhi_year, &pillar_name(bazi.year));
+        prop_assert_eq!(&lunar.ganzhi_month, &pillar_name(bazi.month));
+    }
+
+    /// 运势年干支与黄历年干支一致
+    #[test]
+    fn prop_fortune_matches_lunar_year(seconds in START..END) {
+        let dt = instant(seconds);
+        let lunar = compute_lunar(&dt, &zone(480), 116.4, SolarTimePolicy::Meridian, &EspenakMeeus).unwrap();
+        let fortune = compute_fortune(&FortuneRequest {
+            datetime: dt,
+            tz_offset_minutes: Some(480),
+            timezone: None,
+            lon: Some(116.4),
+            use_true_solar_time: Some(false),
+            solar_time: None,
+            mode: Some(CalcMode::Algorithm),
+            year_start: Some(YearStartMode::Lichun),
+            calendar: None,
+            primary: None,
+            delta_t_model: DeltaTModel::default(),
+        });
+        prop_assert_eq!(fortune.nian_ganzhi, lunar.ganzhi_year);
+    }
+
+    /// 立春岁首的经世年与黄历年干支同属一年
+    #[test]
+    fn prop_hj_year_matches_lunar_year(seconds in START..END) {
+        let dt = instant(seconds);
+        let rule_dt = to_rule_datetime(dt, &zone(480), 116.4, SolarTimePolicy::Meridian);
+        let hj_year = datetime_to_hj_year(rule_dt, YearStartMode::Lichun);
+        let lunar = compute_lunar(&dt, &zone(480), 116.4, SolarTimePolicy::Meridian, &EspenakMeeus).unwrap();
+        prop_assert_eq!(GanZhi::from_cycle(hj_year as i64 - 4).to_string(), lunar.ganzhi_year);
+    }
+
+    /// 黄历日干支取本地民用日期
+    #[test]
+    fn prop_lunar_day_uses_local_date(seconds in START..END, tz_hours in -12i32..=14) {
+        let dt = instant(seconds);
+        let lunar = compute_lunar(&dt, &zone(tz_hours * 60), 0.0, SolarTimePolicy::Meridian, &EspenakMeeus).unwrap();
+        let local_date = to_rule_datetime(dt, &zone(tz_hours * 60), 0.0, SolarTimePolicy::Meridian).date_naive();
+        prop_assert_eq!(lunar.ganzhi_day, Sexagenary::day_pillar(local_date).to_string());
+    }
+
+    /// IANA 时区按历史偏移（含夏令时、地方平时）取本地民用日期
+    #[test]
+    fn prop_lunar_day_follows_iana_zone(seconds in START..END) {
+        let dt = instant(seconds);
+        let shanghai = TimeZoneSpec::parse("Asia/Shanghai").unwrap();
+        let lunar = compute_lunar(&dt, &shanghai, 121.5, SolarTimePolicy::Meridian, &EspenakMeeus).unwrap();
+        let local_date = shanghai.to_local(&dt).date_naive();
+        prop_assert_eq!(lunar.ganzhi_day, Sexagenary::day_pillar(local_date).to_string());
+    }
+}
+
+#[test]
+fn test_month_calendar_matches_lunar() {
+    let calendar = compute_month_calendar(2025, 2, 480, None, &EspenakMeeus).unwrap();
+    for day in &calendar.days {
+        assert_eq!(day.ganzhi_day, Sexagenary::day_pillar(day.date).to_string());
+    }
+    // 2025-02-03 立春前属甲辰年，2025-02-04 立春（北京时间 04:10）后属乙巳年
+    let before = compute_lunar(
+        &Utc.with_ymd_and_hms(2025, 2, 3, 4, 0, 0).unwrap(),
+        &zone(480),
+        120.0,
+        SolarTimePolicy::Meridian,
+        &EspenakMeeus,
+    )
+    .unwrap();
+    let after = compute_lunar(
+        &Utc.with_ymd_and_hms(2025, 2, 4, 4, 0, 0).unwrap(),
+        &zone(480),
+        120.0,
+        SolarTimePolicy::Meridian,
+        &EspenakMeeus,
+    )
+    .unwrap();
+    assert_eq!(before.ganzhi_year, "甲辰");
+    assert_eq!(after.ganzhi_year, "乙巳");
+    assert_eq!(after.ganzhi_month, "戊寅");
+    assert_eq!(calendar.days[0].date.month(), 2);
+}
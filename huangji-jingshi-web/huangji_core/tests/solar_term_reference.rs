@@ -0,0 +1,41 @@
+//! 节气时刻与公开发布时刻对照
+//!
+//! 参照表见 `data/solar_terms_reference.json`：二分二至取自 USNO，立春取自香港天文台，
+//! 均为 UT 并取整到分钟。
+
+use chrono::{DateTime, Utc};
+use huangji_core::astro::solar::jd_to_utc;
+use huangji_core::calendar::jieqi::{find_solar_term_jd, SolarTerm};
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+struct Reference {
+    year: i32,
+    term: String,
+    utc: DateTime<Utc>,
+    source: String,
+}
+
+#[test]
+fn test_solar_terms_match_published_times() {
+    let references: Vec<Reference> =
+        serde_json::from_str(include_str!("../data/solar_terms_reference.json")).unwrap();
+    assert!(references.len() >= 30);
+
+    for reference in &references {
+        let term = SolarTerm::from_name(&reference.term).unwrap();
+        let computed = jd_to_utc(find_solar_term_jd(reference.year, term)).unwrap();
+        let diff = (computed - reference.utc).num_seconds().abs();
+        // 发布值取整到分钟（±30 秒），截断 VSOP87 误差约十余秒，ΔT 多项式误差数秒
+        assert!(
+            diff <= 45,
+            "{} {} ({}): 计算 {} 与发布 {} 相差 {} 秒",
+            reference.year,
+            reference.term,
+            reference.source,
+            computed,
+            reference.utc,
+            diff
+        );
+    }
+}